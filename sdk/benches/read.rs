@@ -0,0 +1,43 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use c2pa::jumbf_io::load_jumbf_from_stream;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// the bytes read don't grow with the media size, see test_read_cai_skips_media_boxes
+const PAD_SIZE: u32 = 16 * 1024 * 1024;
+
+// video1.mp4 followed by a large free box standing in for a large mdat
+fn large_mp4() -> Vec<u8> {
+    let mut data = std::fs::read("tests/fixtures/video1.mp4").unwrap();
+    data.extend_from_slice(&PAD_SIZE.to_be_bytes());
+    data.extend_from_slice(b"free");
+    data.resize(data.len() + PAD_SIZE as usize - 8, 0);
+    data
+}
+
+fn read_bmff_manifest(c: &mut Criterion) {
+    let data = large_mp4();
+
+    c.bench_function("read bmff manifest", |b| {
+        b.iter(|| {
+            let mut stream = Cursor::new(&data);
+            black_box(load_jumbf_from_stream("mp4", &mut stream).unwrap())
+        })
+    });
+}
+
+criterion_group!(benches, read_bmff_manifest);
+criterion_main!(benches);
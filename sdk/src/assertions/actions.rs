@@ -385,6 +385,10 @@ pub struct Actions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub templates: Option<Vec<ActionTemplate>>,
 
+    /// Whether the [`Action`]s list all the actions taken on the asset.
+    #[serde(rename = "allActionsIncluded", skip_serializing_if = "Option::is_none")]
+    pub all_actions_included: Option<bool>,
+
     /// Additional information about the assertion.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
@@ -401,13 +405,14 @@ impl Actions {
         Self {
             actions: Vec::new(),
             templates: None,
+            all_actions_included: None,
             metadata: None,
         }
     }
 
     /// determines if actions is V2
     fn is_v2(&self) -> bool {
-        if self.templates.is_some() {
+        if self.templates.is_some() || self.all_actions_included.is_some() {
             return true;
         };
         self.actions.iter().any(|a| a.is_v2())
@@ -423,6 +428,28 @@ impl Actions {
         &mut self.actions
     }
 
+    /// Returns the list of [`ActionTemplate`]s.
+    pub fn templates(&self) -> &[ActionTemplate] {
+        self.templates.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the [`ActionTemplate`] that applies to an [`Action`].
+    ///
+    /// A template for the action's label takes precedence over a template for all actions,
+    /// whose label is `*`.
+    pub fn template_for(&self, action: &Action) -> Option<&ActionTemplate> {
+        let templates = self.templates();
+        templates
+            .iter()
+            .find(|template| template.action == action.action())
+            .or_else(|| templates.iter().find(|template| template.action == "*"))
+    }
+
+    /// Returns whether the [`Action`]s list all the actions taken on the asset, if stated.
+    pub fn all_actions_included(&self) -> Option<bool> {
+        self.all_actions_included
+    }
+
     /// Returns the assertion's [`Metadata`], if it exists.
     pub fn metadata(&self) -> Option<&Metadata> {
         self.metadata.as_ref()
@@ -440,6 +467,18 @@ impl Actions {
         self
     }
 
+    /// Adds an [`ActionTemplate`] to this assertion's list of templates.
+    pub fn add_template(mut self, template: ActionTemplate) -> Self {
+        self.templates.get_or_insert_with(Vec::new).push(template);
+        self
+    }
+
+    /// Sets whether the [`Action`]s list all the actions taken on the asset.
+    pub fn set_all_actions_included(mut self, all_actions_included: bool) -> Self {
+        self.all_actions_included = Some(all_actions_included);
+        self
+    }
+
     /// Sets [`Metadata`] for the action.
     pub fn add_metadata(mut self, metadata: Metadata) -> Self {
         self.metadata = Some(metadata);
@@ -833,4 +872,59 @@ pub mod tests {
             }]
         );
     }
+
+    #[test]
+    fn test_region_of_interest_round_trip() {
+        use crate::assertions::region_of_interest::{PageRect, Role, UnitType};
+
+        let page_box = PageRect::new(0.0, 0.0, 612.0, 792.0);
+        let face = PageRect::new(153.0, 594.0, 306.0, 792.0);
+
+        let original = Actions::new().add_action(
+            Action::new("c2pa.edited")
+                .set_reason("face blurred")
+                .add_change(
+                    RegionOfInterest::page_rectangle(2, face, page_box)
+                        .set_role(Role::Redacted)
+                        .set_name("face"),
+                )
+                .add_change(RegionOfInterest::rectangle(
+                    UnitType::Pixel,
+                    10.0,
+                    20.0,
+                    300.0,
+                    200.0,
+                )),
+        );
+
+        let assertion = original.to_assertion().expect("build_assertion");
+        let result = Actions::from_assertion(&assertion).expect("extract_assertion");
+        assert_eq!(original.actions, result.actions);
+
+        let changes = result.actions[0].changes().unwrap();
+        assert_eq!(
+            serde_json::to_value(&changes[0]).unwrap(),
+            serde_json::json!({
+                "region": [
+                    {"type": "frame", "frame": {"start": 2, "end": 2}},
+                    {
+                        "type": "spatial",
+                        "shape": {
+                            "type": "rectangle",
+                            "unit": "percent",
+                            "origin": {"x": 25.0, "y": 0.0},
+                            "width": 25.0,
+                            "height": 25.0
+                        }
+                    }
+                ],
+                "name": "face",
+                "role": "c2pa.redacted"
+            })
+        );
+        assert_eq!(changes[0].to_page_rectangle(page_box), Some((2, face)));
+
+        // pixel rectangles aren't in page coordinates
+        assert_eq!(changes[1].to_page_rectangle(page_box), None);
+    }
 }
@@ -0,0 +1,252 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Asset Metadata Assertion
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    assertion::{Assertion, AssertionBase, AssertionJson},
+    assertions::labels,
+    Error, Result,
+};
+
+const DC_TITLE: &str = "dc:title";
+const DC_SUBJECT: &str = "dc:subject";
+const DC_RIGHTS: &str = "dc:rights";
+
+/// The metadata assertion describes the asset using JSON-LD terms from established
+/// metadata schemas, such as Dublin Core and XMP.
+///
+/// See <https://c2pa.org/specifications/specifications/2.0/specs/C2PA_Specification.html#_metadata>.
+///
+/// Every key must be a compact IRI, `prefix:name`, whose prefix is defined in the
+/// `@context`. Keys starting with `@` are reserved by JSON-LD.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AssetMetadata {
+    #[serde(rename = "@context")]
+    context: HashMap<String, String>,
+    #[serde(flatten)]
+    value: HashMap<String, Value>,
+}
+
+impl AssetMetadata {
+    /// Label prefix for a metadata assertion.
+    pub const LABEL: &'static str = labels::METADATA;
+
+    pub fn new() -> Self {
+        Self {
+            context: HashMap::from([
+                (
+                    "dc".to_owned(),
+                    "http://purl.org/dc/elements/1.1/".to_owned(),
+                ),
+                ("xmp".to_owned(), "http://ns.adobe.com/xap/1.0/".to_owned()),
+                (
+                    "xmpRights".to_owned(),
+                    "http://ns.adobe.com/xap/1.0/rights/".to_owned(),
+                ),
+            ]),
+            value: HashMap::new(),
+        }
+    }
+
+    /// Creates the assertion from a map of keys and values.
+    /// # Errors
+    ///
+    /// Returns [`Error::BadParam`] if any key is reserved or uses an undefined prefix.
+    pub fn from_map(map: HashMap<String, Value>) -> Result<Self> {
+        map.into_iter()
+            .try_fold(Self::new(), |metadata, (key, value)| {
+                metadata.insert(key, value)
+            })
+    }
+
+    /// creates the struct from a correctly formatted JSON string
+    /// # Errors
+    ///
+    /// Returns [`Error::BadParam`] if any key is reserved or uses an undefined prefix.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        let metadata: Self = serde_json::from_slice(json.as_bytes()).map_err(Error::JsonError)?;
+        for key in metadata.value.keys() {
+            metadata.check_key(key)?;
+        }
+        Ok(metadata)
+    }
+
+    /// Adds a prefix to the `@context`, so keys can use it.
+    pub fn add_context<S: Into<String>>(mut self, prefix: S, iri: S) -> Self {
+        self.context.insert(prefix.into(), iri.into());
+        self
+    }
+
+    /// Returns the `@context` mapping prefixes to their IRIs.
+    pub fn context(&self) -> &HashMap<String, String> {
+        &self.context
+    }
+
+    /// get values by key as an instance of type `T`.
+    /// This return T is owned, not a reference
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.value
+            .get(key)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// insert key / value pair of instance of type `T`
+    /// # Errors
+    ///
+    /// Returns [`Error::BadParam`] if the key is reserved or uses an undefined prefix.
+    /// The conversion can also fail if `T`'s implementation of `Serialize` decides to
+    /// fail, or if `T` contains a map with non-string keys.
+    pub fn insert<S: Into<String>, T: Serialize>(mut self, key: S, value: T) -> Result<Self> {
+        let key = key.into();
+        self.check_key(&key)?;
+        self.value.insert(key, serde_json::to_value(value)?);
+        Ok(self)
+    }
+
+    /// Returns the title of the asset (`dc:title`).
+    pub fn title(&self) -> Option<String> {
+        self.get(DC_TITLE)
+    }
+
+    /// Sets the title of the asset (`dc:title`).
+    pub fn set_title<S: Into<String>>(self, title: S) -> Result<Self> {
+        self.insert(DC_TITLE, title.into())
+    }
+
+    /// Returns the keywords describing the asset (`dc:subject`).
+    pub fn keywords(&self) -> Option<Vec<String>> {
+        self.get(DC_SUBJECT)
+    }
+
+    /// Sets the keywords describing the asset (`dc:subject`).
+    pub fn set_keywords<S: Into<String>>(self, keywords: Vec<S>) -> Result<Self> {
+        let keywords: Vec<String> = keywords.into_iter().map(Into::into).collect();
+        self.insert(DC_SUBJECT, keywords)
+    }
+
+    /// Returns the rights statement for the asset (`dc:rights`).
+    pub fn rights(&self) -> Option<String> {
+        self.get(DC_RIGHTS)
+    }
+
+    /// Sets the rights statement for the asset (`dc:rights`).
+    pub fn set_rights<S: Into<String>>(self, rights: S) -> Result<Self> {
+        self.insert(DC_RIGHTS, rights.into())
+    }
+
+    // keys must be compact IRIs using a prefix defined in the context
+    fn check_key(&self, key: &str) -> Result<()> {
+        if key.starts_with('@') {
+            return Err(Error::BadParam(format!("{key} is a reserved key")));
+        }
+
+        match key.split_once(':') {
+            Some((prefix, name)) if !name.is_empty() && self.context.contains_key(prefix) => Ok(()),
+            _ => Err(Error::BadParam(format!(
+                "{key} must use a prefix defined in the @context"
+            ))),
+        }
+    }
+}
+
+impl Default for AssetMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssertionJson for AssetMetadata {}
+
+impl AssertionBase for AssetMetadata {
+    const LABEL: &'static str = labels::METADATA;
+
+    fn to_assertion(&self) -> Result<Assertion> {
+        Self::to_json_assertion(self)
+    }
+
+    fn from_assertion(assertion: &Assertion) -> Result<Self> {
+        Self::from_json_assertion(assertion)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn metadata_to_assertion() {
+        let original = AssetMetadata::new()
+            .add_context("photoshop", "http://ns.adobe.com/photoshop/1.0/")
+            .set_title("Sunset")
+            .unwrap()
+            .set_keywords(vec!["sun", "sea"])
+            .unwrap()
+            .set_rights("CC-BY-4.0")
+            .unwrap()
+            .insert("photoshop:City", "Lisbon")
+            .unwrap();
+
+        let assertion = original.to_assertion().expect("to_assertion");
+        assert_eq!(assertion.content_type(), "application/json");
+        assert_eq!(assertion.label(), AssetMetadata::LABEL);
+
+        let result = AssetMetadata::from_assertion(&assertion).expect("from_assertion");
+        assert_eq!(result, original);
+        assert_eq!(result.title().as_deref(), Some("Sunset"));
+        assert_eq!(
+            result.keywords(),
+            Some(vec!["sun".to_owned(), "sea".to_owned()])
+        );
+        assert_eq!(result.rights().as_deref(), Some("CC-BY-4.0"));
+        assert_eq!(result.get::<String>("photoshop:City").unwrap(), "Lisbon");
+    }
+
+    #[test]
+    fn metadata_from_map() {
+        let map = HashMap::from([
+            ("dc:title".to_owned(), json!("Sunset")),
+            ("xmp:Rating".to_owned(), json!(5)),
+        ]);
+        let metadata = AssetMetadata::from_map(map).unwrap();
+        assert_eq!(metadata.title().as_deref(), Some("Sunset"));
+        assert_eq!(metadata.get::<u8>("xmp:Rating"), Some(5));
+    }
+
+    #[test]
+    fn metadata_rejects_reserved_keys() {
+        for key in ["@id", "title", "dc:", "iptc:City"] {
+            assert!(
+                matches!(
+                    AssetMetadata::new().insert(key, "value"),
+                    Err(Error::BadParam(_))
+                ),
+                "{key} should be rejected"
+            );
+        }
+
+        assert!(matches!(
+            AssetMetadata::from_json_str(r#"{"@context": {"dc": "x"}, "other:title": "x"}"#),
+            Err(Error::BadParam(_))
+        ));
+    }
+}
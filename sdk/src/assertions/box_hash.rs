@@ -21,7 +21,10 @@ use crate::{
     assertions::labels,
     asset_io::{AssetBoxHash, CAIRead},
     error::{Error, Result},
-    utils::hash_utils::{hash_stream_by_alg, verify_stream_by_alg, HashRange},
+    utils::{
+        hash_utils::{hash_stream_by_alg, verify_stream_by_alg, HashRange},
+        io_utils::stream_len,
+    },
     validation_status::ASSERTION_BOXHASH_UNKNOWN,
 };
 
@@ -46,15 +49,45 @@ pub struct BoxMap {
     pub range_len: usize,
 }
 
-/// Helper class to create BoxHash assertion
+/// A segment appended to an append-only asset, such as a growing log, bound by its byte range.
+///
+/// Segments follow each other without gaps from the start of the asset, each starting where the
+/// previous one ends, so a new segment can be bound without hashing the ones before it again.
+/// Since they must cover the whole asset, segments only bind assets with a sidecar manifest.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SegmentMap {
+    /// Offset of the segment's first byte in the asset.
+    pub start: u64,
+
+    /// Length of the segment in bytes.
+    pub length: u64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+
+    pub hash: ByteBuf,
+}
+
+/// Helper class to create BoxHash assertion
+///
+/// Besides the hashes of named boxes, a box hash may list the [`SegmentMap`]s of an
+/// append-only asset in its `segments` field. A box hash lists either boxes or segments.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
 pub struct BoxHash {
     boxes: Vec<BoxMap>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    segments: Vec<SegmentMap>,
 }
 
 impl BoxHash {
     pub const LABEL: &'static str = labels::BOX_HASH;
 
+    /// Creates a box hash with no boxes or segments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     pub fn verify_hash(
         &self,
         asset_path: &Path,
@@ -84,10 +117,44 @@ impl BoxHash {
         bhp: &dyn AssetBoxHash,
     ) -> Result<()> {
         // it is a failure if no hashes are listed
+        if self.boxes.is_empty() && self.segments.is_empty() {
+            return Err(Error::HashMismatch("No box hash found".to_string()));
+        }
+
+        if !self.segments.is_empty() {
+            if !self.boxes.is_empty() {
+                return Err(Error::HashMismatch(
+                    "Box hash lists both boxes and segments".to_string(),
+                ));
+            }
+            return self.verify_segments_from(reader, alg, 0);
+        }
+
+        if self.mismatched_boxes(reader, alg, bhp)?.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::HashMismatch("Hashes do not match".to_owned()))
+        }
+    }
+
+    /// Returns the names and byte ranges of the hashed boxes whose data no longer matches
+    /// their hash.
+    ///
+    /// Boxes that are missing, renamed or out of order are reported as an error, since the
+    /// box list of the asset can then no longer be lined up with the hashed one.
+    pub(crate) fn mismatched_boxes(
+        &self,
+        reader: &mut dyn CAIRead,
+        alg: Option<&str>,
+        bhp: &dyn AssetBoxHash,
+    ) -> Result<Vec<(String, HashRange)>> {
+        // segments are only checked as a whole
         if self.boxes.is_empty() {
             return Err(Error::HashMismatch("No box hash found".to_string()));
         }
 
+        let mut mismatched = Vec::new();
+
         // get source box list
         let source_bms = bhp.get_box_map(reader)?;
         let mut source_index = 0;
@@ -146,7 +213,7 @@ impl BoxHash {
                 continue;
             }
 
-            inclusions.push(inclusion);
+            inclusions.push(inclusion.clone());
 
             let curr_alg = match &bm.alg {
                 Some(a) => a.clone(),
@@ -157,10 +224,108 @@ impl BoxHash {
             };
 
             if !verify_stream_by_alg(&curr_alg, &bm.hash, reader, Some(inclusions), false) {
-                return Err(Error::HashMismatch("Hashes do not match".to_owned()));
+                mismatched.push((bm.names.join(","), inclusion));
+            }
+        }
+
+        Ok(mismatched)
+    }
+
+    /// Returns the appended segments, in the order they were added.
+    pub fn segments(&self) -> &[SegmentMap] {
+        &self.segments
+    }
+
+    /// Hashes the `length` bytes of `reader` starting at `start` as a new appended segment.
+    ///
+    /// Only the new segment is read. Returns [`Error::BadParam`] if it doesn't start where the
+    /// last segment ends, or at 0 for the first segment.
+    pub fn append_segment(
+        &mut self,
+        reader: &mut dyn CAIRead,
+        start: u64,
+        length: u64,
+        alg: &str,
+    ) -> Result<()> {
+        let expected_start = self
+            .segments
+            .last()
+            .map_or(Some(0), |segment| segment.start.checked_add(segment.length));
+        if Some(start) != expected_start {
+            return Err(Error::BadParam(format!(
+                "segment must start at {}, the end of the last segment",
+                expected_start.unwrap_or(u64::MAX)
+            )));
+        }
+
+        let inclusion = segment_range(start, length).map_err(Error::BadParam)?;
+        let hash = hash_stream_by_alg(alg, reader, Some(vec![inclusion]), false)?;
+
+        self.segments.push(SegmentMap {
+            start,
+            length,
+            alg: Some(alg.to_string()),
+            hash: ByteBuf::from(hash),
+        });
+        Ok(())
+    }
+
+    /// Verifies the segments from index `first` on, skipping segments validated earlier.
+    ///
+    /// All segments must still follow each other from offset 0 to the end of the asset, so no
+    /// data can be added before, between or after them.
+    pub fn verify_segments_from(
+        &self,
+        reader: &mut dyn CAIRead,
+        alg: Option<&str>,
+        first: usize,
+    ) -> Result<()> {
+        let asset_len = stream_len(reader)?;
+
+        let mut expected_start = 0u64;
+        for (index, segment) in self.segments.iter().enumerate() {
+            if segment.start != expected_start {
+                return Err(Error::HashMismatch(format!(
+                    "Segment at {} does not start at {expected_start}",
+                    segment.start
+                )));
+            }
+            let inclusion =
+                segment_range(segment.start, segment.length).map_err(Error::HashMismatch)?;
+            expected_start = segment.start + segment.length; // checked by segment_range
+
+            if index < first {
+                continue;
+            }
+
+            let curr_alg = match (&segment.alg, alg) {
+                (Some(a), _) => a.as_str(),
+                (None, Some(a)) => a,
+                (None, None) => {
+                    return Err(Error::HashMismatch("No algorithm specified".to_string()))
+                }
+            };
+
+            if !verify_stream_by_alg(
+                curr_alg,
+                &segment.hash,
+                reader,
+                Some(vec![inclusion]),
+                false,
+            ) {
+                return Err(Error::HashMismatch(format!(
+                    "Hash of segment at {} does not match",
+                    segment.start
+                )));
             }
         }
 
+        if expected_start != asset_len {
+            return Err(Error::HashMismatch(format!(
+                "Segments end at {expected_start} but the asset is {asset_len} bytes"
+            )));
+        }
+
         Ok(())
     }
 
@@ -301,6 +466,15 @@ impl AssertionBase for BoxHash {
     }
 }
 
+// Returns the byte range of a segment, if its end and bounds fit in memory offsets.
+fn segment_range(start: u64, length: u64) -> std::result::Result<HashRange, String> {
+    let out_of_range = || format!("segment at {start} with length {length} is out of range");
+    let end = start.checked_add(length).ok_or_else(out_of_range)?;
+    usize::try_from(end).map_err(|_| out_of_range())?;
+
+    Ok(HashRange::new(start as usize, length as usize))
+}
+
 #[cfg(feature = "file_io")]
 #[cfg(test)]
 mod tests {
@@ -321,7 +495,7 @@ mod tests {
 
         let mut input = File::open(&ap).unwrap();
 
-        let mut bh = BoxHash { boxes: Vec::new() };
+        let mut bh = BoxHash::new();
 
         // generate box hashes
         bh.generate_box_hash_from_stream(&mut input, "sha256", bhp, false)
@@ -343,7 +517,7 @@ mod tests {
 
         let mut input = File::open(&ap).unwrap();
 
-        let mut bh = BoxHash { boxes: Vec::new() };
+        let mut bh = BoxHash::new();
 
         // generate box hashes
         bh.generate_box_hash_from_stream(&mut input, "sha256", bhp, true)
@@ -365,7 +539,7 @@ mod tests {
 
         let mut input = File::open(&ap).unwrap();
 
-        let mut bh = BoxHash { boxes: Vec::new() };
+        let mut bh = BoxHash::new();
 
         // generate box hashes
         bh.generate_box_hash_from_stream(&mut input, "sha256", bhp, false)
@@ -387,7 +561,7 @@ mod tests {
 
         let mut input = File::open(&ap).unwrap();
 
-        let mut bh = BoxHash { boxes: Vec::new() };
+        let mut bh = BoxHash::new();
 
         // generate box hashes
         bh.generate_box_hash_from_stream(&mut input, "sha256", bhp, false)
@@ -400,6 +574,91 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_append_segment() {
+        let mut log = b"first entry\n".to_vec();
+        let first_len = log.len() as u64;
+
+        let mut bh = BoxHash::new();
+        bh.append_segment(&mut Cursor::new(&log), 0, first_len, "sha256")
+            .unwrap();
+
+        // append a segment, hashing only the new bytes
+        log.extend_from_slice(b"second entry\n");
+        let second_len = log.len() as u64 - first_len;
+        bh.append_segment(&mut Cursor::new(&log), first_len, second_len, "sha256")
+            .unwrap();
+        assert_eq!(bh.segments().len(), 2);
+        assert_eq!(bh.segments()[1].start, first_len);
+
+        // segments must be contiguous
+        assert!(matches!(
+            bh.append_segment(&mut Cursor::new(&log), 0, 1, "sha256"),
+            Err(Error::BadParam(_))
+        ));
+
+        // the segments survive a round trip through the assertion
+        let reloaded = BoxHash::from_cbor_assertion(&bh.to_cbor_assertion().unwrap()).unwrap();
+        assert_eq!(reloaded, bh);
+
+        // a change to the first segment is only found when it is validated
+        log[0] = b'F';
+        reloaded
+            .verify_segments_from(&mut Cursor::new(&log), None, 1)
+            .unwrap();
+        assert!(reloaded
+            .verify_segments_from(&mut Cursor::new(&log), None, 0)
+            .is_err());
+        log[0] = b'f';
+
+        // the segments must cover the whole asset
+        let mut appended = log.clone();
+        appended.extend_from_slice(b"unbound entry\n");
+        assert!(reloaded
+            .verify_segments_from(&mut Cursor::new(&appended), None, 1)
+            .is_err());
+
+        let mut prepended = b"unbound entry\n".to_vec();
+        prepended.extend_from_slice(&log);
+        assert!(reloaded
+            .verify_stream_hash(&mut Cursor::new(&prepended), None, &NoBoxes)
+            .is_err());
+        reloaded
+            .verify_stream_hash(&mut Cursor::new(&log), None, &NoBoxes)
+            .unwrap();
+
+        // the first segment starts at 0
+        assert!(matches!(
+            BoxHash::new().append_segment(&mut Cursor::new(&log), 1, 1, "sha256"),
+            Err(Error::BadParam(_))
+        ));
+
+        // segment ends that overflow are rejected
+        let mut overflowing = BoxHash::new();
+        overflowing.segments.push(SegmentMap {
+            start: 0,
+            length: u64::MAX,
+            alg: None,
+            hash: ByteBuf::new(),
+        });
+        assert!(matches!(
+            overflowing.append_segment(&mut Cursor::new(&log), 0, 1, "sha256"),
+            Err(Error::BadParam(_))
+        ));
+        assert!(overflowing
+            .verify_segments_from(&mut Cursor::new(&log), Some("sha256"), 0)
+            .is_err());
+    }
+
+    // an asset format without boxes, for box hashes made of segments
+    struct NoBoxes;
+
+    impl AssetBoxHash for NoBoxes {
+        fn get_box_map(&self, _input_stream: &mut dyn CAIRead) -> Result<Vec<BoxMap>> {
+            Ok(Vec::new())
+        }
+    }
+
     #[test]
     fn test_json_round_trop() {
         let ap = fixture_path("CA.jpg");
@@ -411,7 +670,7 @@ mod tests {
 
         let mut input = File::open(&ap).unwrap();
 
-        let mut bh = BoxHash { boxes: Vec::new() };
+        let mut bh = BoxHash::new();
 
         // generate box hashes
         bh.generate_box_hash_from_stream(&mut input, "sha256", bhp, true)
@@ -440,7 +699,7 @@ mod tests {
 
         let mut input = File::open(&ap).unwrap();
 
-        let mut bh = BoxHash { boxes: Vec::new() };
+        let mut bh = BoxHash::new();
 
         // generate box hashes
         bh.generate_box_hash_from_stream(&mut input, "sha256", bhp, true)
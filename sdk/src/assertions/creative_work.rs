@@ -24,6 +24,8 @@ use crate::{
 
 const ASSERTION_CREATION_VERSION: usize = 1;
 const CW_AUTHOR: &str = "author";
+const CW_PUBLISHER: &str = "publisher";
+const CW_URL: &str = "url";
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreativeWork(SchemaDotOrg);
@@ -71,6 +73,26 @@ impl CreativeWork {
         v.push(author);
         self.insert(CW_AUTHOR.to_owned(), &v)
     }
+
+    // get publisher field if it exists
+    pub fn publisher(&self) -> Option<SchemaDotOrg> {
+        self.get(CW_PUBLISHER)
+    }
+
+    /// Sets the publisher, a schema.org `Organization` or `Person`.
+    pub fn set_publisher(self, publisher: SchemaDotOrg) -> Result<Self> {
+        self.insert(CW_PUBLISHER.to_owned(), publisher)
+    }
+
+    // get url field if it exists
+    pub fn url(&self) -> Option<String> {
+        self.get(CW_URL)
+    }
+
+    /// Sets the URL of the published work this asset belongs to.
+    pub fn set_url<S: Into<String>>(self, url: S) -> Result<Self> {
+        self.insert(CW_URL.to_owned(), url.into())
+    }
 }
 
 impl Default for CreativeWork {
@@ -202,6 +224,34 @@ pub mod tests {
         assert_eq!(original_publisher.name(), result_publisher.name());
     }
 
+    #[test]
+    fn assertion_creative_work_publisher_url() {
+        let publisher = SchemaDotOrg::new("Organization".to_owned())
+            .insert("name".to_owned(), "BBC News")
+            .unwrap();
+        let original = CreativeWork::new()
+            .add_author(SchemaDotOrgPerson::new_person(USER, IDENTITY_URI).unwrap())
+            .unwrap()
+            .set_publisher(publisher)
+            .unwrap()
+            .set_url("https://www.bbc.co.uk/news/av/world-europe-57194011")
+            .unwrap();
+
+        let assertion = original.to_assertion().expect("build_assertion");
+        let result = CreativeWork::from_assertion(&assertion).expect("extract_assertion");
+        let result_publisher = result.publisher().unwrap();
+        assert_eq!(result_publisher.object_type(), "Organization");
+        assert_eq!(
+            result_publisher.get::<String>("name").as_deref(),
+            Some("BBC News")
+        );
+        assert_eq!(result.url(), original.url());
+
+        // a publisher without a @type is still read
+        let sample = CreativeWork::from_json_str(SAMPLE_CREATIVE_WORK).expect("from_json_str");
+        assert_eq!(sample.publisher().unwrap().object_type(), "Thing");
+    }
+
     #[test]
     fn from_creative_work_stock() {
         let original = CreativeWork::from_json_str(STOCK_CREATIVE_WORK).expect("from_json_str");
@@ -33,7 +33,7 @@ use crate::{
 const ASSERTION_CREATION_VERSION: usize = 1;
 
 /// Helper class to create DataHash assertion
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct DataHash {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exclusions: Option<Vec<HashRange>>,
@@ -219,11 +219,17 @@ impl DataHash {
             return Err(Error::BadParam("asset hash is remote".to_owned()));
         }
 
-        let curr_alg = alg.unwrap_or("sha256");
+        let curr_alg = match &self.alg {
+            Some(a) => a.clone(),
+            None => match alg {
+                Some(a) => a.to_owned(),
+                None => "sha256".to_string(),
+            },
+        };
 
         let exclusions = self.exclusions.as_ref().cloned();
 
-        if verify_asset_by_alg(curr_alg, &self.hash, asset_path, exclusions) {
+        if verify_asset_by_alg(&curr_alg, &self.hash, asset_path, exclusions) {
             Ok(())
         } else {
             Err(Error::HashMismatch("Hashes do not match".to_owned()))
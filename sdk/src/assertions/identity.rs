@@ -0,0 +1,559 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! CAWG Identity Assertion
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+use crate::{
+    assertion::{Assertion, AssertionBase, AssertionCbor},
+    assertions::labels,
+    claim::Claim,
+    cose_validator::verify_cose,
+    error::Result,
+    hashed_uri::HashedUri,
+    status_tracker::{report_has_status, DetailedStatusTracker, StatusTracker},
+    trust_handler::TrustHandlerConfig,
+    utils::hash_utils::vec_compare,
+    validation_status,
+};
+
+/// The data signed by the named actor of a CAWG identity assertion.
+///
+/// A signer payload read from an identity assertion is verified against its bytes as stored,
+/// so fields this type doesn't hold, such as `expected_partial_claim`, are still covered by
+/// the signature.
+///
+/// See <https://cawg.io/identity/1.1/#_signer_payload_map>.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignerPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<String>>,
+    sig_type: String,
+    referenced_assertions: Vec<HashedUri>,
+}
+
+impl SignerPayload {
+    /// Signature type of an identity assertion signed with an X.509 certificate in a
+    /// COSE_Sign1.
+    pub const X509_COSE: &'static str = "cawg.x509.cose";
+
+    pub fn new(sig_type: &str, referenced_assertions: Vec<HashedUri>) -> Self {
+        Self {
+            roles: None,
+            sig_type: sig_type.to_owned(),
+            referenced_assertions,
+        }
+    }
+
+    /// Sets the roles the named actor had in creating the asset, such as `cawg.creator`.
+    pub fn set_roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = Some(roles);
+        self
+    }
+
+    /// Returns the roles the named actor had in creating the asset.
+    pub fn roles(&self) -> &[String] {
+        self.roles.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the type of the signature over the signer payload.
+    pub fn sig_type(&self) -> &str {
+        &self.sig_type
+    }
+
+    /// Returns the assertions of the claim that the named actor vouches for.
+    pub fn referenced_assertions(&self) -> &[HashedUri] {
+        &self.referenced_assertions
+    }
+
+    /// Returns the CBOR serialization of the signer payload, which is what the credential
+    /// holder signs for a new identity assertion.
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        Ok(serde_cbor::to_vec(self)?)
+    }
+}
+
+/// A CAWG identity assertion binds the identity of a named actor to a set of assertions of
+/// the claim holding it, through a signature by that actor's credential.
+///
+/// Only identity assertions signed with an X.509 certificate (`cawg.x509.cose`) can be
+/// validated.
+///
+/// See <https://cawg.io/identity/1.1/>.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IdentityAssertion {
+    signer_payload: SignerPayload,
+    signature: ByteBuf,
+    pad1: ByteBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pad2: Option<ByteBuf>,
+
+    // the signer payload as stored in the assertion, which is what was signed
+    #[serde(skip)]
+    signer_payload_cbor: Option<Vec<u8>>,
+}
+
+impl IdentityAssertion {
+    /// Label prefix for a CAWG identity assertion.
+    pub const LABEL: &'static str = labels::CAWG_IDENTITY;
+
+    /// Creates an identity assertion from a signer payload and the signature over its
+    /// [`SignerPayload::to_cbor`] bytes.
+    pub fn new(signer_payload: SignerPayload, signature: Vec<u8>) -> Self {
+        Self {
+            signer_payload,
+            signature: ByteBuf::from(signature),
+            pad1: ByteBuf::new(),
+            pad2: None,
+            signer_payload_cbor: None,
+        }
+    }
+
+    /// Returns the data signed by the named actor.
+    pub fn signer_payload(&self) -> &SignerPayload {
+        &self.signer_payload
+    }
+
+    /// Returns the signature over the signer payload, in the format given by its `sig_type`.
+    pub fn signature(&self) -> &[u8] {
+        self.signature.as_slice()
+    }
+
+    /// Validates the identity assertion against the claim holding it.
+    ///
+    /// Returns the organization of the signing certificate, when the signature could be
+    /// verified, and the validation status code for the identity assertion.
+    pub(crate) fn validate(
+        &self,
+        claim: &Claim,
+        th: &dyn TrustHandlerConfig,
+    ) -> (Option<String>, &'static str) {
+        // padding only reserves space and must be all zeros
+        let mut padding = self.pad1.iter().chain(self.pad2.iter().flatten());
+        if padding.any(|b| *b != 0) {
+            return (None, validation_status::CAWG_IDENTITY_PAD_INVALID);
+        }
+
+        let mut referenced = HashSet::new();
+        for hashed_uri in self.signer_payload.referenced_assertions() {
+            let link = Claim::assertion_label_from_link(&hashed_uri.url());
+            if !referenced.insert(link.clone()) {
+                return (None, validation_status::CAWG_IDENTITY_ASSERTION_DUPLICATE);
+            }
+
+            let in_claim = claim.all_assertions().any(|assertion| {
+                Claim::assertion_label_from_link(&assertion.url()) == link
+                    && vec_compare(&assertion.hash(), &hashed_uri.hash())
+            });
+            if !in_claim {
+                return (None, validation_status::CAWG_IDENTITY_ASSERTION_MISMATCH);
+            }
+        }
+
+        let hard_bindings: Vec<String> = claim
+            .hash_assertions()
+            .iter()
+            .map(|assertion| assertion.label())
+            .collect();
+        if !referenced
+            .iter()
+            .any(|(label, _)| hard_bindings.contains(label))
+        {
+            return (None, validation_status::CAWG_IDENTITY_HARD_BINDING_MISSING);
+        }
+
+        if self.signer_payload.sig_type() != SignerPayload::X509_COSE {
+            return (None, validation_status::CAWG_IDENTITY_SIG_TYPE_UNKNOWN);
+        }
+
+        let payload = match &self.signer_payload_cbor {
+            Some(payload) => Ok(payload.clone()),
+            None => self.signer_payload.to_cbor(),
+        };
+        let Ok(payload) = payload else {
+            return (None, validation_status::CAWG_IDENTITY_CBOR_INVALID);
+        };
+
+        let mut validation_log = DetailedStatusTracker::new();
+        match verify_cose(
+            self.signature(),
+            &payload,
+            b"",
+            true,
+            th,
+            &mut validation_log,
+        ) {
+            Ok(vi) if vi.validated => {
+                let status = if report_has_status(
+                    validation_log.get_log(),
+                    validation_status::SIGNING_CREDENTIAL_TRUSTED,
+                ) {
+                    validation_status::CAWG_IDENTITY_TRUSTED
+                } else {
+                    validation_status::CAWG_IDENTITY_WELL_FORMED
+                };
+                (vi.issuer_org, status)
+            }
+            Ok(_) => (None, validation_status::CAWG_X509_SIGNATURE_INVALID),
+            Err(_) => {
+                // report why the credential was rejected, if it was
+                let status = [
+                    validation_status::SIGNING_CREDENTIAL_UNTRUSTED,
+                    validation_status::SIGNING_CREDENTIAL_EXPIRED,
+                    validation_status::SIGNING_CREDENTIAL_REVOKED,
+                    validation_status::SIGNING_CREDENTIAL_INVALID,
+                ]
+                .into_iter()
+                .find(|status| report_has_status(validation_log.get_log(), status))
+                .unwrap_or(validation_status::CAWG_X509_SIGNATURE_INVALID);
+                (None, status)
+            }
+        }
+    }
+}
+
+impl AssertionCbor for IdentityAssertion {}
+
+impl AssertionBase for IdentityAssertion {
+    const LABEL: &'static str = labels::CAWG_IDENTITY;
+
+    fn to_assertion(&self) -> Result<Assertion> {
+        Self::to_cbor_assertion(self)
+    }
+
+    fn from_assertion(assertion: &Assertion) -> Result<Self> {
+        let mut identity = Self::from_cbor_assertion(assertion)?;
+        // serializing the decoded signer payload again would drop unknown fields and could
+        // reorder the rest, so keep the bytes that were signed
+        identity.signer_payload_cbor =
+            cbor_map_value(assertion.data(), "signer_payload").map(<[u8]>::to_vec);
+        Ok(identity)
+    }
+}
+
+// Returns the encoded value of the text `key` of the CBOR map `data`.
+fn cbor_map_value<'a>(data: &'a [u8], key: &str) -> Option<&'a [u8]> {
+    let (map_len, mut pos) = cbor_head(data, 5)?;
+    let mut entries = 0;
+    while map_len.map_or(data.get(pos) != Some(&0xff), |len| entries < len) {
+        let key_len = cbor_item_len(data.get(pos..)?)?;
+        let value_start = pos + key_len;
+        let value_len = cbor_item_len(data.get(value_start..)?)?;
+
+        // keys of other types, or indefinite length text, never match
+        let text = cbor_head(&data[pos..value_start], 3)
+            .filter(|(len, _)| len.is_some())
+            .map(|(_, head_len)| &data[pos + head_len..value_start]);
+        if text == Some(key.as_bytes()) {
+            return data.get(value_start..value_start + value_len);
+        }
+        pos = value_start + value_len;
+        entries += 1;
+    }
+    None
+}
+
+// Decodes the head of the CBOR data item at the start of `data`, which must be of type
+// `major`. Returns its argument, or `None` for an indefinite length, and the length of the
+// head.
+fn cbor_head(data: &[u8], major: u8) -> Option<(Option<u64>, usize)> {
+    let initial = *data.first()?;
+    if initial >> 5 != major {
+        return None;
+    }
+    let argument_len = match initial & 0x1f {
+        info @ 0..=23 => return Some((Some(info.into()), 1)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        31 => return Some((None, 1)),
+        _ => return None,
+    };
+    let argument = data
+        .get(1..1 + argument_len)?
+        .iter()
+        .fold(0u64, |value, b| value << 8 | u64::from(*b));
+    Some((Some(argument), 1 + argument_len))
+}
+
+// Returns the encoded length of the CBOR data item at the start of `data`. The item has
+// already been decoded, which bounds how deeply it nests.
+fn cbor_item_len(data: &[u8]) -> Option<usize> {
+    let major = *data.first()? >> 5;
+    let (argument, head_len) = cbor_head(data, major)?;
+
+    // the lengths of the items nested in an indefinite length item, up to its break
+    let indefinite_len = || {
+        let mut pos = head_len;
+        while *data.get(pos)? != 0xff {
+            pos += cbor_item_len(data.get(pos..)?)?;
+        }
+        Some(pos + 1)
+    };
+
+    let items = match (major, argument) {
+        (0 | 1 | 7, _) => return Some(head_len),
+        (2 | 3, Some(len)) => {
+            return head_len
+                .checked_add(usize::try_from(len).ok()?)
+                .filter(|end| *end <= data.len())
+        }
+        (6, Some(_)) => 1,
+        (4, Some(len)) => len,
+        (5, Some(len)) => len.checked_mul(2)?,
+        (2..=5, None) => return indefinite_len(),
+        _ => return None,
+    };
+
+    let mut pos = head_len;
+    for _ in 0..items {
+        pos += cbor_item_len(data.get(pos..)?)?;
+    }
+    Some(pos)
+}
+
+#[cfg(test)]
+pub mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{assertions::DataHash, trust_handler::TrustPassThrough};
+
+    fn identity_claim() -> Claim {
+        let mut data_hash = DataHash::new("jumbf manifest", "sha256");
+        data_hash.set_hash(vec![0; 32]);
+
+        let mut claim = Claim::new("identity unit test", Some("contentauth"));
+        claim.add_assertion(&data_hash).unwrap();
+        claim
+    }
+
+    #[cfg(feature = "openssl_sign")]
+    fn sign_payload(signer_payload: &SignerPayload) -> Vec<u8> {
+        let signer = crate::utils::test::temp_signer();
+        crate::cose_sign::cose_sign(
+            signer.as_ref(),
+            &signer_payload.to_cbor().unwrap(),
+            signer.reserve_size(),
+        )
+        .unwrap()
+    }
+
+    // encodes an identity assertion as another tool might, with a signer payload holding a
+    // field this type doesn't know and its maps in another order than `SignerPayload`'s
+    #[cfg(feature = "openssl_sign")]
+    fn foreign_identity_assertion(
+        referenced_assertions: &[HashedUri],
+    ) -> crate::assertions::UserCbor {
+        use std::collections::BTreeMap;
+
+        use serde_cbor::Value;
+
+        let text = |s: &str| Value::Text(s.to_owned());
+        let referenced = referenced_assertions
+            .iter()
+            .map(|hashed_uri| {
+                let mut map = BTreeMap::from([
+                    (text("url"), text(&hashed_uri.url())),
+                    (text("hash"), Value::Bytes(hashed_uri.hash())),
+                ]);
+                if let Some(alg) = hashed_uri.alg() {
+                    map.insert(text("alg"), text(&alg));
+                }
+                Value::Map(map)
+            })
+            .collect();
+        let signer_payload = Value::Map(BTreeMap::from([
+            (text("sig_type"), text(SignerPayload::X509_COSE)),
+            (text("referenced_assertions"), Value::Array(referenced)),
+            (text("expected_countersigners"), Value::Array(Vec::new())),
+        ]));
+
+        let signer = crate::utils::test::temp_signer();
+        let signature = crate::cose_sign::cose_sign(
+            signer.as_ref(),
+            &serde_cbor::to_vec(&signer_payload).unwrap(),
+            signer.reserve_size(),
+        )
+        .unwrap();
+
+        let identity = Value::Map(BTreeMap::from([
+            (text("signer_payload"), signer_payload),
+            (text("signature"), Value::Bytes(signature)),
+            (text("pad1"), Value::Bytes(Vec::new())),
+        ]));
+        crate::assertions::UserCbor::new(
+            IdentityAssertion::LABEL,
+            serde_cbor::to_vec(&identity).unwrap(),
+        )
+    }
+
+    #[test]
+    fn identity_to_assertion() {
+        let signer_payload = SignerPayload::new(SignerPayload::X509_COSE, Vec::new())
+            .set_roles(vec!["cawg.creator".to_owned()]);
+        let original = IdentityAssertion::new(signer_payload, b"signature".to_vec());
+
+        let assertion = original.to_assertion().expect("to_assertion");
+        assert_eq!(assertion.content_type(), "application/cbor");
+        assert_eq!(assertion.label(), IdentityAssertion::LABEL);
+
+        let result = IdentityAssertion::from_assertion(&assertion).expect("from_assertion");
+        assert_eq!(result.signer_payload(), original.signer_payload());
+        assert_eq!(
+            result.signer_payload_cbor,
+            Some(original.signer_payload().to_cbor().unwrap())
+        );
+        assert_eq!(result.signer_payload().roles(), ["cawg.creator"]);
+        assert_eq!(result.signature(), b"signature");
+    }
+
+    #[test]
+    #[cfg(feature = "openssl_sign")]
+    fn identity_validate() {
+        let claim = identity_claim();
+        let signer_payload =
+            SignerPayload::new(SignerPayload::X509_COSE, claim.assertions().clone());
+        let identity =
+            IdentityAssertion::new(signer_payload.clone(), sign_payload(&signer_payload));
+
+        let (org, status) = identity.validate(&claim, &TrustPassThrough::new());
+        assert_eq!(status, validation_status::CAWG_IDENTITY_WELL_FORMED);
+        assert!(org.is_some());
+
+        // the signature is over another payload
+        let other_payload =
+            SignerPayload::new(SignerPayload::X509_COSE, claim.assertions().clone())
+                .set_roles(vec!["cawg.editor".to_owned()]);
+        let forged = IdentityAssertion::new(other_payload, sign_payload(&signer_payload));
+        let (_, status) = forged.validate(&claim, &TrustPassThrough::new());
+        assert_eq!(status, validation_status::CAWG_X509_SIGNATURE_INVALID);
+    }
+
+    #[test]
+    #[cfg(feature = "openssl_sign")]
+    fn identity_validate_foreign_encoding() {
+        let claim = identity_claim();
+        let assertion = foreign_identity_assertion(claim.assertions())
+            .to_assertion()
+            .unwrap();
+        let identity = IdentityAssertion::from_assertion(&assertion).unwrap();
+
+        // serializing the signer payload again doesn't reproduce the bytes that were signed
+        assert_ne!(
+            identity.signer_payload_cbor,
+            Some(identity.signer_payload().to_cbor().unwrap())
+        );
+        let (org, status) = identity.validate(&claim, &TrustPassThrough::new());
+        assert_eq!(status, validation_status::CAWG_IDENTITY_WELL_FORMED);
+        assert!(org.is_some());
+    }
+
+    #[test]
+    #[cfg(all(feature = "file_io", feature = "openssl_sign"))]
+    fn identity_reports_embedded() {
+        use std::io::Cursor;
+
+        use crate::{
+            assertion::AssertionJson,
+            assertions::{labels::BOX_HASH, BoxHash},
+            asset_io::HashBlockObjectType,
+            jumbf_io::get_assetio_handler_from_path,
+            store::Store,
+            utils::test::{create_test_claim, fixture_path, temp_signer},
+            Reader,
+        };
+
+        // a box hash is supplied by the caller, so the identity assertion can reference the
+        // hard binding before the manifest is signed
+        let box_hash_data = std::fs::read(fixture_path("boxhash.json")).unwrap();
+        let box_hash = Assertion::from_data_json(BOX_HASH, &box_hash_data)
+            .and_then(|assertion| BoxHash::from_json_assertion(&assertion))
+            .unwrap();
+
+        let mut claim = create_test_claim().unwrap();
+        let hard_binding = claim.add_assertion(&box_hash).unwrap();
+        claim
+            .add_assertion(&foreign_identity_assertion(&[hard_binding]))
+            .unwrap();
+
+        let mut store = Store::new();
+        store.commit_claim(claim).unwrap();
+        let signer = temp_signer();
+        let manifest = store
+            .get_box_hashed_embeddable_manifest(signer.as_ref())
+            .unwrap();
+        let composed = Store::get_composed_manifest(&manifest, "jpg").unwrap();
+
+        // insert the manifest where the box hash expects it
+        let path = fixture_path("boxhash.jpg");
+        let cai_loc = get_assetio_handler_from_path(&path)
+            .unwrap()
+            .get_object_locations(&path)
+            .unwrap()
+            .into_iter()
+            .find(|o| o.htype == HashBlockObjectType::Cai)
+            .unwrap();
+        let original = std::fs::read(&path).unwrap();
+        let (before, after) = original.split_at(cai_loc.offset);
+        let asset = [before, &composed, after].concat();
+
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(asset)).unwrap();
+        let reports = reader.identity_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(
+            reports[0].sig_type.as_deref(),
+            Some(SignerPayload::X509_COSE)
+        );
+        assert_eq!(
+            reports[0].status,
+            validation_status::CAWG_IDENTITY_WELL_FORMED
+        );
+        assert!(reports[0].identity.is_some());
+    }
+
+    #[test]
+    fn identity_validate_references() {
+        let claim = identity_claim();
+        let th = TrustPassThrough::new();
+        let data_hash = claim.assertions()[0].clone();
+
+        let duplicate = SignerPayload::new(
+            SignerPayload::X509_COSE,
+            vec![data_hash.clone(), data_hash.clone()],
+        );
+        let (_, status) = IdentityAssertion::new(duplicate, Vec::new()).validate(&claim, &th);
+        assert_eq!(status, validation_status::CAWG_IDENTITY_ASSERTION_DUPLICATE);
+
+        let wrong_hash = HashedUri::new(data_hash.url(), data_hash.alg(), &[1; 32]);
+        let mismatch = SignerPayload::new(SignerPayload::X509_COSE, vec![wrong_hash]);
+        let (_, status) = IdentityAssertion::new(mismatch, Vec::new()).validate(&claim, &th);
+        assert_eq!(status, validation_status::CAWG_IDENTITY_ASSERTION_MISMATCH);
+
+        let unbound = SignerPayload::new(SignerPayload::X509_COSE, Vec::new());
+        let (_, status) = IdentityAssertion::new(unbound, Vec::new()).validate(&claim, &th);
+        assert_eq!(
+            status,
+            validation_status::CAWG_IDENTITY_HARD_BINDING_MISSING
+        );
+
+        let unknown = SignerPayload::new("cawg.example", vec![data_hash]);
+        let (_, status) = IdentityAssertion::new(unknown, Vec::new()).validate(&claim, &th);
+        assert_eq!(status, validation_status::CAWG_IDENTITY_SIG_TYPE_UNKNOWN);
+    }
+}
@@ -21,7 +21,7 @@ use crate::{
     assertions::{labels, Metadata, ReviewRating},
     error::Result,
     hashed_uri::HashedUri,
-    validation_status::ValidationStatus,
+    validation_status::{ValidationResults, ValidationStatus},
 };
 
 const ASSERTION_CREATION_VERSION: usize = 2;
@@ -54,6 +54,10 @@ pub struct Ingredient {
     pub c2pa_manifest: Option<HashedUri>,
     #[serde(rename = "validationStatus", skip_serializing_if = "Option::is_none")]
     pub validation_status: Option<Vec<ValidationStatus>>,
+    #[serde(rename = "activeManifest", skip_serializing_if = "Option::is_none")]
+    pub active_manifest: Option<HashedUri>,
+    #[serde(rename = "validationResults", skip_serializing_if = "Option::is_none")]
+    pub validation_results: Option<ValidationResults>,
     pub relationship: Relationship,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thumbnail: Option<HashedUri>,
@@ -97,6 +101,11 @@ impl Ingredient {
         }
     }
 
+    /// determines if an ingredient is a v3 ingredient
+    fn is_v3(&self) -> bool {
+        self.active_manifest.is_some() || self.validation_results.is_some()
+    }
+
     /// determines if an ingredient is a v2 ingredient
     fn is_v2(&self) -> bool {
         self.instance_id.is_none()
@@ -116,6 +125,15 @@ impl Ingredient {
         self
     }
 
+    /// Returns the reference to the ingredient's manifest.
+    ///
+    /// This is `activeManifest` in v3 ingredients and `c2pa_manifest` in earlier versions.
+    pub fn manifest_uri(&self) -> Option<&HashedUri> {
+        self.active_manifest
+            .as_ref()
+            .or(self.c2pa_manifest.as_ref())
+    }
+
     pub fn set_thumbnail_hash_link(mut self, thumbnail: Option<&str>) -> Self {
         self.thumbnail =
             thumbnail.map(|thumb| HashedUri::new(thumb.to_owned(), None, "Hash".as_bytes()));
@@ -155,9 +173,11 @@ impl AssertionBase for Ingredient {
     const LABEL: &'static str = Self::LABEL;
     const VERSION: Option<usize> = Some(ASSERTION_CREATION_VERSION);
 
-    /// if we require v2 fields then use V2
+    /// if we require v2 or v3 fields then use the lowest version that has them
     fn version(&self) -> Option<usize> {
-        if self.is_v2() {
+        if self.is_v3() {
+            Some(3)
+        } else if self.is_v2() {
             Some(2)
         } else {
             Some(1)
@@ -305,4 +325,36 @@ pub mod tests {
         assert_eq!(reviews[0].explanation, "a 3rd party plugin was used");
         assert_eq!(reviews[0].value, 1);
     }
+
+    #[test]
+    fn test_v3_validation_results() {
+        let mut original = Ingredient::new_v2("image 1.jpg", "image/jpeg");
+        original.active_manifest = Some(HashedUri::new(
+            "self#jumbf=/c2pa/urn:uuid:ab9c7e42-1a6c-4c3b-8e34-6d1b9f0a7c11".to_string(),
+            None,
+            b"hash",
+        ));
+        original.validation_results = Some(ValidationResults::from_statuses(&[
+            ValidationStatus::new(crate::validation_status::CLAIM_SIGNATURE_VALIDATED),
+            ValidationStatus::new(crate::validation_status::SIGNING_CREDENTIAL_UNTRUSTED),
+        ]));
+
+        let assertion = original.to_assertion().expect("build_assertion");
+        assert_eq!(assertion.label(), format!("{}.v3", Ingredient::LABEL));
+
+        let restored = Ingredient::from_cbor_assertion(&assertion).expect("from_assertion");
+        assert_eq!(restored.manifest_uri(), original.active_manifest.as_ref());
+        assert_eq!(restored.validation_results, original.validation_results);
+
+        let results = restored.validation_results.unwrap();
+        let codes = results.active_manifest.as_ref().unwrap();
+        assert_eq!(
+            codes.success[0].code(),
+            crate::validation_status::CLAIM_SIGNATURE_VALIDATED
+        );
+        assert_eq!(
+            results.failures()[0].code(),
+            crate::validation_status::SIGNING_CREDENTIAL_UNTRUSTED
+        );
+    }
 }
@@ -119,6 +119,21 @@ pub const EXIF: &str = "stds.exif";
 /// See <https://c2pa.org/specifications/specifications/1.0/specs/C2PA_Specification.html#_iptc_photo_metadata>.
 pub const IPTC_PHOTO_METADATA: &str = "stds.iptc.photo-metadata";
 
+/// Label prefix for an asset metadata assertion.
+///
+/// See <https://c2pa.org/specifications/specifications/2.0/specs/C2PA_Specification.html#_metadata>.
+pub const METADATA: &str = "c2pa.metadata";
+
+/// Label prefix for a time-stamp assertion.
+///
+/// See <https://c2pa.org/specifications/specifications/2.1/specs/C2PA_Specification.html#_time_stamps>.
+pub const TIMESTAMP: &str = "c2pa.time-stamp";
+
+/// Label prefix for a CAWG identity assertion.
+///
+/// See <https://cawg.io/identity/1.1/#_cbor_schema>.
+pub const CAWG_IDENTITY: &str = "cawg.identity";
+
 /// Label prefix for any assertion based on a schema.org grammar.
 ///
 /// See <https://c2pa.org/specifications/specifications/1.0/specs/C2PA_Specification.html#_use_of_schema_org>.
@@ -16,11 +16,14 @@
 mod actions;
 pub use actions::{c2pa_action, Action, ActionTemplate, Actions, SoftwareAgent};
 
+mod asset_metadata;
+pub use asset_metadata::AssetMetadata;
+
 mod bmff_hash;
 pub use bmff_hash::{BmffHash, BmffMerkleMap, DataMap, ExclusionsMap, SubsetMap};
 
 mod box_hash;
-pub use box_hash::{BoxHash, BoxMap, C2PA_BOXHASH};
+pub use box_hash::{BoxHash, BoxMap, SegmentMap, C2PA_BOXHASH};
 
 mod data_hash;
 pub use data_hash::DataHash;
@@ -31,6 +34,9 @@ pub use creative_work::CreativeWork;
 mod exif;
 pub use exif::Exif;
 
+mod identity;
+pub use identity::{IdentityAssertion, SignerPayload};
+
 #[allow(dead_code)] // will become public later
 mod ingredient;
 pub(crate) use ingredient::Ingredient;
@@ -49,6 +55,9 @@ pub use schema_org::{SchemaDotOrg, SchemaDotOrgPerson};
 mod thumbnail;
 pub(crate) use thumbnail::Thumbnail;
 
+mod timestamp;
+pub use timestamp::TimeStamp;
+
 mod user;
 pub(crate) use user::User;
 
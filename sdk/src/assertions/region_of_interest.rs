@@ -72,6 +72,23 @@ pub struct Shape {
     pub vertices: Option<Vec<Coordinate>>,
 }
 
+impl Shape {
+    /// Creates a rectangle with its top-left corner at (`x`, `y`).
+    ///
+    /// Coordinates are measured from the top-left corner of the asset.
+    pub fn rectangle(unit: UnitType, x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            shape_type: ShapeType::Rectangle,
+            unit,
+            origin: Coordinate { x, y },
+            width: Some(width),
+            height: Some(height),
+            inside: None,
+            vertices: None,
+        }
+    }
+}
+
 /// The type of time.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
@@ -181,6 +198,62 @@ pub struct Range {
     pub text: Option<Text>,
 }
 
+impl Range {
+    /// Creates a spatial range covering `shape`.
+    pub fn spatial(shape: Shape) -> Self {
+        Self {
+            range_type: RangeType::Spatial,
+            shape: Some(shape),
+            time: None,
+            frame: None,
+            text: None,
+        }
+    }
+
+    /// Creates a frame range from `start` to `end` inclusive. For paged assets such as PDFs the
+    /// frames are pages, starting at 0.
+    pub fn frames(start: Option<i32>, end: Option<i32>) -> Self {
+        Self {
+            range_type: RangeType::Frame,
+            shape: None,
+            time: None,
+            frame: Some(Frame { start, end }),
+            text: None,
+        }
+    }
+}
+
+/// A rectangle in PDF page coordinates, in points from the bottom-left corner of the page.
+///
+/// This is the layout of a PDF rectangle such as a page's `/MediaBox`: the lower-left corner
+/// followed by the upper-right corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRect {
+    /// The x coordinate of the lower-left corner.
+    pub llx: f64,
+    /// The y coordinate of the lower-left corner.
+    pub lly: f64,
+    /// The x coordinate of the upper-right corner.
+    pub urx: f64,
+    /// The y coordinate of the upper-right corner.
+    pub ury: f64,
+}
+
+impl PageRect {
+    /// Creates a rectangle from its lower-left and upper-right corners.
+    pub fn new(llx: f64, lly: f64, urx: f64, ury: f64) -> Self {
+        Self { llx, lly, urx, ury }
+    }
+
+    fn width(&self) -> f64 {
+        self.urx - self.llx
+    }
+
+    fn height(&self) -> f64 {
+        self.ury - self.lly
+    }
+}
+
 /// A role describing the region.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
@@ -245,3 +318,95 @@ pub struct RegionOfInterest {
     /// Additional information about the asset.
     pub metadata: Option<Box<Metadata>>,
 }
+
+impl RegionOfInterest {
+    /// Creates a region of interest from the ranges that describe it.
+    pub fn new(region: Vec<Range>) -> Self {
+        Self {
+            region,
+            name: None,
+            identifier: None,
+            region_type: None,
+            role: None,
+            description: None,
+            metadata: None,
+        }
+    }
+
+    /// Creates a rectangular region of an image with its top-left corner at (`x`, `y`).
+    pub fn rectangle(unit: UnitType, x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self::new(vec![Range::spatial(Shape::rectangle(
+            unit, x, y, width, height,
+        ))])
+    }
+
+    /// Creates a rectangular region of a PDF page from its `rect` in page coordinates.
+    ///
+    /// `page` starts at 0 and `page_box` is the page's visible area, usually its `/CropBox` or
+    /// `/MediaBox`. The region is serialized as a frame range selecting the page and a spatial
+    /// range giving the rectangle in percent of the page, measured from its top-left corner.
+    pub fn page_rectangle(page: i32, rect: PageRect, page_box: PageRect) -> Self {
+        let x = (rect.llx - page_box.llx) / page_box.width() * 100.0;
+        let y = (page_box.ury - rect.ury) / page_box.height() * 100.0;
+        let width = rect.width() / page_box.width() * 100.0;
+        let height = rect.height() / page_box.height() * 100.0;
+
+        Self::new(vec![
+            Range::frames(Some(page), Some(page)),
+            Range::spatial(Shape::rectangle(UnitType::Percent, x, y, width, height)),
+        ])
+    }
+
+    /// Returns the page and the rectangle in page coordinates of a region created with
+    /// [`RegionOfInterest::page_rectangle`] for a page with the given `page_box`.
+    ///
+    /// Returns `None` if the region isn't a single percent-based rectangle on a single page.
+    pub fn to_page_rectangle(&self, page_box: PageRect) -> Option<(i32, PageRect)> {
+        let page = self.region.iter().find_map(|range| match range.frame {
+            Some(Frame {
+                start: Some(start),
+                end: Some(end),
+            }) if start == end => Some(start),
+            _ => None,
+        })?;
+
+        let shape = self
+            .region
+            .iter()
+            .find_map(|range| range.shape.as_ref())
+            .filter(|shape| {
+                shape.shape_type == ShapeType::Rectangle && shape.unit == UnitType::Percent
+            })?;
+
+        let llx = page_box.llx + shape.origin.x / 100.0 * page_box.width();
+        let ury = page_box.ury - shape.origin.y / 100.0 * page_box.height();
+        let urx = llx + shape.width? / 100.0 * page_box.width();
+        let lly = ury - shape.height? / 100.0 * page_box.height();
+
+        Some((page, PageRect::new(llx, lly, urx, ury)))
+    }
+
+    /// Sets the human-readable name of the region.
+    pub fn set_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the machine-readable identifier of the region.
+    pub fn set_identifier<S: Into<String>>(mut self, identifier: S) -> Self {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
+    /// Sets the role of the region among other regions.
+    pub fn set_role(mut self, role: Role) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    /// Sets the description of the region.
+    pub fn set_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
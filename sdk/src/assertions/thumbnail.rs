@@ -18,7 +18,8 @@ use crate::{
         get_thumbnail_image_type, Assertion, AssertionBase, AssertionData, AssertionDecodeError,
     },
     assertions::labels,
-    error::Result,
+    error::{Error, Result},
+    utils::mime::image_mime_from_bytes,
 };
 
 /// A Thumbnail assertion
@@ -50,6 +51,21 @@ impl Thumbnail {
             content_type,
         }
     }
+
+    /// Creates a thumbnail, detecting the image format from the leading bytes of `data`.
+    ///
+    /// `label` is the base thumbnail label, such as `c2pa.thumbnail.claim`; the detected
+    /// format is appended to it. Returns [`Error::UnsupportedType`] if the format of `data`
+    /// cannot be determined.
+    pub fn from_image_data(label: &str, data: Vec<u8>) -> Result<Self> {
+        let content_type = image_mime_from_bytes(&data).ok_or(Error::UnsupportedType)?;
+
+        Ok(Thumbnail {
+            data,
+            label: labels::add_thumbnail_format(label, content_type),
+            content_type: content_type.to_owned(),
+        })
+    }
 }
 
 impl AssertionBase for Thumbnail {
@@ -126,4 +142,21 @@ pub mod tests {
         let result = Thumbnail::from_assertion(&assertion);
         assert!(result.is_err())
     }
+
+    #[test]
+    fn assertion_thumbnail_from_png_data() {
+        let data = include_bytes!("../../tests/fixtures/sample1.png").to_vec();
+        let thumbnail = Thumbnail::from_image_data(labels::CLAIM_THUMBNAIL, data).unwrap();
+        assert_eq!(thumbnail.label, labels::PNG_CLAIM_THUMBNAIL);
+        assert_eq!(thumbnail.content_type, "image/png");
+
+        let assertion = thumbnail.to_assertion().unwrap();
+        assert_eq!(assertion.content_type(), "image/png");
+    }
+
+    #[test]
+    fn assertion_thumbnail_from_unknown_data() {
+        let result = Thumbnail::from_image_data(labels::CLAIM_THUMBNAIL, some_binary_data());
+        assert!(matches!(result, Err(Error::UnsupportedType)));
+    }
 }
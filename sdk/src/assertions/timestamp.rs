@@ -0,0 +1,101 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Time-stamp Assertion
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+use crate::{
+    assertion::{Assertion, AssertionBase, AssertionCbor},
+    assertions::labels,
+    error::Result,
+};
+
+/// The time-stamp assertion holds RFC 3161 time-stamp responses for manifests.
+///
+/// Each time-stamp is keyed by the label of the manifest it is for, and is computed over
+/// the `signature` field of that manifest's claim signature. Unlike the time-stamp in the
+/// claim signature's COSE header, it can be added after the manifest was signed, for
+/// example by a later manifest time-stamping an ingredient's manifest.
+///
+/// See <https://c2pa.org/specifications/specifications/2.1/specs/C2PA_Specification.html#_time_stamps>.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct TimeStamp(BTreeMap<String, ByteBuf>);
+
+impl TimeStamp {
+    /// Label prefix for a time-stamp assertion.
+    pub const LABEL: &'static str = labels::TIMESTAMP;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the DER encoded RFC 3161 time-stamp response for the manifest with the given label.
+    pub fn add_timestamp(&mut self, manifest_id: &str, timestamp: &[u8]) {
+        self.0
+            .insert(manifest_id.to_owned(), ByteBuf::from(timestamp.to_vec()));
+    }
+
+    /// Returns the time-stamp response for the manifest with the given label.
+    pub fn get_timestamp(&self, manifest_id: &str) -> Option<&[u8]> {
+        self.0.get(manifest_id).map(|ts| ts.as_slice())
+    }
+
+    /// Returns the labels of the time-stamped manifests and their time-stamp responses.
+    pub fn timestamps(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.0.iter().map(|(id, ts)| (id.as_str(), ts.as_slice()))
+    }
+}
+
+impl AssertionCbor for TimeStamp {}
+
+impl AssertionBase for TimeStamp {
+    const LABEL: &'static str = labels::TIMESTAMP;
+
+    fn to_assertion(&self) -> Result<Assertion> {
+        Self::to_cbor_assertion(self)
+    }
+
+    fn from_assertion(assertion: &Assertion) -> Result<Self> {
+        Self::from_cbor_assertion(assertion)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    #![allow(clippy::expect_used)]
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn timestamp_to_assertion() {
+        let mut original = TimeStamp::new();
+        original.add_timestamp("urn:uuid:1234", b"time-stamp response");
+
+        let assertion = original.to_assertion().expect("to_assertion");
+        assert_eq!(assertion.content_type(), "application/cbor");
+        assert_eq!(assertion.label(), TimeStamp::LABEL);
+
+        let result = TimeStamp::from_assertion(&assertion).expect("from_assertion");
+        assert_eq!(result, original);
+        assert_eq!(
+            result.get_timestamp("urn:uuid:1234"),
+            Some(b"time-stamp response".as_slice())
+        );
+        assert!(result.get_timestamp("urn:uuid:5678").is_none());
+        assert_eq!(result.timestamps().count(), 1);
+    }
+}
@@ -22,12 +22,11 @@ use std::{
 use atree::{Arena, Token};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use conv::ValueFrom;
-use tempfile::Builder;
 
 use crate::{
     assertions::{BmffMerkleMap, ExclusionsMap},
     asset_io::{
-        rename_or_move, AssetIO, AssetPatch, CAIRead, CAIReadWrite, CAIReader, CAIWriter,
+        rewrite_asset, AssetIO, AssetPatch, CAIRead, CAIReadWrite, CAIReader, CAIWriter,
         HashObjectPositions, RemoteRefEmbed, RemoteRefEmbedType,
     },
     error::{Error, Result},
@@ -436,6 +435,11 @@ fn get_top_level_boxes(
     tl_boxes
 }
 
+// Converts a file offset or length to a `usize`, which may be too small for it on 32-bit targets.
+fn to_usize(value: u64) -> Result<usize> {
+    usize::value_from(value).map_err(|_err| Error::InvalidAsset("value out of range".to_string()))
+}
+
 pub fn bmff_to_jumbf_exclusions<R>(
     reader: &mut R,
     bmff_exclusions: &[ExclusionsMap],
@@ -553,12 +557,12 @@ where
                 if let Some(subset_vec) = &bmff_exclusion.subset {
                     for subset in subset_vec {
                         let exclusion = HashRange::new(
-                            (exclusion_start + subset.offset as u64) as usize,
-                            (if subset.length == 0 {
+                            to_usize(exclusion_start + subset.offset as u64)?,
+                            to_usize(if subset.length == 0 {
                                 exclusion_length - subset.offset as u64
                             } else {
                                 min(subset.length as u64, exclusion_length)
-                            }) as usize,
+                            })?,
                         );
 
                         exclusions.push(exclusion);
@@ -566,7 +570,7 @@ where
                 } else {
                     // exclude box in its entirty
                     let exclusion =
-                        HashRange::new(exclusion_start as usize, exclusion_length as usize);
+                        HashRange::new(to_usize(exclusion_start)?, to_usize(exclusion_length)?);
 
                     exclusions.push(exclusion);
 
@@ -584,7 +588,7 @@ where
     // note: this is technically not an exclusion but a replacement with a new range of bytes to be hashed
     if bmff_v2 {
         for tl_start in tl_offsets {
-            let mut exclusion = HashRange::new(tl_start as usize, 1);
+            let mut exclusion = HashRange::new(to_usize(tl_start)?, 1);
             exclusion.set_bmff_offset(tl_start);
 
             exclusions.push(exclusion);
@@ -594,6 +598,29 @@ where
     Ok(exclusions)
 }
 
+// Adjusts an absolute file offset by the number of bytes added or removed before it.
+fn adjust_offset(offset: u64, adjust: i32) -> Result<u64> {
+    offset
+        .checked_add_signed(adjust.into())
+        .ok_or_else(|| Error::InvalidAsset("Bad BMFF offset adjustment".to_string()))
+}
+
+// Adjusts a 32-bit absolute file offset, failing if the result no longer fits in 32 bits.
+fn adjust_offset_u32(offset: u32, adjust: i32) -> Result<u32> {
+    u32::try_from(adjust_offset(offset.into(), adjust)?)
+        .map_err(|_| Error::InvalidAsset("Bad BMFF offset adjustment".to_string()))
+}
+
+// Returns the change in file offsets after a box of `old_size` bytes is replaced by `new_size` bytes.
+fn box_size_change(new_size: usize, old_size: usize) -> Result<i32> {
+    let change = if new_size >= old_size {
+        i32::try_from(new_size - old_size)
+    } else {
+        i32::try_from(old_size - new_size).map(|change| -change)
+    };
+    change.map_err(|_| Error::InvalidAsset("Bad BMFF offset adjustment".to_string()))
+}
+
 // `iloc`, `stco` and `co64` elements contain absolute file offsets so they need to be adjusted based on whether content was added or removed.
 // todo: when fragment support is added adjust these (/moof/iloc, /moof/mfro, /moof/traf/saio, /sidx)
 fn adjust_known_offsets<W: Write + CAIRead + ?Sized>(
@@ -633,18 +660,7 @@ fn adjust_known_offsets<W: Write + CAIRead + ?Sized>(
             let mut entries: Vec<u32> = Vec::new();
             for _e in 0..entry_count {
                 let offset = output.read_u32::<BigEndian>()?;
-                let new_offset = if adjust < 0 {
-                    offset
-                        - u32::try_from(adjust.abs()).map_err(|_| {
-                            Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                        })?
-                } else {
-                    offset
-                        + u32::try_from(adjust).map_err(|_| {
-                            Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                        })?
-                };
-                entries.push(new_offset);
+                entries.push(adjust_offset_u32(offset, adjust)?);
             }
 
             // write updated offsets
@@ -684,18 +700,7 @@ fn adjust_known_offsets<W: Write + CAIRead + ?Sized>(
             let mut entries: Vec<u64> = Vec::new();
             for _e in 0..entry_count {
                 let offset = output.read_u64::<BigEndian>()?;
-                let new_offset = if adjust < 0 {
-                    offset
-                        - u64::try_from(adjust.abs()).map_err(|_| {
-                            Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                        })?
-                } else {
-                    offset
-                        + u64::try_from(adjust).map_err(|_| {
-                            Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                        })?
-                };
-                entries.push(new_offset);
+                entries.push(adjust_offset(offset, adjust)?);
             }
 
             // write updated offsets
@@ -797,36 +802,19 @@ fn adjust_known_offsets<W: Write + CAIRead + ?Sized>(
                 if construction_method == 0 {
                     // file offset construction method
                     if base_offset_size == 4 {
-                        let new_offset = if adjust < 0 {
-                            u32::try_from(base_offset).map_err(|_| {
-                                Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                            })? - u32::try_from(adjust.abs()).map_err(|_| {
-                                Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                            })?
-                        } else {
+                        let new_offset = adjust_offset_u32(
                             u32::try_from(base_offset).map_err(|_| {
                                 Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                            })? + u32::try_from(adjust).map_err(|_| {
-                                Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                            })?
-                        };
+                            })?,
+                            adjust,
+                        )?;
 
                         output.seek(SeekFrom::Start(base_offset_file_pos))?;
                         output.write_u32::<BigEndian>(new_offset)?;
                     }
 
                     if base_offset_size == 8 {
-                        let new_offset = if adjust < 0 {
-                            base_offset
-                                - u64::try_from(adjust.abs()).map_err(|_| {
-                                    Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                                })?
-                        } else {
-                            base_offset
-                                + u64::try_from(adjust).map_err(|_| {
-                                    Error::InvalidAsset("Bad BMFF offset adjustment".to_string())
-                                })?
-                        };
+                        let new_offset = adjust_offset(base_offset, adjust)?;
 
                         output.seek(SeekFrom::Start(base_offset_file_pos))?;
                         output.write_u64::<BigEndian>(new_offset)?;
@@ -865,39 +853,18 @@ fn adjust_known_offsets<W: Write + CAIRead + ?Sized>(
                         output.seek(SeekFrom::Start(extent_offset_file_pos))?;
                         match offset_size {
                             4 => {
-                                let new_offset = if adjust < 0 {
-                                    extent_offset as u32
-                                        - u32::try_from(adjust.abs()).map_err(|_| {
-                                            Error::InvalidAsset(
-                                                "Bad BMFF offset adjustment".to_string(),
-                                            )
-                                        })?
-                                } else {
-                                    extent_offset as u32
-                                        + u32::try_from(adjust).map_err(|_| {
-                                            Error::InvalidAsset(
-                                                "Bad BMFF offset adjustment".to_string(),
-                                            )
-                                        })?
-                                };
+                                let new_offset = adjust_offset_u32(
+                                    u32::try_from(extent_offset).map_err(|_| {
+                                        Error::InvalidAsset(
+                                            "Bad BMFF offset adjustment".to_string(),
+                                        )
+                                    })?,
+                                    adjust,
+                                )?;
                                 output.write_u32::<BigEndian>(new_offset)?;
                             }
                             8 => {
-                                let new_offset = if adjust < 0 {
-                                    extent_offset
-                                        - u64::try_from(adjust.abs()).map_err(|_| {
-                                            Error::InvalidAsset(
-                                                "Bad BMFF offset adjustment".to_string(),
-                                            )
-                                        })?
-                                } else {
-                                    extent_offset
-                                        + u64::try_from(adjust).map_err(|_| {
-                                            Error::InvalidAsset(
-                                                "Bad BMFF offset adjustment".to_string(),
-                                            )
-                                        })?
-                                };
+                                let new_offset = adjust_offset(extent_offset, adjust)?;
                                 output.write_u64::<BigEndian>(new_offset)?;
                             }
                             _ => {
@@ -1252,11 +1219,119 @@ pub(crate) fn read_bmff_c2pa_boxes(reader: &mut dyn CAIRead) -> Result<C2PABmffB
     })
 }
 
+// Find the C2PA manifest store by walking only the top level boxes.  Box payloads
+// (mdat, moov, etc.) are skipped with a seek so only the headers and the manifest
+// itself are read from the stream.
+pub(crate) fn read_bmff_manifest_bytes(reader: &mut dyn CAIRead) -> Result<Option<Vec<u8>>> {
+    let Some((offset, len)) = find_bmff_manifest(reader)? else {
+        return Ok(None);
+    };
+
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut manifest = vec![0u8; len as usize];
+    reader.read_exact(&mut manifest)?;
+    Ok(Some(manifest))
+}
+
+// Returns the offset and length of the C2PA manifest store data, walking only the top
+// level boxes.
+fn find_bmff_manifest(reader: &mut dyn CAIRead) -> Result<Option<(u64, u64)>> {
+    let size = stream_len(reader)?;
+    reader.rewind()?;
+
+    let mut output: Option<(u64, u64)> = None;
+
+    let mut current = 0;
+    while current < size {
+        let header = BoxHeaderLite::read(reader)
+            .map_err(|err| Error::InvalidAsset(format!("Bad BMFF {}", err)))?;
+
+        let start = box_start(reader, header.large_size)?;
+
+        // a size of zero means the box runs to the end of the stream
+        let box_size = if header.size == 0 {
+            size - start
+        } else {
+            header.size
+        };
+        let header_size = if header.large_size {
+            HEADER_SIZE_LARGE
+        } else {
+            HEADER_SIZE
+        };
+
+        if box_size < header_size || start + box_size > size {
+            return Err(Error::InvalidAsset("Bad BMFF box size".to_string()));
+        }
+
+        if header.name == BoxType::UuidBox && box_size >= header_size + 16 + 4 {
+            let mut extended_type = [0u8; 16]; // 16 bytes of UUID
+            reader.read_exact(&mut extended_type)?;
+
+            if vec_compare(&C2PA_UUID, &extended_type) {
+                let mut data_len = box_size - header_size - 16 /*UUID*/;
+
+                // Fullbox => 8 bits for version 24 bits for flags
+                let (_version, _flags) = read_box_header_ext(reader)?;
+                data_len -= 4;
+
+                // get the purpose
+                let mut purpose = Vec::with_capacity(64);
+                loop {
+                    if data_len == 0 {
+                        return Err(Error::InvalidAsset("Bad BMFF C2PA box".to_string()));
+                    }
+                    let mut buf = [0; 1];
+                    reader.read_exact(&mut buf)?;
+                    data_len -= 1;
+                    if buf[0] == 0x00 {
+                        break;
+                    } else {
+                        purpose.push(buf[0]);
+                    }
+                }
+
+                // only the manifest box is needed, merkle boxes are skipped
+                if vec_compare(&purpose, MANIFEST.as_bytes()) {
+                    if output.is_some() {
+                        return Err(Error::TooManyManifestStores);
+                    }
+
+                    if data_len < 8 {
+                        return Err(Error::InvalidAsset("Bad BMFF C2PA box".to_string()));
+                    }
+
+                    // skip offset to first aux uuid
+                    let mut buf = [0u8; 8];
+                    reader.read_exact(&mut buf)?;
+                    data_len -= 8;
+
+                    output = Some((reader.stream_position()?, data_len));
+                }
+            }
+        }
+
+        // position seek pointer at the next top level box
+        current = skip_bytes_to(reader, start + box_size)?;
+    }
+
+    Ok(output)
+}
+
 impl CAIReader for BmffIO {
     fn read_cai(&self, reader: &mut dyn CAIRead) -> Result<Vec<u8>> {
-        let c2pa_boxes = read_bmff_c2pa_boxes(reader)?;
+        read_bmff_manifest_bytes(reader)?.ok_or(Error::JumbfNotFound)
+    }
+
+    fn read_cai_to_writer(&self, reader: &mut dyn CAIRead, writer: &mut dyn Write) -> Result<u64> {
+        let (offset, len) = find_bmff_manifest(reader)?.ok_or(Error::JumbfNotFound)?;
 
-        c2pa_boxes.manifest_bytes.ok_or(Error::JumbfNotFound)
+        reader.seek(SeekFrom::Start(offset))?;
+        let copied = std::io::copy(&mut Read::take(&mut *reader, len), writer)?;
+        if copied != len {
+            return Err(Error::InvalidAsset("Bad BMFF C2PA box".to_string()));
+        }
+        Ok(copied)
     }
 
     // Get XMP block
@@ -1283,15 +1358,9 @@ impl AssetIO for BmffIO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut input_stream, &mut temp_file, store_bytes)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut input_stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(
@@ -1305,15 +1374,9 @@ impl AssetIO for BmffIO {
     fn remove_cai_store(&self, asset_path: &Path) -> Result<()> {
         let mut input_file = std::fs::File::open(asset_path)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.remove_cai_store_from_stream(&mut input_file, &mut temp_file)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.remove_cai_store_from_stream(&mut input_file, output)
+        })
     }
 
     fn new(asset_type: &str) -> Self
@@ -1427,14 +1490,8 @@ impl CAIWriter for BmffIO {
         output_stream.write_all(&new_c2pa_box)?;
 
         // calc offset adjustments
-        let offset_adjust: i32 = if end == 0 {
-            new_c2pa_box_size as i32
-        } else {
-            // value could be negative if box is truncated
-            let existing_c2pa_box_size = end - start;
-            let pad_size: i32 = new_c2pa_box_size as i32 - existing_c2pa_box_size as i32;
-            pad_size
-        };
+        // value could be negative if box is truncated
+        let offset_adjust = box_size_change(new_c2pa_box_size, end - start)?;
 
         // write content after ContentProvenanceBox
         input_stream.seek(SeekFrom::Start(end as u64))?;
@@ -1549,9 +1606,7 @@ impl CAIWriter for BmffIO {
 
         // calc offset adjustments
         // value will be negative since the box is truncated
-        let new_c2pa_box_size: i32 = 0;
-        let existing_c2pa_box_size = end - start;
-        let offset_adjust = new_c2pa_box_size - existing_c2pa_box_size as i32;
+        let offset_adjust = box_size_change(0, end - start)?;
 
         // write content after ContentProvenanceBox
         input_stream.seek(SeekFrom::Start(end as u64))?;
@@ -1791,14 +1846,8 @@ impl RemoteRefEmbed for BmffIO {
                 output_stream.write_all(&new_xmp_box)?;
 
                 // calc offset adjustments
-                let offset_adjust: i32 = if end == 0 {
-                    new_xmp_box_size as i32
-                } else {
-                    // value could be negative if box is truncated
-                    let existing_xmp_box_size = end - start;
-                    let pad_size: i32 = new_xmp_box_size as i32 - existing_xmp_box_size as i32;
-                    pad_size
-                };
+                // value could be negative if box is truncated
+                let offset_adjust = box_size_change(new_xmp_box_size, end - start)?;
 
                 // write content after XMP box
                 input_stream.seek(SeekFrom::Start(end as u64))?;
@@ -1856,7 +1905,10 @@ pub mod tests {
     use tempfile::tempdir;
 
     use super::*;
-    use crate::utils::test::{fixture_path, temp_dir_path};
+    use crate::{
+        assertions::DataMap,
+        utils::test::{fixture_path, temp_dir_path},
+    };
 
     #[cfg(not(target_arch = "wasm32"))]
     #[cfg(feature = "file_io")]
@@ -2002,4 +2054,179 @@ pub mod tests {
             _ => unreachable!(),
         }
     }
+
+    // counts the number of bytes actually read from the wrapped stream
+    struct CountingReader<R> {
+        inner: R,
+        bytes_read: u64,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.bytes_read += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl<R: Seek> Seek for CountingReader<R> {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn test_read_cai_skips_media_boxes() {
+        let source = fixture_path("video1.mp4");
+        let mut data = std::fs::read(source).unwrap();
+
+        let bmff = BmffIO::new("mp4");
+        let expected = bmff.read_cai(&mut Cursor::new(&data)).unwrap();
+
+        // append a large free box to simulate a large media payload
+        let pad_size: u32 = 64 * 1024 * 1024;
+        data.extend_from_slice(&pad_size.to_be_bytes());
+        data.extend_from_slice(b"free");
+        data.resize(data.len() + pad_size as usize - 8, 0);
+
+        let mut reader = CountingReader {
+            inner: Cursor::new(&data),
+            bytes_read: 0,
+        };
+        let manifest = bmff.read_cai(&mut reader).unwrap();
+
+        assert_eq!(manifest, expected);
+        assert!(reader.bytes_read < (manifest.len() + 64 * 1024) as u64);
+    }
+
+    #[test]
+    fn test_read_cai_to_writer_matches_read_cai() {
+        let data = std::fs::read(fixture_path("video1.mp4")).unwrap();
+        let bmff = BmffIO::new("mp4");
+
+        let mut streamed = Vec::new();
+        let len = bmff
+            .read_cai_to_writer(&mut Cursor::new(&data), &mut streamed)
+            .unwrap();
+        assert_eq!(len, streamed.len() as u64);
+        assert_eq!(streamed, bmff.read_cai(&mut Cursor::new(&data)).unwrap());
+
+        // a lone ftyp box
+        let no_manifest = [&16u32.to_be_bytes()[..], b"ftypisom", &[0; 4]].concat();
+        assert!(matches!(
+            bmff.read_cai_to_writer(&mut Cursor::new(&no_manifest), &mut std::io::sink()),
+            Err(Error::JumbfNotFound)
+        ));
+    }
+
+    // A stream of `prefix`, then `gap` zero bytes, then `suffix`, without storing the gap.
+    struct SparseStream {
+        prefix: Vec<u8>,
+        gap: u64,
+        suffix: Vec<u8>,
+        pos: u64,
+    }
+
+    impl SparseStream {
+        fn len(&self) -> u64 {
+            self.prefix.len() as u64 + self.gap + self.suffix.len() as u64
+        }
+    }
+
+    impl Read for SparseStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let suffix_start = self.prefix.len() as u64 + self.gap;
+            let mut read = 0;
+            for b in buf.iter_mut() {
+                if self.pos >= self.len() {
+                    break;
+                }
+                *b = if self.pos < self.prefix.len() as u64 {
+                    self.prefix[self.pos as usize]
+                } else if self.pos < suffix_start {
+                    0
+                } else {
+                    self.suffix[(self.pos - suffix_start) as usize]
+                };
+                self.pos += 1;
+                read += 1;
+            }
+            Ok(read)
+        }
+    }
+
+    impl Seek for SparseStream {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.pos = match pos {
+                SeekFrom::Start(pos) => pos,
+                SeekFrom::End(offset) => self.len().saturating_add_signed(offset),
+                SeekFrom::Current(offset) => self.pos.saturating_add_signed(offset),
+            };
+            Ok(self.pos)
+        }
+    }
+
+    #[test]
+    fn test_offsets_past_4gb() {
+        let mdat_size: u64 = 5 * 1024 * 1024 * 1024;
+
+        let mut prefix = Vec::new();
+        BoxHeaderLite::new(BoxType::FtypBox, 16, "ftyp")
+            .write(&mut prefix)
+            .unwrap();
+        prefix.extend_from_slice(b"isom\0\0\0\0");
+        // a media box with a 64-bit size, written with a large size header
+        prefix.extend_from_slice(&1u32.to_be_bytes());
+        prefix.extend_from_slice(b"mdat");
+        prefix.extend_from_slice(&mdat_size.to_be_bytes());
+
+        let manifest = b"not really a manifest".to_vec();
+        let mut suffix = Vec::new();
+        write_c2pa_box(&mut suffix, &manifest, true, &[]).unwrap();
+
+        let mut stream = SparseStream {
+            gap: mdat_size - 16,
+            prefix,
+            suffix,
+            pos: 0,
+        };
+        let c2pa_offset = 16 + mdat_size;
+        assert!(c2pa_offset > u32::MAX as u64);
+
+        let bmff = BmffIO::new("mp4");
+        assert_eq!(bmff.read_cai(&mut stream).unwrap(), manifest);
+
+        let mut uuid = ExclusionsMap::new("/uuid".to_owned());
+        uuid.data = Some(vec![DataMap {
+            offset: 8,
+            value: C2PA_UUID.to_vec(),
+        }]);
+
+        let exclusions = bmff_to_jumbf_exclusions(&mut stream, &[uuid], false);
+        match usize::try_from(c2pa_offset) {
+            Ok(start) => {
+                let exclusions = exclusions.unwrap();
+                assert_eq!(exclusions.len(), 1);
+                assert_eq!(exclusions[0].start(), start);
+                assert_eq!(exclusions[0].length(), stream.suffix.len());
+            }
+            // the offset can't be represented on 32-bit targets
+            Err(_) => assert!(exclusions.is_err()),
+        }
+    }
+
+    #[test]
+    fn test_adjust_offset() {
+        let offset = 5 * 1024 * 1024 * 1024;
+        assert_eq!(adjust_offset(offset, 100).unwrap(), offset + 100);
+        assert_eq!(adjust_offset(offset, -100).unwrap(), offset - 100);
+        assert!(adjust_offset(10, -100).is_err());
+
+        assert_eq!(adjust_offset_u32(1000, -100).unwrap(), 900);
+        assert!(adjust_offset_u32(u32::MAX - 10, 100).is_err());
+
+        assert_eq!(box_size_change(100, 40).unwrap(), 60);
+        assert_eq!(box_size_change(40, 100).unwrap(), -60);
+        assert!(box_size_change(usize::MAX, 0).is_err());
+    }
 }
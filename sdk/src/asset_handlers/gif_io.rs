@@ -20,7 +20,6 @@ use std::{
 
 use byteorder::{ReadBytesExt, WriteBytesExt};
 use serde_bytes::ByteBuf;
-use tempfile::Builder;
 
 use crate::{
     assertions::{BoxMap, C2PA_BOXHASH},
@@ -344,14 +343,9 @@ impl AssetIO for GifIO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut stream, &mut temp_file, store_bytes)?;
-
-        asset_io::rename_or_move(temp_file, asset_path)
+        asset_io::rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(&self, asset_path: &Path) -> Result<Vec<HashObjectPositions>> {
@@ -365,14 +359,9 @@ impl AssetIO for GifIO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.remove_cai_store_from_stream(&mut stream, &mut temp_file)?;
-
-        asset_io::rename_or_move(temp_file, asset_path)
+        asset_io::rewrite_asset(asset_path, |output| {
+            self.remove_cai_store_from_stream(&mut stream, output)
+        })
     }
 
     fn supported_types(&self) -> &[&str] {
@@ -27,16 +27,16 @@ use img_parts::{
     Bytes, DynImage,
 };
 use serde_bytes::ByteBuf;
-use tempfile::Builder;
 
 use crate::{
     assertions::{BoxMap, C2PA_BOXHASH},
     asset_io::{
-        rename_or_move, AssetBoxHash, AssetIO, CAIRead, CAIReadWrite, CAIReader, CAIWriter,
+        rewrite_asset, AssetBoxHash, AssetIO, CAIRead, CAIReadWrite, CAIReader, CAIWriter,
         ComposedManifestRef, HashBlockObjectType, HashObjectPositions, RemoteRefEmbed,
         RemoteRefEmbedType,
     },
     error::{Error, Result},
+    settings::get_settings_value,
     utils::xmp_inmemory_utils::{add_provenance, MIN_XMP},
 };
 
@@ -49,6 +49,17 @@ const MAX_JPEG_MARKER_SIZE: usize = 64000; // technically it's 64K but a bit sma
 
 const C2PA_MARKER: [u8; 4] = [0x63, 0x32, 0x70, 0x61];
 
+// Returns the first four bytes of a manifest store UUID given as 32 hex digits, which identify
+// its JUMBF box in APP11 segments.
+fn uuid_marker(uuid: &str) -> Option<[u8; 4]> {
+    hex::decode(uuid.get(..8)?).ok()?.try_into().ok()
+}
+
+// Returns true if the JUMBF box type identifies a manifest store with one of the markers.
+fn is_store_marker(markers: &[[u8; 4]], jumb_type: &[u8]) -> bool {
+    markers.iter().any(|marker| vec_compare(marker, jumb_type))
+}
+
 fn vec_compare(va: &[u8], vb: &[u8]) -> bool {
     (va.len() == vb.len()) &&  // zip stops at the shortest
      va.iter()
@@ -81,6 +92,7 @@ fn xmp_from_bytes(asset_bytes: &[u8]) -> Option<String> {
 fn add_required_segs_to_stream(
     input_stream: &mut dyn CAIRead,
     output_stream: &mut dyn CAIReadWrite,
+    markers: &[[u8; 4]],
 ) -> Result<()> {
     let mut buf: Vec<u8> = Vec::new();
     input_stream.rewind()?;
@@ -92,13 +104,13 @@ fn add_required_segs_to_stream(
 
     if let Some(DynImage::Jpeg(jpeg)) = dimg_opt {
         // check for JUMBF Seg
-        let cai_app11 = get_cai_segments(&jpeg)?; // make sure we only check for C2PA segments
+        let cai_app11 = get_cai_segments(&jpeg, markers)?; // make sure we only check for C2PA segments
 
         if cai_app11.is_empty() {
             // create dummy JUMBF seg
             let mut no_bytes: Vec<u8> = vec![0; 50]; // enough bytes to be valid
             no_bytes.splice(16..20, C2PA_MARKER); // cai UUID signature
-            let aio = JpegIO {};
+            let aio = JpegIO::new("");
             aio.write_cai(input_stream, output_stream, &no_bytes)?;
         } else {
             // just clone
@@ -114,7 +126,7 @@ fn add_required_segs_to_stream(
 }
 
 // all cai specific segments
-fn get_cai_segments(jpeg: &img_parts::jpeg::Jpeg) -> Result<Vec<usize>> {
+fn get_cai_segments(jpeg: &img_parts::jpeg::Jpeg, markers: &[[u8; 4]]) -> Result<Vec<usize>> {
     let mut cai_segs: Vec<usize> = Vec::new();
 
     let segments = jpeg.segments();
@@ -142,7 +154,7 @@ fn get_cai_segments(jpeg: &img_parts::jpeg::Jpeg) -> Result<Vec<usize>> {
             } else {
                 // check if this is a CAI JUMBF block
                 let jumb_type = &raw_vec.as_mut_slice()[24..28];
-                let is_cai = vec_compare(&C2PA_MARKER, jumb_type);
+                let is_cai = is_store_marker(markers, jumb_type);
                 if is_cai {
                     cai_segs.push(i);
                     cai_seg_cnt = 1;
@@ -156,8 +168,8 @@ fn get_cai_segments(jpeg: &img_parts::jpeg::Jpeg) -> Result<Vec<usize>> {
 }
 
 // delete cai segments
-fn delete_cai_segments(jpeg: &mut img_parts::jpeg::Jpeg) -> Result<()> {
-    let cai_segs = get_cai_segments(jpeg)?;
+fn delete_cai_segments(jpeg: &mut img_parts::jpeg::Jpeg, markers: &[[u8; 4]]) -> Result<()> {
+    let cai_segs = get_cai_segments(jpeg, markers)?;
     let jpeg_segs = jpeg.segments_mut();
 
     // remove cai segments
@@ -167,10 +179,64 @@ fn delete_cai_segments(jpeg: &mut img_parts::jpeg::Jpeg) -> Result<()> {
     Ok(())
 }
 
-pub struct JpegIO {}
+// Returns the JPEG with its CAI segments spliced out, leaving all other bytes unchanged.
+fn splice_out_cai_segments(buf: Bytes, markers: &[[u8; 4]]) -> Result<Vec<u8>> {
+    let jpeg = Jpeg::from_bytes(buf.clone()).map_err(|_err| Error::EmbeddingError)?;
+    let cai_segs = get_cai_segments(&jpeg, markers)?;
+
+    let mut output = Vec::with_capacity(buf.len());
+    let mut offset = 2; // start after JPEG marker
+    let mut copied = 0;
+    for (i, seg) in jpeg.segments().iter().enumerate() {
+        let len = seg.len_with_entropy();
+        if cai_segs.contains(&i) {
+            // make sure the segment is where we expect it before cutting it out
+            if buf.get(offset..offset + 2) != Some(&[P, markers::APP11][..]) {
+                return Err(Error::InvalidAsset(
+                    "JPEG segment not at expected offset".to_owned(),
+                ));
+            }
+            output.extend_from_slice(&buf[copied..offset]);
+            copied = offset + len;
+        }
+        offset += len;
+    }
+    output.extend_from_slice(&buf[copied..]);
+
+    Ok(output)
+}
+
+pub struct JpegIO {
+    // marker of the private manifest store this writer embeds, if any
+    manifest_store_marker: Option<[u8; 4]>,
+}
+
+impl JpegIO {
+    // Returns the markers of the manifest stores to locate: the standard "c2pa" one, that of
+    // the private manifest store configured in the settings, and that of the private manifest
+    // store this writer embeds. The settings are read once per operation.
+    fn store_markers(&self) -> Vec<[u8; 4]> {
+        let configured = get_settings_value::<Option<String>>("core.manifest_store_uuid")
+            .ok()
+            .flatten()
+            .and_then(|uuid| uuid_marker(&uuid));
+
+        let mut markers = vec![C2PA_MARKER];
+        for marker in [configured, self.manifest_store_marker]
+            .into_iter()
+            .flatten()
+        {
+            if !markers.contains(&marker) {
+                markers.push(marker);
+            }
+        }
+        markers
+    }
+}
 
 impl CAIReader for JpegIO {
     fn read_cai(&self, asset_reader: &mut dyn CAIRead) -> Result<Vec<u8>> {
+        let markers = self.store_markers();
         let mut buffer: Vec<u8> = Vec::new();
 
         let mut manifest_store_cnt = 0;
@@ -218,7 +284,7 @@ impl CAIReader for JpegIO {
                                 // must be at least 28 bytes for this to be a valid JUMBF box
                                 // check if this is a CAI JUMBF block
                                 let jumb_type = &raw_vec.as_mut_slice()[24..28];
-                                let is_cai = vec_compare(&C2PA_MARKER, jumb_type);
+                                let is_cai = is_store_marker(&markers, jumb_type);
 
                                 if is_cai {
                                     if manifest_store_cnt == 1 {
@@ -273,7 +339,7 @@ impl CAIWriter for JpegIO {
         let mut jpeg = Jpeg::from_bytes(buf.into()).map_err(|_err| Error::EmbeddingError)?;
 
         // remove existing CAI segments
-        delete_cai_segments(&mut jpeg)?;
+        delete_cai_segments(&mut jpeg, &self.store_markers())?;
 
         let jumbf_len = store_bytes.len();
         let num_segments = (jumbf_len / MAX_JPEG_MARKER_SIZE) + 1;
@@ -333,6 +399,7 @@ impl CAIWriter for JpegIO {
         &self,
         input_stream: &mut dyn CAIRead,
     ) -> Result<Vec<HashObjectPositions>> {
+        let markers = self.store_markers();
         let mut cai_en: Vec<u8> = Vec::new();
         let mut cai_seg_cnt: u32 = 0;
 
@@ -342,7 +409,7 @@ impl CAIWriter for JpegIO {
         let output_vec: Vec<u8> = Vec::new();
         let mut output_stream = Cursor::new(output_vec);
         // make sure the file has the required segments so we can generate all the required offsets
-        add_required_segs_to_stream(input_stream, &mut output_stream)?;
+        add_required_segs_to_stream(input_stream, &mut output_stream, &markers)?;
 
         let buf: Vec<u8> = output_stream.into_inner();
 
@@ -378,7 +445,7 @@ impl CAIWriter for JpegIO {
                                 } else {
                                     // check if this is a CAI JUMBF block
                                     let jumb_type = raw_vec.as_mut_slice()[24..28].to_vec();
-                                    let is_cai = vec_compare(&C2PA_MARKER, &jumb_type);
+                                    let is_cai = is_store_marker(&markers, &jumb_type);
                                     if is_cai {
                                         cai_seg_cnt = 1;
                                         cai_en.clone_from(&en); // store the identifier
@@ -432,6 +499,10 @@ impl CAIWriter for JpegIO {
         Ok(positions)
     }
 
+    fn set_manifest_store_uuid(&mut self, uuid: &str) {
+        self.manifest_store_marker = uuid_marker(uuid);
+    }
+
     fn remove_cai_store_from_stream(
         &self,
         input_stream: &mut dyn CAIRead,
@@ -441,15 +512,12 @@ impl CAIWriter for JpegIO {
         // read the whole asset
         input_stream.rewind()?;
         input_stream.read_to_end(&mut buf).map_err(Error::IoError)?;
-        let mut jpeg = Jpeg::from_bytes(buf.into()).map_err(|_err| Error::EmbeddingError)?;
 
         // remove existing CAI segments
-        delete_cai_segments(&mut jpeg)?;
+        let output = splice_out_cai_segments(buf.into(), &self.store_markers())?;
 
         output_stream.rewind()?;
-        jpeg.encoder()
-            .write_to(output_stream)
-            .map_err(|_err| Error::InvalidAsset("JPEG write error".to_owned()))?;
+        output_stream.write_all(&output)?;
 
         Ok(())
     }
@@ -469,15 +537,9 @@ impl AssetIO for JpegIO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut input_stream, &mut temp_file, store_bytes)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut input_stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(&self, asset_path: &Path) -> Result<Vec<HashObjectPositions>> {
@@ -493,22 +555,11 @@ impl AssetIO for JpegIO {
     fn remove_cai_store(&self, asset_path: &Path) -> Result<()> {
         let input = std::fs::read(asset_path).map_err(Error::IoError)?;
 
-        let mut jpeg = Jpeg::from_bytes(input.into()).map_err(|_err| Error::EmbeddingError)?;
-
         // remove existing CAI segments
-        delete_cai_segments(&mut jpeg)?;
+        let output = splice_out_cai_segments(input.into(), &self.store_markers())?;
 
         // save updated file
-        let output = std::fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .truncate(true)
-            .open(asset_path)
-            .map_err(Error::IoError)?;
-
-        jpeg.encoder()
-            .write_to(output)
-            .map_err(|_err| Error::InvalidAsset("JPEG write error".to_owned()))?;
+        std::fs::write(asset_path, output)?;
 
         Ok(())
     }
@@ -517,7 +568,9 @@ impl AssetIO for JpegIO {
     where
         Self: Sized,
     {
-        JpegIO {}
+        JpegIO {
+            manifest_store_marker: None,
+        }
     }
 
     fn get_handler(&self, asset_type: &str) -> Box<dyn AssetIO> {
@@ -688,7 +741,7 @@ fn get_seg_size(input_stream: &mut dyn CAIRead) -> Result<usize> {
     }
 }
 
-fn make_box_maps(input_stream: &mut dyn CAIRead) -> Result<Vec<BoxMap>> {
+fn make_box_maps(input_stream: &mut dyn CAIRead, markers: &[[u8; 4]]) -> Result<Vec<BoxMap>> {
     let segment_names = HashMap::from([
         (0xe0u8, "APP0"),
         (0xe1u8, "APP1"),
@@ -799,7 +852,7 @@ fn make_box_maps(input_stream: &mut dyn CAIRead) -> Result<Vec<BoxMap>> {
                     } else {
                         // check if this is a CAI JUMBF block
                         let jumb_type = raw_vec.as_mut_slice()[24..28].to_vec();
-                        let is_cai = vec_compare(&C2PA_MARKER, &jumb_type);
+                        let is_cai = is_store_marker(markers, &jumb_type);
                         if is_cai {
                             cai_seg_cnt = 1;
                             cai_en.clone_from(&en); // store the identifier
@@ -978,7 +1031,7 @@ fn make_box_maps(input_stream: &mut dyn CAIRead) -> Result<Vec<BoxMap>> {
 
 impl AssetBoxHash for JpegIO {
     fn get_box_map(&self, input_stream: &mut dyn CAIRead) -> Result<Vec<BoxMap>> {
-        let mut box_maps = make_box_maps(input_stream)?;
+        let mut box_maps = make_box_maps(input_stream, &self.store_markers())?;
 
         for bm in box_maps.iter_mut() {
             if bm.names[0] == C2PA_BOXHASH {
@@ -1112,7 +1165,7 @@ pub mod tests {
         let output = crate::utils::test::temp_dir_path(&temp_dir, "CA_test.jpg");
 
         std::fs::copy(source, &output).unwrap();
-        let jpeg_io = JpegIO {};
+        let jpeg_io = JpegIO::new("");
 
         jpeg_io.remove_cai_store(&output).unwrap();
 
@@ -1130,7 +1183,7 @@ pub mod tests {
         let source_bytes = std::fs::read(source).unwrap();
         let mut source_stream = Cursor::new(source_bytes);
 
-        let jpeg_io = JpegIO {};
+        let jpeg_io = JpegIO::new("");
         let jpg_writer = jpeg_io.get_writer("jpg").unwrap();
 
         let output_bytes = Vec::new();
@@ -1148,6 +1201,37 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_remove_c2pa_from_stream_keeps_other_bytes() {
+        let source = include_bytes!("../../tests/fixtures/CA.jpg");
+
+        let jpeg_io = JpegIO::new("");
+        let locations = jpeg_io
+            .get_object_locations_from_stream(&mut Cursor::new(source))
+            .unwrap();
+        let cai: Vec<_> = locations
+            .iter()
+            .filter(|l| l.htype == HashBlockObjectType::Cai)
+            .collect();
+        assert!(!cai.is_empty());
+
+        // the expected output is the source with only the CAI segments cut out
+        let mut expected = Vec::new();
+        let mut copied = 0;
+        for l in &cai {
+            expected.extend_from_slice(&source[copied..l.offset]);
+            copied = l.offset + l.length;
+        }
+        expected.extend_from_slice(&source[copied..]);
+
+        let mut output_stream = Cursor::new(Vec::new());
+        jpeg_io
+            .remove_cai_store_from_stream(&mut Cursor::new(source), &mut output_stream)
+            .unwrap();
+
+        assert_eq!(output_stream.into_inner(), expected);
+    }
+
     #[test]
     fn test_xmp_read_write() {
         let source = crate::utils::test::fixture_path("CA.jpg");
@@ -1218,7 +1302,7 @@ pub mod tests {
 
     #[test]
     fn test_embeddable_manifest() {
-        let jpeg_io = JpegIO {};
+        let jpeg_io = JpegIO::new("");
 
         let source = crate::utils::test::fixture_path("CA.jpg");
 
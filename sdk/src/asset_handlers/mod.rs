@@ -30,3 +30,5 @@ pub mod tiff_io;
 pub(crate) mod pdf;
 #[cfg(feature = "pdf")]
 pub mod pdf_io;
+#[cfg(feature = "pdf")]
+pub(crate) mod pdf_scan;
@@ -24,11 +24,10 @@ use id3::{
     *,
 };
 use memchr::memmem;
-use tempfile::Builder;
 
 use crate::{
     asset_io::{
-        rename_or_move, AssetIO, AssetPatch, CAIRead, CAIReadWrapper, CAIReadWrite,
+        rewrite_asset, AssetIO, AssetPatch, CAIRead, CAIReadWrapper, CAIReadWrite,
         CAIReadWriteWrapper, CAIReader, CAIWriter, HashBlockObjectType, HashObjectPositions,
         RemoteRefEmbed, RemoteRefEmbedType,
     },
@@ -48,7 +47,7 @@ const GEOB_FRAME_DESCRIPTION: &str = "c2pa manifest store";
 struct ID3V2Header {
     _version_major: u8,
     _version_minor: u8,
-    _flags: u8,
+    flags: u8,
     tag_size: u32,
 }
 
@@ -77,13 +76,31 @@ impl ID3V2Header {
         Ok(ID3V2Header {
             _version_major: version_major,
             _version_minor: version_minor,
-            _flags: flags,
+            flags,
             tag_size,
         })
     }
 
     pub fn get_size(&self) -> u32 {
-        self.tag_size + 10
+        // ID3v2.4 tags may be followed by a 10 byte footer
+        if self.flags & 0x10 != 0 {
+            self.tag_size + 20
+        } else {
+            self.tag_size + 10
+        }
+    }
+
+    // Returns the size of the ID3v2 tag at the start of the stream, or 0 if there is none.
+    fn read_tag_size(reader: &mut dyn CAIRead) -> Result<u32> {
+        reader.rewind()?;
+        let size = match ID3V2Header::read_header(reader) {
+            Ok(header) => header.get_size(),
+            Err(Error::UnsupportedType) => 0,
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => 0,
+            Err(e) => return Err(e),
+        };
+        reader.rewind()?;
+        Ok(size)
     }
 
     fn decode_tag_size(n: u32) -> u32 {
@@ -188,10 +205,7 @@ impl RemoteRefEmbed for Mp3IO {
     ) -> Result<()> {
         match embed_ref {
             RemoteRefEmbedType::Xmp(url) => {
-                source_stream.rewind()?;
-
-                let header = ID3V2Header::read_header(source_stream)?;
-                source_stream.rewind()?;
+                let tag_size = ID3V2Header::read_tag_size(source_stream)?;
 
                 let mut out_tag = Tag::new();
 
@@ -237,7 +251,7 @@ impl RemoteRefEmbed for Mp3IO {
                     .write_to(writer, Version::Id3v24)
                     .map_err(|_e| Error::EmbeddingError)?;
 
-                source_stream.seek(SeekFrom::Start(header.get_size() as u64))?;
+                source_stream.seek(SeekFrom::Start(tag_size as u64))?;
                 std::io::copy(source_stream, output_stream)?;
 
                 Ok(())
@@ -306,15 +320,9 @@ impl AssetIO for Mp3IO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut input_stream, &mut temp_file, store_bytes)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut input_stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(
@@ -346,10 +354,8 @@ impl CAIWriter for Mp3IO {
         output_stream: &mut dyn CAIReadWrite,
         store_bytes: &[u8],
     ) -> Result<()> {
-        input_stream.rewind()?;
-
-        let header = ID3V2Header::read_header(input_stream)?;
-        input_stream.rewind()?;
+        // files without an ID3v2 tag (for example with only an ID3v1 tag) get a new one
+        let tag_size = ID3V2Header::read_tag_size(input_stream)?;
 
         let mut out_tag = Tag::new();
 
@@ -400,8 +406,8 @@ impl CAIWriter for Mp3IO {
             .write_to(writer, Version::Id3v24)
             .map_err(|_e| Error::EmbeddingError)?;
 
-        // skip past old ID3V2
-        input_stream.seek(SeekFrom::Start(header.get_size() as u64))?;
+        // skip past old ID3V2, any ID3v1 tag at the end is copied unchanged
+        input_stream.seek(SeekFrom::Start(tag_size as u64))?;
 
         // copy source data to output
         std::io::copy(input_stream, output_stream)?;
@@ -556,6 +562,66 @@ pub mod tests {
         assert!(success)
     }
 
+    #[test]
+    fn test_write_mp3_with_id3v1_only() {
+        let source = include_bytes!("../../tests/fixtures/sample-id3v1.mp3");
+        let mp3_io = Mp3IO::new("mp3");
+
+        let mut output = Cursor::new(Vec::new());
+        mp3_io
+            .write_cai(&mut Cursor::new(source.to_vec()), &mut output, &[1, 2, 3])
+            .unwrap();
+
+        assert_eq!(mp3_io.read_cai(&mut output).unwrap(), vec![1, 2, 3]);
+
+        // the audio frames and the ID3v1 tag follow the new ID3v2 tag unchanged
+        let output = output.into_inner();
+        assert!(output.ends_with(source));
+        assert_eq!(&output[output.len() - 128..][..3], b"TAG");
+    }
+
+    #[test]
+    fn test_sign_mp3_detects_audio_tampering() {
+        use crate::{
+            status_tracker::{report_split_errors, DetailedStatusTracker, StatusTracker},
+            store::Store,
+            utils::test::{create_test_claim, temp_signer},
+            validation_status,
+        };
+
+        let source = include_bytes!("../../tests/fixtures/sample-id3v1.mp3");
+
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+
+        let signer = temp_signer();
+        let mut output = Cursor::new(Vec::new());
+        store
+            .save_to_stream(
+                "mp3",
+                &mut Cursor::new(source.to_vec()),
+                &mut output,
+                signer.as_ref(),
+            )
+            .unwrap();
+        let mut signed = output.into_inner();
+        assert!(signed.ends_with(source));
+
+        let mut report = DetailedStatusTracker::new();
+        Store::load_from_memory("mp3", &signed, true, &mut report).unwrap();
+        assert!(report_split_errors(report.get_log_mut()).is_empty());
+
+        // change a byte in the last audio frame, before the ID3v1 tag
+        let audio_byte = signed.len() - 128 - 100;
+        signed[audio_byte] ^= 0xff;
+
+        let mut report = DetailedStatusTracker::new();
+        Store::load_from_memory("mp3", &signed, true, &mut report).unwrap();
+        let errors = report_split_errors(report.get_log_mut());
+        assert!(errors.iter().any(|item| item.validation_status.as_deref()
+            == Some(validation_status::ASSERTION_DATAHASH_MISMATCH)));
+    }
+
     #[test]
     fn test_remove_c2pa() {
         let source = fixture_path("sample1.mp3");
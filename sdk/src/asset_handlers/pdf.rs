@@ -14,13 +14,20 @@
 // TODO: Remove this after we finish the PDF write feature.
 #![allow(dead_code)]
 
-use std::io::{Read, Write};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
 
+use chrono::{DateTime, Utc};
 use lopdf::{
-    dictionary, Document, Object,
+    dictionary,
+    xref::XrefEntry,
+    Dictionary, Document, Object,
     Object::{Array, Integer, Name, Reference},
-    ObjectId, Stream,
+    ObjectId, Stream, StringFormat,
 };
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 // Associated File Relationship
@@ -33,6 +40,30 @@ static EMBEDDED_FILES_KEY: &[u8] = b"EmbeddedFiles";
 static SUBTYPE_KEY: &[u8] = b"Subtype";
 static TYPE_KEY: &[u8] = b"Type";
 static NAMES_KEY: &[u8] = b"Names";
+static STRUCT_TREE_ROOT_KEY: &[u8] = b"StructTreeRoot";
+static STRUCT_PARENT_KEY: &[u8] = b"StructParent";
+static CONTENTS_KEY: &[u8] = b"Contents";
+
+// The catalog entries that mark a PDF as tagged.
+static TAGGED_CATALOG_KEYS: [&str; 2] = ["StructTreeRoot", "MarkInfo"];
+
+// Keys of structure tree dictionaries that refer back to parents, pages and content rather than
+// to further parts of the structure tree.
+static STRUCTURE_BACK_REFERENCE_KEYS: [&[u8]; 5] = [b"P", b"Pg", b"Obj", b"Stm", b"StmOwn"];
+
+// Values allowed for an `AFRelationship`. `C2PA_Manifest` is the relationship defined by the C2PA
+// specification, the remaining values are the ones defined by ISO 32000-2.
+static ALLOWED_AF_RELATIONSHIPS: [&str; 9] = [
+    "C2PA_Manifest",
+    "Source",
+    "Data",
+    "Alternative",
+    "Supplement",
+    "EncryptedPayload",
+    "FormData",
+    "Schema",
+    "Unspecified",
+];
 
 /// Error representing failure scenarios while interacting with PDFs.
 #[derive(Debug, Error)]
@@ -42,6 +73,19 @@ pub enum Error {
     #[error(transparent)]
     UnableToReadPdf(#[from] lopdf::Error),
 
+    /// An object of the PDF could not be read. `object_id` identifies the offending object and
+    /// `offset` is the byte offset the PDF's cross-reference table records for it, if any.
+    #[error("Unable to read PDF object {} {} (offset: {:?}): {source}", .object_id.0, .object_id.1, .offset)]
+    UnableToReadObject {
+        object_id: ObjectId,
+        offset: Option<usize>,
+        source: lopdf::Error,
+    },
+
+    /// Error occurred while reading the PDF's bytes from its source.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     /// No Manifest is present in the PDF.
     #[error("No manifest is present in the PDF.")]
     NoManifest,
@@ -59,15 +103,98 @@ pub enum Error {
     /// file specification in the array of Associated Files defined in the catalog.
     #[error("Unable to find a C2PA embedded file specification in PDF's associated files array")]
     FindingC2PAFileSpec,
+
+    /// The requested `AFRelationship` is not one of the values allowed by the PDF or C2PA
+    /// specifications.
+    #[error("Invalid AFRelationship value: {0}")]
+    InvalidAFRelationship(String),
+
+    /// The PDF's `/EmbeddedFiles` name tree is malformed, so the C2PA manifest's file
+    /// specification could not be added to it.
+    #[error("The PDF's embedded files name tree is malformed.")]
+    InvalidNameTree,
+}
+
+/// Options controlling how the C2PA manifest's embedded file specification is written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EmbeddedFileOptions {
+    /// Name of the embedded file, used for the `/F` and `/UF` entries and as the key in the
+    /// `/EmbeddedFiles` name tree.
+    pub name: String,
+
+    /// Value of the file specification's `/AFRelationship`.
+    pub relationship: String,
+
+    /// Value of the file specification's `/Desc`.
+    pub description: String,
+
+    /// Written as the embedded file's `/CreationDate` and `/ModDate`, normally the signing time.
+    /// The dates are omitted when this is `None`.
+    pub date: Option<DateTime<Utc>>,
+}
+
+impl EmbeddedFileOptions {
+    /// Returns an [Error::InvalidAFRelationship] if the relationship is not one of the allowed
+    /// values.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if !ALLOWED_AF_RELATIONSHIPS.contains(&self.relationship.as_str()) {
+            return Err(Error::InvalidAFRelationship(self.relationship.clone()));
+        }
+        Ok(())
+    }
+}
+
+impl Default for EmbeddedFileOptions {
+    fn default() -> Self {
+        Self {
+            name: CONTENT_CREDS.to_owned(),
+            relationship: String::from_utf8_lossy(C2PA_RELATIONSHIP).into_owned(),
+            description: CONTENT_CREDS.to_owned(),
+            date: None,
+        }
+    }
+}
+
+/// A file embedded in a PDF, either in the `/EmbeddedFiles` name tree, the catalog's associated
+/// files or a `FileAttachment` annotation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EmbeddedFile {
+    /// Name of the embedded file, from the `/EmbeddedFiles` name tree or the file
+    /// specification's `/UF` or `/F` entries.
+    pub name: String,
+
+    /// Size in bytes of the embedded file's decoded content.
+    pub size: usize,
+
+    /// Value of the file specification's `/AFRelationship`, if it has one.
+    pub relationship: Option<String>,
 }
 
 const C2PA_MIME_TYPE: &str = "application/x-c2pa-manifest-store";
 
+// Maximum depth of `/Kids` followed when walking the `/EmbeddedFiles` name tree.
+const MAX_NAME_TREE_DEPTH: usize = 32;
+
+/// Catalog entries introduced in PDF 2.0 that the writer doesn't take into account when it
+/// adds a manifest: document part hierarchies and document security stores.
+const PDF_2_CATALOG_FEATURES: [&str; 2] = ["DPartRoot", "DSS"];
+
+/// The largest byte offset a cross-reference table entry can hold.
+const MAX_XREF_OFFSET: u64 = 9_999_999_999;
+
 #[cfg_attr(test, mockall::automock)]
 pub(crate) trait C2paPdf: Sized {
     /// Save the `C2paPdf` implementation to the provided `writer`.
     fn save_to<W: Write + 'static>(&mut self, writer: &mut W) -> Result<(), std::io::Error>;
 
+    /// Save the `C2paPdf` implementation to the provided `writer` as an incremental update of
+    /// the PDF it was loaded from.
+    fn save_incremental_to<W: Write + 'static>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<(), std::io::Error>;
+
     /// Returns `true` if the `PDF` is password protected, `false` otherwise.
     fn is_password_protected(&self) -> bool;
 
@@ -81,13 +208,29 @@ pub(crate) trait C2paPdf: Sized {
     #[allow(clippy::needless_lifetimes, clippy::type_complexity)] // required for automock::mockall
     fn read_manifest_bytes<'a>(&'a self) -> Result<Option<Vec<(&'a [u8], usize)>>, Error>;
 
+    /// Returns `true` if the C2PA manifest stream is compressed with the `/FlateDecode` filter.
+    fn is_manifest_compressed(&self) -> bool;
+
     fn remove_manifest_bytes(&mut self) -> Result<(), Error>;
 
+    /// Replaces the content of the existing C2PA manifest stream with the provided `bytes`.
+    fn replace_manifest_bytes(&mut self, bytes: Vec<u8>) -> Result<(), Error>;
+
     fn read_xmp(&self) -> Option<String>;
+
+    /// Returns the manifest reference held by the first of `keys` found in the document
+    /// catalog or in the trailer, which is the cross-reference stream dictionary for PDFs
+    /// using cross-reference streams.
+    fn read_manifest_reference(&self, keys: &[String]) -> Option<String>;
 }
 
 pub(crate) struct Pdf {
     document: Document,
+    embedded_file_options: EmbeddedFileOptions,
+
+    // The bytes and objects of the PDF as loaded, used to write incremental updates.
+    original_bytes: Vec<u8>,
+    original_objects: BTreeMap<ObjectId, Object>,
 }
 
 impl C2paPdf for Pdf {
@@ -96,6 +239,24 @@ impl C2paPdf for Pdf {
         self.document.save_to(writer)
     }
 
+    /// Saves the in-memory PDF to the provided `writer` as an incremental update.
+    ///
+    /// The original bytes of the PDF are written unchanged, followed by the objects that were
+    /// added or modified, a new cross-reference section and a trailer pointing back to the
+    /// original cross-reference section through `/Prev`.
+    ///
+    /// Because the original bytes are preserved, the manifest written this way can later be
+    /// covered by a PDF digital signature: sign with C2PA first, then apply the PDF signature
+    /// as a further incremental update whose `/ByteRange` covers the whole C2PA-signed file,
+    /// manifest included. The C2PA manifest remains valid for the revision it was embedded in,
+    /// which is a prefix of the final file.
+    fn save_incremental_to<W: Write>(&mut self, writer: &mut W) -> Result<(), std::io::Error> {
+        let (update, _) = self.incremental_update()?;
+
+        writer.write_all(&self.original_bytes)?;
+        writer.write_all(&update)
+    }
+
     fn is_password_protected(&self) -> bool {
         self.document.is_encrypted()
     }
@@ -108,8 +269,8 @@ impl C2paPdf for Pdf {
 
         self.push_associated_file(file_spec_ref)?;
 
-        let mut manifest_name_file_pair = vec![
-            Object::string_literal(CONTENT_CREDS),
+        let manifest_name_file_pair = vec![
+            Object::string_literal(self.embedded_file_options.name.as_str()),
             Reference(file_spec_ref),
         ];
 
@@ -138,8 +299,8 @@ impl C2paPdf for Pdf {
         };
 
         let Ok(embedded_files) = names_dictionary.get_mut(EMBEDDED_FILES_KEY) else {
-            // We have a /Names dictionary, but are missing the /EmbeddedFiles dictionary
-            // and its /Names array of embedded files.
+            // We have a /Names dictionary, but are missing the /EmbeddedFiles name tree. Create
+            // it as a single root node holding the manifest's name and file specification.
             names_dictionary.set(
                 EMBEDDED_FILES_KEY,
                 dictionary! { NAMES_KEY => manifest_name_file_pair },
@@ -153,13 +314,16 @@ impl C2paPdf for Pdf {
             _ => embedded_files.as_dict_mut()?,
         };
 
+        // An intermediate root node has no /Names array of its own; the manifest is added to
+        // the leaf whose range covers its name instead. The root node has no /Limits.
+        if let Ok(kids) = embedded_files_dictionary.get(b"Kids").cloned() {
+            return self.insert_into_name_tree_kids(kids, manifest_name_file_pair, 0);
+        }
+
         let Ok(names) = embedded_files_dictionary.get_mut(NAMES_KEY) else {
             // This PDF has the /Names dictionary, and it has the /EmbeddedFiles
             // dictionary, but the /EmbeddedFiles Dictionary is missing the /Names Array.
-            embedded_files_dictionary.set(
-                NAMES_KEY,
-                dictionary! { NAMES_KEY => manifest_name_file_pair },
-            );
+            embedded_files_dictionary.set(NAMES_KEY, manifest_name_file_pair);
 
             return Ok(());
         };
@@ -171,9 +335,9 @@ impl C2paPdf for Pdf {
         };
 
         // The PDF has the /Names dictionary, which contains the /EmbeddedFiles Dictionary, which
-        // contains the /Names array. Append the manifest's name (Content Credentials)
-        // and its reference.
-        names_array.append(&mut manifest_name_file_pair);
+        // contains the /Names array. Insert the manifest's name (Content Credentials)
+        // and its reference, keeping the array sorted by name.
+        insert_name_tree_pair(names_array, manifest_name_file_pair);
 
         Ok(())
     }
@@ -211,20 +375,27 @@ impl C2paPdf for Pdf {
             return Ok(None);
         };
 
-        let ef = &self
-            .document
-            .get_object(id)
-            .and_then(Object::as_dict)?
-            .get_deref(b"EF", &self.document)?
-            .as_dict()?; // EF dictionary
-
-        Ok(ef
-            .get_deref(b"F", &self.document)? // F embedded file stream
+        Ok(self
+            .embedded_file_object(id)?
             .as_stream()
             .ok()
             .and_then(|stream| Some(vec![(&*stream.content, stream.start_position?)])))
     }
 
+    fn is_manifest_compressed(&self) -> bool {
+        let Ok(Some(stream)) = self.manifest_stream() else {
+            return false;
+        };
+
+        match stream.dict.get(b"Filter") {
+            Ok(Name(filter)) => filter == b"FlateDecode",
+            Ok(Object::Array(filters)) => {
+                matches!(filters.as_slice(), [Name(filter)] if filter == b"FlateDecode")
+            }
+            _ => false,
+        }
+    }
+
     fn remove_manifest_bytes(&mut self) -> Result<(), Error> {
         // Find the File Spec, which contains the reference to the manifest.
         let file_spec_ref = self
@@ -259,6 +430,33 @@ impl C2paPdf for Pdf {
         Ok(())
     }
 
+    fn replace_manifest_bytes(&mut self, bytes: Vec<u8>) -> Result<(), Error> {
+        let file_spec_ref = self
+            .c2pa_file_spec_object_id()
+            .ok_or_else(|| Error::NoManifest)?;
+
+        let file_stream_ref = self
+            .document
+            .get_object(file_spec_ref)?
+            .as_dict()?
+            .get_deref(b"EF", &self.document)?
+            .as_dict()?
+            .get(b"F")?
+            .as_reference()?;
+
+        // The manifest is always written unfiltered, so drop any filters the previous
+        // manifest stream may have declared.
+        let stream = self
+            .document
+            .get_object_mut(file_stream_ref)?
+            .as_stream_mut()?;
+        stream.dict.remove(b"Filter");
+        stream.dict.remove(b"DecodeParms");
+        stream.set_content(bytes);
+
+        Ok(())
+    }
+
     /// Reads the `Metadata` field referenced in the PDF document's `Catalog` entry. Will return
     /// `None` if no Metadata is present.
     fn read_xmp(&self) -> Option<String> {
@@ -283,205 +481,857 @@ impl C2paPdf for Pdf {
                 String::from_utf8(stream_dict.content.clone()).ok()
             })
     }
+
+    fn read_manifest_reference(&self, keys: &[String]) -> Option<String> {
+        let dicts = [self.document.catalog().ok(), Some(&self.document.trailer)];
+        keys.iter().find_map(|key| {
+            dicts.iter().flatten().find_map(|dict| {
+                match dict.get_deref(key.as_bytes(), &self.document).ok()? {
+                    Object::String(bytes, _) => Some(decode_text_string(bytes)),
+                    Object::Name(name) => String::from_utf8(name.clone()).ok(),
+                    _ => None,
+                }
+            })
+        })
+    }
 }
 
 impl Pdf {
     #[allow(dead_code)]
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
         let document = Document::load_mem(bytes)?;
-        Ok(Self { document })
+        let original_objects = document.objects.clone();
+
+        Ok(Self {
+            document,
+            embedded_file_options: EmbeddedFileOptions::default(),
+            original_bytes: bytes.to_vec(),
+            original_objects,
+        })
     }
 
-    pub fn from_reader<R: Read>(source: R) -> Result<Self, Error> {
-        let document = Document::load_from(source)?;
-        Ok(Self { document })
+    pub fn from_reader<R: Read>(mut source: R) -> Result<Self, Error> {
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
     }
 
-    /// Returns a reference to the Associated Files array from the PDF's Catalog.
-    fn associated_files(&self) -> Result<&Vec<Object>, Error> {
-        Ok(self
+    /// Sets the name, `/AFRelationship`, `/Desc` and dates used when writing the manifest's
+    /// embedded file specification. Returns an [Error::InvalidAFRelationship] if the relationship is not
+    /// one of the allowed values.
+    pub fn set_embedded_file_options(&mut self, options: EmbeddedFileOptions) -> Result<(), Error> {
+        options.validate()?;
+        self.embedded_file_options = options;
+        Ok(())
+    }
+
+    /// Returns the PDF version of the document, such as `"1.7"` or `"2.0"`.
+    ///
+    /// This is the version in the file header, unless the catalog's `/Version` entry names a
+    /// later one, as an incremental update may do to upgrade the document.
+    pub fn version(&self) -> String {
+        let header = self.document.version.clone();
+        let catalog = self
             .document
-            .catalog()?
-            .get_deref(ASSOCIATED_FILE_KEY, &self.document)?
-            .as_array()?)
+            .catalog()
+            .and_then(|catalog| catalog.get(b"Version"))
+            .and_then(Object::as_name)
+            .map(|version| String::from_utf8_lossy(version).into_owned())
+            .ok();
+
+        match catalog {
+            Some(version) if version_number(&version) > version_number(&header) => version,
+            _ => header,
+        }
     }
 
-    /// Returns the [Object::ObjectId] of the C2PA File Spec Reference, if it is present in the
-    /// PDF's associated files array.
-    fn c2pa_file_spec_object_id(&self) -> Option<ObjectId> {
-        self.associated_files().ok()?.iter().find_map(|value| {
-            let Ok(reference) = value.as_reference() else {
-                return None;
-            };
+    /// Returns the PDF 2.0 features the document uses that may not be preserved when a
+    /// manifest is written to it. Documents before PDF 2.0 don't report any.
+    pub fn unsupported_features(&self) -> Vec<String> {
+        if version_number(&self.version()) < (2, 0) {
+            return Vec::new();
+        }
+        let Ok(catalog) = self.document.catalog() else {
+            return Vec::new();
+        };
+
+        PDF_2_CATALOG_FEATURES
+            .iter()
+            .filter(|feature| catalog.has(feature.as_bytes()))
+            .map(|feature| format!("/{feature}"))
+            .collect()
+    }
+
+    /// Returns `true` if the PDF is tagged, with a structure tree describing its content for
+    /// accessibility.
+    pub fn is_tagged(&self) -> bool {
+        self.document
+            .catalog()
+            .is_ok_and(|catalog| catalog.has(STRUCT_TREE_ROOT_KEY))
+    }
+
+    /// Returns the parts of a tagged PDF's structure that changed since it was loaded, and would
+    /// break its tagging when written. Untagged documents don't report any.
+    ///
+    /// Reported are changes to the catalog's tagging entries, the structure tree and the marked
+    /// content of pages, as well as annotations added to a page without a `/StructParent` entry
+    /// placing them in the structure tree.
+    pub fn tagging_changes(&self) -> Vec<String> {
+        let Some(original_catalog) = self.original_catalog() else {
+            return Vec::new();
+        };
+        if !original_catalog.has(STRUCT_TREE_ROOT_KEY) {
+            return Vec::new();
+        }
+
+        let mut changes = Vec::new();
 
-            let name = self
+        let catalog = self.document.catalog().ok();
+        for key in TAGGED_CATALOG_KEYS {
+            let value = catalog.and_then(|catalog| catalog.get(key.as_bytes()).ok());
+            let original = original_catalog.get(key.as_bytes()).ok();
+            if value.map(serialized) != original.map(serialized) {
+                changes.push(format!("/{key}"));
+            }
+        }
+
+        for id in self.original_structure_tree() {
+            if self.object_changed(id) {
+                changes.push(format!("structure tree object {} {}", id.0, id.1));
+            }
+        }
+
+        for (index, page_id) in self.document.page_iter().enumerate() {
+            let page = self
                 .document
-                .get_object(reference)
+                .get_object(page_id)
                 .and_then(Object::as_dict)
-                .and_then(|dict| dict.get_deref(AF_RELATIONSHIP_KEY, &self.document))
-                .and_then(Object::as_name)
-                .ok()?;
+                .ok();
+            let Some(original_page) = self
+                .original_objects
+                .get(&page_id)
+                .and_then(|page| page.as_dict().ok())
+            else {
+                continue;
+            };
 
-            (name == C2PA_RELATIONSHIP).then_some(reference)
-        })
+            // The marked content is in the page's content streams.
+            let contents = page.and_then(|page| page.get(CONTENTS_KEY).ok());
+            let original_contents = original_page.get(CONTENTS_KEY).ok();
+            let mut content_ids = Vec::new();
+            if let Some(original_contents) = original_contents {
+                collect_references(original_contents, &[], &mut content_ids);
+            }
+            if contents.map(serialized) != original_contents.map(serialized)
+                || content_ids.into_iter().any(|id| self.object_changed(id))
+            {
+                changes.push(format!("page {} content", index + 1));
+            }
+
+            let original_annotations = self.original_annotations(original_page);
+            let untagged_annotation = page
+                .map(|page| self.annotations(page))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|id| !original_annotations.contains(id))
+                .any(|id| {
+                    self.document
+                        .get_object(id)
+                        .and_then(Object::as_dict)
+                        .map_or(true, |annotation| !annotation.has(STRUCT_PARENT_KEY))
+                });
+            if untagged_annotation {
+                changes.push(format!(
+                    "page {} annotation without /StructParent",
+                    index + 1
+                ));
+            }
+        }
+
+        changes
     }
 
-    /// Removes the C2PA File Spec Reference if it exists in the Associated Files [Object::Array] of
-    /// PDF's catalog. This will return an [Err] if the PDF doesn't contain a C2PA File Spec
-    /// Reference.
-    fn remove_c2pa_file_spec_reference(&mut self) -> Result<(), Error> {
-        let c2pa_file_spec_reference = self
+    /// Writes `compressed`, the zlib compressed manifest, as a PDF `Embedded File` whose stream
+    /// declares the `/FlateDecode` filter. See [C2paPdf::write_manifest_as_embedded_file].
+    pub fn write_compressed_manifest_as_embedded_file(
+        &mut self,
+        compressed: Vec<u8>,
+    ) -> Result<(), Error> {
+        self.write_manifest_as_embedded_file(compressed)?;
+
+        let file_spec_ref = self
             .c2pa_file_spec_object_id()
-            .ok_or_else(|| Error::FindingC2PAFileSpec)?;
+            .ok_or_else(|| Error::NoManifest)?;
+        let file_stream_ref = self
+            .document
+            .get_object(file_spec_ref)?
+            .as_dict()?
+            .get_deref(b"EF", &self.document)?
+            .as_dict()?
+            .get(b"F")?
+            .as_reference()?;
 
         self.document
-            .catalog_mut()?
-            .get_mut(ASSOCIATED_FILE_KEY)?
-            .as_array_mut()?
-            .retain(|v| {
-                let Ok(reference) = v.as_reference() else {
-                    return true;
-                };
-
-                reference != c2pa_file_spec_reference
-            });
+            .get_object_mut(file_stream_ref)?
+            .as_stream_mut()?
+            .dict
+            .set("Filter", Name("FlateDecode".into()));
 
         Ok(())
     }
 
-    /// Adds the C2PA `Annotation` to the PDF.
-    ///
-    /// ### Note:
-    /// The `FileAttachment` annotation is added to the first page of the PDF in the lower
-    /// left-hand corner. The `FileAttachment`'s location is not defined in the spec as of version
-    /// `1.3`.
-    fn add_file_attachment_annotation(
-        &mut self,
-        file_spec_reference: ObjectId,
-    ) -> Result<(), Error> {
-        let annotation = dictionary! {
-            "Type" => Name("Annot".into()),
-            "Contents" => Object::string_literal(CONTENT_CREDS),
-            "Name" => Object::string_literal(CONTENT_CREDS),
-            SUBTYPE_KEY => Name("FileAttachment".into()),
-            "FS" => Reference(file_spec_reference),
-            // Places annotation in the lower left-hand corner. The icon will be 10x10.
-            "Rect" => vec![0.into(), 0.into(), 10.into(), 10.into()],
-        };
+    /// Returns the incremental update written after the original bytes of the PDF by
+    /// [C2paPdf::save_incremental_to], and the offset of each object it adds or modifies, or
+    /// `None` for a deleted one. The update is empty if nothing changed.
+    fn incremental_update(
+        &self,
+    ) -> Result<(Vec<u8>, BTreeMap<ObjectId, Option<usize>>), std::io::Error> {
+        let prev_xref = find_startxref(&self.original_bytes).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unable to find the PDF's startxref",
+            )
+        })?;
+
+        let mut update = Vec::new();
+        if !matches!(self.original_bytes.last(), Some(b'\n' | b'\r')) {
+            update.push(b'\n');
+        }
 
-        // Add C2PA annotation as an indirect object.
-        let annotation_ref = self.document.add_object(annotation);
+        // Collect the objects that were added, modified or deleted since the PDF was loaded.
+        let mut entries: BTreeMap<ObjectId, Option<usize>> = BTreeMap::new();
+        for (id, object) in &self.document.objects {
+            let mut serialized = Vec::new();
+            write_object(&mut serialized, object)?;
 
-        // Find the reference to the first page of the PDF.
-        let first_page_ref = self
-            .document
-            .page_iter()
-            .next()
-            .ok_or_else(|| Error::AddingAnnotation)?;
+            if let Some(original) = self.original_objects.get(id) {
+                let mut original_serialized = Vec::new();
+                write_object(&mut original_serialized, original)?;
 
-        // Get a mutable ref to the first page as a Dictionary object.
-        let first_page = self
-            .document
-            .get_object_mut(first_page_ref)?
-            .as_dict_mut()?;
+                if original_serialized == serialized {
+                    continue;
+                }
+            }
 
-        // Ensures the /Annots array exists on the page object.
-        if !first_page.has(ANNOTATIONS_KEY) {
-            first_page.set(ANNOTATIONS_KEY, Array(vec![]))
+            entries.insert(*id, Some(self.original_bytes.len() + update.len()));
+            writeln!(update, "{} {} obj", id.0, id.1)?;
+            update.extend_from_slice(&serialized);
+            update.extend_from_slice(b"\nendobj\n");
         }
 
-        // Follows a reference to the indirect annotations array, if it exists.
-        let annotation_object = first_page.get_mut(ANNOTATIONS_KEY)?;
-        let annotations = if let Ok(v) = annotation_object.as_reference() {
-            self.document.get_object_mut(v)?
-        } else {
-            annotation_object
+        for id in self.original_objects.keys() {
+            if !self.document.objects.contains_key(id) {
+                entries.insert(*id, None);
+            }
         }
-        .as_array_mut()?;
 
-        annotations.push(Reference(annotation_ref));
-        Ok(())
-    }
+        if entries.is_empty() {
+            return Ok((Vec::new(), entries));
+        }
 
-    /// Creates, or appends to, the Associated File (`AF`) array the embedded file spec reference of the
-    /// C2PA data.
-    fn push_associated_file(&mut self, embedded_file_spec_ref: ObjectId) -> Result<(), Error> {
-        let catalog = self.document.catalog_mut()?;
-        if catalog.get_mut(ASSOCIATED_FILE_KEY).is_err() {
-            // Add associated files array to catalog if it isn't already present.
-            catalog.set(ASSOCIATED_FILE_KEY, vec![]);
+        let max_id = self
+            .document
+            .objects
+            .keys()
+            .chain(self.original_objects.keys())
+            .map(|id| id.0)
+            .max()
+            .unwrap_or_default();
+
+        // An update to a PDF using cross-reference streams must use one too, since readers that
+        // predate cross-reference streams can't read the PDF anyway and newer ones may reject a
+        // cross-reference table following a stream.
+        let xref_stream_id =
+            uses_xref_stream(&self.original_bytes, prev_xref).then_some((max_id + 1, 0));
+
+        let size = self
+            .document
+            .trailer
+            .get(b"Size")
+            .and_then(Object::as_i64)
+            .unwrap_or_default()
+            .max(i64::from(xref_stream_id.map_or(max_id, |id| id.0)) + 1);
+
+        let mut trailer = dictionary! {
+            "Size" => size,
+            "Prev" => prev_xref as i64,
+        };
+
+        for key in [&b"Root"[..], b"Info", b"ID", b"Encrypt"] {
+            if let Ok(value) = self.document.trailer.get(key) {
+                trailer.set(key, value.clone());
+            }
         }
 
-        let associated_files = catalog.get_mut(ASSOCIATED_FILE_KEY)?;
-        let associated_files = match associated_files.as_reference() {
-            Ok(object_id) => self.document.get_object_mut(object_id)?,
-            _ => associated_files,
+        // The first element of the document's /ID is permanent. The second identifies the
+        // revision, so it's updated to reflect the contents of this update.
+        if let Ok(Array(id)) = trailer.get_mut(b"ID") {
+            if id.len() == 2 {
+                let mut hasher = Sha256::new();
+                hasher.update(&self.original_bytes);
+                hasher.update(&update);
+                id[1] = Object::String(hasher.finalize()[..16].to_vec(), StringFormat::Hexadecimal);
+            }
         }
-        .as_array_mut()?;
 
-        associated_files.push(Reference(embedded_file_spec_ref));
+        let xref_offset = self.original_bytes.len() + update.len();
+        match xref_stream_id {
+            Some(xref_stream_id) => {
+                let mut xref_entries = entries.clone();
+                xref_entries.insert(xref_stream_id, Some(xref_offset));
+                write_xref_stream(&mut update, xref_stream_id, &xref_entries, trailer)?;
+            }
+            None => {
+                write_xref_table(&mut update, &entries)?;
+                update.extend_from_slice(b"trailer\n");
+                write_dictionary(&mut update, &trailer)?;
+            }
+        }
+        writeln!(update, "\nstartxref\n{xref_offset}\n%%EOF")?;
 
-        Ok(())
+        Ok((update, entries))
     }
 
-    /// Adds the `Embedded File Specification` to the PDF document. Returns the [Reference]
-    /// to the added `Embedded File Specification`.
-    fn add_embedded_file_specification(&mut self, file_stream_ref: ObjectId) -> ObjectId {
-        let embedded_file_stream = dictionary! {
-            AF_RELATIONSHIP_KEY => Name(C2PA_RELATIONSHIP.into()),
-            "Desc" => Object::string_literal(CONTENT_CREDS),
-            "F" => Object::string_literal(CONTENT_CREDS),
-            "EF" => dictionary! {
-                "F" => Reference(file_stream_ref),
-            },
-            TYPE_KEY => Name("FileSpec".into()),
-            "UF" => Object::string_literal(CONTENT_CREDS),
+    /// Returns the bytes of the incremental update written by [C2paPdf::save_incremental_to],
+    /// to be appended to the original PDF, and where the content of the manifest stream starts
+    /// once they are.
+    ///
+    /// The incremental update writes every new object, the manifest stream included, as a
+    /// top-level uncompressed object, even when the PDF uses object streams. The returned
+    /// offset therefore addresses the manifest's raw bytes, which can be hashed directly.
+    /// Returns `None` for the offset if there is no manifest, or it was not written as part of
+    /// the update.
+    pub fn incremental_update_with_manifest_offset(
+        &self,
+    ) -> Result<(Vec<u8>, Option<usize>), std::io::Error> {
+        let (update, offsets) = self.incremental_update()?;
+
+        let Some(stream_id) = self.manifest_stream_id() else {
+            return Ok((update, None));
+        };
+        let Some(Some(object_offset)) = offsets.get(&stream_id) else {
+            return Ok((update, None));
+        };
+        let Ok(stream) = self
+            .document
+            .get_object(stream_id)
+            .and_then(Object::as_stream)
+        else {
+            return Ok((update, None));
         };
 
-        self.document.add_object(embedded_file_stream)
-    }
-
-    /// Adds the provided `bytes` as a `StreamDictionary` to the PDF document. Returns the
-    /// [Reference] of the added [Object].
-    fn add_c2pa_embedded_file_stream(&mut self, bytes: Vec<u8>) -> ObjectId {
-        let stream = Stream::new(
-            dictionary! {
-                "F" => dictionary! {
-                SUBTYPE_KEY => C2PA_MIME_TYPE,
-                "Length" => Integer(bytes.len() as i64),
-                },
-            },
-            bytes,
-        );
+        let mut header = Vec::new();
+        writeln!(header, "{} {} obj", stream_id.0, stream_id.1)?;
+        write_stream_header(&mut header, &stream.dict, stream.content.len())?;
 
-        self.document.add_object(stream)
+        Ok((update, Some(object_offset + header.len())))
     }
 
-    /// Remove the C2PA Manifest `Annotation` from the PDF.
-    fn remove_manifest_from_annotations(&mut self) -> Result<(), Error> {
-        for (_, page_id) in self.document.get_pages() {
-            self.document
-                .get_object_mut(page_id)?
-                .as_dict_mut()?
-                .get_mut(ANNOTATIONS_KEY)?
-                .as_array_mut()?
-                .retain(|obj| {
-                    obj.as_dict()
-                        .and_then(|annot| annot.get(TYPE_KEY))
+    /// Lists every file embedded in the PDF, including the C2PA manifest store.
+    ///
+    /// Files are collected from the `/EmbeddedFiles` name tree, the catalog's associated files
+    /// (`/AF`) and `FileAttachment` annotations, so a file referenced from more than one of
+    /// these is only listed once.
+    pub fn list_embedded_files(&self) -> Result<Vec<EmbeddedFile>, Error> {
+        self.embedded_file_specs()
+            .into_iter()
+            .map(|(name, id)| -> Result<EmbeddedFile, Error> {
+                let stream = self.embedded_file_stream(id)?;
+                let file_spec = self.document.get_dictionary(id)?;
+
+                Ok(EmbeddedFile {
+                    name: name.unwrap_or_else(|| file_spec_name(file_spec)),
+                    size: decoded_content(stream)
+                        .map(|content| content.len())
+                        .unwrap_or(stream.content.len()),
+                    relationship: file_spec
+                        .get_deref(AF_RELATIONSHIP_KEY, &self.document)
                         .and_then(Object::as_name_str)
-                        .map(|str| str != CONTENT_CREDS)
-                        .unwrap_or(true)
-                });
+                        .ok()
+                        .map(str::to_owned),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the decoded content of the embedded file named `name`, as listed by
+    /// [Pdf::list_embedded_files]. Returns `Ok(None)` if there is no such file.
+    pub fn extract_embedded_file(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        for (spec_name, id) in self.embedded_file_specs() {
+            let spec_name = match spec_name {
+                Some(spec_name) => spec_name,
+                None => file_spec_name(self.document.get_dictionary(id)?),
+            };
+
+            if spec_name == name {
+                return decoded_content(self.embedded_file_stream(id)?).map(Some);
+            }
         }
 
-        Ok(())
+        Ok(None)
     }
 
-    /// Removes the manifest from the PDF's embedded files collection.
-    fn remove_manifest_from_embedded_files(&mut self) -> Result<(), Error> {
-        let Ok(names) = self.document.catalog_mut()?.get_mut(NAMES_KEY) else {
-            return Err(Error::NoManifest);
-        };
+    /// Returns the object ids of the file specifications with an embedded file, along with the
+    /// name they have in the `/EmbeddedFiles` name tree, if they are in it.
+    fn embedded_file_specs(&self) -> Vec<(Option<String>, ObjectId)> {
+        let mut specs = Vec::new();
+
+        if let Ok(embedded_files) = self
+            .document
+            .catalog()
+            .and_then(|catalog| catalog.get_deref(NAMES_KEY, &self.document))
+            .and_then(Object::as_dict)
+            .and_then(|names| names.get_deref(EMBEDDED_FILES_KEY, &self.document))
+            .and_then(Object::as_dict)
+        {
+            self.collect_name_tree_specs(embedded_files, 0, &mut specs);
+        }
+
+        if let Ok(associated_files) = self.associated_files() {
+            for value in associated_files {
+                if let Ok(id) = value.as_reference() {
+                    specs.push((None, id));
+                }
+            }
+        }
+
+        for page_id in self.document.get_pages().into_values() {
+            let Ok(annotations) = self
+                .document
+                .get_dictionary(page_id)
+                .and_then(|page| page.get_deref(ANNOTATIONS_KEY, &self.document))
+                .and_then(Object::as_array)
+            else {
+                continue;
+            };
+
+            for annotation in annotations {
+                let annotation = match annotation.as_reference() {
+                    Ok(id) => self.document.get_dictionary(id),
+                    _ => annotation.as_dict(),
+                };
+
+                let Ok(annotation) = annotation else {
+                    continue;
+                };
+
+                let is_file_attachment = annotation
+                    .get(SUBTYPE_KEY)
+                    .and_then(Object::as_name)
+                    .map(|subtype| subtype == b"FileAttachment")
+                    .unwrap_or_default();
+
+                if let (true, Ok(id)) = (
+                    is_file_attachment,
+                    annotation.get(b"FS").and_then(Object::as_reference),
+                ) {
+                    specs.push((None, id));
+                }
+            }
+        }
+
+        // Keep the first occurrence of each file spec, preferring its name tree name.
+        let mut unique: Vec<(Option<String>, ObjectId)> = Vec::new();
+        for (name, id) in specs {
+            if unique.iter().any(|(_, unique_id)| *unique_id == id) {
+                continue;
+            }
+
+            if self.embedded_file_stream(id).is_ok() {
+                unique.push((name, id));
+            }
+        }
+
+        unique
+    }
+
+    /// Appends the file specifications in the `/Names` arrays of the name tree `node` and its
+    /// `/Kids` to `specs`.
+    fn collect_name_tree_specs(
+        &self,
+        node: &Dictionary,
+        depth: usize,
+        specs: &mut Vec<(Option<String>, ObjectId)>,
+    ) {
+        if depth > MAX_NAME_TREE_DEPTH {
+            return;
+        }
+
+        if let Ok(names) = node
+            .get_deref(NAMES_KEY, &self.document)
+            .and_then(Object::as_array)
+        {
+            for pair in names.chunks_exact(2) {
+                if let (Object::String(name, _), Reference(id)) = (&pair[0], &pair[1]) {
+                    specs.push((Some(decode_text_string(name)), *id));
+                }
+            }
+        }
+
+        if let Ok(kids) = node
+            .get_deref(b"Kids", &self.document)
+            .and_then(Object::as_array)
+        {
+            for kid in kids {
+                if let Ok(kid) = kid
+                    .as_reference()
+                    .and_then(|id| self.document.get_dictionary(id))
+                {
+                    self.collect_name_tree_specs(kid, depth + 1, specs);
+                }
+            }
+        }
+    }
+
+    /// Inserts the name and file specification `pair` into the leaf of the name tree reached
+    /// through `kids`, widening the `/Limits` of every node on the way down to cover the name.
+    fn insert_into_name_tree_kids(
+        &mut self,
+        kids: Object,
+        pair: Vec<Object>,
+        depth: usize,
+    ) -> Result<(), Error> {
+        if depth > MAX_NAME_TREE_DEPTH {
+            return Err(Error::InvalidNameTree);
+        }
+
+        let key = name_tree_key(&pair[0])
+            .ok_or(Error::InvalidNameTree)?
+            .to_vec();
+        let kids = match kids.as_reference() {
+            Ok(id) => self.document.get_object(id)?.as_array()?.clone(),
+            _ => kids.as_array()?.clone(),
+        };
+
+        // Descend into the last kid whose range starts at or before the name, or the first kid
+        // if the name sorts before all of them.
+        let mut kid_id = None;
+        for kid in &kids {
+            let id = kid.as_reference()?;
+            let lower = self
+                .document
+                .get_dictionary(id)?
+                .get(b"Limits")
+                .and_then(Object::as_array)
+                .ok()
+                .and_then(|limits| limits.first())
+                .and_then(name_tree_key);
+
+            if kid_id.is_none() || lower.is_some_and(|lower| lower <= key.as_slice()) {
+                kid_id = Some(id);
+            }
+        }
+        let kid_id = kid_id.ok_or(Error::InvalidNameTree)?;
+
+        let kid = self.document.get_dictionary_mut(kid_id)?;
+        match kid.get_mut(b"Limits").and_then(Object::as_array_mut) {
+            Ok(limits) if limits.len() == 2 => {
+                if name_tree_key(&limits[0]).map_or(true, |lower| key.as_slice() < lower) {
+                    limits[0] = Object::string_literal(key.clone());
+                }
+                if name_tree_key(&limits[1]).map_or(true, |upper| key.as_slice() > upper) {
+                    limits[1] = Object::string_literal(key.clone());
+                }
+            }
+            _ => return Err(Error::InvalidNameTree),
+        }
+
+        if let Ok(kids) = kid.get(b"Kids").cloned() {
+            return self.insert_into_name_tree_kids(kids, pair, depth + 1);
+        }
+
+        let names = kid.get_mut(NAMES_KEY).map_err(|_| Error::InvalidNameTree)?;
+        let names_array = match names.as_reference() {
+            Ok(object_id) => self.document.get_object_mut(object_id)?.as_array_mut()?,
+            _ => names.as_array_mut()?,
+        };
+        insert_name_tree_pair(names_array, pair);
+
+        Ok(())
+    }
+
+    /// Returns the embedded file stream of the file specification with the given id.
+    fn embedded_file_stream(&self, id: ObjectId) -> Result<&Stream, Error> {
+        Ok(self
+            .document
+            .get_dictionary(id)?
+            .get_deref(b"EF", &self.document)?
+            .as_dict()? // EF dictionary
+            .get_deref(b"F", &self.document)? // F embedded file stream
+            .as_stream()?)
+    }
+
+    /// Returns a reference to the Associated Files array from the PDF's Catalog.
+    fn associated_files(&self) -> Result<&Vec<Object>, Error> {
+        Ok(self
+            .document
+            .catalog()?
+            .get_deref(ASSOCIATED_FILE_KEY, &self.document)?
+            .as_array()?)
+    }
+
+    /// Returns the references in the Associated Files arrays of the annotations on the PDF's
+    /// pages, in page order.
+    fn annotation_associated_files(&self) -> Vec<ObjectId> {
+        let mut references = Vec::new();
+
+        for page_id in self.document.get_pages().into_values() {
+            let Ok(annotations) = self
+                .document
+                .get_dictionary(page_id)
+                .and_then(|page| page.get_deref(ANNOTATIONS_KEY, &self.document))
+                .and_then(Object::as_array)
+            else {
+                continue;
+            };
+
+            for annotation in annotations {
+                let annotation = match annotation.as_reference() {
+                    Ok(id) => self.document.get_dictionary(id),
+                    _ => annotation.as_dict(),
+                };
+
+                let Ok(associated_files) = annotation
+                    .and_then(|annotation| {
+                        annotation.get_deref(ASSOCIATED_FILE_KEY, &self.document)
+                    })
+                    .and_then(Object::as_array)
+                else {
+                    continue;
+                };
+
+                references.extend(
+                    associated_files
+                        .iter()
+                        .filter_map(|value| value.as_reference().ok()),
+                );
+            }
+        }
+
+        references
+    }
+
+    /// Returns the [Object::ObjectId] of the C2PA File Spec Reference, if it is present in the
+    /// PDF's associated files array.
+    ///
+    /// Some signing tools attach the manifest to the associated files of a page annotation
+    /// rather than to the catalog, so those are searched next.
+    ///
+    /// For linearized PDFs the file spec may live in the main xref section rather than the
+    /// first-page section, so if it can't be resolved through the associated files array we
+    /// fall back to the file specs of the catalog's `/EmbeddedFiles` name tree. File specs
+    /// that nothing in the document refers to are never taken as the manifest.
+    fn c2pa_file_spec_object_id(&self) -> Option<ObjectId> {
+        let associated_file_spec = self.associated_files().ok().and_then(|associated_files| {
+            associated_files.iter().find_map(|value| {
+                let reference = value.as_reference().ok()?;
+                self.is_c2pa_file_spec(reference).then_some(reference)
+            })
+        });
+
+        let associated_file_spec = associated_file_spec.or_else(|| {
+            self.annotation_associated_files()
+                .into_iter()
+                .find(|reference| self.is_c2pa_file_spec(*reference))
+        });
+
+        if associated_file_spec.is_some() || !self.is_linearized() {
+            return associated_file_spec;
+        }
+
+        self.embedded_file_specs()
+            .into_iter()
+            .map(|(_, id)| id)
+            .find(|id| self.is_c2pa_file_spec(*id))
+    }
+
+    /// Returns `true` if the object with the given id is a file specification with a C2PA
+    /// `AFRelationship` and an embedded file.
+    ///
+    /// Other attached files may share a configured non-C2PA relationship, such as
+    /// `Supplement`, so a file specification with that relationship must also have the
+    /// configured name.
+    fn is_c2pa_file_spec(&self, id: ObjectId) -> bool {
+        let Ok(dict) = self.document.get_object(id).and_then(Object::as_dict) else {
+            return false;
+        };
+
+        let Ok(name) = dict
+            .get_deref(AF_RELATIONSHIP_KEY, &self.document)
+            .and_then(Object::as_name)
+        else {
+            return false;
+        };
+
+        if !dict.has(b"EF") {
+            return false;
+        }
+
+        let options = &self.embedded_file_options;
+        name == C2PA_RELATIONSHIP
+            || (name == options.relationship.as_bytes() && file_spec_name(dict) == options.name)
+    }
+
+    /// Returns `true` if the PDF is linearized, meaning its first object is a linearization
+    /// parameter dictionary and its cross-reference table is split into a first-page section
+    /// and a main section.
+    fn is_linearized(&self) -> bool {
+        self.document
+            .objects
+            .values()
+            .any(|object| matches!(object.as_dict(), Ok(dict) if dict.has(b"Linearized")))
+    }
+
+    /// Removes the C2PA File Spec Reference if it exists in the Associated Files [Object::Array] of
+    /// PDF's catalog. This will return an [Err] if the PDF doesn't contain a C2PA File Spec
+    /// Reference.
+    fn remove_c2pa_file_spec_reference(&mut self) -> Result<(), Error> {
+        let c2pa_file_spec_reference = self
+            .c2pa_file_spec_object_id()
+            .ok_or_else(|| Error::FindingC2PAFileSpec)?;
+
+        self.document
+            .catalog_mut()?
+            .get_mut(ASSOCIATED_FILE_KEY)?
+            .as_array_mut()?
+            .retain(|v| {
+                let Ok(reference) = v.as_reference() else {
+                    return true;
+                };
+
+                reference != c2pa_file_spec_reference
+            });
+
+        Ok(())
+    }
+
+    /// Adds the C2PA `Annotation` to the PDF.
+    ///
+    /// ### Note:
+    /// The `FileAttachment` annotation is added to the first page of the PDF in the lower
+    /// left-hand corner. The `FileAttachment`'s location is not defined in the spec as of version
+    /// `1.3`.
+    fn add_file_attachment_annotation(
+        &mut self,
+        file_spec_reference: ObjectId,
+    ) -> Result<(), Error> {
+        let annotation = dictionary! {
+            "Type" => Name("Annot".into()),
+            "Contents" => Object::string_literal(CONTENT_CREDS),
+            "Name" => Object::string_literal(CONTENT_CREDS),
+            SUBTYPE_KEY => Name("FileAttachment".into()),
+            "FS" => Reference(file_spec_reference),
+            // Places annotation in the lower left-hand corner. The icon will be 10x10.
+            "Rect" => vec![0.into(), 0.into(), 10.into(), 10.into()],
+        };
+
+        // Add C2PA annotation as an indirect object.
+        let annotation_ref = self.document.add_object(annotation);
+
+        // Find the reference to the first page of the PDF.
+        let first_page_ref = self
+            .document
+            .page_iter()
+            .next()
+            .ok_or_else(|| Error::AddingAnnotation)?;
+
+        // Get a mutable ref to the first page as a Dictionary object.
+        let first_page = self
+            .document
+            .get_object_mut(first_page_ref)?
+            .as_dict_mut()?;
+
+        // Ensures the /Annots array exists on the page object.
+        if !first_page.has(ANNOTATIONS_KEY) {
+            first_page.set(ANNOTATIONS_KEY, Array(vec![]))
+        }
+
+        // Follows a reference to the indirect annotations array, if it exists.
+        let annotation_object = first_page.get_mut(ANNOTATIONS_KEY)?;
+        let annotations = if let Ok(v) = annotation_object.as_reference() {
+            self.document.get_object_mut(v)?
+        } else {
+            annotation_object
+        }
+        .as_array_mut()?;
+
+        annotations.push(Reference(annotation_ref));
+        Ok(())
+    }
+
+    /// Creates, or appends to, the Associated File (`AF`) array the embedded file spec reference of the
+    /// C2PA data.
+    fn push_associated_file(&mut self, embedded_file_spec_ref: ObjectId) -> Result<(), Error> {
+        let catalog = self.document.catalog_mut()?;
+        if catalog.get_mut(ASSOCIATED_FILE_KEY).is_err() {
+            // Add associated files array to catalog if it isn't already present.
+            catalog.set(ASSOCIATED_FILE_KEY, vec![]);
+        }
+
+        let associated_files = catalog.get_mut(ASSOCIATED_FILE_KEY)?;
+        let associated_files = match associated_files.as_reference() {
+            Ok(object_id) => self.document.get_object_mut(object_id)?,
+            _ => associated_files,
+        }
+        .as_array_mut()?;
+
+        associated_files.push(Reference(embedded_file_spec_ref));
+
+        Ok(())
+    }
+
+    /// Adds the `Embedded File Specification` to the PDF document. Returns the [Reference]
+    /// to the added `Embedded File Specification`.
+    fn add_embedded_file_specification(&mut self, file_stream_ref: ObjectId) -> ObjectId {
+        let options = &self.embedded_file_options;
+        let embedded_file_stream = dictionary! {
+            AF_RELATIONSHIP_KEY => Name(options.relationship.as_bytes().to_vec()),
+            "Desc" => Object::string_literal(options.description.as_str()),
+            "F" => Object::string_literal(options.name.as_str()),
+            "EF" => dictionary! {
+                "F" => Reference(file_stream_ref),
+            },
+            TYPE_KEY => Name("FileSpec".into()),
+            "UF" => Object::string_literal(options.name.as_str()),
+        };
+
+        self.document.add_object(embedded_file_stream)
+    }
+
+    /// Adds the provided `bytes` as a `StreamDictionary` to the PDF document. Returns the
+    /// [Reference] of the added [Object].
+    fn add_c2pa_embedded_file_stream(&mut self, bytes: Vec<u8>) -> ObjectId {
+        let date = self.embedded_file_options.date;
+        let stream = Stream::new(c2pa_embedded_file_stream_dict(bytes.len(), date), bytes);
+
+        self.document.add_object(stream)
+    }
+
+    /// Remove the C2PA Manifest `Annotation` from the PDF.
+    fn remove_manifest_from_annotations(&mut self) -> Result<(), Error> {
+        for (_, page_id) in self.document.get_pages() {
+            self.document
+                .get_object_mut(page_id)?
+                .as_dict_mut()?
+                .get_mut(ANNOTATIONS_KEY)?
+                .as_array_mut()?
+                .retain(|obj| {
+                    obj.as_dict()
+                        .and_then(|annot| annot.get(TYPE_KEY))
+                        .and_then(Object::as_name_str)
+                        .map(|str| str != CONTENT_CREDS)
+                        .unwrap_or(true)
+                });
+        }
+
+        Ok(())
+    }
+
+    /// Removes the manifest from the PDF's embedded files collection.
+    fn remove_manifest_from_embedded_files(&mut self) -> Result<(), Error> {
+        let Ok(names) = self.document.catalog_mut()?.get_mut(NAMES_KEY) else {
+            return Err(Error::NoManifest);
+        };
 
         // Follows the reference to the /Names Dictionary.
         let names_dictionary = match names.as_reference() {
@@ -504,45 +1354,513 @@ impl Pdf {
             _ => names_vector_object.as_array_mut()?,
         };
 
-        // Find the "Content Credentials" marker name in the /Names Array.
+        // Find the manifest's marker name ("Content Credentials" by default) in the /Names Array.
+        let manifest_name = self.embedded_file_options.name.as_str();
         let content_creds_marker_idx = names_vector
             .iter()
             .position(|value| {
                 value
                     .as_string()
-                    .map(|value| value == CONTENT_CREDS)
+                    .map(|value| value == manifest_name)
                     .unwrap_or_default()
             })
             .ok_or_else(|| Error::UnableToFindEmbeddedFileManifest)?;
 
-        let content_creds_reference_idx = content_creds_marker_idx + 1;
-        if content_creds_reference_idx >= names_vector.len() {
-            return Err(Error::UnableToFindEmbeddedFileManifest);
+        let content_creds_reference_idx = content_creds_marker_idx + 1;
+        if content_creds_reference_idx >= names_vector.len() {
+            return Err(Error::UnableToFindEmbeddedFileManifest);
+        }
+
+        // Delete the "Content Credentials" marker object and the reference to the C2PA
+        // manifest in the PDF's embedded files.
+        names_vector.drain(content_creds_marker_idx..=content_creds_reference_idx);
+
+        Ok(())
+    }
+
+    fn manifest_stream(&self) -> Result<Option<&Stream>, Error> {
+        let Some(id) = self.c2pa_file_spec_object_id() else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.embedded_file_object(id)?.as_stream()?))
+    }
+
+    /// Returns the id of the C2PA manifest's embedded file stream, if it is an indirect object.
+    fn manifest_stream_id(&self) -> Option<ObjectId> {
+        let id = self.c2pa_file_spec_object_id()?;
+        let ef = self
+            .get_object_with_context(id)
+            .ok()?
+            .as_dict()
+            .ok()?
+            .get(b"EF")
+            .ok()?;
+
+        self.deref_with_context(ef)
+            .ok()?
+            .as_dict()
+            .ok()?
+            .get(b"F")
+            .and_then(Object::as_reference)
+            .ok()
+    }
+
+    /// Returns the `/F` entry of the `/EF` dictionary of the file specification with the given
+    /// id, which holds its embedded file stream.
+    fn embedded_file_object(&self, id: ObjectId) -> Result<&Object, Error> {
+        let ef = self.get_object_with_context(id)?.as_dict()?.get(b"EF")?;
+        let ef = self.deref_with_context(ef)?.as_dict()?; // EF dictionary
+
+        self.deref_with_context(ef.get(b"F")?) // F embedded file stream
+    }
+
+    /// Returns the object with the given id. If it can't be read, the returned
+    /// [Error::UnableToReadObject] carries the id and the object's cross-reference offset.
+    fn get_object_with_context(&self, id: ObjectId) -> Result<&Object, Error> {
+        self.document
+            .get_object(id)
+            .map_err(|source| Error::UnableToReadObject {
+                object_id: id,
+                offset: self.xref_offset(id),
+                source,
+            })
+    }
+
+    /// Follows `object` to the object it references, if it is a reference.
+    fn deref_with_context<'a>(&'a self, object: &'a Object) -> Result<&'a Object, Error> {
+        match object {
+            Reference(id) => self.get_object_with_context(*id),
+            _ => Ok(object),
+        }
+    }
+
+    /// The catalog of the PDF as loaded.
+    fn original_catalog(&self) -> Option<&Dictionary> {
+        let root = self
+            .document
+            .trailer
+            .get(b"Root")
+            .ok()?
+            .as_reference()
+            .ok()?;
+        self.original_objects.get(&root)?.as_dict().ok()
+    }
+
+    /// The objects of the structure tree of the PDF as loaded, found by following references
+    /// from `/StructTreeRoot`.
+    fn original_structure_tree(&self) -> Vec<ObjectId> {
+        let mut pending = Vec::new();
+        if let Some(root) = self
+            .original_catalog()
+            .and_then(|catalog| catalog.get(STRUCT_TREE_ROOT_KEY).ok())
+        {
+            collect_references(root, &STRUCTURE_BACK_REFERENCE_KEYS, &mut pending);
+        }
+
+        let mut tree = Vec::new();
+        let mut visited = std::collections::BTreeSet::new();
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            tree.push(id);
+
+            if let Some(object) = self.original_objects.get(&id) {
+                collect_references(object, &STRUCTURE_BACK_REFERENCE_KEYS, &mut pending);
+            }
+        }
+        tree
+    }
+
+    /// Returns `true` if the object was modified or removed since the PDF was loaded.
+    fn object_changed(&self, id: ObjectId) -> bool {
+        let original = self.original_objects.get(&id).map(serialized);
+        let current = self.document.objects.get(&id).map(serialized);
+        original != current
+    }
+
+    /// The annotations of a page of the PDF as loaded.
+    fn original_annotations(&self, page: &Dictionary) -> Vec<ObjectId> {
+        let annotations = match page.get(ANNOTATIONS_KEY) {
+            Ok(Reference(id)) => self.original_objects.get(id),
+            Ok(annotations) => Some(annotations),
+            Err(_) => None,
+        };
+
+        annotations
+            .and_then(|annotations| annotations.as_array().ok())
+            .map(|annotations| {
+                annotations
+                    .iter()
+                    .filter_map(|annotation| annotation.as_reference().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The annotations of a page of the in-memory PDF.
+    fn annotations(&self, page: &Dictionary) -> Vec<ObjectId> {
+        page.get(ANNOTATIONS_KEY)
+            .ok()
+            .and_then(|annotations| self.deref_with_context(annotations).ok())
+            .and_then(|annotations| annotations.as_array().ok())
+            .map(|annotations| {
+                annotations
+                    .iter()
+                    .filter_map(|annotation| annotation.as_reference().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the byte offset of the object with the given id in the PDF's cross-reference
+    /// table, if it is stored uncompressed.
+    fn xref_offset(&self, id: ObjectId) -> Option<usize> {
+        match self.document.reference_table.get(id.0)? {
+            XrefEntry::Normal { offset, generation } if *generation == id.1 => {
+                Some(*offset as usize)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses a PDF version such as `"1.7"` into its major and minor numbers. Parts that aren't
+/// numbers are treated as 0.
+fn version_number(version: &str) -> (u32, u32) {
+    let version = version.trim();
+    let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+    (
+        major.parse().unwrap_or_default(),
+        minor.parse().unwrap_or_default(),
+    )
+}
+
+/// Returns the name of a file specification, from its `/UF` entry or else its `/F` entry.
+fn file_spec_name(file_spec: &Dictionary) -> String {
+    [b"UF".as_slice(), b"F".as_slice()]
+        .iter()
+        .find_map(|key| match file_spec.get(key) {
+            Ok(Object::String(name, _)) => Some(decode_text_string(name)),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the bytes of a name tree key, which must be a string.
+fn name_tree_key(object: &Object) -> Option<&[u8]> {
+    match object {
+        Object::String(key, _) => Some(key),
+        _ => None,
+    }
+}
+
+/// Inserts a name tree key and value `pair` into the `/Names` array `names`, after any entries
+/// whose keys sort before or equal to it, so that the array stays sorted.
+fn insert_name_tree_pair(names: &mut Vec<Object>, pair: Vec<Object>) {
+    let key = name_tree_key(&pair[0]).unwrap_or_default();
+    let idx = names
+        .chunks_exact(2)
+        .position(|entry| name_tree_key(&entry[0]).is_some_and(|name| name > key))
+        .map_or(names.len() - names.len() % 2, |position| position * 2);
+
+    names.splice(idx..idx, pair);
+}
+
+/// Decodes a PDF text string, which is either UTF-16BE with a byte order mark or
+/// PDFDocEncoded. PDFDocEncoding is treated as Latin-1, which it matches for printable text.
+fn decode_text_string(bytes: &[u8]) -> String {
+    match bytes.strip_prefix(&[0xfe, 0xff]) {
+        Some(utf16) => {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        None => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Returns the content of `stream` with its filters applied.
+fn decoded_content(stream: &Stream) -> Result<Vec<u8>, Error> {
+    if stream.dict.has(b"Filter") {
+        Ok(stream.decompressed_content()?)
+    } else {
+        Ok(stream.content.clone())
+    }
+}
+
+/// Returns the dictionary of the embedded file stream holding a manifest of `len` bytes, with
+/// `/Params` giving its creation and modification `date` if there is one.
+fn c2pa_embedded_file_stream_dict(len: usize, date: Option<DateTime<Utc>>) -> Dictionary {
+    let mut dict = dictionary! {
+        "F" => dictionary! {
+        SUBTYPE_KEY => C2PA_MIME_TYPE,
+        "Length" => Integer(len as i64),
+        },
+    };
+
+    if let Some(date) = date {
+        let date = pdf_date(date);
+        dict.set(
+            "Params",
+            dictionary! {
+                "CreationDate" => Object::string_literal(date.as_str()),
+                "ModDate" => Object::string_literal(date.as_str()),
+            },
+        );
+    }
+
+    dict
+}
+
+/// Formats `date` as a PDF date string, such as `D:20240102030405Z`.
+fn pdf_date(date: DateTime<Utc>) -> String {
+    date.format("D:%Y%m%d%H%M%SZ").to_string()
+}
+
+/// Returns the offset of the last cross-reference section, as given by the final `startxref`.
+fn find_startxref(bytes: &[u8]) -> Option<usize> {
+    let pos = memchr::memmem::rfind(bytes, b"startxref")?;
+    let digits: Vec<u8> = bytes[pos + b"startxref".len()..]
+        .iter()
+        .skip_while(|b| b.is_ascii_whitespace())
+        .take_while(|b| b.is_ascii_digit())
+        .copied()
+        .collect();
+
+    std::str::from_utf8(&digits).ok()?.parse().ok()
+}
+
+/// Returns `true` if the cross-reference section at `offset` is a cross-reference stream
+/// rather than a cross-reference table.
+fn uses_xref_stream(bytes: &[u8], offset: usize) -> bool {
+    let section = bytes.get(offset..).unwrap_or_default();
+    let start = section
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(section.len());
+    !section[start..].is_empty() && !section[start..].starts_with(b"xref")
+}
+
+/// Splits cross-reference entries into subsections of consecutive object numbers.
+fn xref_subsections(
+    entries: &BTreeMap<ObjectId, Option<usize>>,
+) -> Vec<Vec<(&ObjectId, &Option<usize>)>> {
+    let mut subsections: Vec<Vec<(&ObjectId, &Option<usize>)>> = Vec::new();
+    for entry in entries {
+        match subsections.last_mut() {
+            Some(subsection) if subsection[subsection.len() - 1].0 .0 + 1 == entry.0 .0 => {
+                subsection.push(entry)
+            }
+            _ => subsections.push(vec![entry]),
+        }
+    }
+    subsections
+}
+
+/// Writes a cross-reference table holding `entries`, where `None` marks a deleted object.
+fn write_xref_table<W: Write>(
+    writer: &mut W,
+    entries: &BTreeMap<ObjectId, Option<usize>>,
+) -> std::io::Result<()> {
+    writer.write_all(b"xref\n")?;
+    for subsection in xref_subsections(entries) {
+        writeln!(writer, "{} {}", subsection[0].0 .0, subsection.len())?;
+
+        for (id, offset) in subsection {
+            match offset {
+                Some(offset) => write_xref_entry(writer, *offset, id.1)?,
+                None => write!(writer, "{:010} {:05} f\r\n", 0, id.1 + 1)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes an uncompressed cross-reference stream object `id` holding `entries`, where `None`
+/// marks a deleted object, with the trailer entries of `trailer`.
+fn write_xref_stream<W: Write>(
+    writer: &mut W,
+    id: ObjectId,
+    entries: &BTreeMap<ObjectId, Option<usize>>,
+    mut trailer: Dictionary,
+) -> std::io::Result<()> {
+    // type, offset or next free object number, generation
+    const WIDTHS: [usize; 3] = [1, 8, 2];
+
+    let mut index = Vec::new();
+    let mut rows = Vec::new();
+    for subsection in xref_subsections(entries) {
+        index.push(Object::Integer(i64::from(subsection[0].0 .0)));
+        index.push(Object::Integer(subsection.len() as i64));
+
+        for (id, offset) in subsection {
+            let (kind, field, generation) = match offset {
+                Some(offset) => (1u8, *offset as u64, id.1),
+                None => (0u8, 0, id.1.wrapping_add(1)),
+            };
+            rows.push(kind);
+            rows.extend_from_slice(&field.to_be_bytes());
+            rows.extend_from_slice(&generation.to_be_bytes());
+        }
+    }
+
+    trailer.set(TYPE_KEY, Name(b"XRef".to_vec()));
+    trailer.set("Index", index);
+    trailer.set(
+        "W",
+        WIDTHS
+            .iter()
+            .map(|width| Object::Integer(*width as i64))
+            .collect::<Vec<_>>(),
+    );
+
+    writeln!(writer, "{} {} obj", id.0, id.1)?;
+    write_stream_header(writer, &trailer, rows.len())?;
+    writer.write_all(&rows)?;
+    writer.write_all(b"\nendstream\nendobj")
+}
+
+/// Writes the cross-reference entry of an object in use at `offset`.
+///
+/// Entries hold offsets in a fixed width field of 10 digits, so an error is returned for
+/// offsets past the largest one the field can hold.
+fn write_xref_entry<W: Write>(
+    writer: &mut W,
+    offset: usize,
+    generation: u16,
+) -> std::io::Result<()> {
+    if offset as u64 > MAX_XREF_OFFSET {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the PDF is too large for a cross-reference table",
+        ));
+    }
+    write!(writer, "{offset:010} {generation:05} n\r\n")
+}
+
+/// Collects the references in `object`, including those nested in arrays and dictionaries,
+/// except for the dictionary entries named in `skip_keys`.
+fn collect_references(object: &Object, skip_keys: &[&[u8]], references: &mut Vec<ObjectId>) {
+    match object {
+        Reference(id) => references.push(*id),
+        Array(array) => {
+            for object in array {
+                collect_references(object, skip_keys, references);
+            }
+        }
+        Object::Dictionary(dict) | Object::Stream(Stream { dict, .. }) => {
+            for (key, object) in dict.iter() {
+                if !skip_keys.contains(&key.as_slice()) {
+                    collect_references(object, skip_keys, references);
+                }
+            }
         }
+        _ => {}
+    }
+}
 
-        // Delete the "Content Credentials" marker object and the reference to the C2PA
-        // manifest in the PDF's embedded files.
-        names_vector.drain(content_creds_marker_idx..=content_creds_reference_idx);
+/// Returns the PDF syntax form of an [Object], used to compare objects.
+fn serialized(object: &Object) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // writing to a Vec can't fail
+    let _ = write_object(&mut bytes, object);
+    bytes
+}
 
-        Ok(())
+/// Serializes a PDF [Object] in its PDF syntax form.
+fn write_object<W: Write>(writer: &mut W, object: &Object) -> std::io::Result<()> {
+    match object {
+        Object::Null => writer.write_all(b"null"),
+        Object::Boolean(value) => write!(writer, "{value}"),
+        Object::Integer(value) => write!(writer, "{value}"),
+        Object::Real(value) => write!(writer, "{value}"),
+        Object::Name(name) => write_name(writer, name),
+        Object::String(bytes, format) => write_string(writer, bytes, format),
+        Object::Array(array) => {
+            writer.write_all(b"[")?;
+            for (i, object) in array.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b" ")?;
+                }
+                write_object(writer, object)?;
+            }
+            writer.write_all(b"]")
+        }
+        Object::Dictionary(dict) => write_dictionary(writer, dict),
+        Object::Stream(stream) => {
+            write_stream_header(writer, &stream.dict, stream.content.len())?;
+            writer.write_all(&stream.content)?;
+            writer.write_all(b"\nendstream")
+        }
+        Object::Reference((id, generation)) => write!(writer, "{id} {generation} R"),
     }
+}
 
-    fn manifest_stream(&self) -> Result<Option<&Stream>, Error> {
-        let Some(id) = self.c2pa_file_spec_object_id() else {
-            return Ok(None);
-        };
+/// Writes the dictionary of a stream with `len` bytes of content, up to where the content starts.
+fn write_stream_header<W: Write>(
+    writer: &mut W,
+    dict: &Dictionary,
+    len: usize,
+) -> std::io::Result<()> {
+    let mut dict = dict.clone();
+    dict.set("Length", len as i64);
+
+    write_dictionary(writer, &dict)?;
+    writer.write_all(b"\nstream\n")
+}
 
-        let ef = &self
-            .document
-            .get_object(id)
-            .and_then(Object::as_dict)?
-            .get_deref(b"EF", &self.document)?
-            .as_dict()?; // EF dictionary
+fn write_dictionary<W: Write>(writer: &mut W, dict: &Dictionary) -> std::io::Result<()> {
+    writer.write_all(b"<<")?;
+    for (key, value) in dict.iter() {
+        write_name(writer, key)?;
+        writer.write_all(b" ")?;
+        write_object(writer, value)?;
+    }
+    writer.write_all(b">>")
+}
 
-        Ok(Some(
-            ef.get_deref(b"F", &self.document)? // F embedded file stream
-                .as_stream()?,
-        ))
+fn write_name<W: Write>(writer: &mut W, name: &[u8]) -> std::io::Result<()> {
+    writer.write_all(b"/")?;
+    for &b in name {
+        // Regular characters are written as is, everything else is written as a #xx escape.
+        if b.is_ascii_graphic() && !b"#()<>[]{}/%".contains(&b) {
+            writer.write_all(&[b])?;
+        } else {
+            write!(writer, "#{b:02X}")?;
+        }
+    }
+    Ok(())
+}
+
+fn write_string<W: Write>(
+    writer: &mut W,
+    bytes: &[u8],
+    format: &StringFormat,
+) -> std::io::Result<()> {
+    match format {
+        StringFormat::Literal => {
+            writer.write_all(b"(")?;
+            for &b in bytes {
+                match b {
+                    b'(' | b')' | b'\\' => writer.write_all(&[b'\\', b])?,
+                    b'\r' => writer.write_all(b"\\r")?,
+                    b'\n' => writer.write_all(b"\\n")?,
+                    _ => writer.write_all(&[b])?,
+                }
+            }
+            writer.write_all(b")")
+        }
+        StringFormat::Hexadecimal => {
+            writer.write_all(b"<")?;
+            for b in bytes {
+                write!(writer, "{b:02X}")?;
+            }
+            writer.write_all(b">")
+        }
     }
 }
 
@@ -574,6 +1892,35 @@ mod tests {
         assert!(matches!(pdf_result, Err(Error::UnableToReadPdf(_))));
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_version() {
+        let bytes = include_bytes!("../../tests/fixtures/basic.pdf");
+        let mut pdf = Pdf::from_bytes(bytes).unwrap();
+        assert_eq!(pdf.version(), "1.6");
+        assert!(pdf.unsupported_features().is_empty());
+
+        let bytes = include_bytes!("../../tests/fixtures/express.pdf");
+        assert_eq!(Pdf::from_bytes(bytes).unwrap().version(), "1.7");
+
+        // the catalog may upgrade the header version, but not downgrade it
+        pdf.document
+            .catalog_mut()
+            .unwrap()
+            .set("Version", Name("2.0".into()));
+        assert_eq!(pdf.version(), "2.0");
+        pdf.document
+            .catalog_mut()
+            .unwrap()
+            .set("Version", Name("1.4".into()));
+        assert_eq!(pdf.version(), "1.6");
+
+        let bytes = include_bytes!("../../tests/fixtures/basic-pdf2.pdf");
+        let pdf = Pdf::from_bytes(bytes).unwrap();
+        assert_eq!(pdf.version(), "2.0");
+        assert_eq!(pdf.unsupported_features(), vec!["/DSS".to_string()]);
+    }
+
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_is_password_protected() {
@@ -662,6 +2009,229 @@ mod tests {
         assert!(pdf.has_c2pa_manifest());
     }
 
+    /// Returns the `/EmbeddedFiles` name tree root of the `document`.
+    fn embedded_files_root(document: &Document) -> &Dictionary {
+        document
+            .catalog()
+            .unwrap()
+            .get_deref(NAMES_KEY, document)
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get_deref(EMBEDDED_FILES_KEY, document)
+            .and_then(Object::as_dict)
+            .unwrap()
+    }
+
+    /// Asserts the name tree `node` is well formed and returns the keys it holds, in order.
+    fn assert_valid_name_tree(document: &Document, node: &Dictionary, root: bool) -> Vec<Vec<u8>> {
+        let names = node
+            .get_deref(NAMES_KEY, document)
+            .and_then(Object::as_array);
+        let kids = node.get_deref(b"Kids", document).and_then(Object::as_array);
+
+        assert_ne!(
+            names.is_ok(),
+            kids.is_ok(),
+            "name tree node must have exactly one of /Names or /Kids"
+        );
+
+        let keys: Vec<Vec<u8>> = match (names, kids) {
+            (Ok(names), _) => {
+                assert_eq!(names.len() % 2, 0, "/Names must hold key and value pairs");
+                names
+                    .chunks_exact(2)
+                    .map(|pair| name_tree_key(&pair[0]).unwrap().to_vec())
+                    .collect()
+            }
+            (_, Ok(kids)) => kids
+                .iter()
+                .flat_map(|kid| {
+                    let kid = document
+                        .get_dictionary(kid.as_reference().unwrap())
+                        .unwrap();
+                    assert_valid_name_tree(document, kid, false)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        assert!(
+            keys.windows(2).all(|keys| keys[0] <= keys[1]),
+            "keys are unsorted"
+        );
+
+        let limits = node.get(b"Limits").and_then(Object::as_array);
+        if root {
+            assert!(limits.is_err(), "the root node must not have /Limits");
+        } else {
+            let limits = limits.unwrap();
+            assert_eq!(name_tree_key(&limits[0]), keys.first().map(Vec::as_slice));
+            assert_eq!(name_tree_key(&limits[1]), keys.last().map(Vec::as_slice));
+        }
+
+        keys
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_manifest_creates_embedded_files_name_tree() {
+        for bytes in [
+            include_bytes!("../../tests/fixtures/basic.pdf").as_slice(),
+            include_bytes!("../../tests/fixtures/basic-empty-names.pdf").as_slice(),
+        ] {
+            let mut pdf = Pdf::from_bytes(bytes).unwrap();
+            pdf.write_manifest_as_embedded_file(vec![10u8, 20u8])
+                .unwrap();
+
+            let mut saved_bytes = vec![];
+            pdf.save_incremental_to(&mut saved_bytes).unwrap();
+            let saved_pdf = Pdf::from_bytes(&saved_bytes).unwrap();
+            let document = &saved_pdf.document;
+
+            let keys = assert_valid_name_tree(document, embedded_files_root(document), true);
+            assert_eq!(keys, vec![CONTENT_CREDS.as_bytes().to_vec()]);
+            assert!(saved_pdf.has_c2pa_manifest());
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_manifest_keeps_embedded_files_names_sorted() {
+        let mut pdf =
+            Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic-attachments.pdf")).unwrap();
+        let keys_before =
+            assert_valid_name_tree(&pdf.document, embedded_files_root(&pdf.document), true);
+
+        pdf.write_manifest_as_embedded_file(vec![10u8, 20u8])
+            .unwrap();
+
+        let keys = assert_valid_name_tree(&pdf.document, embedded_files_root(&pdf.document), true);
+        assert_eq!(keys.len(), keys_before.len() + 1);
+        assert!(keys.contains(&CONTENT_CREDS.as_bytes().to_vec()));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_manifest_to_embedded_files_name_tree_with_kids() {
+        let mut pdf = Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic.pdf")).unwrap();
+
+        let file_spec = pdf
+            .document
+            .add_object(dictionary! { "Type" => "Filespec" });
+        let low_leaf = pdf.document.add_object(dictionary! {
+            "Limits" => vec![Object::string_literal("A"), Object::string_literal("B")],
+            "Names" => vec![
+                Object::string_literal("A"),
+                Reference(file_spec),
+                Object::string_literal("B"),
+                Reference(file_spec),
+            ],
+        });
+        let high_leaf = pdf.document.add_object(dictionary! {
+            "Limits" => vec![Object::string_literal("X"), Object::string_literal("Z")],
+            "Names" => vec![
+                Object::string_literal("X"),
+                Reference(file_spec),
+                Object::string_literal("Z"),
+                Reference(file_spec),
+            ],
+        });
+        let intermediate = pdf.document.add_object(dictionary! {
+            "Limits" => vec![Object::string_literal("A"), Object::string_literal("Z")],
+            "Kids" => vec![Reference(low_leaf), Reference(high_leaf)],
+        });
+        let embedded_files = pdf.document.add_object(dictionary! {
+            "Kids" => vec![Reference(intermediate)],
+        });
+        let names = pdf.document.add_object(dictionary! {
+            "EmbeddedFiles" => Reference(embedded_files),
+        });
+        pdf.document.catalog_mut().unwrap().set(NAMES_KEY, names);
+
+        pdf.write_manifest_as_embedded_file(vec![10u8, 20u8])
+            .unwrap();
+
+        let document = &pdf.document;
+        let root = embedded_files_root(document);
+        assert!(root.get(NAMES_KEY).is_err());
+
+        let keys = assert_valid_name_tree(document, root, true);
+        assert_eq!(
+            keys,
+            vec![
+                b"A".to_vec(),
+                b"B".to_vec(),
+                CONTENT_CREDS.as_bytes().to_vec(),
+                b"X".to_vec(),
+                b"Z".to_vec()
+            ]
+        );
+        assert!(pdf.has_c2pa_manifest());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_list_and_extract_embedded_files() {
+        let mut pdf =
+            Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic-attachments.pdf")).unwrap();
+        let manifest_bytes = vec![10u8, 20u8, 30u8];
+        pdf.write_manifest_as_embedded_file(manifest_bytes.clone())
+            .unwrap();
+
+        let mut saved_bytes = vec![];
+        pdf.save_to(&mut saved_bytes).unwrap();
+        let pdf = Pdf::from_bytes(&saved_bytes).unwrap();
+
+        let png = include_bytes!("../../tests/fixtures/libpng-test.png");
+        let png_with_url = include_bytes!("../../tests/fixtures/libpng-test_with_url.png");
+
+        let files = pdf.list_embedded_files().unwrap();
+        assert_eq!(
+            files,
+            vec![
+                EmbeddedFile {
+                    name: "libpng-test.png".to_owned(),
+                    size: png.len(),
+                    relationship: None,
+                },
+                EmbeddedFile {
+                    name: "libpng-test_with_url.png".to_owned(),
+                    size: png_with_url.len(),
+                    relationship: None,
+                },
+                EmbeddedFile {
+                    name: CONTENT_CREDS.to_owned(),
+                    size: manifest_bytes.len(),
+                    relationship: Some("C2PA_Manifest".to_owned()),
+                },
+            ]
+        );
+
+        assert_eq!(
+            pdf.extract_embedded_file("libpng-test.png").unwrap(),
+            Some(png.to_vec())
+        );
+        assert_eq!(
+            pdf.extract_embedded_file(CONTENT_CREDS).unwrap(),
+            Some(manifest_bytes)
+        );
+        assert_eq!(pdf.extract_embedded_file("missing.png").unwrap(), None);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_list_embedded_files_includes_annotation_manifest() {
+        let mut pdf = Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic.pdf")).unwrap();
+        assert!(pdf.list_embedded_files().unwrap().is_empty());
+
+        pdf.write_manifest_as_annotation(vec![10u8, 20u8]).unwrap();
+
+        let files = pdf.list_embedded_files().unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, CONTENT_CREDS);
+        assert_eq!(files[0].size, 2);
+    }
+
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_save_to() {
@@ -712,6 +2282,28 @@ mod tests {
         ));
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_read_manifest_bytes_reports_unreadable_object() {
+        let pdf = Pdf::from_bytes(include_bytes!(
+            "../../tests/fixtures/basic-bad-manifest-object.pdf"
+        ))
+        .unwrap();
+
+        let err = pdf.read_manifest_bytes().unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnableToReadObject {
+                object_id: (18, 0),
+                offset: Some(9721),
+                ..
+            }
+        ));
+        assert!(err
+            .to_string()
+            .starts_with("Unable to read PDF object 18 0"));
+    }
+
     #[cfg_attr(not(target_arch = "wasm32"), test)]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_read_manifest_bytes_from_pdf_without_bytes_returns_none() {
@@ -795,4 +2387,298 @@ mod tests {
         assert!(pdf.remove_manifest_bytes().is_ok());
         assert!(!pdf.has_c2pa_manifest());
     }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_embedded_file_options_name_in_name_tree() {
+        let mut pdf = Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic.pdf")).unwrap();
+        pdf.set_embedded_file_options(EmbeddedFileOptions {
+            name: "manifest.c2pa".to_owned(),
+            relationship: "Supplement".to_owned(),
+            description: "C2PA Manifest Store".to_owned(),
+            date: None,
+        })
+        .unwrap();
+
+        let manifest_bytes = vec![0u8, 1u8, 1u8, 2u8, 3u8];
+        pdf.write_manifest_as_embedded_file(manifest_bytes.clone())
+            .unwrap();
+
+        let mut saved_bytes = vec![];
+        pdf.save_to(&mut saved_bytes).unwrap();
+        let saved_pdf = Pdf::from_bytes(&saved_bytes).unwrap();
+        let document = &saved_pdf.document;
+
+        let names = document
+            .catalog()
+            .unwrap()
+            .get_deref(NAMES_KEY, document)
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get_deref(EMBEDDED_FILES_KEY, document)
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get_deref(NAMES_KEY, document)
+            .and_then(Object::as_array)
+            .unwrap();
+
+        let name_idx = names
+            .iter()
+            .position(|value| {
+                value
+                    .as_string()
+                    .map(|value| value == "manifest.c2pa")
+                    .unwrap_or_default()
+            })
+            .unwrap();
+
+        let file_spec = document
+            .get_object(names[name_idx + 1].as_reference().unwrap())
+            .and_then(Object::as_dict)
+            .unwrap();
+        assert_eq!(
+            file_spec
+                .get(AF_RELATIONSHIP_KEY)
+                .unwrap()
+                .as_name()
+                .unwrap(),
+            b"Supplement"
+        );
+        assert_eq!(
+            file_spec.get(b"Desc").unwrap().as_string().unwrap(),
+            "C2PA Manifest Store"
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_embedded_file_options_invalid_relationship() {
+        let mut pdf = Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic.pdf")).unwrap();
+        let result = pdf.set_embedded_file_options(EmbeddedFileOptions {
+            relationship: "NotARelationship".to_owned(),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidAFRelationship(relationship)) if relationship == "NotARelationship"
+        ));
+        assert_eq!(pdf.embedded_file_options, EmbeddedFileOptions::default());
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_embedded_file_options_date() {
+        use chrono::TimeZone;
+
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        let date = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+        let mut pdf = Pdf::from_bytes(source).unwrap();
+        pdf.set_embedded_file_options(EmbeddedFileOptions {
+            date: Some(date),
+            ..Default::default()
+        })
+        .unwrap();
+
+        pdf.write_manifest_as_embedded_file(vec![0u8, 1u8, 1u8, 2u8, 3u8])
+            .unwrap();
+        let mut saved_bytes = vec![];
+        pdf.save_incremental_to(&mut saved_bytes).unwrap();
+
+        let saved_pdf = Pdf::from_bytes(&saved_bytes).unwrap();
+
+        let document = &saved_pdf.document;
+        let params = document
+            .get_object(saved_pdf.c2pa_file_spec_object_id().unwrap())
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get_deref(b"EF", document)
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get_deref(b"F", document)
+            .and_then(Object::as_stream)
+            .unwrap()
+            .dict
+            .get(b"Params")
+            .and_then(Object::as_dict)
+            .unwrap();
+        for key in [b"CreationDate".as_slice(), b"ModDate"] {
+            assert_eq!(
+                params.get(key).unwrap().as_string().unwrap(),
+                "D:20240102030405Z"
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_incremental_update_with_manifest_offset() {
+        let source = include_bytes!("../../tests/fixtures/basic-object-streams.pdf");
+        let mut pdf = Pdf::from_bytes(source).unwrap();
+        pdf.write_manifest_as_embedded_file(b"manifest".to_vec())
+            .unwrap();
+
+        let (update, offset) = pdf.incremental_update_with_manifest_offset().unwrap();
+        let offset = offset.unwrap();
+        let out = [&source[..], &update].concat();
+        assert_eq!(&out[offset..offset + 8], b"manifest");
+
+        let mut incremental = Vec::new();
+        pdf.save_incremental_to(&mut incremental).unwrap();
+        assert_eq!(out, incremental);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_save_incremental_keeps_xref_kind() {
+        for (source, xref_stream) in [
+            (&include_bytes!("../../tests/fixtures/basic.pdf")[..], false),
+            (
+                &include_bytes!("../../tests/fixtures/basic-object-streams.pdf")[..],
+                true,
+            ),
+        ] {
+            assert_eq!(
+                uses_xref_stream(source, find_startxref(source).unwrap()),
+                xref_stream
+            );
+
+            let mut pdf = Pdf::from_bytes(source).unwrap();
+            pdf.write_manifest_as_embedded_file(b"manifest".to_vec())
+                .unwrap();
+            let mut out = Vec::new();
+            pdf.save_incremental_to(&mut out).unwrap();
+
+            assert!(out.starts_with(source));
+            assert_eq!(
+                uses_xref_stream(&out, find_startxref(&out).unwrap()),
+                xref_stream
+            );
+
+            let saved = Pdf::from_bytes(&out).unwrap();
+            assert_eq!(
+                saved.read_manifest_bytes().unwrap().unwrap()[0].0,
+                b"manifest"
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_write_xref_entry_past_4gb() {
+        let mut entry = Vec::new();
+        write_xref_entry(&mut entry, 12, 0).unwrap();
+        assert_eq!(entry, b"0000000012 00000 n\r\n");
+
+        // offsets past the 32-bit range, as long as usize can hold them
+        if let Ok(offset) = usize::try_from(5_000_000_000_u64) {
+            let mut entry = Vec::new();
+            write_xref_entry(&mut entry, offset, 1).unwrap();
+            assert_eq!(entry, b"5000000000 00001 n\r\n");
+        }
+        if let Ok(offset) = usize::try_from(MAX_XREF_OFFSET + 1) {
+            assert!(write_xref_entry(&mut Vec::new(), offset, 0).is_err());
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_reads_manifest_bytes_from_linearized_pdf() {
+        let pdf = Pdf::from_bytes(include_bytes!(
+            "../../tests/fixtures/basic-linearized-manifest.pdf"
+        ))
+        .unwrap();
+        assert!(pdf.is_linearized());
+
+        let manifest_bytes = include_bytes!("../../tests/fixtures/cloud_manifest.c2pa");
+        assert!(matches!(
+            pdf.read_manifest_bytes(),
+            Ok(Some(manifests)) if manifests.len() == 1 && manifests[0].0 == manifest_bytes
+        ));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_linearized_pdf_ignores_unreferenced_file_specs() {
+        let source = include_bytes!("../../tests/fixtures/basic-linearized-manifest.pdf");
+        // blanks catalog entries without moving any object
+        let blank = |entry: &[u8]| {
+            let mut bytes = source.to_vec();
+            let at = bytes
+                .windows(entry.len())
+                .position(|window| window == entry)
+                .unwrap();
+            bytes[at..at + entry.len()].fill(b' ');
+            bytes
+        };
+
+        // still referenced from the /EmbeddedFiles name tree
+        let pdf = Pdf::from_bytes(&blank(b"/AF[6 0 R]")).unwrap();
+        assert!(matches!(pdf.read_manifest_bytes(), Ok(Some(manifests)) if manifests.len() == 1));
+
+        // an orphan file spec is not the manifest
+        let pdf = Pdf::from_bytes(&blank(b"/Names 8 0 R/AF[6 0 R]")).unwrap();
+        assert!(pdf.is_linearized());
+        assert!(matches!(pdf.read_manifest_bytes(), Ok(None)));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_add_manifest_preserves_tagged_pdf_structure() {
+        let bytes = include_bytes!("../../tests/fixtures/basic-tagged.pdf");
+        let mut pdf = Pdf::from_bytes(bytes).unwrap();
+        assert!(pdf.is_tagged());
+
+        pdf.write_manifest_as_embedded_file(vec![10u8, 20u8])
+            .unwrap();
+        assert!(pdf.tagging_changes().is_empty());
+
+        let mut saved_bytes = Vec::new();
+        pdf.save_incremental_to(&mut saved_bytes).unwrap();
+        assert!(saved_bytes.starts_with(bytes));
+
+        let original = Document::load_mem(bytes).unwrap();
+        let saved = Document::load_mem(&saved_bytes).unwrap();
+
+        let struct_tree_root = |document: &Document| {
+            document
+                .catalog()
+                .unwrap()
+                .get(STRUCT_TREE_ROOT_KEY)
+                .unwrap()
+                .as_reference()
+                .unwrap()
+        };
+        let root_id = struct_tree_root(&original);
+        assert_eq!(struct_tree_root(&saved), root_id);
+        assert!(saved.catalog().unwrap().has(b"MarkInfo"));
+
+        // the structure tree and the marked content stream are unchanged
+        for id in [root_id, (4, 0), (6, 0), (7, 0)] {
+            assert_eq!(
+                serialized(saved.get_object(id).unwrap()),
+                serialized(original.get_object(id).unwrap())
+            );
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_tagging_changes_reports_untagged_annotation() {
+        let mut pdf =
+            Pdf::from_bytes(include_bytes!("../../tests/fixtures/basic-tagged.pdf")).unwrap();
+        pdf.write_manifest_as_annotation(vec![10u8, 20u8]).unwrap();
+
+        assert_eq!(
+            pdf.tagging_changes(),
+            vec!["page 1 annotation without /StructParent".to_string()]
+        );
+
+        // untagged documents don't report changes
+        let mut pdf = Pdf::from_bytes(include_bytes!("../../tests/fixtures/express.pdf")).unwrap();
+        assert!(!pdf.is_tagged());
+        pdf.write_manifest_as_annotation(vec![10u8, 20u8]).unwrap();
+        assert!(pdf.tagging_changes().is_empty());
+    }
 }
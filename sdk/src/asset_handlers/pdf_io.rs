@@ -11,28 +11,126 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::{fs::File, path::Path};
+use std::{
+    fs::File,
+    io::{Read, SeekFrom, Write},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use log::debug;
 
 use crate::{
-    asset_handlers::pdf::{C2paPdf, Pdf},
+    asset_handlers::{
+        pdf::{C2paPdf, EmbeddedFileOptions, Pdf},
+        pdf_scan::{locate_manifest_stream, ManifestStreamLocation},
+    },
     asset_io::{
         AssetIO, CAIRead, CAIReadWrite, CAIReader, CAIWriter, ComposedManifestRef,
         HashObjectPositions,
     },
-    utils::patch::patch_bytes,
+    settings::get_settings_value,
+    store::Store,
+    utils::{patch::patch_bytes, xmp_inmemory_utils::extract_provenance},
     Error::{self, JumbfNotFound, NotImplemented, PdfReadError},
 };
 
+pub use crate::asset_handlers::pdf::EmbeddedFile;
+
 static SUPPORTED_TYPES: [&str; 2] = ["pdf", "application/pdf"];
 static WRITE_NOT_IMPLEMENTED: &str = "PDF write functionality will be added in a future release";
 
-pub struct PdfIO {}
+// The PDF header may be preceded by arbitrary bytes, but must start within the first 1024.
+const HEADER_SEARCH_LEN: usize = 1024;
+
+// The size of the manifest used to locate where one will be written in a PDF without one.
+const PLACEHOLDER_MANIFEST_LEN: usize = 1;
+
+// Room left after a newly written compressed manifest, in case the signed manifest that replaces
+// it compresses slightly worse. The signature placeholder of a store that compresses its manifest
+// is filled with hash output, so only the CBOR around the signature can shift the compressed size.
+// Unused room is zero padding after the end of the zlib stream, which decoders stop before.
+const COMPRESSED_MANIFEST_SLACK: usize = 256;
+
+/// Reads and writes C2PA manifests embedded in PDF documents.
+///
+/// A manifest is added to a PDF without one as an incremental update, so the bytes of the
+/// original document are kept as a prefix of the output. When a document needs both a C2PA
+/// manifest and a PDF digital signature, sign with C2PA first and apply the PDF signature
+/// afterwards: the PDF signature's `/ByteRange` then covers the embedded manifest, while the
+/// manifest stays valid for the revision it was embedded in.
+///
+/// A newly embedded manifest file is dated with the signing time given by
+/// [`CAIWriter::set_signing_time`], if there is one.
+/// Its name, `/AFRelationship` and `/Desc` are set by the `core.pdf_embedded_file_name`,
+/// `core.pdf_embedded_file_relationship` and `core.pdf_embedded_file_description` settings,
+/// which must also be set to read it back when the relationship isn't `C2PA_Manifest`.
+///
+/// Some signing tools store a reference to the manifest store, rather than the manifest store
+/// itself, in a custom key of the document catalog or of the trailer. The keys checked are set
+/// by the `core.pdf_manifest_reference_keys` setting, `C2PAManifest` and `C2PA` by default.
+/// When a PDF has no embedded manifest store, reading it fails with
+/// [`Error::RemoteManifestUrl`] carrying such a reference if it is a remote URL.
+pub struct PdfIO {
+    signing_time: Option<DateTime<Utc>>,
+}
+
+/// Where the manifest store of a PDF is, as reported by [`PdfIO::manifest_kind`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PdfManifestKind {
+    /// The manifest store is embedded in the PDF, in the stream data starting at the offset
+    /// and running for the length given, in bytes. The data may be compressed.
+    Embedded(usize, usize),
+    /// The PDF's XMP, or one of its manifest reference keys, refers to a remote manifest store
+    /// at this URL.
+    Remote(String),
+    /// The PDF has no manifest store.
+    None,
+}
 
 impl CAIReader for PdfIO {
     fn read_cai(&self, asset_reader: &mut dyn CAIRead) -> crate::Result<Vec<u8>> {
-        asset_reader.rewind()?;
-        let pdf = Pdf::from_reader(asset_reader).map_err(|e| Error::InvalidAsset(e.to_string()))?;
-        self.read_manifest_bytes(pdf)
+        check_pdf_stream(asset_reader)?;
+        let pdf = self.read_pdf(asset_reader)?;
+        let not_found = self.manifest_not_found(&pdf);
+        match self.read_manifest_bytes(pdf) {
+            Err(JumbfNotFound) => Err(not_found),
+            result => result,
+        }
+    }
+
+    fn read_cai_to_writer(
+        &self,
+        asset_reader: &mut dyn CAIRead,
+        writer: &mut dyn Write,
+    ) -> crate::Result<u64> {
+        check_pdf_stream(asset_reader)?;
+        let options = self.embedded_file_options();
+        options
+            .validate()
+            .map_err(|e| Error::BadParam(e.to_string()))?;
+
+        // the manifest stream is usually found from the cross-reference tables alone, otherwise
+        // the whole PDF is parsed to find it
+        let location =
+            match locate_manifest_stream(&mut *asset_reader, options.relationship.as_bytes()) {
+                Some(location) => location,
+                None => {
+                    asset_reader.rewind()?;
+                    self.manifest_stream_location(&mut *asset_reader)?
+                }
+            };
+
+        // copy the manifest stream from the asset
+        asset_reader.seek(SeekFrom::Start(location.offset))?;
+        let mut stream = Read::take(&mut *asset_reader, location.len);
+        let copied = if location.compressed {
+            std::io::copy(&mut ZlibDecoder::new(stream), writer)?
+        } else {
+            std::io::copy(&mut stream, writer)?
+        };
+        Ok(copied)
     }
 
     fn read_xmp(&self, asset_reader: &mut dyn CAIRead) -> Option<String> {
@@ -40,7 +138,7 @@ impl CAIReader for PdfIO {
             return None;
         }
 
-        let Ok(pdf) = Pdf::from_reader(asset_reader) else {
+        let Ok(pdf) = self.read_pdf(asset_reader) else {
             return None;
         };
 
@@ -58,24 +156,113 @@ impl CAIWriter for PdfIO {
         input_stream.rewind()?;
         let mut pdf_bytes = Vec::new();
         input_stream.read_to_end(&mut pdf_bytes)?;
+        check_pdf_header(&pdf_bytes)?;
+
+        let mut pdf = self.load_pdf(&pdf_bytes)?;
+        warn_unsupported_features(&pdf);
 
-        let mut pdf =
-            Pdf::from_bytes(&pdf_bytes).map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        // Where the existing manifest can be patched in place, if there is one. Its stream data
+        // must appear verbatim in the file at the same size, which isn't the case when it's
+        // compressed or when `store_bytes` has a different size.
+        let existing_manifest = match pdf
+            .read_manifest_bytes()
+            .map_err(|e| Error::InvalidAsset(e.to_string()))?
+        {
+            Some(manifests) => {
+                let (current_manifest, offset) = manifests.first().ok_or(Error::JumbfNotFound)?;
+                let end = offset + current_manifest.len();
+                let verbatim = !pdf.is_manifest_compressed()
+                    && current_manifest.len() == store_bytes.len()
+                    && pdf_bytes.get(*offset..end) == Some(*current_manifest);
+                Some(verbatim.then_some(*offset))
+            }
+            None => None,
+        };
+
+        if let Some(patch_offset) = existing_manifest {
+            let out_buf = match patch_offset {
+                Some(offset) => {
+                    pdf_bytes[offset..offset + store_bytes.len()].copy_from_slice(store_bytes);
+                    pdf_bytes
+                }
+                None => {
+                    debug!("the PDF manifest can't be patched in place, rewriting it");
+                    pdf.replace_manifest_bytes(store_bytes.to_vec())
+                        .map_err(|e| Error::PdfManifestReplaceFailed(e.to_string()))?;
+
+                    let mut out_buf = Vec::new();
+                    pdf.save_incremental_to(&mut out_buf)
+                        .map_err(|e| Error::PdfManifestReplaceFailed(e.to_string()))?;
+                    out_buf
+                }
+            };
+
+            output_stream.rewind()?;
+            output_stream.write_all(&out_buf)?;
+        } else {
+            pdf.write_manifest_as_embedded_file(store_bytes.to_vec())
+                .map_err(|e| Error::InvalidAsset(e.to_string()))?;
+            warn_tagging_changes(&pdf);
+
+            let mut out_buf = Vec::new();
+            pdf.save_incremental_to(&mut out_buf)?;
+
+            output_stream.rewind()?;
+            output_stream.write_all(&out_buf)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `store_bytes` as a `/FlateDecode` compressed embedded file.
+    ///
+    /// The manifest is replaced in place when it is signed, so a new compressed manifest
+    /// reserves some extra room, and the manifest replacing it is padded with zeros after the
+    /// end of its zlib data to fill the same space. An existing uncompressed manifest is
+    /// replaced uncompressed.
+    fn write_cai_compressed(
+        &self,
+        input_stream: &mut dyn CAIRead,
+        output_stream: &mut dyn CAIReadWrite,
+        store_bytes: &[u8],
+    ) -> crate::Result<()> {
+        input_stream.rewind()?;
+        let mut pdf_bytes = Vec::new();
+        input_stream.read_to_end(&mut pdf_bytes)?;
+        check_pdf_header(&pdf_bytes)?;
+
+        let mut pdf = self.load_pdf(&pdf_bytes)?;
+        warn_unsupported_features(&pdf);
+        let mut compressed = deflate(store_bytes)?;
 
         if let Some(manifests) = pdf
             .read_manifest_bytes()
             .map_err(|e| Error::InvalidAsset(e.to_string()))?
         {
+            if !pdf.is_manifest_compressed() {
+                return self.write_cai(input_stream, output_stream, store_bytes);
+            }
+
             let (current_manifest, _) = manifests.first().ok_or(Error::JumbfNotFound)?;
-            patch_bytes(&mut pdf_bytes, current_manifest, store_bytes)?;
+            if compressed.len() > current_manifest.len() {
+                return Err(Error::PdfCompressedManifestTooLarge {
+                    needed: compressed.len(),
+                    reserved: current_manifest.len(),
+                });
+            }
+            compressed.resize(current_manifest.len(), 0);
+
+            patch_bytes(&mut pdf_bytes, current_manifest, &compressed)?;
             output_stream.rewind()?;
             output_stream.write_all(&pdf_bytes)?;
         } else {
-            pdf.write_manifest_as_embedded_file(store_bytes.to_vec())
+            compressed.resize(compressed.len() + COMPRESSED_MANIFEST_SLACK, 0);
+            pdf.write_compressed_manifest_as_embedded_file(compressed)
                 .map_err(|e| Error::InvalidAsset(e.to_string()))?;
+            warn_tagging_changes(&pdf);
 
             let mut out_buf = Vec::new();
-            pdf.save_to(&mut out_buf)?;
+            pdf.save_incremental_to(&mut out_buf)?;
 
             output_stream.rewind()?;
             output_stream.write_all(&out_buf)?;
@@ -88,9 +275,10 @@ impl CAIWriter for PdfIO {
         &self,
         input_stream: &mut dyn CAIRead,
     ) -> crate::Result<Vec<HashObjectPositions>> {
-        input_stream.rewind()?;
-        let mut pdf =
-            Pdf::from_reader(input_stream).map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        check_pdf_stream(input_stream)?;
+        let mut pdf_bytes = Vec::new();
+        input_stream.read_to_end(&mut pdf_bytes)?;
+        let mut pdf = self.load_pdf(&pdf_bytes)?;
 
         if let Some(manifests) = pdf
             .read_manifest_bytes()
@@ -104,38 +292,33 @@ impl CAIWriter for PdfIO {
                 htype: crate::asset_io::HashBlockObjectType::Cai,
             }])
         } else {
-            // Write a single byte as a placeholder manifest.
-            pdf.write_manifest_as_embedded_file(vec![0])
+            // Write a single byte as a placeholder manifest. The incremental update writes it as
+            // a top-level uncompressed object, so its offset addresses the raw bytes even when
+            // the PDF uses object streams, without parsing the output again.
+            pdf.write_manifest_as_embedded_file(vec![0; PLACEHOLDER_MANIFEST_LEN])
                 .map_err(|e| Error::InvalidAsset(e.to_string()))?;
 
-            let mut out = Vec::new();
-            pdf.save_to(&mut out)?;
-
-            let pdf = Pdf::from_bytes(&out).map_err(|e| Error::InvalidAsset(e.to_string()))?;
-
-            let manifests = pdf
-                .read_manifest_bytes()
-                .map_err(|e| Error::InvalidAsset(e.to_string()))?
-                .ok_or(Error::JumbfNotFound)?;
-
-            let (current_manifest, offset) = manifests.first().ok_or(Error::JumbfNotFound)?;
+            let (_, offset) = pdf.incremental_update_with_manifest_offset()?;
 
             Ok(vec![HashObjectPositions {
-                offset: *offset,
-                length: current_manifest.len(),
+                offset: offset.ok_or(Error::JumbfNotFound)?,
+                length: PLACEHOLDER_MANIFEST_LEN,
                 htype: crate::asset_io::HashBlockObjectType::Cai,
             }])
         }
     }
 
+    fn set_signing_time(&mut self, signing_time: DateTime<Utc>) {
+        self.signing_time = Some(signing_time);
+    }
+
     fn remove_cai_store_from_stream(
         &self,
         mut input_stream: &mut dyn CAIRead,
         output_stream: &mut dyn CAIReadWrite,
     ) -> crate::Result<()> {
-        input_stream.rewind()?;
-        let mut pdf =
-            Pdf::from_reader(&mut input_stream).map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        check_pdf_stream(input_stream)?;
+        let mut pdf = self.read_pdf(&mut input_stream)?;
 
         if pdf
             .read_manifest_bytes()
@@ -159,10 +342,295 @@ impl CAIWriter for PdfIO {
     }
 }
 
+// The keys of the catalog and trailer that may hold a manifest reference.
+fn manifest_reference_keys() -> Vec<String> {
+    get_settings_value::<Vec<String>>("core.pdf_manifest_reference_keys").unwrap_or_default()
+}
+
+/// Returns [`Error::FormatMismatch`] if `bytes` don't start with a PDF header.
+fn check_pdf_header(bytes: &[u8]) -> crate::Result<()> {
+    let search_len = bytes.len().min(HEADER_SEARCH_LEN);
+    match memchr::memmem::find(&bytes[..search_len], b"%PDF-") {
+        Some(_) => Ok(()),
+        None => Err(Error::FormatMismatch("expected a PDF header".to_string())),
+    }
+}
+
+/// Logs a warning if the PDF uses PDF 2.0 features that may not be preserved when a manifest
+/// is written to it.
+fn warn_unsupported_features(pdf: &Pdf) {
+    let features = pdf.unsupported_features();
+    if !features.is_empty() {
+        log::warn!(
+            "PDF {} features may not be preserved when writing the manifest: {}",
+            pdf.version(),
+            features.join(", ")
+        );
+    }
+}
+
+/// Logs a warning if writing the manifest changed the structure of a tagged PDF in a way that
+/// would break its tagging.
+fn warn_tagging_changes(pdf: &Pdf) {
+    let changes = pdf.tagging_changes();
+    if !changes.is_empty() {
+        log::warn!(
+            "writing the manifest may break the tagged PDF structure: {}",
+            changes.join(", ")
+        );
+    }
+}
+
+/// Compresses `bytes` as a zlib stream, as used by the PDF `/FlateDecode` filter.
+fn deflate(bytes: &[u8]) -> crate::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(bytes)?;
+    Ok(encoder.finish()?)
+}
+
+/// Decompresses the zlib stream at the start of `bytes`. Reading stops at the end of the zlib
+/// stream, so the padding that may follow a compressed manifest is ignored.
+fn inflate(bytes: &[u8]) -> crate::Result<Vec<u8>> {
+    let mut decoder = ZlibDecoder::new(bytes);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+/// Checks the PDF header of `stream`, leaving it rewound.
+fn check_pdf_stream(stream: &mut dyn CAIRead) -> crate::Result<()> {
+    stream.rewind()?;
+    let mut header = Vec::with_capacity(HEADER_SEARCH_LEN);
+    Read::take(&mut *stream, HEADER_SEARCH_LEN as u64).read_to_end(&mut header)?;
+    stream.rewind()?;
+
+    check_pdf_header(&header)
+}
+
 impl PdfIO {
+    /// Replaces the manifest store embedded in the PDF read from `input_stream` with
+    /// `store_bytes`, writing the result to `output_stream`.
+    ///
+    /// When `store_bytes` fits in the space of the existing manifest store, including any padding
+    /// reserved with [`Store::set_padded_size`], it is padded to the same size and patched in
+    /// place. Otherwise, or if the existing manifest store is compressed, the manifest stream is
+    /// rewritten uncompressed as an incremental update, keeping the original PDF bytes intact.
+    ///
+    /// Returns [`JumbfNotFound`] if the PDF doesn't contain a manifest store.
+    pub fn replace_manifest(
+        &self,
+        input_stream: &mut dyn CAIRead,
+        output_stream: &mut dyn CAIReadWrite,
+        store_bytes: &[u8],
+    ) -> crate::Result<()> {
+        input_stream.rewind()?;
+        let mut pdf_bytes = Vec::new();
+        input_stream.read_to_end(&mut pdf_bytes)?;
+        check_pdf_header(&pdf_bytes)?;
+
+        let mut pdf = self.load_pdf(&pdf_bytes)?;
+
+        let current_manifest = pdf
+            .read_manifest_bytes()
+            .map_err(|e| Error::InvalidAsset(e.to_string()))?
+            .ok_or(JumbfNotFound)?
+            .first()
+            .map(|(manifest, _)| manifest.to_vec())
+            .ok_or(JumbfNotFound)?;
+
+        // a manifest that fits in the space of the current one is padded to the same size
+        let mut store_bytes = store_bytes.to_vec();
+        Store::pad_jumbf(&mut store_bytes, current_manifest.len())?;
+
+        // a compressed manifest store can't be patched with uncompressed bytes
+        let patch_in_place =
+            current_manifest.len() == store_bytes.len() && !pdf.is_manifest_compressed();
+
+        let out_buf = if patch_in_place {
+            patch_bytes(&mut pdf_bytes, &current_manifest, &store_bytes)?;
+            pdf_bytes
+        } else {
+            pdf.replace_manifest_bytes(store_bytes)
+                .map_err(|e| Error::InvalidAsset(e.to_string()))?;
+
+            let mut out_buf = Vec::new();
+            pdf.save_incremental_to(&mut out_buf)?;
+            out_buf
+        };
+
+        output_stream.rewind()?;
+        output_stream.write_all(&out_buf)?;
+
+        Ok(())
+    }
+
+    /// Returns the incremental update embedding `store_bytes` in the PDF read from
+    /// `input_stream`, and where the manifest store starts once the update is appended.
+    ///
+    /// Returns [`Error::BadParam`] if the PDF already contains a manifest store.
+    pub(crate) fn manifest_update(
+        &self,
+        input_stream: &mut dyn CAIRead,
+        store_bytes: &[u8],
+    ) -> crate::Result<(Vec<u8>, usize)> {
+        input_stream.rewind()?;
+        let mut pdf_bytes = Vec::new();
+        input_stream.read_to_end(&mut pdf_bytes)?;
+        check_pdf_header(&pdf_bytes)?;
+
+        let mut pdf = self.load_pdf(&pdf_bytes)?;
+        warn_unsupported_features(&pdf);
+
+        if pdf
+            .read_manifest_bytes()
+            .map_err(|e| Error::InvalidAsset(e.to_string()))?
+            .is_some()
+        {
+            return Err(Error::BadParam(
+                "the PDF already contains a manifest store".to_string(),
+            ));
+        }
+
+        pdf.write_manifest_as_embedded_file(store_bytes.to_vec())
+            .map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        warn_tagging_changes(&pdf);
+
+        let (update, offset) = pdf.incremental_update_with_manifest_offset()?;
+        Ok((update, offset.ok_or(Error::JumbfNotFound)?))
+    }
+
+    /// Reports whether the PDF read from `input_stream` embeds a manifest store, refers to a
+    /// remote one in the `dcterms:provenance` of its XMP, or has neither, parsing the PDF once.
+    ///
+    /// An embedded manifest store takes precedence over a remote one.
+    pub fn manifest_kind(&self, input_stream: &mut dyn CAIRead) -> crate::Result<PdfManifestKind> {
+        check_pdf_stream(input_stream)?;
+        let pdf = self.read_pdf(input_stream)?;
+        self.manifest_kind_from_pdf(pdf)
+    }
+
+    /// Lists every file embedded in the PDF read from `input_stream`, including the C2PA
+    /// manifest store.
+    ///
+    /// Files are collected from the `/EmbeddedFiles` name tree, the catalog's associated files
+    /// (`/AF`) and `FileAttachment` annotations, so a file referenced from more than one of
+    /// these is only listed once.
+    pub fn list_embedded_files(
+        &self,
+        input_stream: &mut dyn CAIRead,
+    ) -> crate::Result<Vec<EmbeddedFile>> {
+        check_pdf_stream(input_stream)?;
+        self.read_pdf(input_stream)?
+            .list_embedded_files()
+            .map_err(|e| Error::InvalidAsset(e.to_string()))
+    }
+
+    /// Returns the decoded content of the file named `name` embedded in the PDF read from
+    /// `input_stream`, as listed by [`PdfIO::list_embedded_files`]. Returns `Ok(None)` if there
+    /// is no such file.
+    pub fn extract_embedded_file(
+        &self,
+        input_stream: &mut dyn CAIRead,
+        name: &str,
+    ) -> crate::Result<Option<Vec<u8>>> {
+        check_pdf_stream(input_stream)?;
+        self.read_pdf(input_stream)?
+            .extract_embedded_file(name)
+            .map_err(|e| Error::InvalidAsset(e.to_string()))
+    }
+
+    /// Returns the PDF version of the PDF read from `input_stream`, such as `"1.7"` or `"2.0"`.
+    ///
+    /// This is the version in the file header, unless the catalog's `/Version` entry names a
+    /// later one, as an incremental update may do to upgrade the document.
+    pub fn version(&self, input_stream: &mut dyn CAIRead) -> crate::Result<String> {
+        check_pdf_stream(input_stream)?;
+        Ok(self.read_pdf(input_stream)?.version())
+    }
+
+    /// Returns the PDF 2.0 features used by the PDF read from `input_stream` that may not be
+    /// preserved when a manifest is written to it, such as `"/DSS"`. Documents before
+    /// PDF 2.0 don't report any.
+    pub fn unsupported_features(
+        &self,
+        input_stream: &mut dyn CAIRead,
+    ) -> crate::Result<Vec<String>> {
+        check_pdf_stream(input_stream)?;
+        Ok(self.read_pdf(input_stream)?.unsupported_features())
+    }
+
+    fn manifest_kind_from_pdf(&self, pdf: impl C2paPdf) -> crate::Result<PdfManifestKind> {
+        let manifests = pdf
+            .read_manifest_bytes()
+            .map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        if let Some((bytes, offset)) = manifests.as_deref().and_then(<[_]>::first) {
+            return Ok(PdfManifestKind::Embedded(*offset, bytes.len()));
+        }
+
+        Ok(pdf
+            .read_xmp()
+            .as_deref()
+            .and_then(extract_provenance)
+            .filter(|provenance| Store::is_valid_remote_url(provenance))
+            .or_else(|| {
+                pdf.read_manifest_reference(&manifest_reference_keys())
+                    .filter(|reference| Store::is_valid_remote_url(reference))
+            })
+            .map_or(PdfManifestKind::None, PdfManifestKind::Remote))
+    }
+
+    /// Returns the error for a PDF without an embedded manifest store: the remote manifest URL
+    /// found in one of its reference keys, if any.
+    fn manifest_not_found(&self, pdf: &impl C2paPdf) -> Error {
+        pdf.read_manifest_reference(&manifest_reference_keys())
+            .filter(|reference| Store::is_valid_remote_url(reference))
+            .map_or(JumbfNotFound, Error::RemoteManifestUrl)
+    }
+
+    /// Parses the PDF read from `reader` to find where its manifest stream is.
+    fn manifest_stream_location(
+        &self,
+        reader: &mut dyn CAIRead,
+    ) -> crate::Result<ManifestStreamLocation> {
+        let pdf = self.read_pdf(reader)?;
+        let (offset, len) = match self.manifest_stream(&pdf) {
+            Ok((bytes, offset)) => (offset as u64, bytes.len() as u64),
+            Err(JumbfNotFound) => return Err(self.manifest_not_found(&pdf)),
+            Err(e) => return Err(e),
+        };
+
+        Ok(ManifestStreamLocation {
+            offset,
+            len,
+            compressed: pdf.is_manifest_compressed(),
+        })
+    }
+
     fn read_manifest_bytes(&self, pdf: impl C2paPdf) -> crate::Result<Vec<u8>> {
-        let Ok(result) = pdf.read_manifest_bytes() else {
-            return Err(PdfReadError);
+        let (bytes, _) = self.manifest_stream(&pdf)?;
+        if pdf.is_manifest_compressed() {
+            inflate(bytes)
+        } else {
+            Ok(bytes.to_vec())
+        }
+    }
+
+    /// Returns the data of the single manifest stream of `pdf` and its offset in the PDF.
+    fn manifest_stream<'a>(&self, pdf: &'a impl C2paPdf) -> crate::Result<(&'a [u8], usize)> {
+        let result = match pdf.read_manifest_bytes() {
+            Ok(result) => result,
+            Err(crate::asset_handlers::pdf::Error::UnableToReadObject {
+                object_id,
+                offset,
+                ..
+            }) => {
+                return Err(Error::PdfObjectReadError {
+                    object_number: object_id.0,
+                    generation: object_id.1,
+                    offset,
+                })
+            }
+            Err(_) => return Err(PdfReadError),
         };
 
         let Some(bytes) = result else {
@@ -170,24 +638,65 @@ impl PdfIO {
         };
 
         match bytes.as_slice() {
-            [(bytes, _)] => Ok(bytes.to_vec()),
+            [stream] => Ok(*stream),
             _ => Err(NotImplemented(
                 "c2pa-rs only supports reading PDFs with one manifest".into(),
             )),
         }
     }
 
+    /// Loads the PDF in `bytes`, set up to find and write the manifest's embedded file as the
+    /// `core.pdf_embedded_file_*` settings configure it, dated with the signing time.
+    fn load_pdf(&self, bytes: &[u8]) -> crate::Result<Pdf> {
+        let mut pdf = Pdf::from_bytes(bytes).map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        self.set_embedded_file_options(&mut pdf)?;
+        Ok(pdf)
+    }
+
+    /// Reads the PDF from `reader` and sets it up like [`PdfIO::load_pdf`].
+    fn read_pdf(&self, reader: &mut dyn CAIRead) -> crate::Result<Pdf> {
+        let mut pdf = Pdf::from_reader(reader).map_err(|e| Error::InvalidAsset(e.to_string()))?;
+        self.set_embedded_file_options(&mut pdf)?;
+        Ok(pdf)
+    }
+
+    fn set_embedded_file_options(&self, pdf: &mut Pdf) -> crate::Result<()> {
+        pdf.set_embedded_file_options(self.embedded_file_options())
+            .map_err(|e| Error::BadParam(e.to_string()))
+    }
+
+    // The options for the manifest's embedded file, from the settings.
+    fn embedded_file_options(&self) -> EmbeddedFileOptions {
+        let setting = |key: &str| get_settings_value::<Option<String>>(key).ok().flatten();
+        let defaults = EmbeddedFileOptions::default();
+
+        EmbeddedFileOptions {
+            name: setting("core.pdf_embedded_file_name").unwrap_or(defaults.name),
+            relationship: setting("core.pdf_embedded_file_relationship")
+                .unwrap_or(defaults.relationship),
+            description: setting("core.pdf_embedded_file_description")
+                .unwrap_or(defaults.description),
+            date: self.signing_time,
+        }
+    }
+
     fn read_xmp_from_pdf(&self, pdf: impl C2paPdf) -> Option<String> {
         pdf.read_xmp()
     }
 }
 
+impl Default for PdfIO {
+    fn default() -> Self {
+        Self::new("pdf")
+    }
+}
+
 impl AssetIO for PdfIO {
     fn new(_asset_type: &str) -> Self
     where
         Self: Sized,
     {
-        Self {}
+        Self { signing_time: None }
     }
 
     fn get_handler(&self, asset_type: &str) -> Box<dyn AssetIO> {
@@ -198,8 +707,8 @@ impl AssetIO for PdfIO {
         self
     }
 
-    fn get_writer(&self, _asset_type: &str) -> Option<Box<dyn CAIWriter>> {
-        Some(Box::new(PdfIO {}))
+    fn get_writer(&self, asset_type: &str) -> Option<Box<dyn CAIWriter>> {
+        Some(Box::new(PdfIO::new(asset_type)))
     }
 
     fn read_cai_store(&self, asset_path: &Path) -> crate::Result<Vec<u8>> {
@@ -244,8 +753,12 @@ pub mod tests {
 
     use crate::{
         asset_handlers,
-        asset_handlers::{pdf::MockC2paPdf, pdf_io::PdfIO},
-        asset_io::{AssetIO, CAIReader},
+        asset_handlers::{
+            pdf::MockC2paPdf,
+            pdf_io::{PdfIO, PdfManifestKind},
+            pdf_scan::locate_manifest_stream,
+        },
+        asset_io::{AssetIO, CAIReader, CAIWriter},
     };
 
     static MANIFEST_BYTES: &[u8; 2] = &[10u8, 20u8];
@@ -266,6 +779,21 @@ pub mod tests {
         ))
     }
 
+    #[test]
+    fn test_unreadable_manifest_object_reports_object_and_offset() {
+        let source = include_bytes!("../../tests/fixtures/basic-bad-manifest-object.pdf");
+
+        let pdf_io = PdfIO::new("pdf");
+        assert!(matches!(
+            pdf_io.read_cai(&mut Cursor::new(source)),
+            Err(crate::Error::PdfObjectReadError {
+                object_number: 18,
+                generation: 0,
+                offset: Some(9721),
+            })
+        ))
+    }
+
     #[test]
     fn test_no_manifest_found_returns_no_jumbf_error() {
         let mut mock_pdf = MockC2paPdf::default();
@@ -284,6 +812,7 @@ pub mod tests {
         mock_pdf
             .expect_read_manifest_bytes()
             .returning(|| Ok(Some(vec![MANIFEST_BYTES])));
+        mock_pdf.expect_is_manifest_compressed().returning(|| false);
 
         let pdf_io = PdfIO::new("pdf");
         assert_eq!(
@@ -352,4 +881,814 @@ pub mod tests {
         let mut pdf_stream = Cursor::new(source.to_vec());
         assert!(pdf_io.read_cai(&mut pdf_stream).is_ok());
     }
+
+    #[test]
+    fn test_read_cai_to_writer_matches_read_cai() {
+        let pdf_io = PdfIO::new("pdf");
+
+        // found from the cross-reference tables
+        let source = include_bytes!("../../tests/fixtures/express-signed.pdf");
+        assert!(locate_manifest_stream(&mut Cursor::new(source), b"C2PA_Manifest").is_some());
+        let mut streamed = Vec::new();
+        let len = pdf_io
+            .read_cai_to_writer(&mut Cursor::new(source), &mut streamed)
+            .unwrap();
+        assert_eq!(len, streamed.len() as u64);
+        assert_eq!(streamed, pdf_io.read_cai(&mut Cursor::new(source)).unwrap());
+
+        // a compressed manifest is inflated while it is copied, and found by a full parse in a
+        // PDF with cross-reference streams
+        let mut compressed = Cursor::new(Vec::new());
+        pdf_io
+            .write_cai_compressed(
+                &mut Cursor::new(include_bytes!("../../tests/fixtures/basic.pdf").to_vec()),
+                &mut compressed,
+                &[1u8; 512],
+            )
+            .unwrap();
+        let mut streamed = Vec::new();
+        pdf_io
+            .read_cai_to_writer(&mut compressed, &mut streamed)
+            .unwrap();
+        assert_eq!(streamed, [1u8; 512]);
+    }
+
+    #[test]
+    fn test_manifest_kind_embedded() {
+        let source = include_bytes!("../../tests/fixtures/express-signed.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        let PdfManifestKind::Embedded(offset, len) = pdf_io
+            .manifest_kind(&mut Cursor::new(source.to_vec()))
+            .unwrap()
+        else {
+            panic!("expected an embedded manifest");
+        };
+        let locations = pdf_io
+            .get_object_locations_from_stream(&mut Cursor::new(source.to_vec()))
+            .unwrap();
+        assert_eq!((locations[0].offset, locations[0].length), (offset, len));
+    }
+
+    #[test]
+    fn test_manifest_kind_remote() {
+        let mut mock_pdf = MockC2paPdf::default();
+        mock_pdf.expect_read_manifest_bytes().returning(|| Ok(None));
+        mock_pdf.expect_read_xmp().returning(|| {
+            Some(
+                r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+                    <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                        <rdf:Description xmlns:dcterms="http://purl.org/dc/terms/"
+                            dcterms:provenance="https://example.com/manifest.c2pa"/>
+                    </rdf:RDF>
+                </x:xmpmeta>"#
+                    .to_string(),
+            )
+        });
+
+        let pdf_io = PdfIO::new("pdf");
+        assert_eq!(
+            pdf_io.manifest_kind_from_pdf(mock_pdf).unwrap(),
+            PdfManifestKind::Remote("https://example.com/manifest.c2pa".to_string())
+        );
+    }
+
+    #[test]
+    fn test_manifest_kind_none() {
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        assert_eq!(
+            pdf_io
+                .manifest_kind(&mut Cursor::new(source.to_vec()))
+                .unwrap(),
+            PdfManifestKind::None
+        );
+    }
+
+    #[test]
+    fn test_read_cai_finds_manifest_reference_in_xref_stream() {
+        let source = include_bytes!("../../tests/fixtures/basic-manifest-reference.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        assert!(matches!(
+            pdf_io.read_cai(&mut Cursor::new(source.to_vec())),
+            Err(crate::Error::RemoteManifestUrl(url)) if url == "https://example.com/manifest.c2pa"
+        ));
+        assert!(matches!(
+            pdf_io.read_cai_to_writer(&mut Cursor::new(source.to_vec()), &mut std::io::sink()),
+            Err(crate::Error::RemoteManifestUrl(_))
+        ));
+        assert_eq!(
+            pdf_io
+                .manifest_kind(&mut Cursor::new(source.to_vec()))
+                .unwrap(),
+            PdfManifestKind::Remote("https://example.com/manifest.c2pa".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_cai_finds_manifest_reference_in_configured_catalog_key() {
+        use crate::settings::{reset_default_settings, set_settings_value, tests::PROTECT};
+
+        let with_reference = |reference: &str| {
+            let mut document =
+                lopdf::Document::load_mem(include_bytes!("../../tests/fixtures/basic.pdf"))
+                    .unwrap();
+            let catalog_id = document
+                .trailer
+                .get(b"Root")
+                .and_then(lopdf::Object::as_reference)
+                .unwrap();
+            document
+                .get_object_mut(catalog_id)
+                .and_then(lopdf::Object::as_dict_mut)
+                .unwrap()
+                .set("ExampleManifest", lopdf::Object::string_literal(reference));
+            let mut source = Vec::new();
+            document.save_to(&mut source).unwrap();
+            source
+        };
+        let remote = with_reference("https://example.com/manifest.c2pa");
+        let urn = with_reference("urn:example:manifest");
+
+        let _protect = PROTECT.lock().unwrap();
+        let pdf_io = PdfIO::new("pdf");
+        assert!(matches!(
+            pdf_io.read_cai(&mut Cursor::new(remote.clone())),
+            Err(crate::Error::JumbfNotFound)
+        ));
+
+        set_settings_value(
+            "core.pdf_manifest_reference_keys",
+            vec!["ExampleManifest".to_string()],
+        )
+        .unwrap();
+        let result = pdf_io.read_cai(&mut Cursor::new(remote.clone()));
+        let kind = pdf_io.manifest_kind(&mut Cursor::new(remote)).unwrap();
+        // only remote URLs are reported as remote manifests
+        let urn_result = pdf_io.read_cai(&mut Cursor::new(urn.clone()));
+        let urn_kind = pdf_io.manifest_kind(&mut Cursor::new(urn)).unwrap();
+        reset_default_settings().unwrap();
+
+        assert!(matches!(
+            result,
+            Err(crate::Error::RemoteManifestUrl(url)) if url == "https://example.com/manifest.c2pa"
+        ));
+        assert_eq!(
+            kind,
+            PdfManifestKind::Remote("https://example.com/manifest.c2pa".to_string())
+        );
+        assert!(matches!(urn_result, Err(crate::Error::JumbfNotFound)));
+        assert_eq!(urn_kind, PdfManifestKind::None);
+    }
+
+    #[test]
+    fn test_placeholder_offset_matches_saved_manifest() {
+        use crate::asset_handlers::pdf::{C2paPdf, Pdf};
+
+        let pdf_io = PdfIO::new("pdf");
+        for source in [
+            &include_bytes!("../../tests/fixtures/basic.pdf")[..],
+            include_bytes!("../../tests/fixtures/express.pdf"),
+            // a /Names dictionary is updated along with the catalog
+            include_bytes!("../../tests/fixtures/basic-attachments.pdf"),
+        ] {
+            let positions = pdf_io
+                .get_object_locations_from_stream(&mut Cursor::new(source.to_vec()))
+                .unwrap();
+
+            let mut pdf = Pdf::from_bytes(source).unwrap();
+            pdf.write_manifest_as_embedded_file(vec![0]).unwrap();
+            let mut out = Vec::new();
+            pdf.save_incremental_to(&mut out).unwrap();
+
+            let saved = Pdf::from_bytes(&out).unwrap();
+            assert_eq!(
+                saved.read_manifest_bytes().unwrap().unwrap()[0].1,
+                positions[0].offset
+            );
+        }
+    }
+
+    #[test]
+    fn test_placeholder_offset_in_object_stream_pdf() {
+        use crate::asset_handlers::pdf::{C2paPdf, Pdf};
+
+        // The catalog and its /Names dictionary are stored in a compressed object stream.
+        let source = include_bytes!("../../tests/fixtures/basic-object-streams.pdf");
+        let positions = PdfIO::new("pdf")
+            .get_object_locations_from_stream(&mut Cursor::new(source.to_vec()))
+            .unwrap();
+        assert_eq!(positions[0].length, 1);
+
+        // The offset addresses the raw bytes of a manifest written the same way.
+        let mut pdf = Pdf::from_bytes(source).unwrap();
+        pdf.write_manifest_as_embedded_file(vec![0x5a]).unwrap();
+        let mut out = Vec::new();
+        pdf.save_incremental_to(&mut out).unwrap();
+        assert_eq!(out[positions[0].offset], 0x5a);
+
+        let saved = Pdf::from_bytes(&out).unwrap();
+        assert_eq!(
+            saved.read_manifest_bytes().unwrap().unwrap()[0],
+            (&[0x5a][..], positions[0].offset)
+        );
+    }
+
+    #[test]
+    fn test_read_cai_finds_manifest_in_annotation_associated_files() {
+        use crate::asset_handlers::jpeg_io::JpegIO;
+
+        // The manifest of C.jpg, attached only to the /AF of an annotation on the first page.
+        let source = include_bytes!("../../tests/fixtures/basic-annotation-af.pdf");
+        let manifest = PdfIO::new("pdf")
+            .read_cai(&mut Cursor::new(source.to_vec()))
+            .unwrap();
+
+        let jpeg = include_bytes!("../../tests/fixtures/C.jpg");
+        let expected = JpegIO {}.read_cai(&mut Cursor::new(jpeg.to_vec())).unwrap();
+        assert_eq!(manifest, expected);
+    }
+
+    #[test]
+    fn test_png_bytes_are_format_mismatch() {
+        let source = include_bytes!("../../tests/fixtures/sample1.png");
+        let pdf_io = PdfIO::new("pdf");
+
+        assert!(matches!(
+            pdf_io.read_cai(&mut Cursor::new(source.to_vec())),
+            Err(crate::Error::FormatMismatch(_))
+        ));
+        assert!(matches!(
+            pdf_io.write_cai(
+                &mut Cursor::new(source.to_vec()),
+                &mut Cursor::new(Vec::new()),
+                &[1, 2, 3]
+            ),
+            Err(crate::Error::FormatMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_cai_with_configured_embedded_file_options() {
+        use lopdf::{dictionary, Object, Stream};
+
+        use crate::settings::{reset_default_settings, set_settings_value, tests::PROTECT};
+
+        // basic.pdf with another file attached as a supplement
+        let mut document =
+            lopdf::Document::load_mem(include_bytes!("../../tests/fixtures/basic.pdf")).unwrap();
+        let stream_id = document.add_object(Stream::new(dictionary! {}, b"notes".to_vec()));
+        let file_spec_id = document.add_object(dictionary! {
+            "Type" => "Filespec",
+            "F" => Object::string_literal("notes.txt"),
+            "UF" => Object::string_literal("notes.txt"),
+            "AFRelationship" => "Supplement",
+            "EF" => dictionary! { "F" => stream_id },
+        });
+        let catalog_id = document
+            .trailer
+            .get(b"Root")
+            .and_then(Object::as_reference)
+            .unwrap();
+        document
+            .get_object_mut(catalog_id)
+            .and_then(Object::as_dict_mut)
+            .unwrap()
+            .set("AF", vec![Object::Reference(file_spec_id)]);
+        let mut source = Vec::new();
+        document.save_to(&mut source).unwrap();
+
+        let _protect = PROTECT.lock().unwrap();
+        set_settings_value("core.pdf_embedded_file_name", "manifest.c2pa").unwrap();
+        set_settings_value("core.pdf_embedded_file_relationship", "Supplement").unwrap();
+
+        let pdf_io = PdfIO::new("pdf");
+        let manifest = vec![1u8, 2, 3, 4];
+        let mut output = Cursor::new(Vec::new());
+        let written = pdf_io.write_cai(&mut Cursor::new(source), &mut output, &manifest);
+        let read = pdf_io.read_cai(&mut Cursor::new(output.get_ref().clone()));
+
+        // an invalid relationship is rejected
+        set_settings_value("core.pdf_embedded_file_relationship", "Manifest").unwrap();
+        let invalid = pdf_io.read_cai(&mut Cursor::new(output.into_inner()));
+        reset_default_settings().unwrap();
+
+        written.unwrap();
+        assert_eq!(read.unwrap(), manifest);
+        assert!(matches!(invalid, Err(crate::Error::BadParam(_))));
+    }
+
+    #[test]
+    fn test_replace_manifest_same_size() {
+        let source = include_bytes!("../../tests/fixtures/express-signed.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        let manifest = pdf_io.read_cai(&mut Cursor::new(source.to_vec())).unwrap();
+        let replacement = vec![7u8; manifest.len()];
+
+        let mut output = Cursor::new(Vec::new());
+        pdf_io
+            .replace_manifest(&mut Cursor::new(source.to_vec()), &mut output, &replacement)
+            .unwrap();
+
+        let output = output.into_inner();
+        assert_eq!(output.len(), source.len());
+        assert_eq!(
+            pdf_io.read_cai(&mut Cursor::new(output)).unwrap(),
+            replacement
+        );
+    }
+
+    #[test]
+    fn test_replace_manifest_grown() {
+        let source = include_bytes!("../../tests/fixtures/express-signed.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        let mut replacement = pdf_io.read_cai(&mut Cursor::new(source.to_vec())).unwrap();
+        replacement.extend_from_slice(&[7u8; 1024]);
+
+        let mut output = Cursor::new(Vec::new());
+        pdf_io
+            .replace_manifest(&mut Cursor::new(source.to_vec()), &mut output, &replacement)
+            .unwrap();
+
+        let output = output.into_inner();
+        assert!(output.starts_with(source));
+        assert_eq!(
+            pdf_io.read_cai(&mut Cursor::new(output)).unwrap(),
+            replacement
+        );
+    }
+
+    #[test]
+    fn test_write_cai_over_compressed_manifest_rewrites_it() {
+        use crate::asset_handlers::pdf::{C2paPdf, Pdf};
+
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        let mut compressed = Cursor::new(Vec::new());
+        pdf_io
+            .write_cai_compressed(
+                &mut Cursor::new(source.to_vec()),
+                &mut compressed,
+                &[1u8; 512],
+            )
+            .unwrap();
+        let compressed = compressed.into_inner();
+
+        // the compressed manifest stream has the same size as the new manifest, but its bytes
+        // can't be patched with uncompressed data
+        let pdf = Pdf::from_bytes(&compressed).unwrap();
+        assert!(pdf.is_manifest_compressed());
+        let stored_len = pdf.read_manifest_bytes().unwrap().unwrap()[0].0.len();
+        let replacement = vec![2u8; stored_len];
+
+        let mut output = Cursor::new(Vec::new());
+        pdf_io
+            .write_cai(
+                &mut Cursor::new(compressed.clone()),
+                &mut output,
+                &replacement,
+            )
+            .unwrap();
+
+        let output = output.into_inner();
+        assert!(output.starts_with(&compressed));
+        assert!(!Pdf::from_bytes(&output).unwrap().is_manifest_compressed());
+        assert_eq!(
+            pdf_io.read_cai(&mut Cursor::new(output)).unwrap(),
+            replacement
+        );
+    }
+
+    #[test]
+    fn test_write_cai_compressed_too_large() {
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        let mut compressed = Cursor::new(Vec::new());
+        pdf_io
+            .write_cai_compressed(
+                &mut Cursor::new(source.to_vec()),
+                &mut compressed,
+                &[1u8; 512],
+            )
+            .unwrap();
+
+        // hash output doesn't compress, so it needs more room than the first manifest reserved
+        let mut replacement = Vec::new();
+        while replacement.len() < 4096 {
+            replacement.extend_from_slice(&crate::utils::hash_utils::hash_sha256(&replacement));
+        }
+
+        let result = pdf_io.write_cai_compressed(
+            &mut Cursor::new(compressed.into_inner()),
+            &mut Cursor::new(Vec::new()),
+            &replacement,
+        );
+        assert!(matches!(
+            result,
+            Err(crate::Error::PdfCompressedManifestTooLarge { needed, reserved }) if needed > reserved
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_replace_manifest_within_padding() {
+        use crate::{
+            assertions::User,
+            store::Store,
+            utils::test::{create_test_claim, temp_signer},
+        };
+
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        let signer = temp_signer();
+        let pdf_io = PdfIO::new("pdf");
+
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+        let unpadded = store
+            .save_to_stream(
+                "pdf",
+                &mut Cursor::new(source.to_vec()),
+                &mut Cursor::new(Vec::new()),
+                signer.as_ref(),
+            )
+            .unwrap();
+        let padded_size = unpadded.len() + 1024;
+
+        // sign reserving room for a larger manifest
+        let mut store = Store::new();
+        store.set_padded_size(padded_size);
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+        let mut signed = Cursor::new(Vec::new());
+        store
+            .save_to_stream(
+                "pdf",
+                &mut Cursor::new(source.to_vec()),
+                &mut signed,
+                signer.as_ref(),
+            )
+            .unwrap();
+        let signed = signed.into_inner();
+        assert_eq!(
+            pdf_io
+                .read_cai(&mut Cursor::new(signed.clone()))
+                .unwrap()
+                .len(),
+            padded_size
+        );
+
+        // a larger manifest that still fits in the padding
+        let mut claim = create_test_claim().unwrap();
+        claim
+            .add_assertion(&User::new(
+                "org.test.padding",
+                &format!("\"{}\"", "x".repeat(256)),
+            ))
+            .unwrap();
+        let mut store = Store::new();
+        store.commit_claim(claim).unwrap();
+        let replacement = store
+            .save_to_stream(
+                "pdf",
+                &mut Cursor::new(source.to_vec()),
+                &mut Cursor::new(Vec::new()),
+                signer.as_ref(),
+            )
+            .unwrap();
+        assert!(replacement.len() > unpadded.len());
+        assert!(replacement.len() < padded_size);
+
+        let mut output = Cursor::new(Vec::new());
+        pdf_io
+            .replace_manifest(&mut Cursor::new(signed.clone()), &mut output, &replacement)
+            .unwrap();
+
+        // patched in place, no incremental update was appended
+        let output = output.into_inner();
+        assert_eq!(output.len(), signed.len());
+
+        let manifest = pdf_io.read_cai(&mut Cursor::new(output)).unwrap();
+        assert_eq!(manifest.len(), padded_size);
+        assert!(manifest.starts_with(&replacement));
+
+        let mut log = crate::status_tracker::OneShotStatusTracker::new();
+        let loaded = Store::from_jumbf(&manifest, &mut log).unwrap();
+        assert_eq!(loaded.provenance_label(), store.provenance_label());
+    }
+
+    #[test]
+    fn test_replace_manifest_without_manifest_fails() {
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        let pdf_io = PdfIO::new("pdf");
+
+        assert!(matches!(
+            pdf_io.replace_manifest(
+                &mut Cursor::new(source.to_vec()),
+                &mut Cursor::new(Vec::new()),
+                &[1, 2, 3]
+            ),
+            Err(crate::Error::JumbfNotFound)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_sign_preserves_document_id() {
+        use crate::{
+            store::Store,
+            utils::test::{create_test_claim, temp_signer},
+        };
+
+        fn document_id(bytes: &[u8]) -> Vec<Vec<u8>> {
+            lopdf::Document::load_mem(bytes)
+                .unwrap()
+                .trailer
+                .get(b"ID")
+                .and_then(lopdf::Object::as_array)
+                .unwrap()
+                .iter()
+                .map(|id| id.as_str().unwrap().to_vec())
+                .collect()
+        }
+
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+
+        let signer = temp_signer();
+        let mut output = Cursor::new(Vec::new());
+        store
+            .save_to_stream(
+                "pdf",
+                &mut Cursor::new(source.to_vec()),
+                &mut output,
+                signer.as_ref(),
+            )
+            .unwrap();
+
+        let original_id = document_id(source);
+        let signed_id = document_id(output.get_ref());
+
+        // The permanent identifier is kept, the revision identifier is updated.
+        assert_eq!(signed_id.len(), 2);
+        assert_eq!(signed_id[0], original_id[0]);
+        assert_ne!(signed_id[1], original_id[1]);
+
+        // Updating the manifest in place doesn't change the document's identifiers.
+        let pdf_io = PdfIO::new("pdf");
+        let manifest = pdf_io.read_cai(&mut output).unwrap();
+        let mut patched = Cursor::new(Vec::new());
+        pdf_io
+            .write_cai(&mut output, &mut patched, &vec![0u8; manifest.len()])
+            .unwrap();
+        assert_eq!(document_id(patched.get_ref()), signed_id);
+    }
+
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_pdf_signature_covers_c2pa_manifest() {
+        use sha2::{Digest, Sha256};
+
+        use crate::{
+            status_tracker::{report_split_errors, DetailedStatusTracker, StatusTracker},
+            store::Store,
+            utils::test::{create_test_claim, temp_signer},
+        };
+
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+
+        // Sign with C2PA first.
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+
+        let signer = temp_signer();
+        let mut output = Cursor::new(Vec::new());
+        store
+            .save_to_stream(
+                "pdf",
+                &mut Cursor::new(source.to_vec()),
+                &mut output,
+                signer.as_ref(),
+            )
+            .unwrap();
+
+        let c2pa_signed = output.into_inner();
+        assert!(c2pa_signed.starts_with(source));
+
+        let manifest = PdfIO::new("pdf")
+            .get_object_locations_from_stream(&mut Cursor::new(c2pa_signed.clone()))
+            .unwrap()
+            .remove(0);
+
+        // Then apply a mock PDF signature as a further incremental update. The "signature" is
+        // the SHA-256 digest of the bytes covered by its /ByteRange.
+        let document = lopdf::Document::load_mem(&c2pa_signed).unwrap();
+        let (root_id, root_gen) = document
+            .trailer
+            .get(b"Root")
+            .and_then(lopdf::Object::as_reference)
+            .unwrap();
+        let sig_id = document.max_id + 1;
+        let startxref = memchr::memmem::rfind(&c2pa_signed, b"startxref").unwrap();
+        let prev_xref: usize = String::from_utf8_lossy(&c2pa_signed[startxref + 9..])
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let placeholder = "0".repeat(64);
+        let sig_object = |byte_range: [usize; 4], contents: &str| {
+            format!(
+                "{sig_id} 0 obj\n<</Type /Sig /Filter /Adobe.PPKLite /SubFilter /adbe.pkcs7.detached \
+                 /ByteRange [{:010} {:010} {:010} {:010}] /Contents <{contents}>>>\nendobj\n",
+                byte_range[0], byte_range[1], byte_range[2], byte_range[3]
+            )
+        };
+
+        let sig_offset = c2pa_signed.len();
+        let unsigned_object = sig_object([0; 4], &placeholder);
+        let contents_start =
+            sig_offset + unsigned_object.find("/Contents <").unwrap() + "/Contents ".len();
+        let contents_end = contents_start + placeholder.len() + 2;
+        let xref_offset = sig_offset + unsigned_object.len();
+        let tail = format!(
+            "xref\n{sig_id} 1\n{sig_offset:010} 00000 n\r\ntrailer\n\
+             <</Size {} /Prev {prev_xref} /Root {root_id} {root_gen} R>>\n\
+             startxref\n{xref_offset}\n%%EOF\n",
+            sig_id + 1
+        );
+        let total_len = xref_offset + tail.len();
+        let byte_range = [0, contents_start, contents_end, total_len - contents_end];
+
+        let mut pdf_signed = c2pa_signed.clone();
+        pdf_signed.extend_from_slice(sig_object(byte_range, &placeholder).as_bytes());
+        pdf_signed.extend_from_slice(tail.as_bytes());
+
+        let mut hasher = Sha256::new();
+        hasher.update(&pdf_signed[..contents_start]);
+        hasher.update(&pdf_signed[contents_end..]);
+        let digest = hex::encode(hasher.finalize());
+        pdf_signed[contents_start + 1..contents_end - 1].copy_from_slice(digest.as_bytes());
+
+        // The PDF signature covers the manifest and its digest matches the signed bytes.
+        let document = lopdf::Document::load_mem(&pdf_signed).unwrap();
+        let sig = document
+            .get_object((sig_id, 0))
+            .and_then(lopdf::Object::as_dict)
+            .unwrap();
+        let byte_range: Vec<usize> = sig
+            .get(b"ByteRange")
+            .and_then(lopdf::Object::as_array)
+            .unwrap()
+            .iter()
+            .map(|o| o.as_i64().unwrap() as usize)
+            .collect();
+        let contents = sig
+            .get(b"Contents")
+            .and_then(lopdf::Object::as_str)
+            .unwrap();
+
+        assert!(byte_range[1] >= manifest.offset + manifest.length);
+
+        let mut hasher = Sha256::new();
+        hasher.update(&pdf_signed[byte_range[0]..byte_range[0] + byte_range[1]]);
+        hasher.update(&pdf_signed[byte_range[2]..byte_range[2] + byte_range[3]]);
+        assert_eq!(hasher.finalize().as_slice(), contents);
+
+        // The C2PA manifest is still readable, and the whole PDF-signed file validates against
+        // the revision the manifest was embedded in.
+        assert!(PdfIO::new("pdf")
+            .read_cai(&mut Cursor::new(pdf_signed.clone()))
+            .is_ok());
+
+        let mut report = DetailedStatusTracker::new();
+        Store::load_from_memory("pdf", &pdf_signed, true, &mut report).unwrap();
+        assert!(report_split_errors(report.get_log_mut()).is_empty());
+
+        let has_status = |status: &str| {
+            report
+                .get_log()
+                .iter()
+                .any(|item| item.validation_status.as_deref() == Some(status))
+        };
+        assert!(has_status(
+            crate::validation_status::ASSERTION_DATAHASH_MATCH
+        ));
+        assert!(has_status(
+            crate::validation_status::STATUS_PDF_UNBOUND_REVISIONS
+        ));
+
+        // Changing the manifest revision, here a byte of the binary header comment, still
+        // breaks the data hash.
+        let mut tampered = pdf_signed.clone();
+        tampered[11] ^= 0x01;
+        let mut report = DetailedStatusTracker::new();
+        let _ = Store::load_from_memory("pdf", &tampered, true, &mut report);
+        assert!(report
+            .get_log()
+            .iter()
+            .any(|item| item.validation_status.as_deref()
+                == Some(crate::validation_status::ASSERTION_DATAHASH_MISMATCH)));
+    }
+
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_pdf_content_update_after_c2pa_manifest_fails() {
+        use crate::{
+            status_tracker::{DetailedStatusTracker, StatusTracker},
+            store::Store,
+            utils::test::{create_test_claim, temp_signer},
+        };
+
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+
+        let signer = temp_signer();
+        let mut output = Cursor::new(Vec::new());
+        store
+            .save_to_stream(
+                "pdf",
+                &mut Cursor::new(source.to_vec()),
+                &mut output,
+                signer.as_ref(),
+            )
+            .unwrap();
+        let c2pa_signed = output.into_inner();
+
+        // Append an incremental update that replaces the first page's content.
+        let document = lopdf::Document::load_mem(&c2pa_signed).unwrap();
+        let (root_id, root_gen) = document
+            .trailer
+            .get(b"Root")
+            .and_then(lopdf::Object::as_reference)
+            .unwrap();
+        let page_id = *document.get_pages().get(&1).unwrap();
+        let (parent_id, parent_gen) = document
+            .get_object(page_id)
+            .and_then(lopdf::Object::as_dict)
+            .and_then(|page| page.get(b"Parent"))
+            .and_then(lopdf::Object::as_reference)
+            .unwrap();
+        let content_id = document.max_id + 1;
+        let startxref = memchr::memmem::rfind(&c2pa_signed, b"startxref").unwrap();
+        let prev_xref: usize = String::from_utf8_lossy(&c2pa_signed[startxref + 9..])
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let content = "BT /F1 24 Tf 72 720 Td (Replaced) Tj ET";
+        let content_object = format!(
+            "{content_id} 0 obj\n<</Length {}>>\nstream\n{content}\nendstream\nendobj\n",
+            content.len()
+        );
+        let page_object = format!(
+            "{} {} obj\n<</Type /Page /Parent {parent_id} {parent_gen} R \
+             /MediaBox [0 0 612 792] /Contents {content_id} 0 R>>\nendobj\n",
+            page_id.0, page_id.1
+        );
+        let content_offset = c2pa_signed.len();
+        let page_offset = content_offset + content_object.len();
+        let xref_offset = page_offset + page_object.len();
+        let tail = format!(
+            "xref\n{} 1\n{page_offset:010} {:05} n\r\n\
+             {content_id} 1\n{content_offset:010} 00000 n\r\n\
+             trailer\n<</Size {} /Prev {prev_xref} /Root {root_id} {root_gen} R>>\n\
+             startxref\n{xref_offset}\n%%EOF\n",
+            page_id.0,
+            page_id.1,
+            content_id + 1
+        );
+
+        let mut updated = c2pa_signed;
+        updated.extend_from_slice(content_object.as_bytes());
+        updated.extend_from_slice(page_object.as_bytes());
+        updated.extend_from_slice(tail.as_bytes());
+
+        // The update changes the displayed content, so the data hash must not validate.
+        let mut report = DetailedStatusTracker::new();
+        let _ = Store::load_from_memory("pdf", &updated, true, &mut report);
+        let has_status = |status: &str| {
+            report
+                .get_log()
+                .iter()
+                .any(|item| item.validation_status.as_deref() == Some(status))
+        };
+        assert!(has_status(
+            crate::validation_status::ASSERTION_DATAHASH_MISMATCH
+        ));
+        assert!(!has_status(
+            crate::validation_status::STATUS_PDF_UNBOUND_REVISIONS
+        ));
+    }
 }
@@ -0,0 +1,618 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Finds the C2PA manifest stream of a PDF from its cross-reference tables, reading only the
+//! objects on the way to it instead of parsing the whole document.
+//!
+//! Only the common layout is handled: cross-reference tables rather than streams, an
+//! unencrypted document and a manifest file specification in the catalog's `/AF` array.
+//! Anything else is left to a full parse.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Seek, SeekFrom},
+};
+
+static C2PA_RELATIONSHIP: &[u8] = b"C2PA_Manifest";
+
+// How much of the end of the file is searched for the final `startxref`.
+const TAIL_LEN: u64 = 1024;
+
+// Bytes first read to parse an object or a cross-reference section, doubled while the parse
+// runs out of data, up to the maximum.
+const CHUNK_LEN: usize = 4096;
+const MAX_READ_LEN: usize = 64 * 1024 * 1024;
+
+// Maximum nesting of arrays and dictionaries.
+const MAX_DEPTH: usize = 64;
+
+/// Where the data of a PDF's manifest stream is, as found by [`locate_manifest_stream`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ManifestStreamLocation {
+    /// Offset of the stream data in the PDF.
+    pub offset: u64,
+    /// Length of the stream data, as given by its `/Length`.
+    pub len: u64,
+    /// `true` if the stream data is compressed with `FlateDecode`.
+    pub compressed: bool,
+}
+
+/// Finds the manifest stream of the PDF read from `reader` by following its cross-reference
+/// tables from the catalog's `/AF` array.
+///
+/// `relationship` is the configured `/AFRelationship` of the manifest's file specification.
+/// A file specification with it is only the manifest if it also has the configured name, which
+/// this doesn't check, so finding one returns `None` as well.
+///
+/// Returns `None` when the manifest can't be found this way, including when the PDF has none,
+/// so the caller must then parse the whole document.
+pub(crate) fn locate_manifest_stream<R: Read + Seek + ?Sized>(
+    reader: &mut R,
+    relationship: &[u8],
+) -> Option<ManifestStreamLocation> {
+    let len = reader.seek(SeekFrom::End(0)).ok()?;
+    let mut scanner = Scanner {
+        reader,
+        len,
+        xref: HashMap::new(),
+    };
+    scanner.locate(relationship)
+}
+
+// The entries of a cross-reference table: the offset and generation of each object in use, `None`
+// for freed objects.
+type XrefEntries = Vec<(u32, Option<(u64, u16)>)>;
+
+/// A PDF object, without the content of strings, which are never needed.
+#[derive(Clone, Debug, PartialEq)]
+enum Obj {
+    Null,
+    Bool,
+    Int(i64),
+    Real,
+    Str,
+    Name(Vec<u8>),
+    Array(Vec<Obj>),
+    Dict(Dict),
+    Ref(u32, u16),
+}
+
+impl Obj {
+    fn as_dict(&self) -> Option<&Dict> {
+        match self {
+            Obj::Dict(dict) => Some(dict),
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> Option<u64> {
+        match self {
+            Obj::Int(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Dict(Vec<(Vec<u8>, Obj)>);
+
+impl Dict {
+    fn get(&self, key: &[u8]) -> Option<&Obj> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    fn has(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+struct Scanner<'a, R: Read + Seek + ?Sized> {
+    reader: &'a mut R,
+    len: u64,
+    // the offset and generation of each object in use, `None` for freed objects
+    xref: HashMap<u32, Option<(u64, u16)>>,
+}
+
+impl<R: Read + Seek + ?Sized> Scanner<'_, R> {
+    fn locate(&mut self, relationship: &[u8]) -> Option<ManifestStreamLocation> {
+        let root = self.read_xref()?;
+        let catalog = self.object(root.0, root.1)?.0;
+        let associated_files = self.resolve(catalog.as_dict()?.get(b"AF")?)?;
+        let Obj::Array(associated_files) = associated_files else {
+            return None;
+        };
+
+        for file_spec in associated_files {
+            let Obj::Ref(number, generation) = file_spec else {
+                continue;
+            };
+            let (file_spec, _) = self.object(number, generation)?;
+            let Some(file_spec) = file_spec.as_dict() else {
+                continue;
+            };
+            let name = match file_spec.get(b"AFRelationship") {
+                Some(name) => self.resolve(name)?,
+                None => continue,
+            };
+            let Obj::Name(name) = name else {
+                continue;
+            };
+            if !file_spec.has(b"EF") {
+                continue;
+            }
+
+            if name == C2PA_RELATIONSHIP {
+                return self.stream_location(file_spec);
+            }
+            if name == relationship {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Reads the cross-reference tables, newest first, and returns the id of the catalog.
+    fn read_xref(&mut self) -> Option<(u32, u16)> {
+        let tail_start = self.len.saturating_sub(TAIL_LEN);
+        let tail = self.read_at(tail_start, TAIL_LEN as usize)?;
+        let pos = rfind(&tail, b"startxref")?;
+        let mut parser = Parser::new(&tail[pos + b"startxref".len()..]);
+        let mut next = parser
+            .integer()
+            .and_then(|offset| u64::try_from(offset).ok());
+
+        let mut root = None;
+        let mut visited = HashSet::new();
+        while let Some(offset) = next {
+            if !visited.insert(offset) {
+                return None;
+            }
+
+            let (entries, trailer) = self.parse_at(offset, |parser| parser.xref_section())?;
+            // encrypted documents and hybrid files, with objects only listed in a
+            // cross-reference stream, need a full parse
+            if trailer.has(b"Encrypt") || trailer.has(b"XRefStm") {
+                return None;
+            }
+            if root.is_none() {
+                root = match trailer.get(b"Root") {
+                    Some(Obj::Ref(number, generation)) => Some((*number, *generation)),
+                    _ => None,
+                };
+            }
+            for (number, entry) in entries {
+                self.xref.entry(number).or_insert(entry);
+            }
+
+            next = trailer.get(b"Prev").and_then(Obj::as_u64);
+        }
+
+        root
+    }
+
+    /// Returns the location of the embedded file stream of `file_spec`.
+    fn stream_location(&mut self, file_spec: &Dict) -> Option<ManifestStreamLocation> {
+        let embedded_files = self.resolve(file_spec.get(b"EF")?)?;
+        let Some(Obj::Ref(number, generation)) = embedded_files.as_dict()?.get(b"F") else {
+            return None;
+        };
+        let (stream, offset) = self.object(*number, *generation)?;
+        let (stream, offset) = (stream.as_dict()?, offset?);
+        let len = self.resolve(stream.get(b"Length")?)?.as_u64()?;
+
+        // the length must end the data right before `endstream`, else it is left to the full
+        // parse to recover the stream
+        let end = offset.checked_add(len)?;
+        let after = self.read_at(end, 32)?;
+        let mut parser = Parser::new(&after);
+        if !parser.keyword(b"endstream") {
+            return None;
+        }
+
+        let compressed = match stream.get(b"Filter") {
+            Some(Obj::Name(filter)) => filter == b"FlateDecode",
+            Some(Obj::Array(filters)) => {
+                matches!(filters.as_slice(), [Obj::Name(filter)] if filter == b"FlateDecode")
+            }
+            _ => false,
+        };
+
+        Some(ManifestStreamLocation {
+            offset,
+            len,
+            compressed,
+        })
+    }
+
+    /// Follows `object` to the object it references, if it is a reference.
+    fn resolve(&mut self, object: &Obj) -> Option<Obj> {
+        match object {
+            Obj::Ref(number, generation) => self.object(*number, *generation).map(|(obj, _)| obj),
+            _ => Some(object.clone()),
+        }
+    }
+
+    /// Reads the object with the given id, with the offset of its data if it is a stream.
+    fn object(&mut self, number: u32, generation: u16) -> Option<(Obj, Option<u64>)> {
+        let (offset, xref_generation) = (*self.xref.get(&number)?)?;
+        if xref_generation != generation {
+            return None;
+        }
+
+        let (object, data_pos) =
+            self.parse_at(offset, |parser| parser.indirect_object(number, generation))?;
+        Some((object, data_pos.map(|pos| offset + pos as u64)))
+    }
+
+    /// Parses the data at `offset` with `parse`, reading more of it while the parse runs out of
+    /// data.
+    fn parse_at<T>(&mut self, offset: u64, parse: impl Fn(&mut Parser) -> Option<T>) -> Option<T> {
+        let mut len = CHUNK_LEN;
+        loop {
+            let data = self.read_at(offset, len)?;
+            let mut parser = Parser::new(&data);
+            if data.len() == len {
+                parser.more_data = true;
+            }
+
+            match parse(&mut parser) {
+                Some(result) => return Some(result),
+                None if parser.truncated && len < MAX_READ_LEN => len *= 2,
+                None => return None,
+            }
+        }
+    }
+
+    /// Reads up to `len` bytes at `offset`, fewer at the end of the file.
+    fn read_at(&mut self, offset: u64, len: usize) -> Option<Vec<u8>> {
+        let len = self.len.checked_sub(offset)?.min(len as u64);
+        self.reader.seek(SeekFrom::Start(offset)).ok()?;
+        let mut data = Vec::with_capacity(len as usize);
+        Read::take(&mut *self.reader, len)
+            .read_to_end(&mut data)
+            .ok()?;
+        Some(data)
+    }
+}
+
+/// Parses PDF syntax. A parse that reaches the end of `data` while more of the file follows is
+/// marked as truncated, so it can be retried with more data.
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    more_data: bool,
+    truncated: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            more_data: false,
+            truncated: false,
+        }
+    }
+
+    /// Returns the next byte, or `None` at the end of the data.
+    fn peek(&mut self) -> Option<u8> {
+        let byte = self.data.get(self.pos).copied();
+        if byte.is_none() && self.more_data {
+            self.truncated = true;
+        }
+        byte
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'%' => {
+                    while !matches!(self.peek(), None | Some(b'\r' | b'\n')) {
+                        self.pos += 1;
+                    }
+                }
+                _ if is_whitespace(byte) => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Consumes `keyword` if it comes next, followed by a delimiter or whitespace.
+    fn keyword(&mut self, keyword: &[u8]) -> bool {
+        self.skip_whitespace();
+        let end = self.pos + keyword.len();
+        if self.data.get(self.pos..end) != Some(keyword) {
+            if self.data.len() < end && self.more_data {
+                self.truncated = true;
+            }
+            return false;
+        }
+
+        let saved = self.pos;
+        self.pos = end;
+        if matches!(self.peek(), Some(byte) if is_regular(byte)) {
+            self.pos = saved;
+            return false;
+        }
+        true
+    }
+
+    fn integer(&mut self) -> Option<i64> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'+' | b'-' | b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// Parses a cross-reference table and the trailer that follows it.
+    fn xref_section(&mut self) -> Option<(XrefEntries, Dict)> {
+        if !self.keyword(b"xref") {
+            return None;
+        }
+
+        let mut entries = Vec::new();
+        while !self.keyword(b"trailer") {
+            let start = u32::try_from(self.integer()?).ok()?;
+            let count = u32::try_from(self.integer()?).ok()?;
+            for number in start..start.checked_add(count)? {
+                let offset = u64::try_from(self.integer()?).ok()?;
+                let generation = u16::try_from(self.integer()?).ok()?;
+                let entry = if self.keyword(b"n") {
+                    Some((offset, generation))
+                } else if self.keyword(b"f") {
+                    None
+                } else {
+                    return None;
+                };
+                entries.push((number, entry));
+            }
+        }
+
+        match self.object(0)? {
+            Obj::Dict(trailer) => Some((entries, trailer)),
+            _ => None,
+        }
+    }
+
+    /// Parses the indirect object with the given id, with the position of its data if it is a
+    /// stream.
+    fn indirect_object(&mut self, number: u32, generation: u16) -> Option<(Obj, Option<usize>)> {
+        if self.integer()? != i64::from(number)
+            || self.integer()? != i64::from(generation)
+            || !self.keyword(b"obj")
+        {
+            return None;
+        }
+
+        let object = self.object(0)?;
+        if !self.keyword(b"stream") {
+            return Some((object, None));
+        }
+
+        // the data starts after the end of line following `stream`
+        match (self.peek(), self.data.get(self.pos + 1)) {
+            (Some(b'\r'), Some(b'\n')) => self.pos += 2,
+            (Some(b'\r'), None) => {
+                self.truncated = self.more_data;
+                return None;
+            }
+            (Some(b'\n' | b'\r'), _) => self.pos += 1,
+            _ => return None,
+        }
+        Some((object, Some(self.pos)))
+    }
+
+    fn object(&mut self, depth: usize) -> Option<Obj> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        self.skip_whitespace();
+        match self.peek()? {
+            b'/' => self.name().map(Obj::Name),
+            b'(' => self.literal_string(),
+            b'<' if self.data.get(self.pos + 1) == Some(&b'<') => self.dictionary(depth),
+            b'<' => {
+                let len = self.data[self.pos..].iter().position(|b| *b == b'>');
+                match len {
+                    Some(len) => {
+                        self.pos += len + 1;
+                        Some(Obj::Str)
+                    }
+                    None => {
+                        self.truncated = self.more_data;
+                        None
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek()? == b']' {
+                        self.pos += 1;
+                        return Some(Obj::Array(items));
+                    }
+                    items.push(self.object(depth + 1)?);
+                }
+            }
+            b'+' | b'-' | b'.' | b'0'..=b'9' => self.number(),
+            _ if self.keyword(b"true") || self.keyword(b"false") => Some(Obj::Bool),
+            _ if self.keyword(b"null") => Some(Obj::Null),
+            _ => None,
+        }
+    }
+
+    fn dictionary(&mut self, depth: usize) -> Option<Obj> {
+        self.pos += 2;
+        let mut dict = Dict::default();
+        loop {
+            self.skip_whitespace();
+            if self.peek()? == b'>' {
+                if self.data.get(self.pos + 1) != Some(&b'>') {
+                    self.truncated = self.more_data;
+                    return None;
+                }
+                self.pos += 2;
+                return Some(Obj::Dict(dict));
+            }
+
+            let key = self.name()?;
+            let value = self.object(depth + 1)?;
+            dict.0.push((key, value));
+        }
+    }
+
+    /// Parses a number, or a reference if it is followed by a generation and `R`.
+    fn number(&mut self) -> Option<Obj> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'+' | b'-' | b'.' | b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        let token = &self.data[start..self.pos];
+        if token.contains(&b'.') {
+            return Some(Obj::Real);
+        }
+        let value: i64 = std::str::from_utf8(token).ok()?.parse().ok()?;
+
+        let saved = self.pos;
+        let reference = match (u32::try_from(value), self.integer()) {
+            (Ok(number), Some(generation)) if self.keyword(b"R") => u16::try_from(generation)
+                .ok()
+                .map(|gen| Obj::Ref(number, gen)),
+            _ => None,
+        };
+        if reference.is_none() {
+            self.pos = saved;
+        }
+        reference.or(Some(Obj::Int(value)))
+    }
+
+    fn name(&mut self) -> Option<Vec<u8>> {
+        if self.peek()? != b'/' {
+            return None;
+        }
+        self.pos += 1;
+
+        let mut name = Vec::new();
+        while let Some(byte) = self.peek().filter(|byte| is_regular(*byte)) {
+            self.pos += 1;
+            if byte != b'#' {
+                name.push(byte);
+                continue;
+            }
+
+            let hex = self.data.get(self.pos..self.pos + 2)?;
+            let decoded = u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?;
+            name.push(decoded);
+            self.pos += 2;
+        }
+        Some(name)
+    }
+
+    fn literal_string(&mut self) -> Option<Obj> {
+        let mut depth = 0;
+        loop {
+            let byte = self.peek()?;
+            self.pos += 1;
+            match byte {
+                b'\\' => self.pos += 1,
+                b'(' => depth += 1,
+                b')' if depth == 1 => return Some(Obj::Str),
+                b')' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b'\0' | b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+}
+
+// Returns `true` for bytes that are neither whitespace nor delimiters.
+fn is_regular(byte: u8) -> bool {
+    !is_whitespace(byte) && !b"()<>[]{}/%".contains(&byte)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_locate_manifest_stream() {
+        let source = include_bytes!("../../tests/fixtures/express-signed.pdf");
+        let location = locate_manifest_stream(&mut Cursor::new(source), C2PA_RELATIONSHIP).unwrap();
+        assert!(!location.compressed);
+
+        // the data is a JUMBF superbox
+        let start = location.offset as usize;
+        let data = &source[start..start + location.len as usize];
+        assert_eq!(&data[4..8], b"jumb");
+    }
+
+    #[test]
+    fn test_locate_manifest_stream_in_xref_stream() {
+        // cross-reference streams are left to a full parse
+        let source = include_bytes!("../../tests/fixtures/basic.pdf");
+        assert_eq!(
+            locate_manifest_stream(&mut Cursor::new(source), C2PA_RELATIONSHIP),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_objects() {
+        let mut parser = Parser::new(b"<</A 1 0 R/B[1 -2 3.5 (a(b)\\)c) <0f>]/C#20D true>>");
+        let dict = parser.object(0).unwrap();
+        let dict = dict.as_dict().unwrap();
+        assert_eq!(dict.get(b"A"), Some(&Obj::Ref(1, 0)));
+        assert_eq!(
+            dict.get(b"B"),
+            Some(&Obj::Array(vec![
+                Obj::Int(1),
+                Obj::Int(-2),
+                Obj::Real,
+                Obj::Str,
+                Obj::Str
+            ]))
+        );
+        assert_eq!(dict.get(b"C D"), Some(&Obj::Bool));
+
+        // running out of data is reported when more of the file follows
+        let mut parser = Parser::new(b"<</A 1");
+        parser.more_data = true;
+        assert_eq!(parser.object(0), None);
+        assert!(parser.truncated);
+    }
+}
@@ -21,12 +21,11 @@ use byteorder::{BigEndian, ReadBytesExt};
 use conv::ValueFrom;
 use png_pong::chunk::InternationalText;
 use serde_bytes::ByteBuf;
-use tempfile::Builder;
 
 use crate::{
     assertions::{BoxMap, C2PA_BOXHASH},
     asset_io::{
-        rename_or_move, AssetBoxHash, AssetIO, CAIRead, CAIReadWrite, CAIReader, CAIWriter,
+        rewrite_asset, AssetBoxHash, AssetIO, CAIRead, CAIReadWrite, CAIReader, CAIWriter,
         ComposedManifestRef, HashBlockObjectType, HashObjectPositions, RemoteRefEmbed,
         RemoteRefEmbedType,
     },
@@ -480,15 +479,9 @@ impl AssetIO for PngIO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut stream, &mut temp_file, store_bytes)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(
@@ -1021,6 +1014,31 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_remove_c2pa_from_stream_keeps_other_bytes() {
+        let source = include_bytes!("../../tests/fixtures/exp-test1.png");
+
+        let png_io = PngIO {};
+        let locations = png_io
+            .get_object_locations_from_stream(&mut Cursor::new(source))
+            .unwrap();
+        let cai = locations
+            .iter()
+            .find(|l| l.htype == HashBlockObjectType::Cai)
+            .unwrap();
+
+        // the expected output is the source with only the manifest chunk cut out
+        let mut expected = source[..cai.offset].to_vec();
+        expected.extend_from_slice(&source[cai.offset + cai.length..]);
+
+        let mut output_stream = Cursor::new(Vec::new());
+        png_io
+            .remove_cai_store_from_stream(&mut Cursor::new(source), &mut output_stream)
+            .unwrap();
+
+        assert_eq!(output_stream.into_inner(), expected);
+    }
+
     #[test]
     fn test_embeddable_manifest() {
         let png_io = PngIO {};
@@ -21,11 +21,10 @@ use std::{
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use conv::ValueFrom;
 use riff::*;
-use tempfile::Builder;
 
 use crate::{
     asset_io::{
-        rename_or_move, AssetIO, AssetPatch, CAIRead, CAIReadWrapper, CAIReadWrite,
+        rewrite_asset, AssetIO, AssetPatch, CAIRead, CAIReadWrapper, CAIReadWrite,
         CAIReadWriteWrapper, CAIReader, CAIWriter, HashBlockObjectType, HashObjectPositions,
         RemoteRefEmbed, RemoteRefEmbedType,
     },
@@ -363,15 +362,9 @@ impl AssetIO for RiffIO {
     fn save_cai_store(&self, asset_path: &Path, store_bytes: &[u8]) -> Result<()> {
         let mut input_stream = File::open(asset_path)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut input_stream, &mut temp_file, store_bytes)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut input_stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(&self, asset_path: &Path) -> Result<Vec<HashObjectPositions>> {
@@ -664,6 +657,73 @@ pub mod tests {
         assert!(success)
     }
 
+    // Returns the id and padded contents of the top level chunks of a RIFF file.
+    fn top_level_chunks(data: &[u8]) -> Vec<(&[u8], &[u8])> {
+        let mut chunks = Vec::new();
+        let mut pos = 12; // skip the RIFF header and form type
+        while pos + 8 <= data.len() {
+            let len = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().unwrap()) as usize;
+            let padded_len = len + len % 2;
+            chunks.push((&data[pos..pos + 4], &data[pos + 8..pos + 8 + padded_len]));
+            pos += 8 + padded_len;
+        }
+        chunks
+    }
+
+    #[test]
+    fn test_sign_bwf_preserves_chunks() {
+        use crate::{
+            status_tracker::{report_split_errors, DetailedStatusTracker, StatusTracker},
+            store::Store,
+            utils::test::{create_test_claim, temp_signer},
+        };
+
+        let source = include_bytes!("../../tests/fixtures/sample-bwf.wav");
+
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+
+        let signer = temp_signer();
+        let mut output = Cursor::new(Vec::new());
+        store
+            .save_to_stream(
+                "wav",
+                &mut Cursor::new(source.to_vec()),
+                &mut output,
+                signer.as_ref(),
+            )
+            .unwrap();
+        let signed = output.into_inner();
+
+        // the bext and odd length iXML chunks keep their order, contents and padding
+        let source_chunks = top_level_chunks(source);
+        let signed_chunks = top_level_chunks(&signed);
+        assert_eq!(
+            source_chunks.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            [&b"fmt "[..], b"bext", b"iXML", b"data"]
+        );
+        assert_eq!(signed_chunks.len(), source_chunks.len() + 1);
+        assert_eq!(signed_chunks[..source_chunks.len()], source_chunks[..]);
+        assert_eq!(signed_chunks[source_chunks.len()].0, b"C2PA");
+
+        // the manifest chunk is the only region excluded from the data hash
+        let riff_io = RiffIO::new("wav");
+        let (manifest_pos, manifest_len) = get_manifest_pos(&mut Cursor::new(&signed)).unwrap();
+        let positions = riff_io
+            .get_object_locations_from_stream(&mut Cursor::new(&signed))
+            .unwrap();
+        let excluded: Vec<_> = positions
+            .iter()
+            .filter(|p| p.htype == HashBlockObjectType::Cai)
+            .map(|p| (p.offset as u64, p.length as u32))
+            .collect();
+        assert_eq!(excluded, vec![(manifest_pos, manifest_len)]);
+
+        let mut report = DetailedStatusTracker::new();
+        Store::load_from_memory("wav", &signed, true, &mut report).unwrap();
+        assert!(report_split_errors(report.get_log_mut()).is_empty());
+    }
+
     #[test]
     fn test_read_cai_with_incorrect_header_size_does_not_panic() {
         let riff_io = RiffIO::new("wav");
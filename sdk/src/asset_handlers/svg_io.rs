@@ -22,11 +22,10 @@ use fast_xml::{
     events::{BytesText, Event},
     Reader, Writer,
 };
-use tempfile::Builder;
 
 use crate::{
     asset_io::{
-        rename_or_move,
+        rewrite_asset,
         AssetIO,
         AssetPatch,
         CAIRead,
@@ -116,15 +115,9 @@ impl AssetIO for SvgIO {
             .open(asset_path)
             .map_err(Error::IoError)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.write_cai(&mut input_stream, &mut temp_file, store_bytes)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.write_cai(&mut input_stream, output, store_bytes)
+        })
     }
 
     fn get_object_locations(
@@ -140,15 +133,9 @@ impl AssetIO for SvgIO {
     fn remove_cai_store(&self, asset_path: &Path) -> Result<()> {
         let mut input_file = File::open(asset_path)?;
 
-        let mut temp_file = Builder::new()
-            .prefix("c2pa_temp")
-            .rand_bytes(5)
-            .tempfile()?;
-
-        self.remove_cai_store_from_stream(&mut input_file, &mut temp_file)?;
-
-        // copy temp file to asset
-        rename_or_move(temp_file, asset_path)
+        rewrite_asset(asset_path, |output| {
+            self.remove_cai_store_from_stream(&mut input_file, output)
+        })
     }
 
     fn remote_ref_writer_ref(&self) -> Option<&dyn RemoteRefEmbed> {
@@ -17,9 +17,10 @@ use std::{
     path::Path,
 };
 
+use chrono::{DateTime, Utc};
 use tempfile::NamedTempFile;
 
-use crate::{assertions::BoxMap, error::Result};
+use crate::{assertions::BoxMap, error::Result, utils::temp_files};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HashBlockObjectType {
@@ -113,6 +114,18 @@ pub trait CAIReader: Sync + Send {
     // Return entire CAI block as Vec<u8>
     fn read_cai(&self, asset_reader: &mut dyn CAIRead) -> Result<Vec<u8>>;
 
+    // Writes the entire CAI block to writer, returning the number of bytes written.
+    // Formats that can locate the CAI block in the asset copy it without buffering it whole.
+    fn read_cai_to_writer(
+        &self,
+        asset_reader: &mut dyn CAIRead,
+        writer: &mut dyn Write,
+    ) -> Result<u64> {
+        let cai = self.read_cai(asset_reader)?;
+        writer.write_all(&cai)?;
+        Ok(cai.len() as u64)
+    }
+
     // Get XMP block
     fn read_xmp(&self, asset_reader: &mut dyn CAIRead) -> Option<String>;
 }
@@ -126,12 +139,31 @@ pub trait CAIWriter: Sync + Send {
         store_bytes: &[u8],
     ) -> Result<()>;
 
+    // Writes store_bytes like write_cai, but compressed where the format allows it.
+    // Formats without compressed manifest storage write store_bytes unchanged.
+    fn write_cai_compressed(
+        &self,
+        input_stream: &mut dyn CAIRead,
+        output_stream: &mut dyn CAIReadWrite,
+        store_bytes: &[u8],
+    ) -> Result<()> {
+        self.write_cai(input_stream, output_stream, store_bytes)
+    }
+
     // Finds location where the C2PA manifests will be placed in the asset specified by input_stream
     fn get_object_locations_from_stream(
         &self,
         input_stream: &mut dyn CAIRead,
     ) -> Result<Vec<HashObjectPositions>>;
 
+    // Sets the signing time, for formats that record when the manifest was written.
+    // Other formats ignore it.
+    fn set_signing_time(&mut self, _signing_time: DateTime<Utc>) {}
+
+    // Sets the UUID, as 32 hex digits, of the private manifest store being embedded, for
+    // formats that locate the manifest store by its UUID. Other formats ignore it.
+    fn set_manifest_store_uuid(&mut self, _uuid: &str) {}
+
     // Remove entire C2PA manifest store from asset
     fn remove_cai_store_from_stream(
         &self,
@@ -260,6 +292,26 @@ pub trait ComposedManifestRef {
     fn compose_manifest(&self, manifest_data: &[u8], format: &str) -> Result<Vec<u8>>;
 }
 
+/// Replaces the contents of the asset at `asset_path` with the output of `write`.
+///
+/// The output is staged in a temporary file, or in memory when the `core.in_memory` setting is
+/// `true`, so the asset is only replaced once `write` succeeds.
+pub(crate) fn rewrite_asset<P, F>(asset_path: P, write: F) -> Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut dyn CAIReadWrite) -> Result<()>,
+{
+    if temp_files::in_memory() {
+        let mut output = Cursor::new(Vec::new());
+        write(&mut output)?;
+        return fs::write(asset_path, output.into_inner()).map_err(crate::Error::IoError);
+    }
+
+    let mut temp_file = temp_files::temp_file()?;
+    write(&mut temp_file)?;
+    rename_or_move(temp_file, asset_path)
+}
+
 /// Utility function to rename a file or, if the provided paths are on separate mounting points,
 /// move a file from a temporary location to its final location.
 ///
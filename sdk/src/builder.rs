@@ -12,28 +12,34 @@
 // each license.
 
 #[cfg(feature = "file_io")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{
     collections::HashMap,
+    fmt,
     io::{Read, Seek, Write},
+    sync::Arc,
 };
 
 use async_generic::async_generic;
+use chrono::{DateTime, Utc};
 #[cfg(feature = "json_schema")]
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+#[cfg(feature = "pdf")]
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 use zip::{write::FileOptions, ZipArchive, ZipWriter};
 
 use crate::{
     assertion::AssertionDecodeError,
     assertions::{
-        labels, Actions, CreativeWork, DataHash, Exif, Metadata, SoftwareAgent, Thumbnail, User,
-        UserCbor,
+        c2pa_action, labels, Action, Actions, CreativeWork, DataHash, Exif, Metadata, Relationship,
+        SoftwareAgent, Thumbnail, User, UserCbor,
     },
     claim::Claim,
     error::{Error, Result},
+    metrics::MetricsObserver,
     // manifest_definition::{AssertionData, AssertionDefinition, ManifestDefinition},
     resource_store::{ResourceRef, ResourceResolver, ResourceStore},
     salt::DefaultSalt,
@@ -43,18 +49,35 @@ use crate::{
     ClaimGeneratorInfo,
     HashRange,
     Ingredient,
+    ManifestAssertionKind,
+    Reader,
     Signer,
 };
+#[cfg(feature = "pdf")]
+use crate::{
+    asset_handlers::pdf_io::PdfIO,
+    asset_io::{AssetIO, CAIWriter},
+    utils::{hash_utils::Hasher, io_utils::stream_len},
+};
 
 /// Version of the Builder Archive file
 const ARCHIVE_VERSION: &str = "1";
 
+/// Version of the signed manifest bundle written by [`Builder::sign_to_archive`].
+const BUNDLE_VERSION: &str = "1";
+
+/// Path of the signed manifest store in a signed manifest bundle.
+pub(crate) const BUNDLE_MANIFEST: &str = "manifest.c2pa";
+
+/// Folder holding the external resources in a signed manifest bundle.
+pub(crate) const BUNDLE_RESOURCES: &str = "resources/";
+
 /// A Manifest Definition
 /// This is used to define a manifest and is used to build a ManifestStore
 /// A Manifest is a collection of ingredients and assertions
 /// It is used to define a claim that can be signed and embedded into a file
 #[skip_serializing_none]
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
 #[non_exhaustive]
 pub struct ManifestDefinition {
@@ -127,8 +150,18 @@ pub enum AssertionData {
 pub struct AssertionDefinition {
     pub label: String,
     pub data: AssertionData,
+    /// The encoding used to store the assertion, either `Cbor` or `Json`.
+    ///
+    /// When not set, the assertion is stored as CBOR unless it was added as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<ManifestAssertionKind>,
 }
 impl AssertionDefinition {
+    /// Returns the encoding requested for the assertion, if any.
+    pub fn kind(&self) -> Option<&ManifestAssertionKind> {
+        self.kind.as_ref()
+    }
+
     pub(crate) fn to_assertion<T: DeserializeOwned>(&self) -> Result<T> {
         match &self.data {
             AssertionData::Json(value) => serde_json::from_value(value.clone()).map_err(|e| {
@@ -151,6 +184,19 @@ impl AssertionDefinition {
             }
         }
     }
+
+    // Returns true if the assertion should be stored as a JSON assertion.
+    fn is_json(&self) -> Result<bool> {
+        match (&self.kind, &self.data) {
+            (Some(ManifestAssertionKind::Json), _) => Ok(true),
+            (Some(ManifestAssertionKind::Cbor), _) => Ok(false),
+            (None, data) => Ok(matches!(data, AssertionData::Json(_))),
+            (Some(kind), _) => Err(Error::BadParam(format!(
+                "unsupported assertion kind {kind:?} for {}",
+                self.label
+            ))),
+        }
+    }
 }
 
 /// A Builder is used to add a signed manifest to an asset.
@@ -205,7 +251,7 @@ impl AssertionDefinition {
 /// # }
 /// ```
 #[skip_serializing_none]
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
 pub struct Builder {
     #[serde(flatten)]
@@ -217,6 +263,20 @@ pub struct Builder {
     // If true, the manifest store will not be embedded in the asset on sign
     pub no_embed: bool,
 
+    /// Optional size to pad the embedded manifest store to
+    pub padded_size: Option<usize>,
+
+    /// If true, the embedded manifest store is compressed where the asset format allows it
+    #[serde(default)]
+    pub compress_manifest: bool,
+
+    /// If true, no claim or ingredient thumbnails are added to the manifest
+    #[serde(default)]
+    pub no_thumbnails: bool,
+
+    /// Optional maximum size of the manifest store, met by downscaling or leaving out thumbnails
+    pub max_manifest_bytes: Option<usize>,
+
     /// Base path to search for resources.
     #[cfg(feature = "file_io")]
     pub base_path: Option<PathBuf>,
@@ -224,6 +284,89 @@ pub struct Builder {
     /// container for binary assets (like thumbnails)
     #[serde(skip)]
     resources: ResourceStore,
+
+    /// Source of the signing time recorded in the asset
+    #[serde(skip)]
+    time_provider: Option<Arc<dyn TimeProvider>>,
+
+    /// Receives the time spent in each stage of signing
+    #[serde(skip)]
+    metrics: Option<Arc<dyn MetricsObserver>>,
+
+    /// Makes thumbnails smaller to keep the manifest store within `max_manifest_bytes`
+    #[serde(skip)]
+    thumbnail_downscaler: Option<Arc<dyn ThumbnailDownscaler>>,
+
+    /// Label and UUID of a private manifest store superbox, the standard c2pa ones if not set
+    #[serde(skip)]
+    manifest_store_box: Option<(String, String)>,
+}
+
+/// Provides the signing time for a [`Builder`].
+///
+/// Asset formats that date the embedded manifest record this time, currently PDF, as the
+/// `/CreationDate` and `/ModDate` of the manifest's embedded file. Any
+/// `Fn() -> DateTime<Utc>` closure can be used as a time provider.
+pub trait TimeProvider: Send + Sync {
+    /// Returns the time the manifest is signed.
+    fn signing_time(&self) -> DateTime<Utc>;
+}
+
+impl<F> TimeProvider for F
+where
+    F: Fn() -> DateTime<Utc> + Send + Sync,
+{
+    fn signing_time(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+impl fmt::Debug for dyn TimeProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeProvider").finish_non_exhaustive()
+    }
+}
+
+/// Makes thumbnails smaller for a [`Builder`] with a maximum manifest size.
+///
+/// Any `Fn(&str, &[u8]) -> Option<Vec<u8>>` closure can be used as a downscaler.
+pub trait ThumbnailDownscaler: Send + Sync {
+    /// Returns a smaller version of a thumbnail, in the same `format`, or `None` if it cannot
+    /// be made smaller.
+    fn downscale(&self, format: &str, thumbnail: &[u8]) -> Option<Vec<u8>>;
+}
+
+impl<F> ThumbnailDownscaler for F
+where
+    F: Fn(&str, &[u8]) -> Option<Vec<u8>> + Send + Sync,
+{
+    fn downscale(&self, format: &str, thumbnail: &[u8]) -> Option<Vec<u8>> {
+        self(format, thumbnail)
+    }
+}
+
+impl fmt::Debug for dyn ThumbnailDownscaler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThumbnailDownscaler")
+            .finish_non_exhaustive()
+    }
+}
+
+/// A manifest store signed by [`Builder::sign_detached`] and the binding to its asset.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DetachedManifest {
+    /// The signed manifest store, to be stored apart from the asset.
+    pub manifest: Vec<u8>,
+
+    /// The hash algorithm of the binding.
+    pub alg: String,
+
+    /// The hash of the bound asset bytes.
+    pub hash: Vec<u8>,
+
+    /// The byte ranges of the asset covered by the hash.
+    pub ranges: Vec<HashRange>,
 }
 
 impl AsRef<Builder> for Builder {
@@ -298,6 +441,125 @@ impl Builder {
         self
     }
 
+    /// Sets the size to pad the manifest store to for this [`Builder`].
+    /// The manifest store is followed by a JUMBF padding box up to this size when signing,
+    /// leaving room to later replace it with a larger manifest without growing the asset.
+    /// # Arguments
+    /// * `size` - The size in bytes to pad the manifest store to.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn set_padded_size(&mut self, size: usize) -> &mut Self {
+        self.padded_size = Some(size);
+        self
+    }
+
+    /// Sets whether to compress the embedded manifest store for this [`Builder`].
+    /// Compression is used for formats that can store a compressed manifest, currently PDF,
+    /// where it is written as a `/FlateDecode` embedded file. Other formats are not affected.
+    /// # Arguments
+    /// * `compress` - If true, the manifest store is compressed when signing.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn set_compress_manifest(&mut self, compress: bool) -> &mut Self {
+        self.compress_manifest = compress;
+        self
+    }
+
+    /// Signs the manifest of this [`Builder`] into a private manifest store, with a custom
+    /// JUMBF superbox label and UUID instead of the standard c2pa ones.
+    ///
+    /// Readers locate a private manifest store, in addition to standard ones, when its label
+    /// and UUID are configured in the `core.manifest_store_label` and `core.manifest_store_uuid`
+    /// settings.
+    /// # Arguments
+    /// * `label` - The label of the manifest store superbox.
+    /// * `uuid` - The UUID of the manifest store superbox as 32 hex digits.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    /// # Errors
+    /// * If the label is empty or the UUID is not 32 hex digits.
+    pub fn set_manifest_store_box(&mut self, label: &str, uuid: &str) -> Result<&mut Self> {
+        if label.is_empty() {
+            return Err(Error::BadParam(
+                "manifest store label must not be empty".into(),
+            ));
+        }
+        if uuid.len() != 32 || !uuid.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::BadParam(
+                "manifest store UUID must be 32 hex digits".into(),
+            ));
+        }
+        self.manifest_store_box = Some((label.to_owned(), uuid.to_owned()));
+        Ok(self)
+    }
+
+    /// Leaves all thumbnails out of the manifest built by this [`Builder`].
+    /// Neither the claim thumbnail nor any ingredient thumbnails are added when signing,
+    /// including thumbnails referenced from an ingredient's own manifest, which keeps the
+    /// manifest store small. All other assertions are unchanged.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn without_thumbnails(&mut self) -> &mut Self {
+        self.no_thumbnails = true;
+        self
+    }
+
+    /// Sets the maximum size of the manifest store signed by [`Builder::sign`].
+    /// When the manifest store would be larger, its thumbnails are downscaled with the
+    /// [`ThumbnailDownscaler`], if one is set, and then left out. Signing fails with
+    /// [`Error::ManifestTooLarge`] if the manifest store is still too large without them.
+    /// # Arguments
+    /// * `size` - The maximum size in bytes of the manifest store.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn set_max_manifest_bytes(&mut self, size: usize) -> &mut Self {
+        self.max_manifest_bytes = Some(size);
+        self
+    }
+
+    /// Sets the [`ThumbnailDownscaler`] used to keep the manifest store within the size set
+    /// with [`Builder::set_max_manifest_bytes`].
+    /// Without one, thumbnails are left out of a manifest store that is too large.
+    /// # Arguments
+    /// * `downscaler` - Makes a thumbnail smaller.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn set_thumbnail_downscaler<T>(&mut self, downscaler: T) -> &mut Self
+    where
+        T: ThumbnailDownscaler + 'static,
+    {
+        self.thumbnail_downscaler = Some(Arc::new(downscaler));
+        self
+    }
+
+    /// Sets the [`TimeProvider`] giving the signing time recorded in the asset.
+    /// Without one, PDF embedded manifest files are written without dates.
+    /// # Arguments
+    /// * `time_provider` - The source of the signing time.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn set_time_provider<T>(&mut self, time_provider: T) -> &mut Self
+    where
+        T: TimeProvider + 'static,
+    {
+        self.time_provider = Some(Arc::new(time_provider));
+        self
+    }
+
+    /// Sets a [`MetricsObserver`] to report the time spent in each stage of signing.
+    /// Without one, signing is not timed.
+    /// # Arguments
+    /// * `metrics` - The observer receiving the stage timings.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    pub fn set_metrics_observer<T>(&mut self, metrics: T) -> &mut Self
+    where
+        T: MetricsObserver + 'static,
+    {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
     /// Sets a thumbnail for the [`Builder`].
     ///
     /// The thumbnail should represent the associated asset for this [`Builder`].
@@ -326,6 +588,34 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets a thumbnail for the [`Builder`], detecting its format from the image data.
+    ///
+    /// The format is determined from the leading magic bytes of the stream rather than
+    /// being supplied by the caller.
+    ///
+    /// # Arguments
+    /// * `stream` - A stream to read the thumbnail from.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    /// # Errors
+    /// * Returns [`Error::UnsupportedType`] if the thumbnail is not a recognized image format.
+    pub fn set_thumbnail_from_stream<R>(&mut self, stream: &mut R) -> Result<&mut Self>
+    where
+        R: Read + Seek + ?Sized,
+    {
+        let mut resource = Vec::new();
+        stream.read_to_end(&mut resource)?;
+        let format =
+            crate::utils::mime::image_mime_from_bytes(&resource).ok_or(Error::UnsupportedType)?;
+
+        self.resources.add(&self.definition.instance_id, resource)?;
+        self.definition.thumbnail = Some(ResourceRef::new(
+            format,
+            self.definition.instance_id.clone(),
+        ));
+        Ok(self)
+    }
+
     /// Adds a CBOR assertion to the manifest.
     /// # Arguments
     /// * `label` - A label for the assertion.
@@ -342,6 +632,7 @@ impl Builder {
         self.definition.assertions.push(AssertionDefinition {
             label: label.into(),
             data: AssertionData::Cbor(serde_cbor::value::to_value(data)?),
+            kind: None,
         });
         Ok(self)
     }
@@ -362,10 +653,57 @@ impl Builder {
         self.definition.assertions.push(AssertionDefinition {
             label: label.into(),
             data: AssertionData::Json(serde_json::to_value(data)?),
+            kind: Some(ManifestAssertionKind::Json),
         });
         Ok(self)
     }
 
+    /// Adds an assertion for each assertion file in a directory.
+    ///
+    /// Each file is labeled with its name without the extension, so `c2pa.actions.json` adds a
+    /// `c2pa.actions` assertion. Files with a `.json` extension are added as JSON assertions and
+    /// files with a `.cbor` extension as CBOR assertions, in order of file name. Other files
+    /// are ignored, and files that can't be parsed are skipped with a warning.
+    /// # Arguments
+    /// * `path` - The directory to read the assertion files from.
+    /// # Returns
+    /// * A mutable reference to the [`Builder`].
+    /// # Errors
+    /// * If the directory can't be read.
+    #[cfg(feature = "file_io")]
+    pub fn add_assertions_from_dir<P: AsRef<Path>>(&mut self, path: P) -> Result<&mut Self> {
+        let mut paths = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<PathBuf>>>()?;
+        paths.sort();
+
+        for path in paths.iter().filter(|path| path.is_file()) {
+            let (Some(label), Some(ext)) = (
+                path.file_stem().and_then(|stem| stem.to_str()),
+                path.extension().and_then(|ext| ext.to_str()),
+            ) else {
+                continue;
+            };
+
+            let added = match ext.to_lowercase().as_str() {
+                "json" => std::fs::read(path).map_err(Error::from).and_then(|bytes| {
+                    let value: serde_json::Value = serde_json::from_slice(&bytes)?;
+                    self.add_assertion_json(label, &value).map(|_| ())
+                }),
+                "cbor" => std::fs::read(path).map_err(Error::from).and_then(|bytes| {
+                    let value: serde_cbor::Value = serde_cbor::from_slice(&bytes)?;
+                    self.add_assertion(label, &value).map(|_| ())
+                }),
+                _ => continue,
+            };
+
+            if let Err(err) = added {
+                log::warn!("skipping assertion file {}: {err}", path.display());
+            }
+        }
+        Ok(self)
+    }
+
     /// Adds an [`Ingredient`] to the manifest with JSON and a stream.
     /// # Arguments
     /// * `ingredient_json` - A JSON string representing the [`Ingredient`].
@@ -406,6 +744,97 @@ impl Builder {
         self
     }
 
+    /// Adds an [`Ingredient`] to the manifest with an explicit [`Relationship`].
+    ///
+    /// The relationship replaces any the ingredient already had, and is written to the
+    /// ingredient assertion as is.
+    /// # Arguments
+    /// * `ingredient` - The [`Ingredient`] to add.
+    /// * `relationship` - How the ingredient relates to the asset being signed.
+    /// # Errors
+    /// * If `relationship` is [`Relationship::ParentOf`] and the manifest already has a
+    ///   parent ingredient, since a manifest may have at most one.
+    pub fn add_ingredient_with_relationship<I>(
+        &mut self,
+        ingredient: I,
+        relationship: Relationship,
+    ) -> Result<&mut Self>
+    where
+        I: Into<Ingredient>,
+    {
+        if relationship == Relationship::ParentOf
+            && self.definition.ingredients.iter().any(|i| i.is_parent())
+        {
+            return Err(Error::BadParam(
+                "a manifest may only have one parentOf ingredient".to_string(),
+            ));
+        }
+        let mut ingredient = ingredient.into();
+        ingredient.set_relationship(relationship);
+        self.definition.ingredients.push(ingredient);
+        Ok(self)
+    }
+
+    /// Merges ingredients with the same content hash and relationship.
+    ///
+    /// The first of each set of duplicates is kept, and actions referencing a removed
+    /// ingredient by its instance ID are updated to reference the kept one. Ingredients with
+    /// the same hash but different relationships are all kept, as are ingredients without a
+    /// hash.
+    /// # Errors
+    /// * If an actions assertion of the manifest cannot be decoded.
+    pub fn dedupe_ingredients(&mut self) -> Result<&mut Self> {
+        // hash, relationship and instance ID of the ingredients kept so far
+        let mut kept: Vec<(String, Relationship, String)> = Vec::new();
+        let mut replaced: HashMap<String, String> = HashMap::new();
+
+        self.definition.ingredients.retain(|ingredient| {
+            let Some(hash) = ingredient.hash() else {
+                return true;
+            };
+            let duplicate_of = kept
+                .iter()
+                .find(|(h, r, _)| h == hash && r == ingredient.relationship());
+            match duplicate_of {
+                Some((_, _, instance_id)) => {
+                    replaced.insert(ingredient.instance_id().to_owned(), instance_id.clone());
+                    false
+                }
+                None => {
+                    kept.push((
+                        hash.to_owned(),
+                        ingredient.relationship().clone(),
+                        ingredient.instance_id().to_owned(),
+                    ));
+                    true
+                }
+            }
+        });
+
+        if replaced.is_empty() {
+            return Ok(self);
+        }
+
+        for definition in self.definition.assertions.iter_mut() {
+            if !definition.label.starts_with(Actions::LABEL) {
+                continue;
+            }
+            let mut actions: Actions = definition.to_assertion()?;
+            for action in actions.actions_mut() {
+                if let Some(instance_id) = action.instance_id().and_then(|id| replaced.get(id)) {
+                    *action = action.clone().set_instance_id(instance_id);
+                }
+            }
+            definition.data = match definition.data {
+                AssertionData::Json(_) => AssertionData::Json(serde_json::to_value(&actions)?),
+                AssertionData::Cbor(_) => {
+                    AssertionData::Cbor(serde_cbor::value::to_value(&actions)?)
+                }
+            };
+        }
+        Ok(self)
+    }
+
     /// Adds a resource to the manifest.
     /// The id should match up with an identifier in the manifest.
     /// # Arguments
@@ -624,7 +1053,11 @@ impl Builder {
         definition.format.clone_into(&mut claim.format);
         definition.instance_id.clone_into(&mut claim.instance_id);
 
-        if let Some(thumb_ref) = definition.thumbnail.as_ref() {
+        if let Some(thumb_ref) = definition
+            .thumbnail
+            .as_ref()
+            .filter(|_| !self.no_thumbnails)
+        {
             // Setting the format to "none" will ensure that no claim thumbnail is added
             if thumb_ref.format != "none" {
                 //let data = self.resources.get(&thumb_ref.identifier)?;
@@ -646,6 +1079,7 @@ impl Builder {
                 &mut claim,
                 definition.redactions.clone(),
                 Some(&self.resources),
+                !self.no_thumbnails,
             )?;
             ingredient_map.insert(ingredient.instance_id().to_string(), uri);
         }
@@ -755,16 +1189,23 @@ impl Builder {
                     let exif: Exif = manifest_assertion.to_assertion()?;
                     claim.add_assertion_with_salt(&exif, &salt)
                 }
-                _ => match &manifest_assertion.data {
-                    AssertionData::Json(value) => claim.add_assertion_with_salt(
-                        &User::new(&manifest_assertion.label, &serde_json::to_string(&value)?),
-                        &salt,
-                    ),
-                    AssertionData::Cbor(value) => claim.add_assertion_with_salt(
-                        &UserCbor::new(&manifest_assertion.label, serde_cbor::to_vec(value)?),
-                        &salt,
-                    ),
-                },
+                _ => {
+                    // store the assertion using the requested encoding
+                    let label = &manifest_assertion.label;
+                    if manifest_assertion.is_json()? {
+                        let json = match &manifest_assertion.data {
+                            AssertionData::Json(value) => serde_json::to_string(value)?,
+                            AssertionData::Cbor(value) => serde_json::to_string(value)?,
+                        };
+                        claim.add_assertion_with_salt(&User::new(label, &json), &salt)
+                    } else {
+                        let cbor = match &manifest_assertion.data {
+                            AssertionData::Json(value) => serde_cbor::to_vec(value)?,
+                            AssertionData::Cbor(value) => serde_cbor::to_vec(value)?,
+                        };
+                        claim.add_assertion_with_salt(&UserCbor::new(label, cbor), &salt)
+                    }
+                }
             }?;
         }
 
@@ -776,6 +1217,19 @@ impl Builder {
         let claim = self.to_claim()?;
         // commit the claim
         let mut store = Store::new();
+        if let Some(size) = self.padded_size {
+            store.set_padded_size(size);
+        }
+        store.set_compress_manifest(self.compress_manifest);
+        if let Some((label, uuid)) = &self.manifest_store_box {
+            store.set_manifest_store_box(label, uuid);
+        }
+        if let Some(time_provider) = &self.time_provider {
+            store.set_signing_time(time_provider.signing_time());
+        }
+        if let Some(metrics) = &self.metrics {
+            store.set_metrics_observer(metrics.clone());
+        }
         let _provenance = store.commit_claim(claim)?;
         Ok(store)
     }
@@ -787,7 +1241,7 @@ impl Builder {
     {
         // check settings to see if we should auto generate a thumbnail
         let auto_thumbnail = crate::settings::get_settings_value::<bool>("builder.auto_thumbnail")?;
-        if self.definition.thumbnail.is_none() && auto_thumbnail {
+        if self.definition.thumbnail.is_none() && auto_thumbnail && !self.no_thumbnails {
             stream.rewind()?;
             if let Ok((format, image)) =
                 crate::utils::thumbnail::make_thumbnail_from_stream(format, stream)
@@ -804,6 +1258,88 @@ impl Builder {
         Ok(self)
     }
 
+    // Returns a copy of the builder with its thumbnails downscaled, then left out, until the
+    // manifest store for `source` fits in `max_size` bytes, so this builder can be reused as is.
+    // Only the manifest store is measured, with a placeholder of `reserve_size` bytes that the
+    // signature is later written over.
+    fn fit_manifest_size<R>(
+        &self,
+        format: &str,
+        source: &mut R,
+        reserve_size: usize,
+        max_size: usize,
+    ) -> Result<Builder>
+    where
+        R: Read + Seek + Send,
+    {
+        let mut fitted = self.clone();
+        loop {
+            let size = fitted
+                .to_store()?
+                .manifest_size(format, source, reserve_size)?;
+            source.rewind()?;
+
+            if size <= max_size {
+                return Ok(fitted);
+            }
+            if !fitted.shrink_thumbnails()? {
+                return Err(Error::ManifestTooLarge { size, max_size });
+            }
+        }
+    }
+
+    // Replaces the claim and ingredient thumbnails with downscaled versions, or leaves them
+    // all out once none can be made smaller. Returns false if there are no thumbnails left.
+    fn shrink_thumbnails(&mut self) -> Result<bool> {
+        if self.no_thumbnails {
+            return Ok(false);
+        }
+
+        let mut shrunk = false;
+        if let Some(downscaler) = self.thumbnail_downscaler.clone() {
+            let smaller = |format: &str, data: &[u8]| {
+                downscaler
+                    .downscale(format, data)
+                    .filter(|smaller| smaller.len() < data.len())
+            };
+
+            if let Some(thumb_ref) = self
+                .definition
+                .thumbnail
+                .clone()
+                .filter(|thumb_ref| thumb_ref.format != "none")
+            {
+                let mut data = Vec::new();
+                self.resources.open(&thumb_ref)?.read_to_end(&mut data)?;
+                if let Some(image) = smaller(&thumb_ref.format, &data) {
+                    // stored like a generated thumbnail, leaving the original resource as is
+                    self.resources
+                        .add(self.definition.instance_id.clone(), image)?;
+                    self.definition.thumbnail = Some(ResourceRef::new(
+                        thumb_ref.format,
+                        self.definition.instance_id.clone(),
+                    ));
+                    shrunk = true;
+                }
+            }
+
+            for ingredient in self.definition.ingredients.iter_mut() {
+                let thumbnail = ingredient.thumbnail().and_then(|(format, data)| {
+                    smaller(format, &data).map(|image| (format.to_owned(), image))
+                });
+                if let Some((format, image)) = thumbnail {
+                    ingredient.set_thumbnail(format, image)?;
+                    shrunk = true;
+                }
+            }
+        }
+
+        if !shrunk {
+            self.no_thumbnails = true;
+        }
+        Ok(true)
+    }
+
     // Find an assertion in the manifest.
     pub(crate) fn find_assertion<T: DeserializeOwned>(&self, label: &str) -> Result<T> {
         if let Some(manifest_assertion) =
@@ -953,8 +1489,13 @@ impl Builder {
         #[cfg(feature = "add_thumbnails")]
         self.maybe_add_thumbnail(&format, source)?;
 
-        // convert the manifest to a store
-        let mut store = self.to_store()?;
+        // convert the manifest to a store, with smaller thumbnails if it must fit a maximum size
+        let mut store = match self.max_manifest_bytes {
+            Some(max_size) => self
+                .fit_manifest_size(&format, source, signer.reserve_size(), max_size)?
+                .to_store()?,
+            None => self.to_store()?,
+        };
 
         // sign and write our store to to the output image file
         if _sync {
@@ -966,40 +1507,153 @@ impl Builder {
         }
     }
 
-    #[cfg(feature = "file_io")]
-    /// Sign a file using a supplied signer.
+    /// Sign a manifest bound to an asset without modifying the asset.
+    ///
+    /// Nothing is written to the asset. The manifest store is returned for out-of-band storage
+    /// together with its binding, a data hash over all of `source`, so it can later be validated
+    /// against the same unmodified bytes with [`Reader::from_manifest_data_and_stream`].
     /// # Arguments
-    /// * `source` - The path to the file to read from.
-    /// * `dest` - The path to the file to write to (this must not already exist).
     /// * `signer` - The signer to use.
+    /// * `format` - The format of the stream.
+    /// * `source` - The asset the manifest is bound to.
     /// # Returns
-    /// * The bytes of c2pa_manifest that was created.
+    /// * The signed manifest store and its binding.
     /// # Errors
-    /// * If the manifest cannot be signed.
-    pub fn sign_file<S, D>(&mut self, signer: &dyn Signer, source: S, dest: D) -> Result<Vec<u8>>
+    /// * If the manifest definition has a hash binding assertion or cannot be signed.
+    #[async_generic(async_signature(
+        &mut self,
+        signer: &dyn AsyncSigner,
+        format: &str,
+        source: &mut R,
+    ))]
+    pub fn sign_detached<R>(
+        &mut self,
+        signer: &dyn Signer,
+        format: &str,
+        source: &mut R,
+    ) -> Result<DetachedManifest>
     where
-        S: AsRef<std::path::Path>,
-        D: AsRef<std::path::Path>,
+        R: Read + Seek + Send,
     {
-        let source = source.as_ref();
-        let dest = dest.as_ref();
-        // formats must match but allow extensions to be slightly different (i.e. .jpeg vs .jpg)s
-        let format = crate::format_from_path(source).ok_or(crate::Error::UnsupportedType)?;
-        let format_dest = crate::format_from_path(dest).ok_or(crate::Error::UnsupportedType)?;
-        if format != format_dest {
-            return Err(crate::Error::BadParam(
-                "Source and destination file formats must match".to_string(),
-            ));
+        let format = format_to_mime(format);
+        self.definition.format.clone_from(&format);
+        self.definition.instance_id = format!("xmp:iid:{}", Uuid::new_v4());
+
+        #[cfg(feature = "file_io")]
+        if let Some(base_path) = &self.base_path {
+            self.resources.set_base_path(base_path);
         }
-        let mut source = std::fs::File::open(source)?;
-        if !dest.exists() {
-            // ensure the path to the file exists
-            if let Some(output_dir) = dest.parent() {
-                std::fs::create_dir_all(output_dir)?;
-            }
+
+        // generate thumbnail if we don't already have one
+        #[cfg(feature = "add_thumbnails")]
+        self.maybe_add_thumbnail(&format, source)?;
+
+        // the manifest store is never embedded in the asset
+        let no_embed = std::mem::replace(&mut self.no_embed, true);
+        let store = self.to_store();
+        self.no_embed = no_embed;
+        let mut store = store?;
+
+        let (manifest, data_hash) = if _sync {
+            store.get_detached_manifest(source, signer)
         } else {
-            // if the file exists, we need to remove it to avoid appending to it
-            return Err(crate::Error::BadParam(
+            store.get_detached_manifest_async(source, signer).await
+        }?;
+        let len = source.seek(std::io::SeekFrom::End(0))?;
+
+        Ok(DetachedManifest {
+            manifest,
+            alg: data_hash.alg.unwrap_or_default(),
+            hash: data_hash.hash,
+            ranges: vec![HashRange::new(0, len as usize)],
+        })
+    }
+
+    /// Signs a manifest bound to an asset and writes it to a signed manifest bundle together
+    /// with the builder's resources.
+    ///
+    /// The bundle is a zip archive for transferring a manifest store with resources too large
+    /// or too remote to embed, such as full size renditions or cloud data. Like
+    /// [`Builder::sign_detached`], the asset is not modified. The bundle holds:
+    /// * `version.txt` - the version of the bundle layout.
+    /// * `manifest.c2pa` - the signed manifest store in JUMBF format.
+    /// * `resources/<id>` - one file per resource added to the builder. The resources are not
+    ///   covered by the manifest's signature.
+    ///
+    /// Read it back with [`Reader::from_archive`].
+    /// # Arguments
+    /// * `signer` - The signer to use.
+    /// * `format` - The format of the stream.
+    /// * `source` - The asset the manifest is bound to.
+    /// * `archive` - The stream to write the bundle into.
+    /// # Returns
+    /// * The bytes of the signed manifest store.
+    /// # Errors
+    /// * If the manifest cannot be signed or the bundle cannot be written.
+    pub fn sign_to_archive<R>(
+        &mut self,
+        signer: &dyn Signer,
+        format: &str,
+        source: &mut R,
+        archive: impl Write + Seek,
+    ) -> Result<Vec<u8>>
+    where
+        R: Read + Seek + Send,
+    {
+        let detached = self.sign_detached(signer, format, source)?;
+
+        let mut zip = ZipWriter::new(archive);
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("version.txt", options)
+            .map_err(|e| Error::OtherError(Box::new(e)))?;
+        zip.write_all(BUNDLE_VERSION.as_bytes())?;
+        zip.start_file(BUNDLE_MANIFEST, options)
+            .map_err(|e| Error::OtherError(Box::new(e)))?;
+        zip.write_all(&detached.manifest)?;
+        for (id, data) in self.resources.resources() {
+            zip.start_file(format!("{BUNDLE_RESOURCES}{id}"), options)
+                .map_err(|e| Error::OtherError(Box::new(e)))?;
+            zip.write_all(data)?;
+        }
+        zip.finish().map_err(|e| Error::OtherError(Box::new(e)))?;
+
+        Ok(detached.manifest)
+    }
+
+    #[cfg(feature = "file_io")]
+    /// Sign a file using a supplied signer.
+    /// # Arguments
+    /// * `source` - The path to the file to read from.
+    /// * `dest` - The path to the file to write to (this must not already exist).
+    /// * `signer` - The signer to use.
+    /// # Returns
+    /// * The bytes of c2pa_manifest that was created.
+    /// # Errors
+    /// * If the manifest cannot be signed.
+    pub fn sign_file<S, D>(&mut self, signer: &dyn Signer, source: S, dest: D) -> Result<Vec<u8>>
+    where
+        S: AsRef<std::path::Path>,
+        D: AsRef<std::path::Path>,
+    {
+        let source = source.as_ref();
+        let dest = dest.as_ref();
+        // formats must match but allow extensions to be slightly different (i.e. .jpeg vs .jpg)s
+        let format = crate::format_from_path(source).ok_or(crate::Error::UnsupportedType)?;
+        let format_dest = crate::format_from_path(dest).ok_or(crate::Error::UnsupportedType)?;
+        if format != format_dest {
+            return Err(crate::Error::BadParam(
+                "Source and destination file formats must match".to_string(),
+            ));
+        }
+        let mut source = std::fs::File::open(source)?;
+        if !dest.exists() {
+            // ensure the path to the file exists
+            if let Some(output_dir) = dest.parent() {
+                std::fs::create_dir_all(output_dir)?;
+            }
+        } else {
+            // if the file exists, we need to remove it to avoid appending to it
+            return Err(crate::Error::BadParam(
                 "Destination file already exists".to_string(),
             ));
         };
@@ -1019,6 +1673,445 @@ impl Builder {
     }
 }
 
+/// Sign an in-memory asset using a [`Builder`] and a supplied signer.
+///
+/// This is a convenience wrapper around [`Builder::sign`] for assets that
+/// are already held in memory, such as uploads received by a web backend.
+/// # Arguments
+/// * `format` - The format (mime type or extension) of the asset.
+/// * `data` - The bytes of the asset to sign.
+/// * `builder` - The [`Builder`] describing the manifest to embed.
+/// * `signer` - The signer to use.
+/// # Returns
+/// * The bytes of the signed asset with the manifest embedded.
+/// # Errors
+/// * Returns [`Error::UnsupportedType`] if no handler can embed a manifest in `format`.
+/// * Returns an [`Error`] if the manifest cannot be signed.
+/// # Example
+/// ```no_run
+/// # use c2pa::{create_signer, Builder, Result, SigningAlg};
+/// # fn main() -> Result<()> {
+/// let signer = create_signer::from_files(
+///     "tests/fixtures/certs/es256.pub",
+///     "tests/fixtures/certs/es256.pem",
+///     SigningAlg::Es256,
+///     None,
+/// )?;
+/// let image = std::fs::read("tests/fixtures/C.jpg")?;
+/// let mut builder = Builder::from_json(r#"{"title": "Signed Image"}"#)?;
+/// let signed = c2pa::sign_bytes("image/jpeg", &image, &mut builder, signer.as_ref())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn sign_bytes(
+    format: &str,
+    data: &[u8],
+    builder: &mut Builder,
+    signer: &dyn Signer,
+) -> Result<Vec<u8>> {
+    // make sure we can embed into this format before doing any signing work
+    crate::jumbf_io::get_caiwriter_handler(&format_to_mime(format))
+        .ok_or(Error::UnsupportedType)?;
+
+    let mut source = std::io::Cursor::new(data);
+    let mut dest = std::io::Cursor::new(Vec::new());
+    builder.sign(signer, format, &mut source, &mut dest)?;
+
+    Ok(dest.into_inner())
+}
+
+/// Re-sign an asset whose hard binding was broken by an edit made after it was signed.
+///
+/// Tools that make a benign change to a signed asset, such as adding a bookmark to a PDF, can
+/// use this to keep its provenance. If the active manifest's hard binding no longer matches the
+/// asset (see [`Reader::hard_binding_broken`]), a new
+/// manifest is signed that:
+/// * has the edited asset as its parent ingredient, carrying the previous manifests and their
+///   validation status,
+/// * records `edit` in a `c2pa.actions` assertion,
+/// * binds to the edited asset with a new hard binding.
+///
+/// The previous manifest store is removed from the asset before the new one is embedded.
+/// # Arguments
+/// * `format` - The format (mime type or extension) of the asset.
+/// * `source` - The edited asset.
+/// * `dest` - The stream to write the re-signed asset to.
+/// * `edit` - The action describing the edit, such as `c2pa.edited`.
+/// * `signer` - The signer to use.
+/// # Returns
+/// * The bytes of the c2pa_manifest that was embedded, or `None` if the hard binding is
+///   intact, in which case nothing is written to `dest`.
+/// # Errors
+/// * Returns [`Error::JumbfNotFound`] if the asset has no manifest store.
+/// * Returns an [`Error`] if the manifest cannot be signed.
+pub fn resign_after_edit<R, W>(
+    format: &str,
+    source: &mut R,
+    dest: &mut W,
+    edit: Action,
+    signer: &dyn Signer,
+) -> Result<Option<Vec<u8>>>
+where
+    R: Read + Seek + Send,
+    W: Write + Read + Seek + Send,
+{
+    let format = format_to_mime(format);
+
+    source.rewind()?;
+    let reader = Reader::from_stream(&format, &mut *source)?;
+    if !reader.hard_binding_broken() {
+        return Ok(None);
+    }
+
+    // the edited asset, with its manifest store, is the parent of the new manifest
+    source.rewind()?;
+    let mut parent = Ingredient::from_stream(&format, source)?;
+    parent.set_is_parent();
+    let edit = edit.set_instance_id(parent.instance_id());
+
+    let mut builder = Builder::new();
+    builder.definition.title = reader
+        .active_manifest()
+        .and_then(|manifest| manifest.title())
+        .map(str::to_owned);
+    builder.add_ingredient(parent);
+    builder.add_assertion(Actions::LABEL, &Actions::new().add_action(edit))?;
+
+    let manifest_writer =
+        crate::jumbf_io::get_caiwriter_handler(&format).ok_or(Error::UnsupportedType)?;
+    let mut stripped = std::io::Cursor::new(Vec::new());
+    source.rewind()?;
+    manifest_writer.remove_cai_store_from_stream(source, &mut stripped)?;
+
+    stripped.rewind()?;
+    builder.sign(signer, &format, &mut stripped, dest).map(Some)
+}
+
+/// Convert an asset's embedded manifest store into a sidecar manifest store.
+///
+/// This supports platforms that strip embedded metadata. The embedded manifest store is
+/// removed from the asset and a new manifest is signed whose hard binding is computed over the
+/// stripped asset. The new manifest records a `c2pa.repackaged` action and has the original
+/// asset, with its manifests and their validation status, as its parent ingredient.
+/// # Arguments
+/// * `format` - The format (mime type or extension) of the asset.
+/// * `source` - The asset with an embedded manifest store.
+/// * `dest` - The stream to write the asset without a manifest store to.
+/// * `signer` - The signer to use.
+/// # Returns
+/// * The bytes of the sidecar manifest store (`.c2pa`) for the asset written to `dest`.
+/// # Errors
+/// * Returns [`Error::UnsupportedType`] if no handler can write manifests for `format`.
+/// * Returns [`Error::JumbfNotFound`] if the asset has no embedded manifest store.
+/// * Returns an [`Error`] if the manifest cannot be signed.
+pub fn convert_to_sidecar<R, W>(
+    format: &str,
+    source: &mut R,
+    dest: &mut W,
+    signer: &dyn Signer,
+) -> Result<Vec<u8>>
+where
+    R: Read + Seek + Send,
+    W: Write + Read + Seek + Send,
+{
+    let format = format_to_mime(format);
+    let manifest_writer =
+        crate::jumbf_io::get_caiwriter_handler(&format).ok_or(Error::UnsupportedType)?;
+
+    source.rewind()?;
+    let reader = Reader::from_stream(&format, &mut *source)?;
+
+    source.rewind()?;
+    let mut parent = Ingredient::from_stream(&format, source)?;
+    parent.set_is_parent();
+
+    let mut stripped = std::io::Cursor::new(Vec::new());
+    source.rewind()?;
+    manifest_writer.remove_cai_store_from_stream(source, &mut stripped)?;
+
+    stripped.rewind()?;
+    sign_repackaged(&format, &reader, parent, &mut stripped, dest, signer, true)
+}
+
+/// Embed a sidecar manifest store into the asset it was made for.
+///
+/// This is the inverse of [`convert_to_sidecar`]. The sidecar is validated against `source`,
+/// then a new manifest is embedded in the asset with a hard binding computed for the embedded
+/// case. The new manifest records a `c2pa.repackaged` action and has the asset, with the
+/// sidecar's manifests and their validation status, as its parent ingredient.
+/// # Arguments
+/// * `format` - The format (mime type or extension) of the asset.
+/// * `source` - The asset without an embedded manifest store.
+/// * `sidecar` - The bytes of the sidecar manifest store (`.c2pa`) for `source`.
+/// * `dest` - The stream to write the asset with the embedded manifest store to.
+/// * `signer` - The signer to use.
+/// # Returns
+/// * The bytes of the c2pa_manifest that was embedded.
+/// # Errors
+/// * Returns [`Error::UnsupportedType`] if no handler can write manifests for `format`.
+/// * Returns an [`Error`] if the sidecar cannot be read or the manifest cannot be signed.
+pub fn embed_from_sidecar<R, W>(
+    format: &str,
+    source: &mut R,
+    sidecar: &[u8],
+    dest: &mut W,
+    signer: &dyn Signer,
+) -> Result<Vec<u8>>
+where
+    R: Read + Seek + Send,
+    W: Write + Read + Seek + Send,
+{
+    let format = format_to_mime(format);
+    crate::jumbf_io::get_caiwriter_handler(&format).ok_or(Error::UnsupportedType)?;
+
+    source.rewind()?;
+    let reader = Reader::from_manifest_data_and_stream(sidecar, &format, &mut *source)?;
+
+    // the sidecar's manifests are carried by the parent ingredient
+    source.rewind()?;
+    let mut parent = Ingredient::from_stream(&format, source)?;
+    parent.set_is_parent();
+    parent.set_manifest_data(sidecar.to_vec())?;
+    if let Some(label) = reader.active_label() {
+        parent.set_active_manifest(label);
+    }
+    for status in reader.validation_status().unwrap_or_default() {
+        parent.add_validation_status(status.clone());
+    }
+
+    source.rewind()?;
+    sign_repackaged(&format, &reader, parent, source, dest, signer, false)
+}
+
+// Signs a manifest for `asset` recording that it was repackaged from `parent`, either embedded
+// in `dest` or, with `no_embed`, only returned for use as a sidecar.
+fn sign_repackaged<R, W>(
+    format: &str,
+    reader: &Reader,
+    parent: Ingredient,
+    asset: &mut R,
+    dest: &mut W,
+    signer: &dyn Signer,
+    no_embed: bool,
+) -> Result<Vec<u8>>
+where
+    R: Read + Seek + Send,
+    W: Write + Read + Seek + Send,
+{
+    let repackaged = Action::new(c2pa_action::REPACKAGED).set_instance_id(parent.instance_id());
+
+    let mut builder = Builder::new();
+    builder.definition.title = reader
+        .active_manifest()
+        .and_then(|manifest| manifest.title())
+        .map(str::to_owned);
+    builder.set_no_embed(no_embed);
+    builder.add_ingredient(parent);
+    builder.add_assertion(Actions::LABEL, &Actions::new().add_action(repackaged))?;
+
+    builder.sign(signer, format, asset, dest)
+}
+
+/// Sign several in-memory assets in parallel, sharing one [`Builder`] and signer.
+///
+/// Each asset is signed on a worker thread with its own copy of the builder, so the
+/// manifests are independent. The signer is shared by all the workers.
+/// # Arguments
+/// * `assets` - The format (mime type or extension) and bytes of each asset to sign.
+/// * `builder` - The [`Builder`] describing the manifest to embed in every asset.
+/// * `signer` - The signer to use.
+/// # Returns
+/// * The result of signing each asset, in the order of `assets`, as by [`sign_bytes`].
+/// # Errors
+/// * Returns an [`Error`] if a worker thread panics.
+/// # Example
+/// ```no_run
+/// # use c2pa::{create_signer, Builder, Result, SigningAlg};
+/// # fn main() -> Result<()> {
+/// let signer = create_signer::from_files(
+///     "tests/fixtures/certs/es256.pub",
+///     "tests/fixtures/certs/es256.pem",
+///     SigningAlg::Es256,
+///     None,
+/// )?;
+/// let jpeg = std::fs::read("tests/fixtures/C.jpg")?;
+/// let png = std::fs::read("tests/fixtures/sample1.png")?;
+/// let builder = Builder::from_json(r#"{"title": "Signed Image"}"#)?;
+/// let assets = [("image/jpeg", jpeg.as_slice()), ("image/png", png.as_slice())];
+/// for signed in c2pa::sign_batch(&assets, &builder, signer.as_ref())? {
+///     let _signed = signed?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sign_batch(
+    assets: &[(&str, &[u8])],
+    builder: &Builder,
+    signer: &dyn Signer,
+) -> Result<Vec<Result<Vec<u8>>>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(assets.len());
+    let next = AtomicUsize::new(0);
+
+    let mut signed: Vec<(usize, Result<Vec<u8>>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((format, data)) = assets.get(index) else {
+                            break;
+                        };
+                        // signing changes the builder, so each asset starts from a clone,
+                        // which shares the time provider, metrics and downscaler
+                        let mut builder = builder.clone();
+                        let result = sign_bytes(format, data, &mut builder, signer);
+                        results.push((index, result));
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().map_err(|_| Error::ThreadReceiveError))
+            .collect::<Result<Vec<_>>>()
+    })?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    signed.sort_by_key(|(index, _)| *index);
+    Ok(signed.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Signs a PDF while it is being generated.
+///
+/// The PDF generator writes the document through this adapter, which passes the bytes on to
+/// `dest` and hashes them as they go by. [`PdfStreamSigner::finish`] then appends the manifest
+/// store as an incremental update, so the generated document doesn't have to be buffered by the
+/// generator or hashed a second time. Finishing reads the document back from `dest` to update
+/// its catalog.
+/// # Example
+/// ```no_run
+/// # use std::io::Write;
+/// # use c2pa::{create_signer, Builder, PdfStreamSigner, Result, SigningAlg};
+/// # fn main() -> Result<()> {
+/// let signer = create_signer::from_files(
+///     "tests/fixtures/certs/es256.pub",
+///     "tests/fixtures/certs/es256.pem",
+///     SigningAlg::Es256,
+///     None,
+/// )?;
+/// let mut builder = Builder::from_json(r#"{"title": "Generated Document"}"#)?;
+/// let dest = std::fs::OpenOptions::new()
+///     .read(true)
+///     .write(true)
+///     .create(true)
+///     .truncate(true)
+///     .open("signed.pdf")?;
+/// let mut pdf = PdfStreamSigner::new(dest);
+/// pdf.write_all(&std::fs::read("tests/fixtures/basic.pdf")?)?;
+/// pdf.finish(&mut builder, signer.as_ref())?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "pdf")]
+pub struct PdfStreamSigner<W> {
+    dest: W,
+    hasher: Hasher,
+    len: usize,
+}
+
+#[cfg(feature = "pdf")]
+impl<W> PdfStreamSigner<W>
+where
+    W: Read + Write + Seek + Send,
+{
+    /// Creates an adapter writing a PDF to `dest`, which must be empty.
+    pub fn new(dest: W) -> Self {
+        Self {
+            dest,
+            hasher: Hasher::SHA256(Sha256::new()),
+            len: 0,
+        }
+    }
+
+    /// Embeds a manifest signed with `signer` in the PDF written so far.
+    /// # Arguments
+    /// * `builder` - The [`Builder`] describing the manifest to embed.
+    /// * `signer` - The signer to use.
+    /// # Returns
+    /// * The destination, now holding the signed PDF.
+    /// # Errors
+    /// * Returns [`Error::BadParam`] if `dest` wasn't empty, or the PDF already contains a
+    ///   manifest store.
+    /// * Returns an [`Error`] if the PDF can't be read or the manifest cannot be signed.
+    pub fn finish(self, builder: &mut Builder, signer: &dyn Signer) -> Result<W> {
+        let Self {
+            mut dest,
+            mut hasher,
+            len,
+        } = self;
+
+        dest.flush()?;
+        if stream_len(&mut dest)? != len as u64 {
+            return Err(Error::BadParam(
+                "the destination must be empty when the PDF is started".to_string(),
+            ));
+        }
+
+        let format = "application/pdf";
+        let placeholder = builder.data_hashed_placeholder(signer.reserve_size(), format)?;
+
+        let mut pdf_io = PdfIO::new(format);
+        if let Some(time_provider) = &builder.time_provider {
+            pdf_io.set_signing_time(time_provider.signing_time());
+        }
+        let (mut update, offset) = pdf_io.manifest_update(&mut dest, &placeholder)?;
+
+        // finish hashing with the update, leaving out the manifest store
+        let start = offset - len;
+        let end = start + placeholder.len();
+        hasher.update(&update[..start]);
+        hasher.update(&update[end..]);
+
+        let mut data_hash = DataHash::new("jumbf manifest", "sha256");
+        data_hash.exclusions = Some(vec![HashRange::new(offset, placeholder.len())]);
+        data_hash.set_hash(Hasher::finalize(hasher));
+
+        let manifest = builder.sign_data_hashed_embeddable(signer, &data_hash, format)?;
+        if manifest.len() != placeholder.len() {
+            return Err(Error::JumbfCreationError);
+        }
+        update[start..end].copy_from_slice(&manifest);
+
+        dest.seek(std::io::SeekFrom::End(0))?;
+        dest.write_all(&update)?;
+        Ok(dest)
+    }
+}
+
+#[cfg(feature = "pdf")]
+impl<W: Write> Write for PdfStreamSigner<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.dest.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.len += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.dest.flush()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used)]
@@ -1031,11 +2124,11 @@ mod tests {
 
     use super::*;
     use crate::{
-        assertions::BoxHash,
+        assertions::{AssetMetadata, BoxHash},
         asset_handlers::jpeg_io::JpegIO,
         hash_stream_by_alg,
         utils::test::{temp_signer, write_jpeg_placeholder_stream},
-        Reader,
+        validation_status, Reader,
     };
 
     #[cfg(target_arch = "wasm32")]
@@ -1107,6 +2200,7 @@ mod tests {
     const TEST_IMAGE_CLEAN: &[u8] = include_bytes!("../tests/fixtures/IMG_0003.jpg");
     const TEST_IMAGE: &[u8] = include_bytes!("../tests/fixtures/CA.jpg");
     const TEST_THUMBNAIL: &[u8] = include_bytes!("../tests/fixtures/thumbnail.jpg");
+    const TEST_IMAGE_PNG: &[u8] = include_bytes!("../tests/fixtures/sample1.png");
 
     #[test]
     /// example of creating a builder directly with a [`ManifestDefinition`]
@@ -1265,6 +2359,351 @@ mod tests {
         assert_eq!(test_assertion.answer, 42);
     }
 
+    #[test]
+    fn test_builder_validate_ingredient_source() {
+        let format = "image/jpeg";
+        let mut source = Cursor::new(TEST_IMAGE_CLEAN);
+        let mut dest = Cursor::new(Vec::new());
+
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_ingredient_from_stream(parent_json(), format, &mut Cursor::new(TEST_IMAGE))
+            .unwrap();
+
+        let signer = temp_signer();
+        builder
+            .sign(signer.as_ref(), format, &mut source, &mut dest)
+            .unwrap();
+
+        dest.rewind().unwrap();
+        let reader = Reader::from_stream(format, &mut dest).unwrap();
+        let manifest = reader.active_manifest().unwrap();
+        let instance_id = manifest.ingredients()[0].instance_id();
+
+        // the asset the ingredient came from
+        let statuses = reader
+            .validate_ingredient_source(instance_id, format, Cursor::new(TEST_IMAGE))
+            .unwrap();
+        assert!(statuses.is_empty());
+
+        // an asset with a different manifest
+        let statuses = reader
+            .validate_ingredient_source(
+                instance_id,
+                format,
+                Cursor::new(include_bytes!("../tests/fixtures/C.jpg")),
+            )
+            .unwrap();
+        assert!(statuses
+            .iter()
+            .any(|s| s.code() == validation_status::INGREDIENT_HASHEDURI_MISMATCH));
+
+        // the same manifest with modified image data
+        let statuses = reader
+            .validate_ingredient_source(
+                instance_id,
+                format,
+                Cursor::new(include_bytes!("../tests/fixtures/XCA.jpg")),
+            )
+            .unwrap();
+        assert!(statuses
+            .iter()
+            .any(|s| s.code() == validation_status::ASSERTION_DATAHASH_MISMATCH));
+
+        assert!(matches!(
+            reader.validate_ingredient_source("unknown", format, Cursor::new(TEST_IMAGE)),
+            Err(Error::IngredientNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_builder_validate_ingredient_source_without_manifest() {
+        let format = "image/jpeg";
+        let mut dest = Cursor::new(Vec::new());
+
+        // IMG_0003.jpg has no manifest, but has an XMP instance ID
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_ingredient_from_stream(
+                r#"{"title": "IMG_0003.jpg"}"#,
+                format,
+                &mut Cursor::new(TEST_IMAGE_CLEAN),
+            )
+            .unwrap();
+
+        let signer = temp_signer();
+        builder
+            .sign(
+                signer.as_ref(),
+                format,
+                &mut Cursor::new(TEST_IMAGE_CLEAN),
+                &mut dest,
+            )
+            .unwrap();
+
+        dest.rewind().unwrap();
+        let reader = Reader::from_stream(format, &mut dest).unwrap();
+        let manifest = reader.active_manifest().unwrap();
+        let instance_id = manifest.ingredients()[0].instance_id();
+        assert!(manifest.ingredients()[0].active_manifest().is_none());
+
+        let statuses = reader
+            .validate_ingredient_source(instance_id, format, Cursor::new(TEST_IMAGE_CLEAN))
+            .unwrap();
+        assert!(statuses.is_empty());
+
+        // another asset without a manifest, and one with a manifest
+        for other in [
+            &include_bytes!("../tests/fixtures/earth_apollo17.jpg")[..],
+            TEST_IMAGE,
+        ] {
+            let statuses = reader
+                .validate_ingredient_source(instance_id, format, Cursor::new(other))
+                .unwrap();
+            assert!(statuses
+                .iter()
+                .any(|s| s.code() == validation_status::STATUS_INGREDIENT_SOURCE_MISMATCH));
+        }
+    }
+
+    #[test]
+    fn test_builder_json_assertion_round_trip() {
+        #[derive(Serialize, Deserialize)]
+        struct TestAssertion {
+            answer: usize,
+        }
+        const LABEL: &str = "org.test.json_assertion";
+
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_assertion_json(LABEL, &TestAssertion { answer: 42 })
+            .unwrap();
+
+        // the encoding survives a round trip through an archive
+        let mut zipped = Cursor::new(Vec::new());
+        builder.to_archive(&mut zipped).unwrap();
+        zipped.rewind().unwrap();
+        let mut builder = Builder::from_archive(&mut zipped).unwrap();
+        let definition = builder
+            .definition
+            .assertions
+            .iter()
+            .find(|a| a.label == LABEL)
+            .unwrap();
+        assert_eq!(definition.kind(), Some(&ManifestAssertionKind::Json));
+
+        let signer = temp_signer();
+        let mut dest = Cursor::new(Vec::new());
+        builder
+            .sign(
+                signer.as_ref(),
+                "image/jpeg",
+                &mut Cursor::new(TEST_IMAGE_CLEAN),
+                &mut dest,
+            )
+            .unwrap();
+
+        dest.rewind().unwrap();
+        let reader = Reader::from_stream("image/jpeg", &mut dest).unwrap();
+        assert!(reader.validation_status().is_none());
+
+        let manifest_assertion = reader
+            .active_manifest()
+            .unwrap()
+            .assertions()
+            .iter()
+            .find(|a| a.label() == LABEL)
+            .unwrap();
+        assert_eq!(manifest_assertion.kind(), &ManifestAssertionKind::Json);
+        let test_assertion: TestAssertion = manifest_assertion.to_assertion().unwrap();
+        assert_eq!(test_assertion.answer, 42);
+
+        // the digest in the claim matches the hash of the raw JSON assertion box
+        let claim = reader.manifest_store.store().provenance_claim().unwrap();
+        let claim_assertion = claim.get_claim_assertion(LABEL, 0).unwrap();
+        assert_eq!(
+            claim_assertion.assertion().content_type(),
+            "application/json"
+        );
+
+        let hash = Claim::calc_assertion_box_hash(
+            &claim_assertion.label(),
+            claim_assertion.assertion(),
+            claim_assertion.salt().clone(),
+            claim.alg(),
+        )
+        .unwrap();
+        let hashed_uri = claim.assertion_hashed_uri_from_label(LABEL).unwrap();
+        assert_eq!(hashed_uri.hash(), hash);
+    }
+
+    #[test]
+    fn test_builder_asset_metadata_round_trip() {
+        let metadata = AssetMetadata::new()
+            .add_context("photoshop", "http://ns.adobe.com/photoshop/1.0/")
+            .set_title("Sunset over the bay")
+            .unwrap()
+            .set_keywords(vec!["sunset", "bay"])
+            .unwrap()
+            .set_rights("CC-BY-4.0")
+            .unwrap()
+            .insert("photoshop:City", "Lisbon")
+            .unwrap();
+
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_assertion_json(AssetMetadata::LABEL, &metadata)
+            .unwrap();
+
+        let signer = temp_signer();
+        let mut dest = Cursor::new(Vec::new());
+        builder
+            .sign(
+                signer.as_ref(),
+                "image/jpeg",
+                &mut Cursor::new(TEST_IMAGE_CLEAN),
+                &mut dest,
+            )
+            .unwrap();
+
+        dest.rewind().unwrap();
+        let reader = Reader::from_stream("image/jpeg", &mut dest).unwrap();
+        assert!(reader.validation_status().is_none());
+
+        let result = reader.asset_metadata().unwrap();
+        assert_eq!(result, metadata);
+        assert_eq!(result.title().as_deref(), Some("Sunset over the bay"));
+        assert_eq!(
+            result.keywords(),
+            Some(vec!["sunset".to_owned(), "bay".to_owned()])
+        );
+        assert_eq!(result.rights().as_deref(), Some("CC-BY-4.0"));
+        assert_eq!(result.get::<String>("photoshop:City").unwrap(), "Lisbon");
+    }
+
+    #[test]
+    fn test_builder_thumbnail_format_detection() {
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder.definition.thumbnail = None;
+        builder
+            .set_thumbnail_from_stream(&mut Cursor::new(TEST_IMAGE_PNG))
+            .unwrap();
+        assert_eq!(
+            builder.definition.thumbnail.as_ref().unwrap().format,
+            "image/png"
+        );
+
+        let claim = builder.to_claim().unwrap();
+        let thumbnail = claim
+            .get_assertion(labels::PNG_CLAIM_THUMBNAIL, 0)
+            .expect("png thumbnail assertion");
+        assert_eq!(thumbnail.content_type(), "image/png");
+
+        assert!(matches!(
+            builder.set_thumbnail_from_stream(&mut Cursor::new(b"not an image")),
+            Err(Error::UnsupportedType)
+        ));
+    }
+
+    #[test]
+    fn test_sign_bytes_jpeg() {
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        let signer = temp_signer();
+
+        let signed = sign_bytes("image/jpeg", TEST_IMAGE, &mut builder, signer.as_ref()).unwrap();
+
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(signed)).unwrap();
+        assert!(reader.validation_status().is_none());
+        assert_eq!(
+            reader.active_manifest().unwrap().title().unwrap(),
+            "Test_Manifest"
+        );
+    }
+
+    #[test]
+    fn test_sign_bytes_png() {
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        let signer = temp_signer();
+
+        let signed = sign_bytes("png", TEST_IMAGE_PNG, &mut builder, signer.as_ref()).unwrap();
+
+        let reader = Reader::from_stream("image/png", Cursor::new(signed)).unwrap();
+        assert!(reader.validation_status().is_none());
+        assert_eq!(reader.active_manifest().unwrap().format(), "image/png");
+    }
+
+    #[test]
+    fn test_sign_bytes_unsupported_format() {
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        let signer = temp_signer();
+
+        let result = sign_bytes("text/plain", b"not an image", &mut builder, signer.as_ref());
+        assert!(matches!(result, Err(Error::UnsupportedType)));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_sign_batch() {
+        use std::{
+            sync::atomic::{AtomicUsize, Ordering},
+            time::Duration,
+        };
+
+        use crate::metrics::PipelineStage;
+
+        #[derive(Default)]
+        struct SignCounter(AtomicUsize);
+
+        impl MetricsObserver for SignCounter {
+            fn stage_finished(&self, stage: PipelineStage, _elapsed: Duration) {
+                if stage == PipelineStage::Sign {
+                    self.0.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        // the resources and the observer are not part of the builder's JSON
+        let counter = Arc::new(SignCounter::default());
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_resource("thumbnail.jpg", Cursor::new(TEST_THUMBNAIL))
+            .unwrap();
+        builder.metrics = Some(counter.clone());
+        let signer = temp_signer();
+
+        let assets = [
+            ("image/jpeg", TEST_IMAGE),
+            ("png", TEST_IMAGE_PNG),
+            ("image/jpeg", TEST_IMAGE_CLEAN),
+            ("text/plain", b"not an image".as_slice()),
+            ("image/png", TEST_IMAGE_PNG),
+        ];
+        let results = sign_batch(&assets, &builder, signer.as_ref()).unwrap();
+        assert_eq!(results.len(), assets.len());
+
+        let mut instance_ids = Vec::new();
+        for ((format, _), result) in assets.iter().zip(results) {
+            if *format == "text/plain" {
+                assert!(matches!(result, Err(Error::UnsupportedType)));
+                continue;
+            }
+
+            let reader = Reader::from_stream(format, Cursor::new(result.unwrap())).unwrap();
+            assert!(reader.validation_status().is_none());
+            let manifest = reader.active_manifest().unwrap();
+            assert_eq!(manifest.title().unwrap(), "Test_Manifest");
+            assert_eq!(manifest.format(), format_to_mime(format));
+            assert!(manifest.thumbnail_ref().is_some());
+            instance_ids.push(manifest.instance_id().to_owned());
+        }
+
+        // each asset gets its own manifest
+        instance_ids.sort();
+        instance_ids.dedup();
+        assert_eq!(instance_ids.len(), 4);
+        assert_eq!(counter.0.load(Ordering::Relaxed), 4);
+    }
+
     #[test]
     #[cfg(feature = "file_io")]
     fn test_builder_sign_file() {
@@ -1293,6 +2732,36 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_builder_add_assertions_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("org.test.review.json"),
+            r#"{"notes": "approved"}"#,
+        )
+        .unwrap();
+        let cbor = serde_cbor::to_vec(&serde_json::json!({ "count": 2 })).unwrap();
+        std::fs::write(dir.path().join("org.test.data.cbor"), cbor).unwrap();
+        std::fs::write(dir.path().join("org.test.broken.json"), "{ not json").unwrap();
+        std::fs::write(dir.path().join("README.txt"), "assertions").unwrap();
+
+        let mut builder = Builder::new();
+        builder.add_assertions_from_dir(dir.path()).unwrap();
+
+        let assertions = &builder.definition.assertions;
+        assert_eq!(assertions.len(), 2);
+        assert_eq!(assertions[0].label, "org.test.data");
+        assert!(matches!(assertions[0].data, AssertionData::Cbor(_)));
+        assert_eq!(assertions[1].label, "org.test.review");
+        assert!(matches!(assertions[1].data, AssertionData::Json(_)));
+
+        let review: serde_json::Value = builder.find_assertion("org.test.review").unwrap();
+        assert_eq!(review["notes"], "approved");
+        let data: serde_json::Value = builder.find_assertion("org.test.data").unwrap();
+        assert_eq!(data["count"], 2);
+    }
+
     #[test]
     #[cfg(feature = "file_io")]
     fn test_builder_sign_assets() {
@@ -1556,6 +3025,108 @@ mod tests {
         assert_eq!(_reader.validation_status(), None);
     }
 
+    #[test]
+    fn test_builder_single_parent_relationship() {
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_ingredient_with_relationship(
+                Ingredient::new("A.jpg", "image/jpeg", "xmp.iid:a"),
+                Relationship::ParentOf,
+            )
+            .unwrap();
+        builder
+            .add_ingredient_with_relationship(
+                Ingredient::new("B.jpg", "image/jpeg", "xmp.iid:b"),
+                Relationship::InputTo,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            builder.add_ingredient_with_relationship(
+                Ingredient::new("C.jpg", "image/jpeg", "xmp.iid:c"),
+                Relationship::ParentOf,
+            ),
+            Err(Error::BadParam(_))
+        ));
+        assert_eq!(builder.definition.ingredients.len(), 2);
+    }
+
+    #[test]
+    fn test_builder_dedupe_ingredients() {
+        let ingredient = |instance_id: &str| {
+            let mut ingredient = Ingredient::new("A.jpg", "image/jpeg", instance_id);
+            ingredient.set_hash("same content");
+            ingredient
+        };
+
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder.definition.ingredients.clear();
+        builder.add_ingredient(ingredient("xmp.iid:a"));
+        builder.add_ingredient(ingredient("xmp.iid:b"));
+        builder
+            .add_ingredient_with_relationship(ingredient("xmp.iid:c"), Relationship::ParentOf)
+            .unwrap();
+        builder.definition.assertions.clear();
+        builder
+            .add_assertion(
+                Actions::LABEL,
+                &Actions::new()
+                    .add_action(Action::new(c2pa_action::PLACED).set_instance_id("xmp.iid:b")),
+            )
+            .unwrap();
+
+        builder.dedupe_ingredients().unwrap();
+
+        let instance_ids: Vec<&str> = builder
+            .definition
+            .ingredients
+            .iter()
+            .map(|i| i.instance_id())
+            .collect();
+        assert_eq!(instance_ids, ["xmp.iid:a", "xmp.iid:c"]);
+
+        let actions: Actions = builder.find_assertion(Actions::LABEL).unwrap();
+        assert_eq!(actions.actions()[0].instance_id(), Some("xmp.iid:a"));
+    }
+
+    #[test]
+    fn test_builder_require_trusted_timestamp() {
+        use crate::settings::{reset_default_settings, set_settings_value, tests::PROTECT};
+
+        let _protect = PROTECT.lock().unwrap();
+
+        // the test signer doesn't time-stamp its signatures
+        let mut source = Cursor::new(TEST_IMAGE_CLEAN);
+        let mut dest = Cursor::new(Vec::new());
+        let mut builder = Builder::from_json(&simple_manifest()).unwrap();
+        builder
+            .sign(temp_signer().as_ref(), "image/jpeg", &mut source, &mut dest)
+            .unwrap();
+        let signed = dest.into_inner();
+
+        let has_timestamp_required = |reader: &Reader| {
+            reader.validation_status().is_some_and(|statuses| {
+                statuses
+                    .iter()
+                    .any(|s| s.code() == validation_status::STATUS_TIMESTAMP_REQUIRED)
+            })
+        };
+
+        // the policy is off by default
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(&signed)).unwrap();
+        assert!(reader.validation_status().is_none());
+
+        set_settings_value("verify.require_trusted_timestamp", true).unwrap();
+
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(&signed)).unwrap();
+        let timestamped = Reader::from_stream("image/jpeg", Cursor::new(TEST_IMAGE));
+
+        reset_default_settings().unwrap();
+
+        assert!(has_timestamp_required(&reader));
+        assert!(!has_timestamp_required(&timestamped.unwrap()));
+    }
+
     #[cfg(feature = "file_io")]
     #[test]
     fn test_builder_base_path() {
@@ -1595,4 +3166,285 @@ mod tests {
             "image/jpeg",
         );
     }
+
+    #[test]
+    fn test_builder_manifest_store_box() {
+        use crate::settings::{reset_default_settings, set_settings_value, tests::PROTECT};
+
+        const LABEL: &str = "com.example.private";
+        const UUID: &str = "707276740011001080000000AA000001";
+
+        let _protect = PROTECT.lock().unwrap();
+
+        let mut builder = Builder::from_json(&simple_manifest()).unwrap();
+        assert!(matches!(
+            builder.set_manifest_store_box("", UUID),
+            Err(Error::BadParam(_))
+        ));
+        assert!(matches!(
+            builder.set_manifest_store_box(LABEL, "c2pa"),
+            Err(Error::BadParam(_))
+        ));
+
+        // signing replaces the standard manifest store of the source
+        builder.set_manifest_store_box(LABEL, UUID).unwrap();
+        let mut dest = Cursor::new(Vec::new());
+        builder
+            .sign(
+                temp_signer().as_ref(),
+                "image/jpeg",
+                &mut Cursor::new(TEST_IMAGE),
+                &mut dest,
+            )
+            .unwrap();
+        let signed = dest.into_inner();
+
+        // readers not configured for the private manifest store don't find it
+        assert!(Reader::from_stream("image/jpeg", Cursor::new(&signed)).is_err());
+
+        set_settings_value("core.manifest_store_label", LABEL).unwrap();
+        set_settings_value("core.manifest_store_uuid", UUID).unwrap();
+
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(&signed));
+        let standard = Reader::from_stream("image/jpeg", Cursor::new(TEST_IMAGE));
+
+        reset_default_settings().unwrap();
+
+        let reader = reader.unwrap();
+        assert!(reader.validation_status().is_none());
+        assert_eq!(reader.iter_manifests().count(), 1);
+
+        // standard manifest stores are still located
+        assert!(standard.unwrap().active_manifest().is_some());
+    }
+
+    #[test]
+    fn test_builder_without_thumbnails() {
+        let sign = |no_thumbnails: bool| {
+            let mut source = Cursor::new(TEST_IMAGE);
+            let mut dest = Cursor::new(Vec::new());
+
+            let mut builder = Builder::from_json(&manifest_json()).unwrap();
+            builder
+                .add_ingredient_from_stream(parent_json(), "image/jpeg", &mut source)
+                .unwrap();
+            builder
+                .add_resource("thumbnail.jpg", Cursor::new(TEST_THUMBNAIL))
+                .unwrap();
+            if no_thumbnails {
+                builder.without_thumbnails();
+            }
+
+            let signer = temp_signer();
+            builder
+                .sign(signer.as_ref(), "image/jpeg", &mut source, &mut dest)
+                .unwrap();
+            dest.into_inner()
+        };
+
+        let with_thumbnails = sign(false);
+        let stripped = sign(true);
+        assert!(stripped.len() < with_thumbnails.len());
+
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(stripped)).unwrap();
+        assert!(reader.validation_status().is_none());
+
+        let manifest = reader.active_manifest().unwrap();
+        assert!(manifest.thumbnail_ref().is_none());
+        assert_eq!(manifest.ingredients().len(), 2);
+        assert!(manifest
+            .ingredients()
+            .iter()
+            .all(|i| i.thumbnail_ref().is_none()));
+        assert_eq!(manifest.title().unwrap(), "Test_Manifest");
+    }
+
+    #[test]
+    fn test_builder_max_manifest_bytes() {
+        let sign = |max_size: Option<usize>, downscale: bool| {
+            let mut source = Cursor::new(TEST_IMAGE);
+            let mut dest = Cursor::new(Vec::new());
+
+            let mut builder = Builder::from_json(&manifest_json()).unwrap();
+            builder
+                .add_ingredient_from_stream(parent_json(), "image/jpeg", &mut source)
+                .unwrap();
+            builder
+                .add_resource("thumbnail.jpg", Cursor::new(TEST_THUMBNAIL))
+                .unwrap();
+            if let Some(max_size) = max_size {
+                builder.set_max_manifest_bytes(max_size);
+            }
+            if downscale {
+                // keeps the first half of the image data, enough for a size budget
+                builder.set_thumbnail_downscaler(|_format: &str, thumbnail: &[u8]| {
+                    Some(thumbnail[..thumbnail.len() / 2].to_vec())
+                });
+            }
+
+            let signer = temp_signer();
+            builder
+                .sign(signer.as_ref(), "image/jpeg", &mut source, &mut dest)
+                .map(|manifest| (manifest, dest.into_inner()))
+        };
+
+        let (full, _) = sign(None, false).unwrap();
+        let max_size = full.len() - 1;
+
+        // the claim thumbnail is downscaled to fit
+        let (manifest_bytes, asset) = sign(Some(max_size), true).unwrap();
+        assert!(manifest_bytes.len() <= max_size);
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(asset)).unwrap();
+        assert!(reader.validation_status().is_none());
+        let (_, thumbnail) = reader.active_manifest().unwrap().thumbnail().unwrap();
+        assert_eq!(thumbnail.len(), TEST_THUMBNAIL.len() / 2);
+
+        // without a downscaler, thumbnails are left out
+        let (manifest_bytes, asset) = sign(Some(max_size), false).unwrap();
+        assert!(manifest_bytes.len() <= max_size);
+        let reader = Reader::from_stream("image/jpeg", Cursor::new(asset)).unwrap();
+        assert!(reader.active_manifest().unwrap().thumbnail_ref().is_none());
+
+        // a budget that cannot be met fails
+        assert!(matches!(
+            sign(Some(1000), true),
+            Err(Error::ManifestTooLarge { max_size: 1000, .. })
+        ));
+
+        // the builder keeps its thumbnails for the next asset
+        let mut source = Cursor::new(TEST_IMAGE);
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_ingredient_from_stream(parent_json(), "image/jpeg", &mut source)
+            .unwrap();
+        builder
+            .add_resource("thumbnail.jpg", Cursor::new(TEST_THUMBNAIL))
+            .unwrap();
+        builder.set_max_manifest_bytes(max_size);
+        let signer = temp_signer();
+        builder
+            .sign(
+                signer.as_ref(),
+                "image/jpeg",
+                &mut source,
+                &mut Cursor::new(Vec::new()),
+            )
+            .unwrap();
+        assert!(!builder.no_thumbnails);
+
+        builder.max_manifest_bytes = None;
+        let mut dest = Cursor::new(Vec::new());
+        builder
+            .sign(signer.as_ref(), "image/jpeg", &mut source, &mut dest)
+            .unwrap();
+        dest.rewind().unwrap();
+        let reader = Reader::from_stream("image/jpeg", dest).unwrap();
+        assert!(reader.active_manifest().unwrap().thumbnail_ref().is_some());
+    }
+
+    #[test]
+    fn test_builder_metrics_observer() {
+        use std::{sync::Mutex, time::Duration};
+
+        use crate::metrics::PipelineStage;
+
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<(PipelineStage, bool)>>>);
+
+        impl MetricsObserver for Recorder {
+            fn stage_started(&self, stage: PipelineStage) {
+                self.0.lock().unwrap().push((stage, true));
+            }
+
+            fn stage_finished(&self, stage: PipelineStage, _elapsed: Duration) {
+                self.0.lock().unwrap().push((stage, false));
+            }
+        }
+
+        let recorder = Recorder::default();
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_resource("thumbnail.jpg", Cursor::new(TEST_THUMBNAIL))
+            .unwrap();
+        builder.set_metrics_observer(recorder.clone());
+
+        let signer = temp_signer();
+        let mut dest = Cursor::new(Vec::new());
+        builder
+            .sign(
+                signer.as_ref(),
+                "image/jpeg",
+                &mut Cursor::new(TEST_IMAGE),
+                &mut dest,
+            )
+            .unwrap();
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                (PipelineStage::Hash, true),
+                (PipelineStage::Hash, false),
+                (PipelineStage::Sign, true),
+                (PipelineStage::Network, true),
+                (PipelineStage::Network, false),
+                (PipelineStage::Sign, false),
+                (PipelineStage::Embed, true),
+                (PipelineStage::Embed, false),
+            ]
+        );
+
+        // reading reports parsing and then validation
+        let recorder = Recorder::default();
+        dest.rewind().unwrap();
+        let reader = Reader::from_stream_with_metrics("image/jpeg", &mut dest, &recorder).unwrap();
+        assert!(reader.validation_status().is_none());
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                (PipelineStage::Parse, true),
+                (PipelineStage::Parse, false),
+                (PipelineStage::Validate, true),
+                (PipelineStage::Validate, false),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_pdf_stream_signer() {
+        let source = include_bytes!("../tests/fixtures/basic.pdf");
+
+        let mut builder = Builder::from_json(&manifest_json()).unwrap();
+        builder
+            .add_resource("thumbnail.jpg", Cursor::new(TEST_THUMBNAIL))
+            .unwrap();
+
+        // the generator writes the document in small pieces
+        let mut pdf = PdfStreamSigner::new(Cursor::new(Vec::new()));
+        for chunk in source.chunks(100) {
+            pdf.write_all(chunk).unwrap();
+        }
+        let signer = temp_signer();
+        let signed = pdf
+            .finish(&mut builder, signer.as_ref())
+            .unwrap()
+            .into_inner();
+
+        // the manifest is appended as an incremental update
+        assert!(signed.starts_with(source));
+
+        let reader = Reader::from_stream("application/pdf", Cursor::new(signed)).unwrap();
+        assert!(reader.validation_status().is_none());
+        assert_eq!(
+            reader.active_manifest().unwrap().title().unwrap(),
+            "Test_Manifest"
+        );
+
+        // the destination must start out empty
+        let pdf = PdfStreamSigner::new(Cursor::new(b"%PDF-".to_vec()));
+        assert!(matches!(
+            pdf.finish(&mut builder, signer.as_ref()),
+            Err(Error::BadParam(_))
+        ));
+    }
 }
@@ -25,7 +25,9 @@ use crate::{
 ///
 /// The callback should return a signature for the given data.
 /// The callback should return an error if the data cannot be signed.
-pub type CallbackFunc = dyn Fn(*const (), &[u8]) -> std::result::Result<Vec<u8>, Error>;
+/// The callback may be called from several threads at once.
+pub type CallbackFunc =
+    dyn Fn(*const (), &[u8]) -> std::result::Result<Vec<u8>, Error> + Send + Sync;
 
 /// Defines a signer that uses a callback to sign data.
 ///
@@ -50,6 +52,8 @@ pub struct CallbackSigner {
     pub tsa_url: Option<String>,
 }
 
+// The callback is Send + Sync, and the context is only passed back to it,
+// so it must be safe to use from any thread (see set_context).
 unsafe impl Send for CallbackSigner {}
 
 unsafe impl Sync for CallbackSigner {}
@@ -58,7 +62,7 @@ impl CallbackSigner {
     /// Create a new callback signer.
     pub fn new<F, T>(callback: F, alg: SigningAlg, certs: T) -> Self
     where
-        F: Fn(*const (), &[u8]) -> std::result::Result<Vec<u8>, Error> + 'static,
+        F: Fn(*const (), &[u8]) -> std::result::Result<Vec<u8>, Error> + Send + Sync + 'static,
         T: Into<Vec<u8>>,
     {
         let certs = certs.into();
@@ -82,8 +86,9 @@ impl CallbackSigner {
     /// Set a context value for the signer.
     ///
     /// This can be used to store any necessary state for the callback.
-    /// Safety: The context must be valid for the lifetime of the signer.
-    /// There is no Rust memory management for the context since it may also come from FFI.    
+    /// Safety: The context must be valid for the lifetime of the signer, and safe to use
+    /// from any thread since the signer may be shared between threads.
+    /// There is no Rust memory management for the context since it may also come from FFI.
     pub fn set_context(mut self, context: *const ()) -> Self {
         self.context = context;
         self
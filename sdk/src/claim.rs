@@ -13,7 +13,11 @@
 
 #[cfg(feature = "file_io")]
 use std::path::Path;
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{Read, Seek, SeekFrom},
+};
 
 use async_generic::async_generic;
 use chrono::{DateTime, Utc};
@@ -33,8 +37,8 @@ use crate::{
     },
     asset_io::CAIRead,
     cose_validator::{
-        check_ocsp_status, check_ocsp_status_async, get_signing_info, get_signing_info_async,
-        verify_cose, verify_cose_async,
+        check_ocsp_status, check_ocsp_status_async, get_signature_timestamp, get_signing_info,
+        get_signing_info_async, verify_cose, verify_cose_async,
     },
     error::{Error, Result},
     hashed_uri::HashedUri,
@@ -50,11 +54,15 @@ use crate::{
     },
     jumbf_io::get_assetio_handler,
     salt::{DefaultSalt, SaltGenerator, NO_SALT},
+    settings::get_settings_value,
     status_tracker::{log_item, OneShotStatusTracker, StatusTracker},
+    time_stamp::validate_timestamp,
     trust_handler::TrustHandlerConfig,
     utils::{
         base64,
-        hash_utils::{hash_by_alg, vec_compare, verify_by_alg},
+        hash_utils::{hash_by_alg, vec_compare, verify_by_alg, HashRange},
+        io_utils::stream_len,
+        mime::format_to_mime,
     },
     validation_status,
     validator::ValidationInfo,
@@ -240,6 +248,9 @@ pub struct Claim {
     signature: String,              // link to signature box
     assertions: Vec<C2PAAssertion>, // list of assertion hashed URIs
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gathered_assertions: Option<Vec<C2PAAssertion>>, // list of gathered assertion hashed URIs
+
     // original JSON bytes of claim; only present when reading from asset
     #[serde(skip_deserializing, skip_serializing)]
     original_bytes: Option<Vec<u8>>,
@@ -337,6 +348,7 @@ impl Claim {
             assertion_store: Vec::new(),
             vc_store: Vec::new(),
             assertions: Vec::new(),
+            gathered_assertions: None,
             original_bytes: None,
             original_box_order: None,
             redacted_assertions: None,
@@ -372,6 +384,7 @@ impl Claim {
             assertion_store: Vec::new(),
             vc_store: Vec::new(),
             assertions: Vec::new(),
+            gathered_assertions: None,
             original_bytes: None,
             original_box_order: None,
             redacted_assertions: None,
@@ -922,6 +935,7 @@ impl Claim {
         let Some(f) = self
             .assertions
             .iter_mut()
+            .chain(self.gathered_assertions.iter_mut().flatten())
             .find(|f| f.url().contains(&target_label) && vec_compare(&f.hash(), &original_hash))
         else {
             return Err(Error::NotFound);
@@ -1082,6 +1096,8 @@ impl Claim {
         // check certificate revocation
         check_ocsp_status_async(&sig, &data, th, validation_log).await?;
 
+        Claim::verify_timestamp_required(claim, &sig, &data, th, validation_log)?;
+
         let verified =
             verify_cose_async(sig, data, additional_bytes, cert_check, th, validation_log).await;
 
@@ -1128,11 +1144,52 @@ impl Claim {
         // check certificate revocation
         check_ocsp_status(sig, data, th, validation_log)?;
 
+        Claim::verify_timestamp_required(claim, sig, data, th, validation_log)?;
+
         let verified = verify_cose(sig, data, &additional_bytes, cert_check, th, validation_log);
 
         Claim::verify_internal(claim, asset_data, is_provenance, verified, validation_log)
     }
 
+    /// Enforces the `verify.require_trusted_timestamp` setting for a claim signature.
+    ///
+    /// The signature must carry an RFC 3161 time-stamp that matches it and, when trust
+    /// verification is enabled, whose authority is on the trust list. Otherwise
+    /// [`STATUS_TIMESTAMP_REQUIRED`](validation_status::STATUS_TIMESTAMP_REQUIRED) is logged.
+    fn verify_timestamp_required(
+        claim: &Claim,
+        sig: &[u8],
+        data: &[u8],
+        th: &dyn TrustHandlerConfig,
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<()> {
+        if !get_settings_value::<bool>("verify.require_trusted_timestamp").unwrap_or(false) {
+            return Ok(());
+        }
+        let verify_trust = get_settings_value::<bool>("verify.verify_trust").unwrap_or(false);
+
+        let trusted = match get_signature_timestamp(sig, data) {
+            Ok(Some((ts, ts_data))) => match validate_timestamp(&ts, &ts_data, th).1 {
+                validation_status::TIMESTAMP_TRUSTED => true,
+                validation_status::TIMESTAMP_UNTRUSTED => !verify_trust,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if !trusted {
+            let log_item = log_item!(
+                claim.signature_uri(),
+                "claim signature has no trusted time-stamp",
+                "verify_timestamp_required"
+            )
+            .error(Error::CoseTimeStampRequired)
+            .validation_status(validation_status::STATUS_TIMESTAMP_REQUIRED);
+            validation_log.log(log_item, Some(Error::CoseTimeStampRequired))?;
+        }
+        Ok(())
+    }
+
     /// Get the signing certificate chain as PEM bytes
     pub fn get_cert_chain(&self) -> Result<Vec<u8>> {
         let sig = self.signature_val();
@@ -1144,6 +1201,45 @@ impl Claim {
         Ok(vi.cert_chain)
     }
 
+    // Flags assertions listed twice under the same label, and extra instances of the
+    // assertions a claim may only hold once, whatever their version or format. These are
+    // informational, as `add_assertion` and earlier versions of the SDK add such instances.
+    fn check_duplicate_assertions(claim: &Claim, validation_log: &mut impl StatusTracker) {
+        const UNIQUE_ASSERTIONS: [&str; 2] = [labels::ACTIONS, labels::CLAIM_THUMBNAIL];
+
+        let mut seen: Vec<(String, usize)> = Vec::new();
+        let mut unique_seen: Vec<&str> = Vec::new();
+        for assertion in claim.all_assertions() {
+            let link = Claim::assertion_label_from_link(&assertion.url());
+
+            let unique = UNIQUE_ASSERTIONS.into_iter().find(|unique| {
+                link.0
+                    .strip_prefix(unique)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            });
+            let duplicate = match unique {
+                _ if seen.contains(&link) => true,
+                Some(unique) if unique_seen.contains(&unique) => true,
+                Some(unique) => {
+                    unique_seen.push(unique);
+                    false
+                }
+                None => false,
+            };
+            seen.push(link);
+
+            if duplicate {
+                let log_item = log_item!(
+                    assertion.url(),
+                    format!("assertion not allowed more than once: {}", assertion.url()),
+                    "verify_internal"
+                )
+                .validation_status(validation_status::STATUS_ASSERTION_DUPLICATE);
+                validation_log.log_silent(log_item);
+            }
+        }
+    }
+
     fn verify_internal(
         claim: &Claim,
         asset_data: &mut ClaimAssetData<'_>,
@@ -1151,9 +1247,6 @@ impl Claim {
         verified: Result<ValidationInfo>,
         validation_log: &mut impl StatusTracker,
     ) -> Result<()> {
-        const UNNAMED: &str = "unnamed";
-        let default_str = |s: &String| s.clone();
-
         match verified {
             Ok(vi) => {
                 if !vi.validated {
@@ -1228,7 +1321,7 @@ impl Claim {
         }
 
         // verify assertion structure comparing hashes from assertion list to contents of assertion store
-        for assertion in claim.assertions() {
+        for assertion in claim.all_assertions() {
             let (label, instance) = Claim::assertion_label_from_link(&assertion.url());
             match claim.get_claim_assertion(&label, instance) {
                 // get the assertion if label and hash match
@@ -1281,6 +1374,8 @@ impl Claim {
             }
         }
 
+        Claim::check_duplicate_assertions(claim, validation_log);
+
         // verify data hashes for provenance claims
         if is_provenance {
             // must have at least one hard binding for normal manifests
@@ -1307,60 +1402,149 @@ impl Claim {
                 validation_log.log(log_item, Some(Error::UpdateManifestInvalid))?;
             }
 
-            for hash_binding_assertion in claim.hash_assertions() {
-                if hash_binding_assertion.label_root() == DataHash::LABEL {
-                    let dh = DataHash::from_assertion(hash_binding_assertion)?;
-                    let name = dh.name.as_ref().map_or(UNNAMED.to_string(), default_str);
-                    if !dh.is_remote_hash() {
-                        // only verify local hashes here
-                        let hash_result = match asset_data {
-                            #[cfg(feature = "file_io")]
-                            ClaimAssetData::Path(asset_path) => {
-                                dh.verify_hash(asset_path, Some(claim.alg()))
-                            }
-                            ClaimAssetData::Bytes(asset_bytes, _) => {
-                                dh.verify_in_memory_hash(asset_bytes, Some(claim.alg()))
-                            }
-                            ClaimAssetData::Stream(stream_data, _) => {
-                                dh.verify_stream_hash(*stream_data, Some(claim.alg()))
-                            }
-                            _ => return Err(Error::UnsupportedType), /* this should never happen (coding error) */
-                        };
+            Claim::verify_hard_bindings(claim, asset_data, validation_log)?;
+        }
+        Ok(())
+    }
 
-                        match hash_result {
-                            Ok(_a) => {
-                                let log_item = log_item!(
-                                    claim.assertion_uri(&hash_binding_assertion.label()),
-                                    "data hash valid",
-                                    "verify_internal"
-                                )
-                                .validation_status(validation_status::ASSERTION_DATAHASH_MATCH);
-                                validation_log.log_silent(log_item);
+    /// Verifies a PDF data hash against the revision holding the manifest.
+    ///
+    /// A PDF digital signature is appended as an incremental update after the `%%EOF` that
+    /// ends the revision holding the manifest. Returns the length of that revision if the data
+    /// hash matches it and the bytes past it only add signature dictionaries and signature
+    /// fields, so that no later update can change the document's content unnoticed.
+    fn verify_pdf_revision_hash(
+        dh: &DataHash,
+        claim: &Claim,
+        asset_data: &mut ClaimAssetData<'_>,
+    ) -> Option<usize> {
+        let manifest_end = dh
+            .exclusions
+            .as_ref()?
+            .iter()
+            .map(|e| e.start().saturating_add(e.length()))
+            .max()?;
 
-                                continue;
-                            }
-                            Err(e) => {
-                                let log_item = log_item!(
-                                    claim.assertion_uri(&hash_binding_assertion.label()),
-                                    format!("asset hash error, name: {name}, error: {e}"),
-                                    "verify_internal"
-                                )
-                                .error(Error::HashMismatch(format!("Asset hash failure: {e}")))
-                                .validation_status(validation_status::ASSERTION_DATAHASH_MISMATCH);
+        // the revision ends with `%%EOF` and, unless the next update added it, an end of line
+        let revision_lens = |tail: &[u8]| -> Vec<usize> {
+            let Some(eof) = tail.windows(5).position(|w| w == b"%%EOF") else {
+                return Vec::new();
+            };
+            let end = manifest_end + eof + 5;
+            let eol = match &tail[eof + 5..] {
+                [b'\r', b'\n', ..] => 2,
+                [b'\r' | b'\n', ..] => 1,
+                _ => 0,
+            };
+            let total = manifest_end + tail.len();
+            [end + eol, end]
+                .into_iter()
+                .filter(|len| *len < total)
+                .collect()
+        };
 
-                                validation_log.log(
-                                    log_item,
-                                    Some(Error::HashMismatch(format!("Asset hash failure: {e}"))),
-                                )?;
-                            }
-                        }
-                    }
-                } else if hash_binding_assertion.label_root() == BmffHash::LABEL {
-                    // handle BMFF data hashes
-                    let dh = BmffHash::from_assertion(hash_binding_assertion)?;
+        // every object of the updates must be a signature dictionary or a signature field
+        let is_signature_update = |update: &[u8]| -> bool {
+            let text: String = String::from_utf8_lossy(update)
+                .split_ascii_whitespace()
+                .collect();
+            let has_sig_name = |body: &str, key: &str| {
+                body.match_indices(key).any(|(i, _)| {
+                    body[i + key.len()..]
+                        .chars()
+                        .next()
+                        .map_or(true, |c| !c.is_ascii_alphanumeric())
+                })
+            };
+
+            let mut objects = text.split("endobj").enumerate().peekable();
+            while let Some((i, object)) = objects.next() {
+                if objects.peek().is_none() {
+                    // the xref sections and trailers after the last object
+                    return i > 0 && !object.contains("obj");
+                }
+                let mut parts = object.split("obj");
+                let (Some(_header), Some(body), None) = (parts.next(), parts.next(), parts.next())
+                else {
+                    return false;
+                };
+                if body.contains("stream")
+                    || body.contains("/Type/Page")
+                    || body.contains("/Type/Catalog")
+                    || !(has_sig_name(body, "/Type/Sig") || has_sig_name(body, "/FT/Sig"))
+                {
+                    return false;
+                }
+            }
+            false
+        };
+
+        let verify_stream = |stream: &mut dyn CAIRead| -> Option<usize> {
+            let total = usize::try_from(stream_len(stream).ok()?).ok()?;
+            let mut tail = Vec::new();
+            stream.seek(SeekFrom::Start(manifest_end as u64)).ok()?;
+            stream.read_to_end(&mut tail).ok()?;
+
+            revision_lens(&tail).into_iter().find(|len| {
+                if !is_signature_update(&tail[len - manifest_end..]) {
+                    return false;
+                }
+
+                // exclude everything past the revision
+                let mut revision_dh = dh.clone();
+                revision_dh.add_exclusion(HashRange::new(*len, total - len));
+                stream.rewind().is_ok()
+                    && revision_dh
+                        .verify_stream_hash(stream, Some(claim.alg()))
+                        .is_ok()
+            })
+        };
+
+        let is_pdf = |format: &str| format_to_mime(format) == "application/pdf";
+
+        match asset_data {
+            #[cfg(feature = "file_io")]
+            ClaimAssetData::Path(asset_path) => {
+                let format = asset_path.extension()?.to_str()?;
+                if !is_pdf(format) {
+                    return None;
+                }
+                verify_stream(&mut std::fs::File::open(*asset_path).ok()?)
+            }
+            ClaimAssetData::Bytes(asset_bytes, format) if is_pdf(*format) => {
+                revision_lens(asset_bytes.get(manifest_end..)?)
+                    .into_iter()
+                    .find(|len| {
+                        dh.verify_in_memory_hash(&asset_bytes[..*len], Some(claim.alg()))
+                            .is_ok()
+                    })
+            }
+            ClaimAssetData::Stream(stream_data, format) if is_pdf(*format) => {
+                verify_stream(*stream_data)
+            }
+            _ => None,
+        }
+    }
 
-                    let name = dh.name().map_or("unnamed".to_string(), default_str);
+    /// Verifies every hard binding assertion of `claim` against `asset_data`.
+    ///
+    /// Data hash, BMFF hash and box hash assertions are each checked in the way their type
+    /// requires, and a match or mismatch status is logged for each one. Remote data hashes are
+    /// not checked.
+    pub(crate) fn verify_hard_bindings(
+        claim: &Claim,
+        asset_data: &mut ClaimAssetData<'_>,
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<()> {
+        const UNNAMED: &str = "unnamed";
+        let default_str = |s: &String| s.clone();
 
+        for hash_binding_assertion in claim.hash_assertions() {
+            if hash_binding_assertion.label_root() == DataHash::LABEL {
+                let dh = DataHash::from_assertion(hash_binding_assertion)?;
+                let name = dh.name.as_ref().map_or(UNNAMED.to_string(), default_str);
+                if !dh.is_remote_hash() {
+                    // only verify local hashes here
                     let hash_result = match asset_data {
                         #[cfg(feature = "file_io")]
                         ClaimAssetData::Path(asset_path) => {
@@ -1372,19 +1556,17 @@ impl Claim {
                         ClaimAssetData::Stream(stream_data, _) => {
                             dh.verify_stream_hash(*stream_data, Some(claim.alg()))
                         }
-                        ClaimAssetData::StreamFragment(initseg_data, fragment_data, _) => dh
-                            .verify_stream_segment(
-                                *initseg_data,
-                                *fragment_data,
-                                Some(claim.alg()),
-                            ),
-                        #[cfg(feature = "file_io")]
-                        ClaimAssetData::StreamFragments(initseg_data, fragment_paths, _) => dh
-                            .verify_stream_segments(
-                                *initseg_data,
-                                fragment_paths,
-                                Some(claim.alg()),
-                            ),
+                        _ => return Err(Error::UnsupportedType), /* this should never happen (coding error) */
+                    };
+
+                    // a PDF may have been digitally signed, or otherwise updated, after its
+                    // manifest was embedded, in which case only its earlier revisions are bound
+                    let (hash_result, revision_len) = match hash_result {
+                        Err(e) => match Claim::verify_pdf_revision_hash(&dh, claim, asset_data) {
+                            Some(len) => (Ok(()), Some(len)),
+                            None => (Err(e), None),
+                        },
+                        ok => (ok, None),
                     };
 
                     match hash_result {
@@ -1394,9 +1576,19 @@ impl Claim {
                                 "data hash valid",
                                 "verify_internal"
                             )
-                            .validation_status(validation_status::ASSERTION_BMFFHASH_MATCH);
+                            .validation_status(validation_status::ASSERTION_DATAHASH_MATCH);
                             validation_log.log_silent(log_item);
 
+                            if let Some(len) = revision_len {
+                                let log_item = log_item!(
+                                    claim.assertion_uri(&hash_binding_assertion.label()),
+                                    format!("PDF revisions after byte {len} are not covered by the data hash"),
+                                    "verify_internal"
+                                )
+                                .validation_status(validation_status::STATUS_PDF_UNBOUND_REVISIONS);
+                                validation_log.log_silent(log_item);
+                            }
+
                             continue;
                         }
                         Err(e) => {
@@ -1406,7 +1598,7 @@ impl Claim {
                                 "verify_internal"
                             )
                             .error(Error::HashMismatch(format!("Asset hash failure: {e}")))
-                            .validation_status(validation_status::ASSERTION_BMFFHASH_MISMATCH);
+                            .validation_status(validation_status::ASSERTION_DATAHASH_MISMATCH);
 
                             validation_log.log(
                                 log_item,
@@ -1414,81 +1606,124 @@ impl Claim {
                             )?;
                         }
                     }
-                } else if hash_binding_assertion.label_root() == BoxHash::LABEL {
-                    // box hash case
-                    // handle BMFF data hashes
-                    let bh = BoxHash::from_assertion(hash_binding_assertion)?;
+                }
+            } else if hash_binding_assertion.label_root() == BmffHash::LABEL {
+                // handle BMFF data hashes
+                let dh = BmffHash::from_assertion(hash_binding_assertion)?;
 
-                    let hash_result = match asset_data {
-                        #[cfg(feature = "file_io")]
-                        ClaimAssetData::Path(asset_path) => {
-                            let box_hash_processor =
-                                crate::jumbf_io::get_assetio_handler_from_path(asset_path)
-                                    .ok_or(Error::UnsupportedType)?
-                                    .asset_box_hash_ref()
-                                    .ok_or(Error::HashMismatch(
-                                        "Box hash not supported".to_string(),
-                                    ))?;
-
-                            bh.verify_hash(asset_path, Some(claim.alg()), box_hash_processor)
-                        }
-                        ClaimAssetData::Bytes(asset_bytes, asset_type) => {
-                            let box_hash_processor = get_assetio_handler(asset_type)
-                                .ok_or(Error::UnsupportedType)?
-                                .asset_box_hash_ref()
-                                .ok_or(Error::HashMismatch(format!(
-                                    "Box hash not supported for: {asset_type}"
-                                )))?;
-
-                            bh.verify_in_memory_hash(
-                                asset_bytes,
-                                Some(claim.alg()),
-                                box_hash_processor,
-                            )
-                        }
-                        ClaimAssetData::Stream(stream_data, asset_type) => {
-                            let box_hash_processor = get_assetio_handler(asset_type)
+                let name = dh.name().map_or("unnamed".to_string(), default_str);
+
+                let hash_result = match asset_data {
+                    #[cfg(feature = "file_io")]
+                    ClaimAssetData::Path(asset_path) => {
+                        dh.verify_hash(asset_path, Some(claim.alg()))
+                    }
+                    ClaimAssetData::Bytes(asset_bytes, _) => {
+                        dh.verify_in_memory_hash(asset_bytes, Some(claim.alg()))
+                    }
+                    ClaimAssetData::Stream(stream_data, _) => {
+                        dh.verify_stream_hash(*stream_data, Some(claim.alg()))
+                    }
+                    ClaimAssetData::StreamFragment(initseg_data, fragment_data, _) => {
+                        dh.verify_stream_segment(*initseg_data, *fragment_data, Some(claim.alg()))
+                    }
+                    #[cfg(feature = "file_io")]
+                    ClaimAssetData::StreamFragments(initseg_data, fragment_paths, _) => {
+                        dh.verify_stream_segments(*initseg_data, fragment_paths, Some(claim.alg()))
+                    }
+                };
+
+                match hash_result {
+                    Ok(_a) => {
+                        let log_item = log_item!(
+                            claim.assertion_uri(&hash_binding_assertion.label()),
+                            "data hash valid",
+                            "verify_internal"
+                        )
+                        .validation_status(validation_status::ASSERTION_BMFFHASH_MATCH);
+                        validation_log.log_silent(log_item);
+
+                        continue;
+                    }
+                    Err(e) => {
+                        let log_item = log_item!(
+                            claim.assertion_uri(&hash_binding_assertion.label()),
+                            format!("asset hash error, name: {name}, error: {e}"),
+                            "verify_internal"
+                        )
+                        .error(Error::HashMismatch(format!("Asset hash failure: {e}")))
+                        .validation_status(validation_status::ASSERTION_BMFFHASH_MISMATCH);
+
+                        validation_log.log(
+                            log_item,
+                            Some(Error::HashMismatch(format!("Asset hash failure: {e}"))),
+                        )?;
+                    }
+                }
+            } else if hash_binding_assertion.label_root() == BoxHash::LABEL {
+                // box hash case
+                // handle BMFF data hashes
+                let bh = BoxHash::from_assertion(hash_binding_assertion)?;
+
+                let hash_result = match asset_data {
+                    #[cfg(feature = "file_io")]
+                    ClaimAssetData::Path(asset_path) => {
+                        let box_hash_processor =
+                            crate::jumbf_io::get_assetio_handler_from_path(asset_path)
                                 .ok_or(Error::UnsupportedType)?
                                 .asset_box_hash_ref()
-                                .ok_or(Error::HashMismatch(format!(
-                                    "Box hash not supported for: {asset_type}"
-                                )))?;
-
-                            bh.verify_stream_hash(
-                                *stream_data,
-                                Some(claim.alg()),
-                                box_hash_processor,
-                            )
-                        }
-                        _ => return Err(Error::UnsupportedType),
-                    };
+                                .ok_or(Error::HashMismatch("Box hash not supported".to_string()))?;
 
-                    match hash_result {
-                        Ok(_a) => {
-                            let log_item = log_item!(
-                                claim.assertion_uri(&hash_binding_assertion.label()),
-                                "data hash valid",
-                                "verify_internal"
-                            )
-                            .validation_status(validation_status::ASSERTION_BOXHASH_MATCH);
-                            validation_log.log_silent(log_item);
+                        bh.verify_hash(asset_path, Some(claim.alg()), box_hash_processor)
+                    }
+                    ClaimAssetData::Bytes(asset_bytes, asset_type) => {
+                        let box_hash_processor = get_assetio_handler(asset_type)
+                            .ok_or(Error::UnsupportedType)?
+                            .asset_box_hash_ref()
+                            .ok_or(Error::HashMismatch(format!(
+                                "Box hash not supported for: {asset_type}"
+                            )))?;
+
+                        bh.verify_in_memory_hash(asset_bytes, Some(claim.alg()), box_hash_processor)
+                    }
+                    ClaimAssetData::Stream(stream_data, asset_type) => {
+                        let box_hash_processor = get_assetio_handler(asset_type)
+                            .ok_or(Error::UnsupportedType)?
+                            .asset_box_hash_ref()
+                            .ok_or(Error::HashMismatch(format!(
+                                "Box hash not supported for: {asset_type}"
+                            )))?;
+
+                        bh.verify_stream_hash(*stream_data, Some(claim.alg()), box_hash_processor)
+                    }
+                    _ => return Err(Error::UnsupportedType),
+                };
 
-                            continue;
-                        }
-                        Err(e) => {
-                            let log_item = log_item!(
-                                claim.assertion_uri(&hash_binding_assertion.label()),
-                                format!("asset hash error: {e}"),
-                                "verify_internal"
-                            )
-                            .error(Error::HashMismatch(format!("Asset hash failure: {e}")))
-                            .validation_status(validation_status::ASSERTION_BOXHASH_MISMATCH);
+                match hash_result {
+                    Ok(_a) => {
+                        let log_item = log_item!(
+                            claim.assertion_uri(&hash_binding_assertion.label()),
+                            "data hash valid",
+                            "verify_internal"
+                        )
+                        .validation_status(validation_status::ASSERTION_BOXHASH_MATCH);
+                        validation_log.log_silent(log_item);
 
-                            validation_log.log(
-                                log_item,
-                                Some(Error::HashMismatch(format!("Asset hash failure: {e}"))),
-                            )?;
-                        }
+                        continue;
+                    }
+                    Err(e) => {
+                        let log_item = log_item!(
+                            claim.assertion_uri(&hash_binding_assertion.label()),
+                            format!("asset hash error: {e}"),
+                            "verify_internal"
+                        )
+                        .error(Error::HashMismatch(format!("Asset hash failure: {e}")))
+                        .validation_status(validation_status::ASSERTION_BOXHASH_MISMATCH);
+
+                        validation_log.log(
+                            log_item,
+                            Some(Error::HashMismatch(format!("Asset hash failure: {e}"))),
+                        )?;
                     }
                 }
             }
@@ -1633,6 +1868,20 @@ impl Claim {
         &self.assertions
     }
 
+    /// Return the hashed URIs of the assertions the claim generator gathered from other
+    /// sources, rather than created itself.
+    ///
+    /// These are not included in [`Claim::assertions`]. Only v2 claims list gathered
+    /// assertions, so this is empty for the v1 claims written by this SDK.
+    pub fn gathered_assertions(&self) -> &[C2PAAssertion] {
+        self.gathered_assertions.as_deref().unwrap_or_default()
+    }
+
+    /// Return the hashed URIs of both the created and the gathered assertions.
+    pub fn all_assertions(&self) -> impl Iterator<Item = &C2PAAssertion> {
+        self.assertions.iter().chain(self.gathered_assertions())
+    }
+
     /// Returns the cbor binary value of the claim data.
     /// If this claim was read from a file, returns the exact byte
     /// sequence that was read from the file. If this claim was
@@ -1649,6 +1898,58 @@ impl Claim {
         self.original_bytes = None;
     }
 
+    /// Migrates a claim loaded from a prerelease (pre-1.0) manifest to the current layout.
+    ///
+    /// Action `parameters` given as a string become a `description` parameter and the assertion
+    /// references are rehashed over the whole assertion box. The signature no longer covers the
+    /// migrated claim, so it is dropped.
+    pub(crate) fn migrate_legacy(&mut self) -> Result<()> {
+        for claim_assertion in self.assertion_store.iter_mut() {
+            if claim_assertion.label_raw() != labels::ACTIONS {
+                continue;
+            }
+
+            let mut value = claim_assertion.assertion().as_json_object()?;
+            let mut migrated = false;
+            if let Some(actions) = value.get_mut("actions").and_then(Value::as_array_mut) {
+                for action in actions {
+                    if let Some(Value::String(description)) = action.get("parameters").cloned() {
+                        action["parameters"] = json!({ "description": description });
+                        migrated = true;
+                    }
+                }
+            }
+
+            if migrated {
+                let assertion = assertions::Actions::from_json_value(&value)?.to_assertion()?;
+                let hash = Claim::calc_assertion_box_hash(
+                    &claim_assertion.label(),
+                    &assertion,
+                    claim_assertion.salt().clone(),
+                    claim_assertion.hash_alg(),
+                )?;
+                claim_assertion.update_assertion(assertion, hash)?;
+            }
+        }
+
+        // prerelease hashes only cover the assertion data
+        for hashed_uri in self.assertions.iter_mut() {
+            let (label, instance) = Claim::assertion_label_from_link(&hashed_uri.url());
+            if let Some(claim_assertion) = self
+                .assertion_store
+                .iter()
+                .find(|ca| ca.label_raw() == label && ca.instance() == instance)
+            {
+                hashed_uri.update_hash(claim_assertion.hash().to_vec());
+            }
+        }
+
+        self.clear_data();
+        self.set_signature_val(Vec::new());
+
+        Ok(())
+    }
+
     /// Create claim from binary data (not including assertions).
     pub fn from_data(label: &str, data: &[u8]) -> Result<Claim> {
         let mut claim: Claim = serde_cbor::from_slice(data).map_err(|_err| Error::ClaimDecoding)?;
@@ -1675,9 +1976,7 @@ impl Claim {
                 if let Value::Object(ref mut map) = v {
                     // merge the label with the data
                     let mut json_map: Map<String, Value> = Map::new();
-                    let iter = self.assertions.iter().zip(&self.assertion_store);
-
-                    for (_key, claim_assertion) in iter {
+                    for claim_assertion in &self.assertion_store {
                         let link = claim_assertion.label();
                         let (label, instance) = Self::assertion_label_from_link(&link);
                         let label = Self::label_with_instance(&label, instance);
@@ -1917,8 +2216,7 @@ impl Claim {
     }
 
     pub fn assertion_hashed_uri_from_label(&self, assertion_label: &str) -> Option<&C2PAAssertion> {
-        self.assertions()
-            .iter()
+        self.all_assertions()
             .find(|hashed_uri| hashed_uri.url().contains(assertion_label))
     }
 
@@ -1965,8 +2263,7 @@ impl Claim {
     pub fn count_instances(&self, in_label: &str) -> usize {
         let (l, i) = Claim::assertion_label_from_link(in_label);
         let label = Claim::label_with_instance(&l, i);
-        self.assertions
-            .iter()
+        self.all_assertions()
             .filter(|assertion| assertion.url().contains(&label))
             .count()
     }
@@ -2052,6 +2349,35 @@ pub mod tests {
         println!("Claim: {json_str}");
     }
 
+    #[test]
+    fn test_read_gathered_assertions() {
+        let mut claim = create_test_claim().expect("create test claim");
+        claim.build().expect("bad claim");
+        let created = claim.assertions().len();
+
+        // list the last assertion as gathered, as a v2 claim would
+        let mut value: serde_cbor::Value = serde_cbor::from_slice(&claim.data().unwrap()).unwrap();
+        let serde_cbor::Value::Map(map) = &mut value else {
+            panic!("claim is not a map");
+        };
+        let Some(serde_cbor::Value::Array(assertions)) =
+            map.get_mut(&serde_cbor::Value::Text("assertions".to_string()))
+        else {
+            panic!("claim has no assertions");
+        };
+        let gathered = assertions.pop().unwrap();
+        map.insert(
+            serde_cbor::Value::Text("gathered_assertions".to_string()),
+            serde_cbor::Value::Array(vec![gathered]),
+        );
+
+        let restored = Claim::from_data("as_adbe_1", &serde_cbor::to_vec(&value).unwrap()).unwrap();
+        assert_eq!(restored.assertions().len(), created - 1);
+        assert_eq!(restored.gathered_assertions().len(), 1);
+        assert_eq!(restored.all_assertions().count(), created);
+        assert!(claim.gathered_assertions().is_empty());
+    }
+
     #[test]
     fn test_build_claim_generator_hints() {
         // Create a new claim.
@@ -2098,4 +2424,114 @@ pub mod tests {
             assert_eq!(r.hash(), b"hashed");
         }
     }
+
+    #[test]
+    fn test_verify_hard_bindings_data_and_box_hash() {
+        use crate::status_tracker::{report_has_status, DetailedStatusTracker};
+
+        let mut asset = std::fs::read(crate::utils::test::fixture_path("CA.jpg")).unwrap();
+        let bhp = get_assetio_handler("image/jpeg")
+            .unwrap()
+            .asset_box_hash_ref()
+            .unwrap();
+
+        let mut data_hash = DataHash::new("jumbf manifest", "sha256");
+        data_hash
+            .gen_hash_from_stream(&mut std::io::Cursor::new(&asset))
+            .unwrap();
+
+        let mut box_hash: BoxHash = serde_json::from_value(json!({ "boxes": [] })).unwrap();
+        box_hash
+            .generate_box_hash_from_stream(&mut std::io::Cursor::new(&asset), "sha256", bhp, false)
+            .unwrap();
+
+        let mut claim = Claim::new("binding unit test", Some("contentauth"));
+        claim.add_assertion(&data_hash).unwrap();
+        claim.add_assertion(&box_hash).unwrap();
+
+        let mut validation_log = DetailedStatusTracker::new();
+        Claim::verify_hard_bindings(
+            &claim,
+            &mut ClaimAssetData::Bytes(&asset, "image/jpeg"),
+            &mut validation_log,
+        )
+        .unwrap();
+        let log = validation_log.get_log();
+        assert_eq!(log.len(), 2);
+        assert!(report_has_status(
+            log,
+            validation_status::ASSERTION_DATAHASH_MATCH
+        ));
+        assert!(report_has_status(
+            log,
+            validation_status::ASSERTION_BOXHASH_MATCH
+        ));
+
+        // alter a byte of the image data, away from any marker
+        let pos = asset.len() - 3;
+        asset[pos] = if asset[pos] == 0x01 { 0x02 } else { 0x01 };
+
+        let mut validation_log = DetailedStatusTracker::new();
+        Claim::verify_hard_bindings(
+            &claim,
+            &mut ClaimAssetData::Bytes(&asset, "image/jpeg"),
+            &mut validation_log,
+        )
+        .unwrap();
+        let log = validation_log.get_log();
+        assert_eq!(log.len(), 2);
+        assert!(report_has_status(
+            log,
+            validation_status::ASSERTION_DATAHASH_MISMATCH
+        ));
+        assert!(report_has_status(
+            log,
+            validation_status::ASSERTION_BOXHASH_MISMATCH
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_assertions() {
+        use crate::{
+            assertions::{Action, Actions, Thumbnail},
+            status_tracker::{report_has_status, DetailedStatusTracker},
+        };
+
+        // actions, a claim thumbnail and two ingredients
+        let mut claim = create_test_claim().unwrap();
+        let mut validation_log = DetailedStatusTracker::new();
+        Claim::check_duplicate_assertions(&claim, &mut validation_log);
+        assert!(!report_has_status(
+            validation_log.get_log(),
+            validation_status::STATUS_ASSERTION_DUPLICATE
+        ));
+
+        // a second actions assertion and a second claim thumbnail are reported
+        let actions = Actions::new().add_action(Action::new("c2pa.edited"));
+        claim.add_assertion(&actions).unwrap();
+        let thumbnail = Thumbnail::new(labels::PNG_CLAIM_THUMBNAIL, vec![0; 8]);
+        claim.add_assertion(&thumbnail).unwrap();
+        // the same assertion listed twice
+        let listed = claim.assertions()[0].clone();
+        claim.assertions.push(listed);
+
+        let mut validation_log = DetailedStatusTracker::new();
+        Claim::check_duplicate_assertions(&claim, &mut validation_log);
+        let duplicates: Vec<_> = validation_log
+            .get_log()
+            .iter()
+            .filter(|item| {
+                item.validation_status.as_deref()
+                    == Some(validation_status::STATUS_ASSERTION_DUPLICATE)
+            })
+            .collect();
+        assert_eq!(duplicates.len(), 3);
+        assert!(duplicates[0].label.contains(labels::ACTIONS));
+        assert!(duplicates[1].label.contains(labels::PNG_CLAIM_THUMBNAIL));
+
+        // as informational codes, which don't make the manifest invalid
+        assert!(validation_status::is_informational(
+            validation_status::STATUS_ASSERTION_DUPLICATE
+        ));
+    }
 }
@@ -28,6 +28,7 @@ use coset::{
 use crate::{
     claim::Claim,
     cose_validator::{check_cert, verify_cose},
+    metrics::{MetricsObserver, PipelineStage, StageTimer},
     settings::get_settings_value,
     status_tracker::OneShotStatusTracker,
     time_stamp::{
@@ -121,6 +122,27 @@ fn signing_cert_valid(signing_cert: &[u8]) -> Result<()> {
     box_size: usize
 ))]
 pub(crate) fn cose_sign(signer: &dyn Signer, data: &[u8], box_size: usize) -> Result<Vec<u8>> {
+    if _sync {
+        cose_sign_with_metrics(signer, data, box_size, None)
+    } else {
+        cose_sign_with_metrics_async(signer, data, box_size, None).await
+    }
+}
+
+/// Same as [`cose_sign`], reporting the time spent waiting for the time stamp authority and
+/// OCSP responder to `metrics` as [`PipelineStage::Network`].
+#[async_generic(async_signature(
+    signer: &dyn AsyncSigner,
+    data: &[u8],
+    box_size: usize,
+    metrics: Option<&dyn MetricsObserver>,
+))]
+pub(crate) fn cose_sign_with_metrics(
+    signer: &dyn Signer,
+    data: &[u8],
+    box_size: usize,
+    metrics: Option<&dyn MetricsObserver>,
+) -> Result<Vec<u8>> {
     // 13.2.1. X.509 Certificates
     //
     // X.509 Certificates are stored in a header named x5chain draft-ietf-cose-x509.
@@ -155,9 +177,9 @@ pub(crate) fn cose_sign(signer: &dyn Signer, data: &[u8], box_size: usize) -> Re
 
     // build complete header
     let (protected_header, unprotected_header) = if _sync {
-        build_headers(signer, data, alg)?
+        build_headers(signer, data, alg, metrics)?
     } else {
-        build_headers_async(signer, data, alg).await?
+        build_headers_async(signer, data, alg, metrics).await?
     };
 
     let aad: &[u8; 0] = b""; // no additional data required here
@@ -205,8 +227,18 @@ pub(crate) fn cose_sign(signer: &dyn Signer, data: &[u8], box_size: usize) -> Re
     Ok(c2pa_sig_data)
 }
 
-#[async_generic(async_signature(signer: &dyn AsyncSigner, data: &[u8], alg: SigningAlg))]
-fn build_headers(signer: &dyn Signer, data: &[u8], alg: SigningAlg) -> Result<(Header, Header)> {
+#[async_generic(async_signature(
+    signer: &dyn AsyncSigner,
+    data: &[u8],
+    alg: SigningAlg,
+    metrics: Option<&dyn MetricsObserver>,
+))]
+fn build_headers(
+    signer: &dyn Signer,
+    data: &[u8],
+    alg: SigningAlg,
+    metrics: Option<&dyn MetricsObserver>,
+) -> Result<(Header, Header)> {
     let mut protected_h = match alg {
         SigningAlg::Ps256 => HeaderBuilder::new().algorithm(iana::Algorithm::PS256),
         SigningAlg::Ps384 => HeaderBuilder::new().algorithm(iana::Algorithm::PS384),
@@ -219,11 +251,14 @@ fn build_headers(signer: &dyn Signer, data: &[u8], alg: SigningAlg) -> Result<(H
 
     let certs = signer.certs()?;
 
+    // the signer may fetch its OCSP response from the responder
+    let timer = StageTimer::start(metrics, PipelineStage::Network);
     let ocsp_val = if _sync {
         signer.ocsp_val()
     } else {
         signer.ocsp_val().await
     };
+    drop(timer);
 
     let sc_der_array_or_bytes = match certs.len() {
         1 => Value::Bytes(certs[0].clone()), // single cert
@@ -248,12 +283,19 @@ fn build_headers(signer: &dyn Signer, data: &[u8], alg: SigningAlg) -> Result<(H
         header: protected_header.clone(),
     };
 
+    // only a configured time stamp authority is contacted
+    let timer = signer
+        .time_authority_url()
+        .and_then(|_| StageTimer::start(metrics, PipelineStage::Network));
     let maybe_cts = if _sync {
         cose_timestamp_countersign(signer, data, &ph2)
     } else {
         cose_timestamp_countersign_async(signer, data, &ph2).await
     };
+    drop(timer);
 
+    // signing always fails if the time stamp authority can't be reached, whatever the
+    // verification policy for network errors
     let mut unprotected_h = if let Some(cts) = maybe_cts {
         let cts = cts?;
         let sigtst_vec = serde_cbor::to_vec(&make_cose_timestamp(&cts))?;
@@ -357,7 +399,7 @@ fn pad_cose_sig(sign1: &mut CoseSign1, end_size: usize) -> Result<Vec<u8>> {
 mod tests {
     #![allow(clippy::unwrap_used)]
 
-    use super::sign_claim;
+    use super::{cose_sign_with_metrics, sign_claim};
     use crate::{claim::Claim, utils::test::temp_signer};
 
     #[test]
@@ -447,4 +489,131 @@ mod tests {
         #[cfg(feature = "openssl")] // there is no verify on sign when openssl is disabled
         assert!(_cose_sign1.is_err());
     }
+
+    // wraps a signer with a time stamp authority that can't be reached
+    struct UnreachableTsaSigner {
+        signer: Box<dyn crate::Signer>,
+    }
+
+    impl crate::Signer for UnreachableTsaSigner {
+        fn sign(&self, data: &[u8]) -> crate::error::Result<Vec<u8>> {
+            self.signer.sign(data)
+        }
+
+        fn alg(&self) -> crate::SigningAlg {
+            self.signer.alg()
+        }
+
+        fn certs(&self) -> crate::error::Result<Vec<Vec<u8>>> {
+            self.signer.certs()
+        }
+
+        fn reserve_size(&self) -> usize {
+            self.signer.reserve_size()
+        }
+
+        fn time_authority_url(&self) -> Option<String> {
+            // nothing listens on the discard port
+            Some("http://127.0.0.1:9/".to_string())
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_sign_claim_unreachable_tsa() {
+        use crate::{
+            settings::{
+                set_settings_value,
+                tests::{ResetSettings, PROTECT},
+            },
+            Error,
+        };
+
+        let _protect = PROTECT.lock().unwrap();
+        let _reset = ResetSettings;
+
+        let mut claim = Claim::new("unreachable_tsa_test", Some("contentauth"));
+        claim.build().unwrap();
+
+        let claim_bytes = claim.data().unwrap();
+
+        let signer = UnreachableTsaSigner {
+            signer: temp_signer(),
+        };
+        let box_size = signer.signer.reserve_size();
+
+        // the verification policy for network errors doesn't let signing drop the time stamp
+        set_settings_value("verify.fail_closed_on_network_error", false).unwrap();
+        let result = sign_claim(&claim_bytes, &signer, box_size);
+        assert!(matches!(result, Err(Error::NetworkUnreachable(_))));
+
+        set_settings_value("verify.fail_closed_on_network_error", true).unwrap();
+        let result = sign_claim(&claim_bytes, &signer, box_size);
+        assert!(matches!(result, Err(Error::NetworkUnreachable(_))));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_cose_sign_network_metrics() {
+        use std::{sync::Mutex, time::Duration};
+
+        use crate::metrics::{MetricsObserver, PipelineStage};
+
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<(PipelineStage, bool)>>);
+
+        impl MetricsObserver for Recorder {
+            fn stage_started(&self, stage: PipelineStage) {
+                self.0.lock().unwrap().push((stage, true));
+            }
+
+            fn stage_finished(&self, stage: PipelineStage, _elapsed: Duration) {
+                self.0.lock().unwrap().push((stage, false));
+            }
+        }
+
+        let mut claim = Claim::new("network_metrics_test", Some("contentauth"));
+        claim.build().unwrap();
+        let claim_bytes = claim.data().unwrap();
+
+        // the OCSP response and the time stamp request are each timed
+        let signer = UnreachableTsaSigner {
+            signer: temp_signer(),
+        };
+        let recorder = Recorder::default();
+        let result = cose_sign_with_metrics(
+            &signer,
+            &claim_bytes,
+            signer.signer.reserve_size(),
+            Some(&recorder),
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                (PipelineStage::Network, true),
+                (PipelineStage::Network, false),
+                (PipelineStage::Network, true),
+                (PipelineStage::Network, false),
+            ]
+        );
+
+        // without a time stamp authority only the OCSP response is
+        let signer = temp_signer();
+        let recorder = Recorder::default();
+        cose_sign_with_metrics(
+            signer.as_ref(),
+            &claim_bytes,
+            signer.reserve_size(),
+            Some(&recorder),
+        )
+        .unwrap();
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                (PipelineStage::Network, true),
+                (PipelineStage::Network, false)
+            ]
+        );
+    }
 }
@@ -19,8 +19,9 @@ use ciborium::value::Value;
 use conv::*;
 use coset::{
     iana::{self, EnumI64},
-    sig_structure_data, Label, TaggedCborSerializable,
+    sig_structure_data, AsCborValue, Label, TaggedCborSerializable,
 };
+use lazy_static::lazy_static;
 use x509_parser::{
     der_parser::{ber::parse_ber_sequence, oid},
     num_bigint::BigUint,
@@ -37,9 +38,11 @@ use crate::{
     error::{Error, Result},
     ocsp_utils::{check_ocsp_response, OcspData},
     settings::get_settings_value,
-    status_tracker::{log_item, StatusTracker},
+    status_tracker::{log_item, OneShotStatusTracker, StatusTracker},
     time_stamp::gt_to_datetime,
-    trust_handler::{has_allowed_oid, TrustHandlerConfig},
+    trust_handler::{
+        complete_chain, has_allowed_oid, TrustHandlerConfig, DEVELOPMENT_TRUST_ANCHORS,
+    },
     utils::sig_utils::parse_ec_der_sig,
     validation_status,
     validator::ValidationInfo,
@@ -89,6 +92,25 @@ fn get_cose_sign1(
 ) -> Result<coset::CoseSign1> {
     match <coset::CoseSign1 as TaggedCborSerializable>::from_tagged_slice(cose_bytes) {
         Ok(mut sign1) => {
+            // C2PA payloads are detached, but other implementations may embed the claim
+            if sign1
+                .payload
+                .as_ref()
+                .is_some_and(|payload| payload != data)
+            {
+                let log_item = log_item!(
+                    "Cose_Sign1",
+                    "embedded payload does not match claim",
+                    "get_cose_sign1"
+                )
+                .error(Error::CoseSignature)
+                .validation_status(validation_status::CLAIM_SIGNATURE_MISMATCH);
+
+                validation_log.log_silent(log_item);
+
+                return Err(Error::CoseSignature);
+            }
+
             sign1.payload = Some(data.to_vec()); // restore payload for verification check
 
             Ok(sign1)
@@ -109,6 +131,20 @@ fn get_cose_sign1(
     }
 }
 
+// Logs a certificate rejected because of its EKUs as `signingCredential.invalid`, with
+// `STATUS_INVALID_EKU` as a second, more specific entry.
+fn log_invalid_eku(description: &str, validation_log: &mut impl StatusTracker) {
+    let log_item = log_item!("Cose_Sign1", description, "check_cert_alg")
+        .error(Error::CoseInvalidCert)
+        .validation_status(validation_status::SIGNING_CREDENTIAL_INVALID);
+    validation_log.log_silent(log_item);
+
+    let log_item = log_item!("Cose_Sign1", description, "check_cert_alg")
+        .error(Error::CoseInvalidCert)
+        .validation_status(validation_status::STATUS_INVALID_EKU);
+    validation_log.log_silent(log_item);
+}
+
 pub(crate) fn check_cert(
     ca_der_bytes: &[u8],
     th: &dyn TrustHandlerConfig,
@@ -380,37 +416,27 @@ pub(crate) fn check_cert(
         return Err(Error::CoseInvalidCert);
     }
 
+    // the key usage and EKU requirements of the C2PA certificate profile can be relaxed for testing
+    let check_usage = get_settings_value::<bool>("verify.check_cert_usage").unwrap_or(true);
+
     let mut aki_good = false;
     let mut ski_good = false;
-    let mut key_usage_good = false;
+    let mut key_usage_good = !check_usage;
     let mut handled_all_critical = true;
     let extended_key_usage_good = match tbscert
         .extended_key_usage()
         .map_err(|_| Error::CoseInvalidCert)?
     {
+        _ if !check_usage => true,
         Some(BasicExtension { value: eku, .. }) => {
             if eku.any {
-                let log_item = log_item!(
-                    "Cose_Sign1",
-                    "certificate 'any' EKU not allowed",
-                    "check_cert_alg"
-                )
-                .error(Error::CoseInvalidCert)
-                .validation_status(validation_status::SIGNING_CREDENTIAL_INVALID);
-                validation_log.log_silent(log_item);
+                log_invalid_eku("certificate 'any' EKU not allowed", validation_log);
 
                 return Err(Error::CoseInvalidCert);
             }
 
             if has_allowed_oid(eku, &th.get_auxillary_ekus()).is_none() {
-                let log_item = log_item!(
-                    "Cose_Sign1",
-                    "certificate missing required EKU",
-                    "check_cert_alg"
-                )
-                .error(Error::CoseInvalidCert)
-                .validation_status(validation_status::SIGNING_CREDENTIAL_INVALID);
-                validation_log.log_silent(log_item);
+                log_invalid_eku("certificate missing required EKU", validation_log);
 
                 return Err(Error::CoseInvalidCert);
             }
@@ -424,21 +450,20 @@ pub(crate) fn check_cert(
                         | eku.server_auth
                         | !eku.other.is_empty()))
             {
-                let log_item = log_item!(
-                    "Cose_Sign1",
-                    "certificate invalid set of EKUs",
-                    "check_cert_alg"
-                )
-                .error(Error::CoseInvalidCert)
-                .validation_status(validation_status::SIGNING_CREDENTIAL_INVALID);
-                validation_log.log_silent(log_item);
+                log_invalid_eku("certificate invalid set of EKUs", validation_log);
 
                 return Err(Error::CoseInvalidCert);
             }
 
             true
         }
-        None => tbscert.is_ca(), // if is not ca it must be present
+        None if tbscert.is_ca() => true,
+        None => {
+            // if is not ca it must be present
+            log_invalid_eku("certificate missing required EKU", validation_log);
+
+            return Err(Error::CoseInvalidCert);
+        }
     };
 
     // populate needed extension info
@@ -450,6 +475,7 @@ pub(crate) fn check_cert(
             ParsedExtension::SubjectKeyIdentifier(_spki) => {
                 ski_good = true;
             }
+            ParsedExtension::KeyUsage(_) if !check_usage => (),
             ParsedExtension::KeyUsage(ku) => {
                 if ku.digital_signature() {
                     if ku.key_cert_sign() && !tbscert.is_ca() {
@@ -565,7 +591,9 @@ fn get_unprotected_header_certs(sign1: &coset::CoseSign1) -> Result<Vec<Vec<u8>>
         .rest
         .iter()
         .find_map(|x: &(Label, Value)| {
-            if x.0 == Label::Text("x5chain".to_string()) {
+            if x.0 == Label::Text("x5chain".to_string())
+                || x.0 == Label::Int(iana::HeaderParameter::X5Chain.to_i64())
+            {
                 Some(x.1.clone())
             } else {
                 None
@@ -737,8 +765,23 @@ pub(crate) fn check_ocsp_status(
                     // get the cert chain
                     let certs = get_sign_certs(&sign1)?;
 
-                    if let Some(ocsp_der) = crate::ocsp_utils::fetch_ocsp_response(&certs) {
-                        // fetch_ocsp_response(&certs) {
+                    let fetched = match crate::ocsp_utils::try_fetch_ocsp_response(&certs) {
+                        Ok(fetched) => fetched,
+                        Err(Error::NetworkUnreachable(url)) => {
+                            let fail_closed =
+                                get_settings_value::<bool>("verify.fail_closed_on_network_error")
+                                    .unwrap_or(false);
+                            crate::ocsp_utils::log_ocsp_unreachable(
+                                &url,
+                                fail_closed,
+                                validation_log,
+                            )?;
+                            None
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    if let Some(ocsp_der) = fetched {
                         let ocsp_response_der = ocsp_der;
 
                         let time_stamp_info = get_timestamp_info(&sign1, data);
@@ -839,6 +882,74 @@ fn get_timestamp_info(sign1: &coset::CoseSign1, data: &[u8]) -> Result<TstInfo>
     Err(Error::NotFound)
 }
 
+// Builds a trust handler that trusts only the given PEM anchors.
+#[allow(unused)]
+fn anchor_trust_handler<T: TrustHandlerConfig>(anchors: &[u8]) -> Option<T> {
+    let mut th = T::new();
+    th.clear();
+    th.append_private_trust_data(&mut Cursor::new(anchors))
+        .ok()?;
+    Some(th)
+}
+
+// The bundled development anchors are parsed once, not on every trust check.
+#[cfg(feature = "openssl")]
+lazy_static! {
+    static ref DEVELOPMENT_TRUST_HANDLER: Option<crate::openssl::OpenSSLTrustHandlerConfig> =
+        anchor_trust_handler(DEVELOPMENT_TRUST_ANCHORS);
+}
+
+#[cfg(target_arch = "wasm32")]
+lazy_static! {
+    static ref DEVELOPMENT_WEB_TRUST_HANDLER: Option<crate::wasm::WebTrustHandlerConfig> =
+        anchor_trust_handler(DEVELOPMENT_TRUST_ANCHORS);
+}
+
+// Returns true if the certificate chains to one of the bundled C2PA test/development anchors.
+#[async_generic]
+#[allow(unused)]
+fn is_development_trust(
+    chain_der: &[Vec<u8>],
+    cert_der: &[u8],
+    signing_time_epoc: Option<i64>,
+) -> bool {
+    if _sync {
+        #[cfg(not(feature = "openssl"))]
+        {
+            false
+        }
+
+        #[cfg(feature = "openssl")]
+        {
+            DEVELOPMENT_TRUST_HANDLER.as_ref().is_some_and(|th| {
+                verify_trust(th, chain_der, cert_der, signing_time_epoc).unwrap_or_default()
+            })
+        }
+    } else {
+        #[cfg(target_arch = "wasm32")]
+        {
+            match DEVELOPMENT_WEB_TRUST_HANDLER.as_ref() {
+                Some(th) => verify_trust_async(th, chain_der, cert_der, signing_time_epoc)
+                    .await
+                    .unwrap_or_default(),
+                None => false,
+            }
+        }
+
+        #[cfg(feature = "openssl")]
+        {
+            DEVELOPMENT_TRUST_HANDLER.as_ref().is_some_and(|th| {
+                verify_trust(th, chain_der, cert_der, signing_time_epoc).unwrap_or_default()
+            })
+        }
+
+        #[cfg(all(not(feature = "openssl"), not(target_arch = "wasm32")))]
+        {
+            false
+        }
+    }
+}
+
 #[async_generic(async_signature( th: &dyn TrustHandlerConfig, chain_der: &[Vec<u8>], cert_der: &[u8], signing_time_epoc: Option<i64>, validation_log: &mut impl StatusTracker))]
 #[allow(unused)]
 fn check_trust(
@@ -858,6 +969,10 @@ fn check_trust(
         Err(e) => return Err(e),
     }
 
+    // add the configured intermediates the certificate chain is missing
+    let chain = complete_chain(chain_der, cert_der, &th.get_intermediates());
+    let chain_der = chain.as_slice();
+
     // is the certificate trusted
 
     let verify_result: Result<bool> = if _sync {
@@ -898,6 +1013,22 @@ fn check_trust(
                     log_item!("Cose_Sign1", "signing certificate trusted", "verify_cose")
                         .validation_status(validation_status::SIGNING_CREDENTIAL_TRUSTED);
                 validation_log.log_silent(log_item);
+
+                let development_trust = if _sync {
+                    is_development_trust(chain_der, cert_der, signing_time_epoc)
+                } else {
+                    is_development_trust_async(chain_der, cert_der, signing_time_epoc).await
+                };
+
+                if development_trust {
+                    let log_item = log_item!(
+                        "Cose_Sign1",
+                        "signing certificate trusted by development anchors",
+                        "verify_cose"
+                    )
+                    .validation_status(validation_status::STATUS_DEVELOPMENT_TRUST);
+                    validation_log.log_silent(log_item);
+                }
                 Ok(())
             } else {
                 let log_item =
@@ -1172,6 +1303,199 @@ pub(crate) fn get_signing_info(
     }
 }
 
+/// Get the bytes covered by a COSE_SIGN1 signature
+/// cose_bytes - byte array containing the raw COSE_SIGN1 data
+/// data:  data that was used to create the cose_bytes
+/// returns - the "to be signed" Sig_structure bytes, the signature, the signing alg
+/// and the DER bytes of the signing certificate
+pub(crate) fn get_signed_payload(
+    cose_bytes: &[u8],
+    data: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>, SigningAlg, Vec<u8>)> {
+    let mut validation_log = OneShotStatusTracker::new();
+    let sign1 = get_cose_sign1(cose_bytes, data, &mut validation_log)?;
+
+    let alg = get_signing_alg(&sign1)?;
+    let signing_cert = get_sign_cert(&sign1)?;
+
+    // claim signatures do not use additional data
+    let tbs = sig_structure_data(
+        coset::SignatureContext::CoseSign1,
+        sign1.protected.clone(),
+        None,
+        &[],
+        data,
+    );
+
+    Ok((tbs, sign1.signature, alg, signing_cert))
+}
+
+/// Finds the first of several trust lists that trusts the signing certificate of a COSE_SIGN1
+/// cose_bytes - byte array containing the raw COSE_SIGN1 data
+/// data:  data that was used to create the cose_bytes
+/// lists - the candidate trust lists, in the order they are tried
+/// returns - the identifier of the matching trust list, None if the signature does not verify,
+/// the signing certificate is invalid or revoked, or no list trusts the signer
+#[cfg(feature = "openssl")]
+pub(crate) fn matching_trust_list<'a>(
+    cose_bytes: &[u8],
+    data: &[u8],
+    lists: &'a [crate::settings::TrustList],
+) -> Result<Option<&'a str>> {
+    use crate::{
+        openssl::OpenSSLTrustHandlerConfig,
+        status_tracker::{report_has_status, DetailedStatusTracker},
+        trust_handler::load_trust_from_data,
+    };
+
+    // every list is checked with the EKU configuration and allowed list of the trust settings
+    let configure = |mut th: OpenSSLTrustHandlerConfig| -> Result<OpenSSLTrustHandlerConfig> {
+        if let Some(tc) = get_settings_value::<Option<String>>("trust.trust_config")? {
+            th.load_configuration(&mut Cursor::new(tc.as_bytes()))?;
+        }
+        if let Some(al) = get_settings_value::<Option<String>>("trust.allowed_list")? {
+            th.load_allowed_list(&mut Cursor::new(al.as_bytes()))?;
+        }
+        Ok(th)
+    };
+    let mut th = OpenSSLTrustHandlerConfig::new();
+    th.clear();
+    let th = configure(th)?;
+
+    let mut validation_log = DetailedStatusTracker::new();
+    let sign1 = get_cose_sign1(cose_bytes, data, &mut validation_log)?;
+
+    // the claim signature must verify before its signer can match a list
+    if !verify_cose(cose_bytes, data, b"", false, &th, &mut validation_log)
+        .is_ok_and(|vi| vi.validated)
+    {
+        return Ok(None);
+    }
+
+    let certs = get_sign_certs(&sign1)?;
+    let cert_der = certs.first().ok_or(Error::CoseMissingKey)?;
+    let tst_info = get_timestamp_info(&sign1, data);
+    if check_cert(cert_der, &th, &mut validation_log, tst_info.as_ref().ok()).is_err() {
+        return Ok(None);
+    }
+
+    // a revoked signing certificate matches no list
+    if check_ocsp_status(cose_bytes, data, &th, &mut validation_log).is_err()
+        || report_has_status(
+            validation_log.get_log(),
+            validation_status::SIGNING_CREDENTIAL_REVOKED,
+        )
+    {
+        return Ok(None);
+    }
+
+    // add the configured intermediates the certificate chain is missing
+    let intermediates = match get_settings_value::<Option<String>>("trust.intermediates")? {
+        Some(pem) => load_trust_from_data(pem.as_bytes())?,
+        None => Vec::new(),
+    };
+    let chain = complete_chain(&certs[1..], cert_der, &intermediates);
+    let signing_time_epoc = tst_info_result_to_timestamp(&tst_info);
+
+    for list in lists {
+        let Some(th) = anchor_trust_handler(list.anchors()) else {
+            continue;
+        };
+        if verify_trust(&configure(th)?, &chain, cert_der, signing_time_epoc).unwrap_or_default() {
+            return Ok(Some(list.id()));
+        }
+    }
+    Ok(None)
+}
+
+/// Verifies the signature of a COSE_SIGN1 with a public key instead of its signing certificate
+/// cose_bytes - byte array containing the raw COSE_SIGN1 data
+/// data:  data that was used to create the cose_bytes
+/// public_key - DER or PEM encoded SubjectPublicKeyInfo of the expected signer
+/// returns - true if the signature was made with the public key
+#[cfg(feature = "openssl")]
+pub(crate) fn verify_cose_with_public_key(
+    cose_bytes: &[u8],
+    data: &[u8],
+    public_key: &[u8],
+) -> Result<bool> {
+    let spki = match x509_parser::pem::parse_x509_pem(public_key) {
+        Ok((_, pem)) if pem.label == "PUBLIC KEY" => pem.contents,
+        _ => public_key.to_vec(),
+    };
+    SubjectPublicKeyInfo::from_der(&spki)
+        .map_err(|_err| Error::BadParam("not a public key".to_string()))?;
+
+    let mut validation_log = OneShotStatusTracker::new();
+    let sign1 = get_cose_sign1(cose_bytes, data, &mut validation_log)?;
+    let alg = get_signing_alg(&sign1)?;
+    check_sig(&sign1.signature, alg)?;
+
+    // claim signatures do not use additional data
+    let tbs = sig_structure_data(
+        coset::SignatureContext::CoseSign1,
+        sign1.protected.clone(),
+        None,
+        &[],
+        data,
+    );
+
+    // a key of another type cannot have made the signature
+    Ok(get_validator(alg)
+        .validate(&sign1.signature, &tbs, &spki)
+        .unwrap_or(false))
+}
+
+/// Returns the RFC 3161 time-stamp response in the unprotected header of a COSE_SIGN1
+/// cose_bytes - byte array containing the raw COSE_SIGN1 data
+/// data:  data that was used to create the cose_bytes
+/// returns - the time-stamp response and the data it time-stamps, or None if the
+/// signature is not time-stamped
+pub(crate) fn get_signature_timestamp(
+    cose_bytes: &[u8],
+    data: &[u8],
+) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let mut validation_log = OneShotStatusTracker::new();
+    let sign1 = get_cose_sign1(cose_bytes, data, &mut validation_log)?;
+
+    let sig_tst = match sign1
+        .unprotected
+        .rest
+        .iter()
+        .find(|x: &&(Label, Value)| x.0 == Label::Text("sigTst".to_string()))
+    {
+        Some((_, t)) => t,
+        None => return Ok(None),
+    };
+
+    let tst_container: crate::time_stamp::TstContainer =
+        serde_cbor::from_slice(&serde_cbor::to_vec(sig_tst)?)
+            .map_err(|_err| Error::CoseInvalidTimeStamp)?;
+
+    Ok(tst_container.tst_tokens.into_iter().next().map(|token| {
+        (
+            token.val,
+            crate::time_stamp::cose_countersign_data(data, &sign1.protected),
+        )
+    }))
+}
+
+/// Returns the unprotected header of a COSE_SIGN1 as a CBOR map
+/// cose_bytes - byte array containing the raw COSE_SIGN1 data
+/// data:  data that was used to create the cose_bytes
+/// returns - the header map, including any non-standard fields
+pub(crate) fn get_unprotected_header(cose_bytes: &[u8], data: &[u8]) -> Result<serde_cbor::Value> {
+    let mut validation_log = OneShotStatusTracker::new();
+    let sign1 = get_cose_sign1(cose_bytes, data, &mut validation_log)?;
+
+    let header = sign1
+        .unprotected
+        .to_cbor_value()
+        .map_err(|_err| Error::CoseSignature)?;
+
+    Ok(serde_cbor::value::to_value(header)?)
+}
+
 /// Validate a COSE_SIGN1 byte vector and verify against expected data
 /// cose_bytes - byte array containing the raw COSE_SIGN1 data
 /// data:  data that was used to create the cose_bytes, these must match
@@ -1525,6 +1849,115 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_cert_eku() {
+        use crate::settings::{set_settings_value, tests::PROTECT};
+
+        let _protect = PROTECT.lock().unwrap();
+        let th = crate::openssl::OpenSSLTrustHandlerConfig::new();
+
+        let pem_to_der = |pem: &[u8]| {
+            openssl::x509::X509::from_pem(pem)
+                .unwrap()
+                .to_der()
+                .unwrap()
+        };
+        let server_auth_cert = pem_to_der(include_bytes!(
+            "../tests/fixtures/certs/es256_server_auth_eku.pub"
+        ));
+        let document_signing_cert = pem_to_der(include_bytes!(
+            "../tests/fixtures/certs/es256_document_signing_eku.pub"
+        ));
+
+        // the serverAuth EKU is not allowed for signing
+        let mut validation_log = DetailedStatusTracker::new();
+        assert!(check_cert(&server_auth_cert, &th, &mut validation_log, None).is_err());
+        let codes: Vec<_> = validation_log
+            .get_log()
+            .iter()
+            .map(|item| item.validation_status.as_deref())
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                Some(validation_status::SIGNING_CREDENTIAL_INVALID),
+                Some(validation_status::STATUS_INVALID_EKU)
+            ]
+        );
+
+        // the documentSigning EKU is
+        let mut validation_log = DetailedStatusTracker::new();
+        assert!(check_cert(&document_signing_cert, &th, &mut validation_log, None).is_ok());
+        assert!(validation_log.get_log().is_empty());
+
+        // the EKU check can be relaxed for testing
+        set_settings_value("verify.check_cert_usage", false).unwrap();
+        let mut validation_log = DetailedStatusTracker::new();
+        let result = check_cert(&server_auth_cert, &th, &mut validation_log, None);
+        set_settings_value("verify.check_cert_usage", true).unwrap();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_development_trust() {
+        use crate::settings::{
+            tests::{ResetSettings, PROTECT},
+            TrustConfig,
+        };
+
+        let _protect = PROTECT.lock().unwrap();
+        let _reset = ResetSettings;
+
+        let has_development_status = |validation_log: &DetailedStatusTracker| {
+            validation_log.get_log().iter().any(|item| {
+                item.validation_status.as_deref()
+                    == Some(validation_status::STATUS_DEVELOPMENT_TRUST)
+            })
+        };
+
+        let cert_dir = crate::utils::test::fixture_path("certs");
+        let (signer, _) = temp_signer::get_ec_signer(&cert_dir, SigningAlg::Es256, None);
+        let certs = signer.certs().unwrap();
+
+        // the development preset trusts the test anchors and says so
+        TrustConfig::development().load().unwrap();
+        let store = crate::store::Store::new();
+        let mut validation_log = DetailedStatusTracker::new();
+        let result = check_trust(
+            store.trust_handler(),
+            &certs[1..],
+            &certs[0],
+            None,
+            &mut validation_log,
+        );
+        assert!(result.is_ok());
+        assert!(has_development_status(&validation_log));
+
+        // a certificate trusted through a production anchor is not flagged
+        TrustConfig::production().load().unwrap();
+        let cert_pem = include_bytes!("../tests/fixtures/certs/es256_document_signing_eku.pub");
+        let cert = openssl::x509::X509::from_pem(cert_pem)
+            .unwrap()
+            .to_der()
+            .unwrap();
+        let mut th = crate::openssl::OpenSSLTrustHandlerConfig::new();
+        th.clear();
+        th.load_allowed_list(&mut Cursor::new(cert_pem)).unwrap();
+        let mut validation_log = DetailedStatusTracker::new();
+        assert!(check_trust(&th, &[], &cert, None, &mut validation_log).is_ok());
+        assert!(!has_development_status(&validation_log));
+
+        // test anchors configured for production are never trusted silently
+        let mut th = crate::openssl::OpenSSLTrustHandlerConfig::new();
+        th.clear();
+        th.append_private_trust_data(&mut Cursor::new(DEVELOPMENT_TRUST_ANCHORS))
+            .unwrap();
+        let mut validation_log = DetailedStatusTracker::new();
+        let result = check_trust(&th, &certs[1..], &certs[0], None, &mut validation_log);
+        assert!(result.is_ok());
+        assert!(has_development_status(&validation_log));
+    }
+
     #[test]
     fn test_no_timestamp() {
         let mut validation_log = DetailedStatusTracker::new();
@@ -1611,5 +2044,153 @@ pub mod tests {
 
         assert_eq!(ocsp_rsp_data, ocsp_stapled.as_slice());
     }
-}
 
+    // Signs the data the way other COSE implementations may, with the given headers.
+    //
+    // The variants are built synthetically here with coset and the test signer, they are not
+    // signatures captured from other implementations.
+    fn sign_with_headers(
+        protected: coset::HeaderBuilder,
+        unprotected: coset::HeaderBuilder,
+        data: &[u8],
+        attach_payload: bool,
+    ) -> Vec<u8> {
+        let signer = crate::utils::test::temp_signer();
+        let protected = protected.algorithm(iana::Algorithm::PS256).build();
+
+        let builder = coset::CoseSign1Builder::new()
+            .protected(protected)
+            .unprotected(unprotected.build());
+        let builder = if attach_payload {
+            builder
+                .payload(data.to_vec())
+                .create_signature(b"", |tbs| signer.sign(tbs).unwrap())
+        } else {
+            builder.create_detached_signature(data, b"", |tbs| signer.sign(tbs).unwrap())
+        };
+
+        builder.build().to_tagged_vec().unwrap()
+    }
+
+    fn x5chain() -> Value {
+        let certs = crate::utils::test::temp_signer().certs().unwrap();
+        Value::Array(certs.into_iter().map(Value::Bytes).collect())
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_verify_cose_interop_headers() {
+        let th = crate::openssl::OpenSSLTrustHandlerConfig::new();
+        let data = b"some claim bytes";
+        let x5chain_label = iana::HeaderParameter::X5Chain.to_i64();
+
+        // synthetic variants of the headers other implementations write
+        let variants = [
+            // explicit payload content type as text
+            sign_with_headers(
+                coset::HeaderBuilder::new()
+                    .content_type("application/cbor".to_string())
+                    .value(x5chain_label, x5chain()),
+                coset::HeaderBuilder::new(),
+                data,
+                false,
+            ),
+            // explicit payload content type as a CoAP content format
+            sign_with_headers(
+                coset::HeaderBuilder::new()
+                    .content_format(iana::CoapContentFormat::Cbor)
+                    .value(x5chain_label, x5chain()),
+                coset::HeaderBuilder::new(),
+                data,
+                false,
+            ),
+            // certificates in the unprotected header using the integer label
+            sign_with_headers(
+                coset::HeaderBuilder::new(),
+                coset::HeaderBuilder::new().value(x5chain_label, x5chain()),
+                data,
+                false,
+            ),
+            // payload embedded rather than detached
+            sign_with_headers(
+                coset::HeaderBuilder::new().value(x5chain_label, x5chain()),
+                coset::HeaderBuilder::new(),
+                data,
+                true,
+            ),
+        ];
+
+        for cose_bytes in variants {
+            let mut validation_log = DetailedStatusTracker::new();
+            let result =
+                verify_cose(&cose_bytes, data, b"", false, &th, &mut validation_log).unwrap();
+            assert!(result.validated);
+            assert_eq!(result.alg, Some(SigningAlg::Ps256));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "openssl_sign")]
+    fn test_matching_trust_list_requires_valid_signature() {
+        use crate::settings::TrustList;
+
+        let signer = crate::create_signer::from_chain(
+            &[
+                include_bytes!("../tests/fixtures/certs/chain/es256_signer.pem").as_slice(),
+                include_bytes!("../tests/fixtures/certs/chain/es256_intermediate.pem").as_slice(),
+            ],
+            include_bytes!("../tests/fixtures/certs/es256.pem"),
+            SigningAlg::Es256,
+            None,
+        )
+        .unwrap();
+
+        let mut claim = crate::claim::Claim::new("trust_list_test", Some("contentauth"));
+        claim.build().unwrap();
+        let claim_bytes = claim.data().unwrap();
+        let cose_bytes =
+            crate::cose_sign::sign_claim(&claim_bytes, signer.as_ref(), 10000).unwrap();
+
+        let lists = [TrustList::new(
+            "es256",
+            include_bytes!("../tests/fixtures/certs/chain/es256_root.pem").as_slice(),
+        )];
+        assert_eq!(
+            matching_trust_list(&cose_bytes, &claim_bytes, &lists).unwrap(),
+            Some("es256")
+        );
+
+        // a genuine certificate does not match when the signed claim was tampered with
+        let mut tampered = claim_bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0xff;
+        assert_eq!(
+            matching_trust_list(&cose_bytes, &tampered, &lists).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn test_verify_cose_embedded_payload_mismatch() {
+        let th = crate::openssl::OpenSSLTrustHandlerConfig::new();
+        let data = b"some claim bytes";
+
+        let cose_bytes = sign_with_headers(
+            coset::HeaderBuilder::new().value(iana::HeaderParameter::X5Chain.to_i64(), x5chain()),
+            coset::HeaderBuilder::new(),
+            b"some other bytes",
+            true,
+        );
+
+        let mut validation_log = DetailedStatusTracker::new();
+        assert!(matches!(
+            verify_cose(&cose_bytes, data, b"", false, &th, &mut validation_log),
+            Err(Error::CoseSignature)
+        ));
+        assert_eq!(
+            validation_log.get_log()[0].validation_status.as_deref(),
+            Some(validation_status::CLAIM_SIGNATURE_MISMATCH)
+        );
+    }
+}
@@ -19,8 +19,8 @@
 use std::path::Path;
 
 use crate::{
-    error::Result,
-    openssl::{EcSigner, EdSigner, RsaSigner},
+    error::{Error, Result},
+    openssl::{check_pem_chain_links, EcSigner, EdSigner, RsaSigner},
     signer::ConfigurableSigner,
     Signer, SigningAlg,
 };
@@ -87,3 +87,136 @@ pub fn from_files<P: AsRef<Path>>(
         )?),
     })
 }
+
+/// Creates a [`Signer`] instance from a certificate chain given as separate
+/// PEM blocks and a private key.
+///
+/// The chain must start with the signing certificate and each following
+/// entry must be the issuer of the one before it. The links are checked
+/// before the signer is created, and a broken or out of order chain is
+/// reported as [`Error::BadParam`] naming the offending certificates.
+///
+/// # Arguments
+///
+/// * `chain` - PEM certificates, ordered from the signing certificate towards the root
+/// * `pkey` - Private key for the signing certificate
+/// * `alg` - Format for signing
+/// * `tsa_url` - Optional URL for a timestamp authority
+pub fn from_chain(
+    chain: &[&[u8]],
+    pkey: &[u8],
+    alg: SigningAlg,
+    tsa_url: Option<String>,
+) -> Result<Box<dyn Signer>> {
+    if chain.is_empty() {
+        return Err(Error::BadParam("certificate chain is empty".to_string()));
+    }
+    check_pem_chain_links(chain)?;
+
+    let mut signcert = Vec::new();
+    for pem in chain {
+        signcert.extend_from_slice(pem);
+        if !signcert.ends_with(b"\n") {
+            signcert.push(b'\n');
+        }
+    }
+    from_keys(&signcert, pkey, alg, tsa_url)
+}
+
+/// Creates a [`Signer`] instance from a certificate chain stored in
+/// separate PEM files and a private key file.
+///
+/// See [`from_chain`] for how the chain is checked.
+///
+/// # Arguments
+///
+/// * `chain_paths` - Paths to the PEM certificate files, signing certificate first
+/// * `pkey_path` - Path to the private key file
+/// * `alg` - Format for signing
+/// * `tsa_url` - Optional URL for a timestamp authority
+#[cfg(feature = "file_io")]
+pub fn from_chain_files<P: AsRef<Path>>(
+    chain_paths: &[P],
+    pkey_path: P,
+    alg: SigningAlg,
+    tsa_url: Option<String>,
+) -> Result<Box<dyn Signer>> {
+    let chain = chain_paths
+        .iter()
+        .map(std::fs::read)
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(Error::IoError)?;
+    let chain: Vec<&[u8]> = chain.iter().map(Vec::as_slice).collect();
+    let pkey = std::fs::read(pkey_path).map_err(Error::IoError)?;
+
+    from_chain(&chain, &pkey, alg, tsa_url)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const SIGNER: &[u8] = include_bytes!("../tests/fixtures/certs/chain/es256_signer.pem");
+    const INTERMEDIATE: &[u8] =
+        include_bytes!("../tests/fixtures/certs/chain/es256_intermediate.pem");
+    const ROOT: &[u8] = include_bytes!("../tests/fixtures/certs/chain/es256_root.pem");
+    const OTHER_ROOT: &[u8] = include_bytes!("../tests/fixtures/certs/chain/other_root.pem");
+    const PKEY: &[u8] = include_bytes!("../tests/fixtures/certs/es256.pem");
+
+    #[test]
+    fn test_from_chain() {
+        let signer =
+            from_chain(&[SIGNER, INTERMEDIATE, ROOT], PKEY, SigningAlg::Es256, None).unwrap();
+        assert_eq!(signer.certs().unwrap().len(), 3);
+        assert_eq!(signer.alg(), SigningAlg::Es256);
+        assert!(!signer.sign(b"some data").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_chain_out_of_order() {
+        let err = from_chain(&[SIGNER, ROOT, INTERMEDIATE], PKEY, SigningAlg::Es256, None)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            Error::BadParam(msg)
+                if msg.contains("certificate 2 (") && msg.contains("certificate 3 (")
+        ));
+    }
+
+    #[test]
+    fn test_from_chain_broken_link() {
+        // same subject as the real root, but it did not issue the intermediate
+        let err = from_chain(
+            &[SIGNER, INTERMEDIATE, OTHER_ROOT],
+            PKEY,
+            SigningAlg::Es256,
+            None,
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(err, Error::BadParam(msg) if msg.contains("CN=Intermediate CA")));
+
+        assert!(matches!(
+            from_chain(&[], PKEY, SigningAlg::Es256, None),
+            Err(Error::BadParam(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_from_chain_files() {
+        let certs = crate::utils::test::fixture_path("certs");
+        let chain = [
+            "es256_signer.pem",
+            "es256_intermediate.pem",
+            "es256_root.pem",
+        ]
+        .map(|name| certs.join("chain").join(name));
+        let signer =
+            from_chain_files(&chain, certs.join("es256.pem"), SigningAlg::Es256, None).unwrap();
+        assert_eq!(signer.certs().unwrap().len(), 3);
+    }
+}
@@ -150,6 +150,10 @@ pub enum Error {
     #[error("COSE TimeStamp Authority failure")]
     CoseTimeStampAuthority,
 
+    /// The signature has no trusted time stamp, and one is required.
+    #[error("COSE signature has no trusted time stamp")]
+    CoseTimeStampRequired,
+
     #[error("COSE Signature too big for JUMBF box")]
     CoseSigboxTooSmall,
 
@@ -178,6 +182,10 @@ pub enum Error {
     #[error("could not create valid JUMBF for claim")]
     JumbfCreationError,
 
+    /// The manifest store is larger than the maximum size set for it, even without thumbnails.
+    #[error("manifest store of {size} bytes exceeds the maximum of {max_size} bytes")]
+    ManifestTooLarge { size: usize, max_size: usize },
+
     #[error("thread receive error")]
     ThreadReceiveError,
 
@@ -193,6 +201,9 @@ pub enum Error {
     #[error("must fetch remote manifests from url")]
     RemoteManifestUrl(String),
 
+    #[error("could not reach {0}")]
+    NetworkUnreachable(String),
+
     #[error("stopped because of logged error")]
     LogStop,
 
@@ -209,6 +220,10 @@ pub enum Error {
     #[error("ingredient file not found")]
     IngredientNotFound,
 
+    /// A claim is reachable from itself through its ingredients, so they cannot be validated.
+    #[error("ingredient cycle: claim {0} is its own ingredient")]
+    IngredientCycle(String),
+
     #[error("file not found: {0}")]
     FileNotFound(String),
 
@@ -236,12 +251,34 @@ pub enum Error {
     #[error("PDF read error")]
     PdfReadError,
 
+    /// An object of a PDF could not be read. `object_number` and `generation` identify the
+    /// offending object and `offset` is its byte offset in the PDF, when known.
+    #[error("PDF object {object_number} {generation} could not be read (offset: {offset:?})")]
+    PdfObjectReadError {
+        object_number: u32,
+        generation: u16,
+        offset: Option<usize>,
+    },
+
+    /// The manifest store of a PDF could neither be patched in place nor rewritten in an
+    /// incremental update.
+    #[error("the manifest store in the PDF could not be replaced: {0}")]
+    PdfManifestReplaceFailed(String),
+
+    /// A signed manifest store compressed to more bytes than the compressed manifest it replaces
+    /// in a PDF.
+    #[error("compressed manifest store needs {needed} bytes but the PDF reserves {reserved}")]
+    PdfCompressedManifestTooLarge { needed: usize, reserved: usize },
+
     #[error(transparent)]
     InvalidClaim(#[from] crate::store::InvalidClaimError),
 
     #[error("asset could not be parsed: {0}")]
     InvalidAsset(String),
 
+    #[error("asset does not match the declared format: {0}")]
+    FormatMismatch(String),
+
     #[error(transparent)]
     JumbfParseError(#[from] crate::jumbf::boxes::JumbfParseError),
 
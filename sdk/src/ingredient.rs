@@ -43,10 +43,13 @@ use crate::{
     status_tracker::{log_item, DetailedStatusTracker, StatusTracker},
     store::Store,
     utils::{base64, xmp_inmemory_utils::XmpInfo},
-    validation_status::{self, status_for_store, ValidationStatus},
+    validation_status::{
+        self, informational_statuses, status_for_store, success_statuses, ValidationResults,
+        ValidationStatus,
+    },
 };
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
 /// An `Ingredient` is any external asset that has been used in the creation of an image.
 pub struct Ingredient {
@@ -101,6 +104,14 @@ pub struct Ingredient {
     #[serde(skip_serializing_if = "Option::is_none")]
     validation_status: Option<Vec<ValidationStatus>>,
 
+    /// Validation results of the ingredient's manifest, computed when the ingredient was
+    /// created from an asset or read from a v3 ingredient assertion.
+    ///
+    /// These are not written to the manifest: v3 ingredient assertions belong in v2 claims,
+    /// and the claims signed by this SDK are v1 claims, which record `validation_status`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation_results: Option<ValidationResults>,
+
     /// A reference to the actual data of the ingredient.
     #[serde(skip_serializing_if = "Option::is_none")]
     data: Option<ResourceRef>,
@@ -275,6 +286,12 @@ impl Ingredient {
         self.validation_status.as_deref()
     }
 
+    /// Returns the [`ValidationResults`] of the ingredient's manifest, computed when the
+    /// ingredient was created from an asset or read from a v3 ingredient assertion, if any.
+    pub fn validation_results(&self) -> Option<&ValidationResults> {
+        self.validation_results.as_ref()
+    }
+
     /// Returns a reference to [`Metadata`] if it exists.
     pub fn metadata(&self) -> Option<&Metadata> {
         self.metadata.as_ref()
@@ -621,6 +638,12 @@ impl Ingredient {
                         }
                     }
                     self.active_manifest = Some(claim.label().to_string());
+
+                    // the full results of this ingestion, successes included
+                    let mut results = success_statuses(validation_log);
+                    results.extend(informational_statuses(validation_log));
+                    results.extend(statuses.iter().cloned());
+                    self.validation_results = Some(ValidationResults::from_statuses(&results));
                 }
 
                 if let Some(bytes) = manifest_bytes {
@@ -660,7 +683,10 @@ impl Ingredient {
                     .get_log()
                     .iter()
                     .filter_map(ValidationStatus::from_validation_item)
-                    .filter(|s| !validation_status::is_success(s.code()))
+                    .filter(|s| {
+                        !validation_status::is_success(s.code())
+                            && !validation_status::is_informational(s.code())
+                    })
                     .collect();
                 self.validation_status = if statuses.is_empty() {
                     None
@@ -1015,7 +1041,7 @@ impl Ingredient {
         };
 
         let active_manifest = ingredient_assertion
-            .c2pa_manifest
+            .manifest_uri()
             .and_then(|hash_url| manifest_label_from_uri(&hash_url.url()));
 
         debug!(
@@ -1106,6 +1132,7 @@ impl Ingredient {
         if !validation_status.is_empty() {
             ingredient.validation_status = Some(validation_status)
         }
+        ingredient.validation_results = ingredient_assertion.validation_results;
         ingredient.metadata = ingredient_assertion.metadata;
         ingredient.description = ingredient_assertion.description;
         ingredient.informational_uri = ingredient_assertion.informational_uri;
@@ -1119,6 +1146,7 @@ impl Ingredient {
         claim: &mut Claim,
         redactions: Option<Vec<String>>,
         resources: Option<&ResourceStore>, // use alternate resource store (for Builder model)
+        with_thumbnail: bool,
     ) -> Result<HashedUri> {
         let mut thumbnail = None;
         // for Builder model, ingredient resources may be in the manifest
@@ -1212,7 +1240,9 @@ impl Ingredient {
 
         // if the ingredient defines a thumbnail, add it to the claim
         // otherwise use the parent claim thumbnail if available
-        if let Some(thumb_ref) = self.thumbnail_ref() {
+        if !with_thumbnail {
+            thumbnail = None;
+        } else if let Some(thumb_ref) = self.thumbnail_ref() {
             let hash_url = match manifest_label_from_uri(&thumb_ref.identifier) {
                 Some(_) => {
                     let hash = match thumb_ref.hash.as_ref() {
@@ -1280,12 +1310,12 @@ impl Ingredient {
         self.document_id
             .clone_into(&mut ingredient_assertion.document_id);
         ingredient_assertion.c2pa_manifest = c2pa_manifest;
-        ingredient_assertion.relationship = self.relationship.clone();
-        ingredient_assertion.thumbnail = thumbnail;
-        ingredient_assertion.metadata.clone_from(&self.metadata);
         ingredient_assertion
             .validation_status
             .clone_from(&self.validation_status);
+        ingredient_assertion.relationship = self.relationship.clone();
+        ingredient_assertion.thumbnail = thumbnail;
+        ingredient_assertion.metadata.clone_from(&self.metadata);
         ingredient_assertion.data = data;
         ingredient_assertion
             .description
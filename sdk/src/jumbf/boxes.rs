@@ -341,6 +341,20 @@ impl JUMBFSuperBox {
             .as_any()
             .downcast_ref::<JUMBFEmbeddedFileDescriptionBox>()
     }
+
+    // find the first data box of type T regardless of where it sits in the superbox
+    pub fn find_data_box<T: 'static>(&self) -> Option<&T> {
+        self.data_boxes
+            .iter()
+            .find_map(|b| b.as_ref().as_any().downcast_ref::<T>())
+    }
+
+    // iterate over the data boxes that are superboxes, skipping padding and content boxes
+    pub fn data_superboxes(&self) -> impl Iterator<Item = &JUMBFSuperBox> {
+        self.data_boxes
+            .iter()
+            .filter_map(|b| b.as_ref().as_any().downcast_ref::<JUMBFSuperBox>())
+    }
 }
 
 impl BMFFBox for JUMBFSuperBox {
@@ -1481,6 +1495,14 @@ impl Cai {
         }
     }
 
+    /// Creates a manifest store superbox with a custom label and UUID, for private manifest
+    /// stores.
+    pub fn with_label(label: &str, uuid: &str) -> Self {
+        Cai {
+            sbox: JUMBFSuperBox::new(label, Some(uuid)),
+        }
+    }
+
     pub fn from(in_box: JUMBFSuperBox) -> Self {
         Cai { sbox: in_box }
     }
@@ -13,7 +13,7 @@
 
 use std::{
     collections::HashMap,
-    io::{Cursor, Read, Seek},
+    io::{Cursor, Read, Seek, Write},
 };
 #[cfg(feature = "file_io")]
 use std::{
@@ -27,41 +27,46 @@ use lazy_static::lazy_static;
 use crate::asset_handlers::jpeg_io::JpegIO;
 #[cfg(feature = "mp3")]
 use crate::asset_handlers::mp3_io::Mp3IO;
+#[cfg(feature = "pdf")]
+use crate::asset_handlers::pdf_io::PdfIO;
 #[cfg(feature = "png")]
 use crate::asset_handlers::png_io::PngIO;
 #[cfg(feature = "riff")]
 use crate::asset_handlers::riff_io::RiffIO;
 #[cfg(feature = "tiff")]
 use crate::asset_handlers::tiff_io::TiffIO;
-#[cfg(feature = "pdf")]
-use crate::asset_handlers::pdf_io::PdfIO;
 use crate::{
     asset_handlers::{bmff_io::BmffIO, c2pa_io::C2paIO, gif_io::GifIO, svg_io::SvgIO},
     asset_io::{AssetIO, CAIRead, CAIReadWrite, CAIReader, CAIWriter, HashObjectPositions},
     error::{Error, Result},
 };
 
+// the registered asset handlers, one per family of formats
+fn asset_handlers() -> Vec<Box<dyn AssetIO>> {
+    vec![
+        #[cfg(feature = "pdf")]
+        Box::new(PdfIO::new("")),
+        Box::new(BmffIO::new("")),
+        Box::new(C2paIO::new("")),
+        #[cfg(feature = "jpeg")]
+        Box::new(JpegIO::new("")),
+        #[cfg(feature = "png")]
+        Box::new(PngIO::new("")),
+        #[cfg(feature = "riff")]
+        Box::new(RiffIO::new("")),
+        Box::new(SvgIO::new("")),
+        #[cfg(feature = "tiff")]
+        Box::new(TiffIO::new("")),
+        #[cfg(feature = "mp3")]
+        Box::new(Mp3IO::new("")),
+        Box::new(GifIO::new("")),
+    ]
+}
+
 // initialize asset handlers
 lazy_static! {
     static ref ASSET_HANDLERS: HashMap<String, Box<dyn AssetIO>> = {
-        let handlers: Vec<Box<dyn AssetIO>> = vec![
-            #[cfg(feature = "pdf")]
-            Box::new(PdfIO::new("")),
-            Box::new(BmffIO::new("")),
-            Box::new(C2paIO::new("")),
-            #[cfg(feature = "jpeg")]
-            Box::new(JpegIO::new("")),
-            #[cfg(feature = "png")]
-            Box::new(PngIO::new("")),
-            #[cfg(feature = "riff")]
-            Box::new(RiffIO::new("")),
-            Box::new(SvgIO::new("")),
-            #[cfg(feature = "tiff")]
-            Box::new(TiffIO::new("")),
-            #[cfg(feature = "mp3")]
-            Box::new(Mp3IO::new("")),
-            Box::new(GifIO::new("")),
-        ];
+        let handlers = asset_handlers();
 
         let mut handler_map = HashMap::new();
 
@@ -80,24 +85,7 @@ lazy_static! {
 // initialize streaming write handlers
 lazy_static! {
     static ref CAI_WRITERS: HashMap<String, Box<dyn CAIWriter>> = {
-        let handlers: Vec<Box<dyn AssetIO>> = vec![
-            #[cfg(feature = "pdf")]
-            Box::new(PdfIO::new("")),
-            Box::new(BmffIO::new("")),
-            Box::new(C2paIO::new("")),
-            #[cfg(feature = "jpeg")]
-            Box::new(JpegIO::new("")),
-            #[cfg(feature = "png")]
-            Box::new(PngIO::new("")),
-            #[cfg(feature = "riff")]
-            Box::new(RiffIO::new("")),
-            Box::new(SvgIO::new("")),
-            #[cfg(feature = "tiff")]
-            Box::new(TiffIO::new("")),
-            #[cfg(feature = "mp3")]
-            Box::new(Mp3IO::new("")),
-            Box::new(GifIO::new("")),
-        ];
+        let handlers = asset_handlers();
         let mut handler_map = HashMap::new();
 
         // build handler map
@@ -138,6 +126,23 @@ pub fn load_jumbf_from_stream(asset_type: &str, input_stream: &mut dyn CAIRead)
     }
     Ok(cai_block)
 }
+
+/// Writes the jumbf block of a stream asset to writer, returning the number of bytes written
+pub fn load_jumbf_to_writer(
+    asset_type: &str,
+    input_stream: &mut dyn CAIRead,
+    writer: &mut dyn Write,
+) -> Result<u64> {
+    let len = match get_cailoader_handler(asset_type) {
+        Some(asset_handler) => asset_handler.read_cai_to_writer(input_stream, writer)?,
+        None => return Err(Error::UnsupportedType),
+    };
+    if len == 0 {
+        return Err(Error::JumbfNotFound);
+    }
+    Ok(len)
+}
+
 /// writes the jumbf data in store_bytes
 /// reads an asset of asset_type from reader, adds jumbf data and then writes to writer
 pub fn save_jumbf_to_stream(
@@ -303,7 +308,7 @@ pub(crate) fn object_locations(in_path: &Path) -> Result<Vec<HashObjectPositions
     }
 }
 
-struct CAIReadAdapter<R> {
+pub(crate) struct CAIReadAdapter<R> {
     pub reader: R,
 }
 
@@ -361,6 +366,57 @@ pub fn get_supported_types() -> Vec<String> {
     ASSET_HANDLERS.keys().map(|k| k.to_owned()).collect()
 }
 
+/// Describes a family of asset formats handled by the SDK.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FormatInfo {
+    /// File extensions for the format, e.g. `"jpg"`.
+    pub extensions: Vec<String>,
+    /// MIME types for the format, e.g. `"image/jpeg"`.
+    pub mime_types: Vec<String>,
+    /// Whether manifests can be read from assets of this format.
+    pub can_read: bool,
+    /// Whether manifests can be written to streams of this format.
+    pub can_write: bool,
+}
+
+/// Returns the formats supported by the SDK, one entry for each registered asset handler.
+///
+/// # Example
+/// ```
+/// let pickable: Vec<String> = c2pa::supported_formats()
+///     .into_iter()
+///     .filter(|format| format.can_write)
+///     .flat_map(|format| format.extensions)
+///     .collect();
+/// assert!(!pickable.is_empty());
+/// ```
+pub fn supported_formats() -> Vec<FormatInfo> {
+    asset_handlers()
+        .iter()
+        .map(|handler| {
+            let (mime_types, extensions): (Vec<String>, Vec<String>) = handler
+                .supported_types()
+                .iter()
+                .map(|t| t.to_string())
+                .partition(|t| t.contains('/'));
+
+            FormatInfo {
+                can_read: extensions
+                    .iter()
+                    .chain(&mime_types)
+                    .any(|t| get_cailoader_handler(t).is_some()),
+                can_write: extensions
+                    .iter()
+                    .chain(&mime_types)
+                    .any(|t| get_caiwriter_handler(t).is_some()),
+                extensions,
+                mime_types,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 pub mod tests {
     #![allow(clippy::panic)]
@@ -463,6 +519,33 @@ pub mod tests {
         assert!(supported.iter().any(|s| s == "mp3"));
     }
 
+    #[test]
+    fn test_supported_formats() {
+        let formats = supported_formats();
+
+        // every supported type is reported exactly once
+        let mut types: Vec<String> = formats
+            .iter()
+            .flat_map(|f| f.extensions.iter().chain(&f.mime_types).cloned())
+            .collect();
+        types.sort();
+        let mut expected = get_supported_types();
+        expected.sort();
+        assert_eq!(types, expected);
+
+        let pdf = formats
+            .iter()
+            .find(|f| f.extensions.iter().any(|e| e == "pdf"));
+        if cfg!(feature = "pdf") {
+            let pdf = pdf.unwrap();
+            assert!(pdf.mime_types.iter().any(|m| m == "application/pdf"));
+            assert!(pdf.can_read);
+            assert!(pdf.can_write);
+        } else {
+            assert!(pdf.is_none());
+        }
+    }
+
     fn test_jumbf(asset_type: &str, reader: &mut dyn CAIRead) {
         let mut writer = Cursor::new(Vec::new());
         let store = create_test_store().unwrap();
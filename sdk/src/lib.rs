@@ -107,17 +107,31 @@ pub mod cose_sign;
 #[cfg(feature = "openssl_sign")]
 pub mod create_signer;
 pub mod jumbf_io;
+pub mod metrics;
+#[cfg(feature = "unstable_api")]
+pub mod policy;
 pub mod settings;
+#[cfg(feature = "unstable_api")]
+pub mod validation_cache;
 pub mod validation_status;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
 // Public exports
 pub use assertions::Relationship;
+#[cfg(all(feature = "unstable_api", feature = "pdf"))]
+pub use asset_handlers::pdf_io::{EmbeddedFile, PdfIO, PdfManifestKind};
 #[cfg(feature = "v1_api")]
 pub use asset_io::{CAIRead, CAIReadWrite};
+#[cfg(all(feature = "unstable_api", not(target_arch = "wasm32")))]
+pub use builder::sign_batch;
 #[cfg(feature = "unstable_api")]
-pub use builder::{Builder, ManifestDefinition};
+pub use builder::{
+    convert_to_sidecar, embed_from_sidecar, resign_after_edit, sign_bytes, Builder,
+    DetachedManifest, ManifestDefinition, ThumbnailDownscaler, TimeProvider,
+};
+#[cfg(all(feature = "unstable_api", feature = "pdf"))]
+pub use builder::PdfStreamSigner;
 pub use callback_signer::{CallbackFunc, CallbackSigner};
 pub use claim_generator_info::ClaimGeneratorInfo;
 pub use error::{Error, Result};
@@ -126,18 +140,25 @@ pub use hash_utils::{hash_stream_by_alg, HashRange};
 pub use ingredient::Ingredient;
 #[cfg(feature = "file_io")]
 pub use ingredient::{DefaultOptions, IngredientOptions};
-pub use manifest::{Manifest, SignatureInfo};
+pub use jumbf_io::{supported_formats, FormatInfo};
+pub use manifest::{parse_manifest, Manifest, SignatureInfo};
 pub use manifest_assertion::{ManifestAssertion, ManifestAssertionKind};
 #[cfg(feature = "v1_api")]
 pub use manifest_store::ManifestStore;
 #[cfg(feature = "v1_api")]
 pub use manifest_store_report::ManifestStoreReport;
 #[cfg(feature = "unstable_api")]
-pub use reader::Reader;
+pub use reader::{
+    AssertionDigestMismatch, AssertionOrigin, HashExclusion, IdentityReport, ManifestSource,
+    ManifestValidationReport, ModificationReport, ModifiedRegion, Reader, SignedPayload,
+    ThumbnailCheck, ThumbnailMatcher, TimestampReport, TimestampSource,
+};
 pub use resource_store::{ResourceRef, ResourceStore};
 pub use signer::{AsyncSigner, RemoteSigner, Signer};
 pub use signing_alg::SigningAlg;
 pub use utils::mime::format_from_path;
+#[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
+pub use utils::mmap_reader::MmapReader;
 
 // Internal modules
 #[allow(dead_code, clippy::enum_variant_names)]
@@ -11,7 +11,7 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::{borrow::Cow, collections::HashMap, io::Cursor, slice::Iter};
+use std::{borrow::Cow, collections::HashMap, io::Cursor, slice::Iter, sync::Arc};
 #[cfg(feature = "file_io")]
 use std::{fs::create_dir_all, path::Path};
 
@@ -38,7 +38,10 @@ use crate::{
     manifest_assertion::ManifestAssertion,
     resource_store::{mime_from_uri, skip_serializing_resources, ResourceRef, ResourceStore},
     salt::DefaultSalt,
+    settings::get_settings_value,
+    status_tracker::DetailedStatusTracker,
     store::Store,
+    utils::cbor_types::cbor_has_tags,
     AsyncSigner, ClaimGeneratorInfo, HashRange, ManifestAssertionKind, ManifestPatchCallback,
     RemoteSigner, Signer, SigningAlg,
 };
@@ -118,6 +121,20 @@ pub struct Manifest {
     #[serde(skip_deserializing)]
     #[serde(skip_serializing_if = "skip_serializing_resources")]
     resources: ResourceStore,
+
+    /// The manifest store a thumbnail left out of `resources` is decoded from
+    #[serde(skip)]
+    thumbnail_source: Option<ThumbnailSource>,
+}
+
+// The manifest store shared by the manifests read from it, holding the data of the thumbnails
+// that `core.lazy_thumbnails` leaves out of their resources.
+struct ThumbnailSource(Arc<Store>);
+
+impl std::fmt::Debug for ThumbnailSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ThumbnailSource")
+    }
 }
 
 fn default_claim_generator() -> String {
@@ -174,10 +191,28 @@ impl Manifest {
     }
 
     /// Returns thumbnail tuple with Some((format, bytes)) or None
+    ///
+    /// When the `core.lazy_thumbnails` setting is enabled, manifests read from an asset don't
+    /// copy the data of their thumbnail into their resources. It is borrowed from the manifest
+    /// store they were read from when it is requested instead.
     pub fn thumbnail(&self) -> Option<(&str, Cow<Vec<u8>>)> {
-        self.thumbnail
-            .as_ref()
-            .and_then(|t| Some(t.format.as_str()).zip(self.resources.get(&t.identifier).ok()))
+        let thumbnail = self.thumbnail.as_ref()?;
+        if let Ok(data) = self.resources.get(&thumbnail.identifier) {
+            return Some((thumbnail.format.as_str(), data));
+        }
+
+        let store = &self.thumbnail_source.as_ref()?.0;
+        let assertion =
+            store.get_assertion_from_uri_and_claim(&thumbnail.identifier, self.label()?)?;
+        match assertion.decode_data() {
+            AssertionData::Binary(data) => Some((thumbnail.format.as_str(), Cow::Borrowed(data))),
+            _ => None,
+        }
+    }
+
+    /// Sets the manifest store the thumbnail is decoded from if it isn't in the resources.
+    pub(crate) fn set_thumbnail_source(&mut self, store: Arc<Store>) {
+        self.thumbnail_source = Some(ThumbnailSource(store));
     }
 
     /// Returns a thumbnail ResourceRef or `None`.
@@ -538,6 +573,16 @@ impl Manifest {
             manifest.with_base_path(base_path)?;
         }
 
+        // Claim thumbnails can be large, so with the `core.lazy_thumbnails` setting their data
+        // isn't copied into the resources, unless the resources are written to files or serialized.
+        #[cfg(feature = "file_io")]
+        let defer_thumbnail = resource_path.is_none();
+        #[cfg(not(feature = "file_io"))]
+        let defer_thumbnail = true;
+        let defer_thumbnail = defer_thumbnail
+            && !cfg!(feature = "serialize_thumbnails")
+            && get_settings_value::<bool>("core.lazy_thumbnails").unwrap_or(false);
+
         if let Some(info_vec) = claim.claim_generator_info() {
             let mut generators = Vec::new();
             for claim_info in info_vec {
@@ -587,15 +632,14 @@ impl Manifest {
         manifest.set_instance_id(claim.instance_id());
 
         manifest.assertion_references = claim
-            .assertions()
-            .iter()
+            .all_assertions()
             .map(|h| {
                 let alg = h.alg().or_else(|| Some(claim.alg().to_string()));
                 HashedUri::new(h.url(), alg, &h.hash())
             })
             .collect();
 
-        for assertion in claim.assertions() {
+        for assertion in claim.all_assertions() {
             let claim_assertion = store.get_claim_assertion_from_uri(
                 &jumbf::labels::to_absolute_uri(claim.label(), &assertion.url()),
             )?;
@@ -663,22 +707,42 @@ impl Manifest {
                     // do not include data hash when reading manifests
                 }
                 label if label.starts_with(labels::CLAIM_THUMBNAIL) => {
-                    let thumbnail = Thumbnail::from_assertion(assertion)?;
                     let id = jumbf::labels::to_assertion_uri(claim.label(), label);
                     let id = jumbf::labels::to_relative_uri(&id);
-                    manifest.thumbnail = Some(manifest.resources.add_uri(
-                        &id,
-                        &thumbnail.content_type,
-                        thumbnail.data,
-                    )?);
+                    if defer_thumbnail {
+                        // the thumbnail stays in the store until it is requested
+                        manifest.thumbnail = Some(ResourceRef::new(assertion.content_type(), id));
+                    } else {
+                        let thumbnail = Thumbnail::from_assertion(assertion)?;
+                        manifest.thumbnail = Some(manifest.resources.add_uri(
+                            &id,
+                            &thumbnail.content_type,
+                            thumbnail.data,
+                        )?);
+                    }
                 }
                 _ => {
                     // inject assertions for all other assertions
                     match assertion.decode_data() {
-                        AssertionData::Cbor(_) => {
-                            let value = assertion.as_json_object()?;
-                            let ma = ManifestAssertion::new(base_label, value)
-                                .set_instance(claim_assertion.instance());
+                        AssertionData::Cbor(data) => {
+                            // Assertions we don't recognize may use CBOR that has no JSON
+                            // form (e.g. integer map keys or semantic tags, which JSON
+                            // silently drops). The claim has already verified their hashes,
+                            // so pass them through as opaque CBOR, keeping the bytes intact
+                            // if the manifest is signed again.
+                            let ma = if cbor_has_tags(data) {
+                                debug!("passing through tagged assertion {label}");
+                                ManifestAssertion::from_cbor_bytes(base_label, data.clone())
+                            } else {
+                                match assertion.as_json_object() {
+                                    Ok(value) => ManifestAssertion::new(base_label, value),
+                                    Err(e) => {
+                                        debug!("passing through opaque assertion {label}: {e}");
+                                        ManifestAssertion::from_cbor_bytes(base_label, data.clone())
+                                    }
+                                }
+                            }
+                            .set_instance(claim_assertion.instance());
 
                             manifest.assertions.push(ma);
                         }
@@ -699,8 +763,10 @@ impl Manifest {
             }
         }
 
-        // get verified signing info
-        let si = if _sync {
+        // get verified signing info, migrated legacy claims are unsigned
+        let si = if claim.signature_val().is_empty() {
+            None
+        } else if _sync {
             claim.signature_info()
         } else {
             claim.signature_info_async().await
@@ -824,7 +890,7 @@ impl Manifest {
         let mut ingredient_map = HashMap::new();
         // add all ingredients to the claim
         for ingredient in &self.ingredients {
-            let uri = ingredient.add_to_claim(&mut claim, self.redactions.clone(), None)?;
+            let uri = ingredient.add_to_claim(&mut claim, self.redactions.clone(), None, true)?;
             ingredient_map.insert(ingredient.instance_id(), uri);
         }
 
@@ -1491,6 +1557,25 @@ impl SignatureInfo {
     }
 }
 
+/// Parses the active manifest of a JUMBF manifest store, such as one extracted from an asset.
+///
+/// The claim and its COSE_Sign1 signature are read from `bytes` directly, without an asset
+/// handler. Nothing is validated: neither the signature nor the claim's bindings to an asset
+/// are checked, so the returned [`Manifest`] must not be trusted as is.
+/// # Errors
+/// If `bytes` is not a manifest store or has no active manifest.
+pub fn parse_manifest(bytes: &[u8]) -> Result<Manifest> {
+    let store = Store::from_jumbf(bytes, &mut DetailedStatusTracker::new())?;
+    let manifest_label = store.provenance_label().ok_or(Error::ProvenanceMissing)?;
+
+    Manifest::from_store(
+        &store,
+        &manifest_label,
+        #[cfg(feature = "file_io")]
+        None,
+    )
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     #![allow(clippy::expect_used)]
@@ -1508,13 +1593,13 @@ pub(crate) mod tests {
 
     #[allow(unused_imports)]
     use crate::{
-        assertions::{c2pa_action, Action, Actions},
+        assertions::{c2pa_action, Action, Actions, UserCbor},
         ingredient::Ingredient,
         reader::Reader,
         status_tracker::{DetailedStatusTracker, StatusTracker},
         store::Store,
-        utils::test::{temp_remote_signer, temp_signer, TEST_VC},
-        Manifest, Result,
+        utils::test::{create_test_claim, temp_remote_signer, temp_signer, TEST_VC},
+        Manifest, ManifestAssertionKind, Result,
     };
     #[cfg(feature = "file_io")]
     use crate::{
@@ -2802,4 +2887,50 @@ pub(crate) mod tests {
         assert!(reader.active_manifest().is_some());
         assert!(reader.validation_status().is_none());
     }
+
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_parse_manifest() {
+        let pdf = include_bytes!("../tests/fixtures/express-signed.pdf");
+        let manifest_bytes = crate::jumbf_io::load_jumbf_from_memory("pdf", pdf).unwrap();
+
+        let manifest = super::parse_manifest(&manifest_bytes).unwrap();
+
+        let reader = Reader::from_stream("application/pdf", Cursor::new(pdf.to_vec())).unwrap();
+        let expected = reader.active_manifest().unwrap();
+        assert_eq!(manifest.label(), expected.label());
+        assert_eq!(manifest.title(), expected.title());
+        assert_eq!(manifest.claim_generator(), expected.claim_generator());
+        assert_eq!(
+            manifest.signature_info().unwrap().issuer,
+            expected.signature_info().unwrap().issuer
+        );
+
+        assert!(super::parse_manifest(b"not a manifest store").is_err());
+    }
+
+    #[test]
+    fn test_tagged_cbor_assertion_passthrough() {
+        // {"value": 1234("x")}, using a tag outside any registry we know
+        let tagged = vec![
+            0xa1, 0x65, b'v', b'a', b'l', b'u', b'e', 0xd9, 0x04, 0xd2, 0x61, b'x',
+        ];
+
+        let mut claim = create_test_claim().unwrap();
+        claim
+            .add_assertion(&UserCbor::new("org.example.tagged", tagged.clone()))
+            .unwrap();
+        let mut store = Store::new();
+        store.commit_claim(claim).unwrap();
+        let jumbf = store.to_jumbf(temp_signer().as_ref()).unwrap();
+
+        let manifest = super::parse_manifest(&jumbf).unwrap();
+        let assertion = manifest
+            .assertions()
+            .iter()
+            .find(|a| a.label() == "org.example.tagged")
+            .unwrap();
+        assert_eq!(assertion.kind(), &ManifestAssertionKind::Cbor);
+        assert_eq!(assertion.binary().unwrap(), tagged.as_slice());
+    }
 }
@@ -153,6 +153,16 @@ impl ManifestAssertion {
         })
     }
 
+    /// Creates a ManifestAssertion that carries already encoded CBOR bytes as is
+    pub(crate) fn from_cbor_bytes<S: Into<String>>(label: S, data: Vec<u8>) -> Self {
+        Self {
+            label: label.into(),
+            data: ManifestData::Binary(data),
+            instance: None,
+            kind: Some(ManifestAssertionKind::Cbor),
+        }
+    }
+
     /// Creates a ManifestAssertion from an AssertionBase object
     ///
     /// # Example: Creating a custom assertion an Action assertion
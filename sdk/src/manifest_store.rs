@@ -14,8 +14,10 @@
 #[cfg(feature = "file_io")]
 use std::path::Path;
 use std::{
+    borrow::Cow,
     collections::HashMap,
     io::{Read, Seek, Write},
+    sync::Arc,
 };
 
 use async_generic::async_generic;
@@ -26,10 +28,14 @@ use serde::{Deserialize, Serialize};
 use crate::{
     claim::ClaimAssetData,
     jumbf::labels::manifest_label_from_uri,
+    metrics::{MetricsObserver, PipelineStage, StageTimer},
     status_tracker::{DetailedStatusTracker, StatusTracker},
     store::Store,
     utils::base64,
-    validation_status::{status_for_store, ValidationStatus},
+    validation_status::{
+        informational_statuses, is_informational, is_success, status_for_store, success_statuses,
+        ValidationStatus, STATUS_MIGRATED,
+    },
     Error, Manifest, Result,
 };
 
@@ -46,8 +52,17 @@ pub struct ManifestStore {
     /// ValidationStatus generated when loading the ManifestStore from an asset
     validation_status: Option<Vec<ValidationStatus>>,
     #[serde(skip)]
-    /// The internal store representing the manifest store
-    store: Store,
+    /// Success codes logged when loading the ManifestStore, which validation_status leaves out
+    validation_successes: Vec<ValidationStatus>,
+    #[serde(skip)]
+    /// Informational codes logged when loading the ManifestStore, which validation_status leaves out
+    validation_informational: Vec<ValidationStatus>,
+    #[serde(skip)]
+    /// The internal store representing the manifest store, shared with the manifests
+    store: Arc<Store>,
+    #[serde(skip)]
+    /// The manifest store in JUMBF format, as read from the asset
+    manifest_data: Option<Vec<u8>>,
 }
 
 impl ManifestStore {
@@ -57,7 +72,10 @@ impl ManifestStore {
             active_manifest: None,
             manifests: HashMap::<String, Manifest>::new(),
             validation_status: None,
-            store: Store::new(),
+            validation_successes: Vec::new(),
+            validation_informational: Vec::new(),
+            store: Arc::new(Store::new()),
+            manifest_data: None,
         }
     }
 
@@ -87,13 +105,28 @@ impl ManifestStore {
     }
 
     // writes a resource identified uri to the given stream
-    pub fn get_resource(&self, uri: &str, stream: impl Write + Read + Seek + Send) -> Result<u64> {
+    pub fn get_resource(
+        &self,
+        uri: &str,
+        mut stream: impl Write + Read + Seek + Send,
+    ) -> Result<u64> {
         // get the manifest referenced by the uri, or the active one if None
         let manifest = match manifest_label_from_uri(uri) {
             Some(label) => self.get(&label),
             None => self.get_active(),
         };
         if let Some(manifest) = manifest {
+            // the thumbnail may not be in the resources, see `core.lazy_thumbnails`
+            if manifest
+                .thumbnail_ref()
+                .is_some_and(|thumbnail| thumbnail.identifier == uri)
+            {
+                if let Some((_, data)) = manifest.thumbnail() {
+                    stream.write_all(&data)?;
+                    return Ok(data.len() as u64);
+                }
+            }
+
             let mut resources = manifest.resources();
             if !resources.exists(uri) {
                 // also search ingredients to support Reader model
@@ -110,11 +143,60 @@ impl ManifestStore {
         }
     }
 
+    /// Returns the format and data of a manifest's thumbnail, as a slice.
+    pub(crate) fn thumbnail<'a>(
+        &'a self,
+        manifest: &'a Manifest,
+    ) -> Option<(&'a str, Cow<'a, [u8]>)> {
+        manifest.thumbnail().map(|(format, data)| match data {
+            Cow::Borrowed(data) => (format, Cow::Borrowed(data.as_slice())),
+            Cow::Owned(data) => (format, Cow::Owned(data)),
+        })
+    }
+
     /// Returns a reference the [ValidationStatus] Vec or None
     pub fn validation_status(&self) -> Option<&[ValidationStatus]> {
         self.validation_status.as_deref()
     }
 
+    /// Replaces the validation status, with `None` if `statuses` is empty.
+    pub(crate) fn set_validation_status(&mut self, statuses: Vec<ValidationStatus>) {
+        self.validation_status = (!statuses.is_empty()).then_some(statuses);
+    }
+
+    /// Returns the success codes logged when validating the manifest store.
+    pub(crate) fn validation_successes(&self) -> &[ValidationStatus] {
+        &self.validation_successes
+    }
+
+    /// Returns the informational codes logged when validating the manifest store.
+    pub(crate) fn validation_informational(&self) -> &[ValidationStatus] {
+        &self.validation_informational
+    }
+
+    /// Returns the validation status, the success codes and the informational codes, such as to
+    /// cache them.
+    pub(crate) fn validation_results(&self) -> Vec<ValidationStatus> {
+        let mut results = self.validation_status().unwrap_or_default().to_vec();
+        results.extend_from_slice(&self.validation_successes);
+        results.extend_from_slice(&self.validation_informational);
+        results
+    }
+
+    /// Replaces the validation status, success codes and informational codes with those of
+    /// `results`.
+    pub(crate) fn set_validation_results(&mut self, results: Vec<ValidationStatus>) {
+        let (successes, others): (Vec<_>, Vec<_>) = results
+            .into_iter()
+            .partition(|status| is_success(status.code()));
+        let (informational, failures) = others
+            .into_iter()
+            .partition(|status| is_informational(status.code()));
+        self.set_validation_status(failures);
+        self.validation_successes = successes;
+        self.validation_informational = informational;
+    }
+
     /// creates a ManifestStore from a Store with validation
     #[async_generic]
     pub(crate) fn from_store(store: Store, validation_log: &impl StatusTracker) -> ManifestStore {
@@ -155,19 +237,22 @@ impl ManifestStore {
         let mut statuses = status_for_store(&store, validation_log);
 
         let mut manifest_store = ManifestStore::new();
+        manifest_store.validation_successes = success_statuses(validation_log);
+        manifest_store.validation_informational = informational_statuses(validation_log);
         manifest_store.active_manifest = store.provenance_label();
-        manifest_store.store = store;
+        manifest_store.store = Arc::new(store);
 
-        let store = &manifest_store.store;
+        let store = Arc::clone(&manifest_store.store);
         for claim in store.claims() {
             let manifest_label = claim.label();
             #[cfg(feature = "file_io")]
-            let result = Manifest::from_store(store, manifest_label, resource_path);
+            let result = Manifest::from_store(&store, manifest_label, resource_path);
             #[cfg(not(feature = "file_io"))]
-            let result = Manifest::from_store(store, manifest_label);
+            let result = Manifest::from_store(&store, manifest_label);
 
             match result {
-                Ok(manifest) => {
+                Ok(mut manifest) => {
+                    manifest.set_thumbnail_source(Arc::clone(&store));
                     manifest_store
                         .manifests
                         .insert(manifest_label.to_owned(), manifest);
@@ -193,19 +278,22 @@ impl ManifestStore {
         let mut statuses = status_for_store(&store, validation_log);
 
         let mut manifest_store = ManifestStore::new();
+        manifest_store.validation_successes = success_statuses(validation_log);
+        manifest_store.validation_informational = informational_statuses(validation_log);
         manifest_store.active_manifest = store.provenance_label();
-        manifest_store.store = store;
+        manifest_store.store = Arc::new(store);
 
-        let store = &manifest_store.store;
+        let store = Arc::clone(&manifest_store.store);
         for claim in store.claims() {
             let manifest_label = claim.label();
             #[cfg(feature = "file_io")]
-            let result = Manifest::from_store_async(store, manifest_label, resource_path).await;
+            let result = Manifest::from_store_async(&store, manifest_label, resource_path).await;
             #[cfg(not(feature = "file_io"))]
-            let result = Manifest::from_store_async(store, manifest_label).await;
+            let result = Manifest::from_store_async(&store, manifest_label).await;
 
             match result {
-                Ok(manifest) => {
+                Ok(mut manifest) => {
+                    manifest.set_thumbnail_source(Arc::clone(&store));
                     manifest_store
                         .manifests
                         .insert(manifest_label.to_owned(), manifest);
@@ -223,6 +311,15 @@ impl ManifestStore {
         manifest_store
     }
 
+    /// Returns the manifest store in JUMBF format, if it was read from an asset or JUMBF data.
+    pub(crate) fn manifest_data(&self) -> Option<&[u8]> {
+        self.manifest_data.as_deref()
+    }
+
+    pub(crate) fn set_manifest_data(&mut self, manifest_data: Vec<u8>) {
+        self.manifest_data = Some(manifest_data);
+    }
+
     pub(crate) fn store(&self) -> &Store {
         &self.store
     }
@@ -264,6 +361,33 @@ impl ManifestStore {
         }
     }
 
+    /// Generate a Store from a stream holding a prerelease (pre-1.0) manifest store.
+    ///
+    /// The claims are migrated to the current layout without verification, are unsigned,
+    /// and the validation status reports [`STATUS_MIGRATED`].
+    pub(crate) fn from_legacy_stream(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<ManifestStore> {
+        let mut validation_log = DetailedStatusTracker::new();
+
+        let manifest_bytes = Store::load_jumbf_from_stream(format, &mut stream)?;
+        let store = Store::from_jumbf_legacy(&manifest_bytes, &mut validation_log)?;
+
+        let mut manifest_store = Self::from_store(store, &validation_log);
+        let mut statuses = manifest_store
+            .validation_status()
+            .map(<[ValidationStatus]>::to_vec)
+            .unwrap_or_default();
+        statuses.push(
+            ValidationStatus::new(STATUS_MIGRATED)
+                .set_explanation("migrated from a prerelease manifest".to_string()),
+        );
+        manifest_store.set_validation_status(statuses);
+
+        Ok(manifest_store)
+    }
+
     /// Generate a Store from a format string and stream.
     #[async_generic(async_signature(
         format: &str,
@@ -274,12 +398,37 @@ impl ManifestStore {
         format: &str,
         mut stream: impl Read + Seek + Send,
         verify: bool,
+    ) -> Result<ManifestStore> {
+        if _sync {
+            Self::from_stream_with_metrics(format, &mut stream, verify, None)
+        } else {
+            Self::from_stream_with_metrics_async(format, &mut stream, verify, None).await
+        }
+    }
+
+    /// Generate a Store from a format string and stream, reporting the time spent parsing and
+    /// validating it to `metrics`.
+    #[async_generic(async_signature(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        verify: bool,
+        metrics: Option<&dyn MetricsObserver>,
+    ))]
+    pub(crate) fn from_stream_with_metrics(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        verify: bool,
+        metrics: Option<&dyn MetricsObserver>,
     ) -> Result<ManifestStore> {
         let mut validation_log = DetailedStatusTracker::new();
 
+        let timer = StageTimer::start(metrics, PipelineStage::Parse);
         let manifest_bytes = Store::load_jumbf_from_stream(format, &mut stream)?;
         let store = Store::from_jumbf(&manifest_bytes, &mut validation_log)?;
+        drop(timer);
+
         if verify {
+            let _timer = StageTimer::start(metrics, PipelineStage::Validate);
             // verify store and claims
             if _sync {
                 Store::verify_store(
@@ -296,7 +445,10 @@ impl ManifestStore {
                 .await?;
             }
         }
-        Ok(Self::from_store(store, &validation_log))
+
+        let mut manifest_store = Self::from_store(store, &validation_log);
+        manifest_store.set_manifest_data(manifest_bytes);
+        Ok(manifest_store)
     }
 
     #[cfg(feature = "file_io")]
@@ -442,7 +594,9 @@ impl ManifestStore {
         )
         .await?;
 
-        Ok(Self::from_store_async(store, &validation_log).await)
+        let mut manifest_store = Self::from_store_async(store, &validation_log).await;
+        manifest_store.set_manifest_data(manifest_bytes.to_vec());
+        Ok(manifest_store)
     }
 
     /// Synchronously loads a manifest from a buffer holding a binary manifest (.c2pa) and validates against an asset buffer
@@ -479,7 +633,9 @@ impl ManifestStore {
             &mut validation_log,
         )?;
 
-        Ok(Self::from_store(store, &validation_log))
+        let mut manifest_store = Self::from_store(store, &validation_log);
+        manifest_store.set_manifest_data(manifest_bytes.to_vec());
+        Ok(manifest_store)
     }
 }
 
@@ -685,4 +841,42 @@ mod tests {
         assert_eq!(manifest.issuer().unwrap(), "C2PA Test Signing Cert");
         assert!(manifest.time().is_some());
     }
+
+    #[test]
+    #[cfg(not(feature = "serialize_thumbnails"))]
+    fn manifest_thumbnail_lazy() {
+        use crate::settings::{set_settings_value, tests::PROTECT};
+
+        let _protect = PROTECT.lock().unwrap();
+        let image_bytes: &[u8] = include_bytes!("../tests/fixtures/CA.jpg");
+
+        set_settings_value("core.lazy_thumbnails", true).unwrap();
+        let result =
+            ManifestStore::from_stream("image/jpeg", std::io::Cursor::new(image_bytes), true);
+        set_settings_value("core.lazy_thumbnails", false).unwrap();
+        let manifest_store = result.unwrap();
+
+        // the thumbnail is referenced, but its data isn't copied out of the store
+        let manifest = manifest_store.get_active().unwrap();
+        let thumbnail_ref = manifest.thumbnail_ref().unwrap();
+        assert!(!manifest.resources().exists(&thumbnail_ref.identifier));
+
+        let eager =
+            ManifestStore::from_stream("image/jpeg", std::io::Cursor::new(image_bytes), true)
+                .unwrap();
+        let (eager_format, eager_data) = eager.get_active().unwrap().thumbnail().unwrap();
+
+        // until it is requested
+        let (format, data) = manifest.thumbnail().unwrap();
+        assert_eq!(format, eager_format);
+        assert_eq!(data, eager_data);
+        let (_, data) = manifest_store.thumbnail(manifest).unwrap();
+        assert_eq!(data.as_ref(), eager_data.as_slice());
+
+        let mut stream = std::io::Cursor::new(Vec::new());
+        manifest_store
+            .get_resource(&thumbnail_ref.identifier, &mut stream)
+            .unwrap();
+        assert_eq!(stream.into_inner(), *eager_data);
+    }
 }
@@ -226,7 +226,7 @@ impl ManifestStoreReport {
                 )?;
 
             // is this an ingredient
-            if let Some(ref c2pa_manifest) = &ingredient_assertion.c2pa_manifest {
+            if let Some(c2pa_manifest) = ingredient_assertion.manifest_uri() {
                 let label = Store::manifest_label_from_path(&c2pa_manifest.url());
                 let hash = &c2pa_manifest.hash()[..5];
 
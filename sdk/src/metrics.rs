@@ -0,0 +1,130 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Reports the time spent in each stage of the signing and validation pipelines.
+//!
+//! See `Builder::set_metrics_observer` and `Reader::from_stream_with_metrics`.
+
+use std::{fmt, time::Duration};
+
+use instant::Instant;
+
+/// A stage of the signing or validation pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PipelineStage {
+    /// Reading the manifest store from an asset and parsing it.
+    Parse,
+    /// Writing a placeholder manifest store into the asset and hashing the asset.
+    Hash,
+    /// Signing the claim, including any time-stamp request.
+    Sign,
+    /// Waiting for a remote signer, or for the OCSP response and time-stamp of a signature.
+    /// This is reported within [`PipelineStage::Sign`].
+    Network,
+    /// Writing the signed manifest store into the asset.
+    Embed,
+    /// Verifying the signatures and hard bindings of a manifest store.
+    Validate,
+}
+
+/// Receives the timing of each [`PipelineStage`] of an operation.
+///
+/// Stages are reported in the order they run. Nothing is timed unless an observer is set.
+pub trait MetricsObserver: Send + Sync {
+    /// Called when `stage` starts.
+    fn stage_started(&self, _stage: PipelineStage) {}
+
+    /// Called when `stage` finishes, successfully or not, after running for `elapsed`.
+    fn stage_finished(&self, stage: PipelineStage, elapsed: Duration);
+}
+
+impl fmt::Debug for dyn MetricsObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsObserver").finish_non_exhaustive()
+    }
+}
+
+/// Reports a stage to a [`MetricsObserver`] when started and again when dropped.
+pub(crate) struct StageTimer<'a> {
+    observer: &'a dyn MetricsObserver,
+    stage: PipelineStage,
+    start: Instant,
+}
+
+impl<'a> StageTimer<'a> {
+    /// Starts timing `stage`, or returns `None` without reading the clock if there is no
+    /// observer.
+    pub(crate) fn start(
+        observer: Option<&'a dyn MetricsObserver>,
+        stage: PipelineStage,
+    ) -> Option<Self> {
+        observer.map(|observer| {
+            observer.stage_started(stage);
+            StageTimer {
+                observer,
+                stage,
+                start: Instant::now(),
+            }
+        })
+    }
+}
+
+impl Drop for StageTimer<'_> {
+    fn drop(&mut self) {
+        self.observer
+            .stage_finished(self.stage, self.start.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(PipelineStage, bool)>>);
+
+    impl MetricsObserver for Recorder {
+        fn stage_started(&self, stage: PipelineStage) {
+            self.0.lock().unwrap().push((stage, true));
+        }
+
+        fn stage_finished(&self, stage: PipelineStage, _elapsed: Duration) {
+            self.0.lock().unwrap().push((stage, false));
+        }
+    }
+
+    #[test]
+    fn test_stage_timer() {
+        assert!(StageTimer::start(None, PipelineStage::Parse).is_none());
+
+        let recorder = Recorder::default();
+        {
+            let _sign = StageTimer::start(Some(&recorder), PipelineStage::Sign);
+            let _network = StageTimer::start(Some(&recorder), PipelineStage::Network);
+        }
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                (PipelineStage::Sign, true),
+                (PipelineStage::Network, true),
+                (PipelineStage::Network, false),
+                (PipelineStage::Sign, false),
+            ]
+        );
+    }
+}
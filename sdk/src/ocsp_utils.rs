@@ -86,8 +86,22 @@ fn extract_aia_responders(cert: &x509_parser::certificate::X509Certificate) -> O
 /// None otherwise.
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn fetch_ocsp_response(certs: &[Vec<u8>]) -> Option<Vec<u8>> {
-    use std::io::Read;
+    try_fetch_ocsp_response(certs).ok().flatten()
+}
+
+/// Same as [`fetch_ocsp_response`] but returns [`Error::NetworkUnreachable`] when none of the
+/// responders could be reached, so the caller can apply the network failure policy.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn try_fetch_ocsp_response(certs: &[Vec<u8>]) -> Result<Option<Vec<u8>>> {
+    match ocsp_requests(certs) {
+        Some(requests) => fetch_from_responders(&requests),
+        None => Ok(None),
+    }
+}
 
+// build the OCSP request url for each responder listed in the end-entity cert
+#[cfg(not(target_arch = "wasm32"))]
+fn ocsp_requests(certs: &[Vec<u8>]) -> Option<Vec<(url::Url, url::Url)>> {
     use rasn::prelude::*;
     use rasn_pkix::Certificate;
     use x509_parser::prelude::*;
@@ -99,94 +113,151 @@ pub(crate) fn fetch_ocsp_response(certs: &[Vec<u8>]) -> Option<Vec<u8>> {
 
     let (_rem, cert) = X509Certificate::from_der(&certs[0]).ok()?;
 
-    if let Some(responders) = extract_aia_responders(&cert) {
-        let sha1_oid = rasn::types::Oid::new(&[1, 3, 14, 3, 2, 26])?; // Sha1 Oid
-        let alg = rasn::types::ObjectIdentifier::from(sha1_oid);
+    let responders = extract_aia_responders(&cert)?;
+
+    let sha1_oid = rasn::types::Oid::new(&[1, 3, 14, 3, 2, 26])?; // Sha1 Oid
+    let alg = rasn::types::ObjectIdentifier::from(sha1_oid);
+
+    let sha1_ai = rasn_pkix::AlgorithmIdentifier {
+        algorithm: alg,
+        parameters: Some(Any::new(rasn::der::encode(&()).ok()?)), /* many OCSP responders expect this to be NULL not None */
+    };
+
+    let mut requests = Vec::new();
+    for r in responders {
+        let url = url::Url::parse(&r).ok()?;
+        let subject: Certificate = rasn::der::decode(&certs[0]).ok()?;
+        let issuer: Certificate = rasn::der::decode(&certs[1]).ok()?;
+
+        let issuer_name_raw = rasn::der::encode(&issuer.tbs_certificate.subject).ok()?;
+        let issuer_key_raw = &issuer
+            .tbs_certificate
+            .subject_public_key_info
+            .subject_public_key
+            .as_raw_slice();
+
+        let issuer_name_hash = OctetString::from(crate::hash_utils::hash_sha1(&issuer_name_raw));
+        let issuer_key_hash = OctetString::from(crate::hash_utils::hash_sha1(issuer_key_raw));
+        let serial_number = subject.tbs_certificate.serial_number;
+
+        // build request structures
+
+        let req_cert = rasn_ocsp::CertId {
+            hash_algorithm: sha1_ai.clone(),
+            issuer_name_hash,
+            issuer_key_hash,
+            serial_number,
+        };
+
+        let ocsp_req = rasn_ocsp::Request {
+            req_cert,
+            single_request_extensions: None,
+        };
+
+        let request_list = vec![ocsp_req];
+
+        let tbs_request = rasn_ocsp::TbsRequest {
+            version: rasn_ocsp::Version::parse_bytes(b"0", 16)?,
+            requestor_name: None,
+            request_list,
+            request_extensions: None,
+        };
+
+        let ocsp_request = rasn_ocsp::OcspRequest {
+            tbs_request,
+            optional_signature: None,
+        };
+
+        // build query param
+        let request_der = rasn::der::encode(&ocsp_request).ok()?;
+        let request_str = crate::utils::base64::encode(&request_der);
+
+        let req_url = url.join(&request_str).ok()?;
+
+        requests.push((url, req_url));
+    }
+    Some(requests)
+}
+
+// send the OCSP requests until a responder answers, the pairs are (responder url, request url)
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_from_responders(requests: &[(url::Url, url::Url)]) -> Result<Option<Vec<u8>>> {
+    use std::io::Read;
+
+    let mut unreachable = None;
 
-        let sha1_ai = rasn_pkix::AlgorithmIdentifier {
-            algorithm: alg,
-            parameters: Some(Any::new(rasn::der::encode(&()).ok()?)), /* many OCSP responders expect this to be NULL not None */
+    for (url, req_url) in requests {
+        // fetch OCSP response
+        let request = ureq::get(req_url.as_str());
+        let response = if let Some(host) = url.host() {
+            request.set("Host", &host.to_string()).call() // for responders that don't support http 1.0
+        } else {
+            request.call()
         };
 
-        for r in responders {
-            let url = url::Url::parse(&r).ok()?;
-            let subject: Certificate = rasn::der::decode(&certs[0]).ok()?;
-            let issuer: Certificate = rasn::der::decode(&certs[1]).ok()?;
-
-            let issuer_name_raw = rasn::der::encode(&issuer.tbs_certificate.subject).ok()?;
-            let issuer_key_raw = &issuer
-                .tbs_certificate
-                .subject_public_key_info
-                .subject_public_key
-                .as_raw_slice();
-
-            let issuer_name_hash =
-                OctetString::from(crate::hash_utils::hash_sha1(&issuer_name_raw));
-            let issuer_key_hash = OctetString::from(crate::hash_utils::hash_sha1(issuer_key_raw));
-            let serial_number = subject.tbs_certificate.serial_number;
-
-            // build request structures
-
-            let req_cert = rasn_ocsp::CertId {
-                hash_algorithm: sha1_ai.clone(),
-                issuer_name_hash,
-                issuer_key_hash,
-                serial_number,
-            };
-
-            let ocsp_req = rasn_ocsp::Request {
-                req_cert,
-                single_request_extensions: None,
-            };
-
-            let request_list = vec![ocsp_req];
-
-            let tbs_request = rasn_ocsp::TbsRequest {
-                version: rasn_ocsp::Version::parse_bytes(b"0", 16)?,
-                requestor_name: None,
-                request_list,
-                request_extensions: None,
-            };
-
-            let ocsp_request = rasn_ocsp::OcspRequest {
-                tbs_request,
-                optional_signature: None,
-            };
-
-            // build query param
-            let request_der = rasn::der::encode(&ocsp_request).ok()?;
-            let request_str = crate::utils::base64::encode(&request_der);
-
-            let req_url = url.join(&request_str).ok()?;
-
-            // fetch OCSP response
-            let request = ureq::get(req_url.as_str());
-            let response = if let Some(host) = url.host() {
-                request.set("Host", &host.to_string()).call().ok()? // for responders that don't support http 1.0
-            } else {
-                request.call().ok()?
-            };
-
-            if response.status() == 200 {
-                let len = response
-                    .header("Content-Length")
-                    .and_then(|s| s.parse::<usize>().ok())
-                    .unwrap_or(10000);
-
-                let mut ocsp_rsp: Vec<u8> = Vec::with_capacity(len);
-
-                response
-                    .into_reader()
-                    .take(1000000)
-                    .read_to_end(&mut ocsp_rsp)
-                    .ok()?;
-
-                return Some(ocsp_rsp);
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Transport(_)) => {
+                unreachable = Some(url.to_string());
+                continue;
             }
+            Err(_) => continue, // the responder answered with an error status
+        };
+
+        if response.status() == 200 {
+            let len = response
+                .header("Content-Length")
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or(10000);
+
+            let mut ocsp_rsp: Vec<u8> = Vec::with_capacity(len);
+
+            response
+                .into_reader()
+                .take(1000000)
+                .read_to_end(&mut ocsp_rsp)
+                .map_err(|_| Error::NetworkUnreachable(url.to_string()))?;
+
+            return Ok(Some(ocsp_rsp));
         }
     }
-    None
+
+    match unreachable {
+        Some(url) => Err(Error::NetworkUnreachable(url)),
+        None => Ok(None),
+    }
 }
+
+/// Logs an OCSP responder that could not be reached.
+///
+/// When failing closed this is a validation failure and the error is returned. Otherwise
+/// revocation is not checked and only an informational status is recorded.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn log_ocsp_unreachable(
+    url: &str,
+    fail_closed: bool,
+    validation_log: &mut impl StatusTracker,
+) -> Result<()> {
+    if fail_closed {
+        let log_item = log_item!("OCSP", "OCSP responder could not be reached", "check_ocsp")
+            .error(Error::NetworkUnreachable(url.to_string()))
+            .validation_status(validation_status::SIGNING_CREDENTIAL_OCSP_INACCESSIBLE);
+        validation_log.log(log_item, Some(Error::NetworkUnreachable(url.to_string())))?;
+
+        Err(Error::NetworkUnreachable(url.to_string()))
+    } else {
+        let log_item = log_item!(
+            "OCSP",
+            "OCSP responder could not be reached, revocation not checked",
+            "check_ocsp"
+        )
+        .validation_status(validation_status::SIGNING_CREDENTIAL_OCSP_INACCESSIBLE);
+        validation_log.log_silent(log_item);
+
+        Ok(())
+    }
+}
+
 // check to OCSP response with optional signing time (if available)
 // Returns - returns OcspData unless their is a structural error in the response.
 pub(crate) fn check_ocsp_response(
@@ -432,7 +503,7 @@ pub mod tests {
     use chrono::TimeZone;
 
     use super::*;
-    use crate::status_tracker::report_split_errors;
+    use crate::status_tracker::{report_has_status, report_split_errors};
     #[test]
     fn test_good_response() {
         let rsp_data = include_bytes!("../tests/fixtures/ocsp_good.data");
@@ -464,4 +535,45 @@ pub mod tests {
         assert!(ocsp_data.revoked_at.is_some());
         assert!(!errors.is_empty());
     }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_unreachable_responder_fail_open() {
+        // nothing listens on the discard port
+        let responder = url::Url::parse("http://127.0.0.1:9/").unwrap();
+        let request = responder.join("request").unwrap();
+
+        let result = fetch_from_responders(&[(responder.clone(), request)]);
+        assert!(matches!(result, Err(Error::NetworkUnreachable(_))));
+
+        let mut validation_log = DetailedStatusTracker::default();
+        log_ocsp_unreachable(responder.as_str(), false, &mut validation_log).unwrap();
+
+        assert!(report_has_status(
+            validation_log.get_log(),
+            validation_status::SIGNING_CREDENTIAL_OCSP_INACCESSIBLE
+        ));
+        assert!(report_split_errors(validation_log.get_log_mut()).is_empty());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_unreachable_responder_fail_closed() {
+        let responder = url::Url::parse("http://127.0.0.1:9/").unwrap();
+        let request = responder.join("request").unwrap();
+
+        let result = fetch_from_responders(&[(responder.clone(), request)]);
+        assert!(matches!(result, Err(Error::NetworkUnreachable(_))));
+
+        let mut validation_log = DetailedStatusTracker::default();
+        let result = log_ocsp_unreachable(responder.as_str(), true, &mut validation_log);
+        assert!(matches!(result, Err(Error::NetworkUnreachable(_))));
+
+        let errors = report_split_errors(validation_log.get_log_mut());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].validation_status.as_deref(),
+            Some(validation_status::SIGNING_CREDENTIAL_OCSP_INACCESSIBLE)
+        );
+    }
 }
@@ -12,7 +12,7 @@
 // each license.
 
 use openssl::{
-    ec::EcKey,
+    ec::{EcKey, EcKeyRef},
     hash::MessageDigest,
     pkey::{PKey, Private},
     x509::X509,
@@ -21,6 +21,7 @@ use openssl::{
 use super::check_chain_order;
 use crate::{
     error::{Error, Result},
+    settings::get_settings_value,
     signer::ConfigurableSigner,
     utils::sig_utils::der_to_p1363,
     Signer, SigningAlg,
@@ -74,15 +75,21 @@ impl Signer for EcSigner {
     fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
         let _openssl = super::OpenSslMutex::acquire()?;
 
-        let key = PKey::from_ec_key(self.pkey.clone()).map_err(Error::OpenSslError)?;
-
-        let mut signer = match self.alg {
-            SigningAlg::Es256 => openssl::sign::Signer::new(MessageDigest::sha256(), &key)?,
-            SigningAlg::Es384 => openssl::sign::Signer::new(MessageDigest::sha384(), &key)?,
-            SigningAlg::Es512 => openssl::sign::Signer::new(MessageDigest::sha512(), &key)?,
+        let md = match self.alg {
+            SigningAlg::Es256 => MessageDigest::sha256(),
+            SigningAlg::Es384 => MessageDigest::sha384(),
+            SigningAlg::Es512 => MessageDigest::sha512(),
             _ => return Err(Error::UnsupportedType),
         };
 
+        if get_settings_value::<bool>("core.deterministic_ecdsa").unwrap_or(false) {
+            return sign_deterministic(&self.pkey, self.alg, data);
+        }
+
+        let key = PKey::from_ec_key(self.pkey.clone()).map_err(Error::OpenSslError)?;
+
+        let mut signer = openssl::sign::Signer::new(md, &key)?;
+
         signer.update(data).map_err(Error::OpenSslError)?;
         let der_sig = signer.sign_to_vec().map_err(Error::OpenSslError)?;
 
@@ -115,13 +122,58 @@ impl Signer for EcSigner {
     }
 }
 
+/// Signs `data` with a nonce derived from the private key and the message digest as
+/// described in RFC 6979, so that signing the same data twice gives the same signature.
+///
+/// Returns the signature in P1363 format (r | s).
+fn sign_deterministic(pkey: &EcKeyRef<Private>, alg: SigningAlg, data: &[u8]) -> Result<Vec<u8>> {
+    use p256::ecdsa::signature::Signer as _;
+
+    let secret = |len| {
+        pkey.private_key()
+            .to_vec_padded(len)
+            .map_err(Error::OpenSslError)
+    };
+    let ecdsa_err = |e: p256::ecdsa::Error| Error::OtherError(Box::new(e));
+
+    match alg {
+        SigningAlg::Es256 => {
+            let key = p256::ecdsa::SigningKey::from_slice(&secret(32)?).map_err(ecdsa_err)?;
+            let signature: p256::ecdsa::Signature = key.try_sign(data).map_err(ecdsa_err)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        SigningAlg::Es384 => {
+            let key = p384::ecdsa::SigningKey::from_slice(&secret(48)?).map_err(ecdsa_err)?;
+            let signature: p384::ecdsa::Signature = key.try_sign(data).map_err(ecdsa_err)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        SigningAlg::Es512 => {
+            let key = p521::ecdsa::SigningKey::from_slice(&secret(66)?).map_err(ecdsa_err)?;
+            let signature: p521::ecdsa::Signature = key.try_sign(data).map_err(ecdsa_err)?;
+            Ok(signature.to_bytes().to_vec())
+        }
+        _ => Err(Error::UnsupportedType),
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "file_io")]
 mod tests {
     #![allow(clippy::unwrap_used)]
 
+    use openssl::{
+        bn::{BigNum, BigNumContext},
+        ec::{EcGroup, EcPoint},
+        nid::Nid,
+    };
+
     use super::*;
-    use crate::{openssl::temp_signer, utils::test::fixture_path};
+    use crate::{
+        openssl::{temp_signer, EcValidator},
+        settings::{reset_default_settings, set_settings_value},
+        utils::test::fixture_path,
+        validator::CoseValidator,
+    };
 
     #[test]
     fn es256_signer() {
@@ -167,4 +219,60 @@ mod tests {
         assert!(signature.len() >= 64);
         assert!(signature.len() <= signer.reserve_size());
     }
+
+    #[test]
+    fn deterministic_signer() {
+        let _protect = crate::settings::tests::PROTECT.lock().unwrap();
+        let cert_dir = fixture_path("certs");
+        let data = b"some sample content to sign";
+
+        set_settings_value("core.deterministic_ecdsa", true).unwrap();
+        for alg in [SigningAlg::Es256, SigningAlg::Es384, SigningAlg::Es512] {
+            let (signer, cert_path) = temp_signer::get_ec_signer(&cert_dir, alg, None);
+
+            let signature = signer.sign(data).unwrap();
+            assert_eq!(signature, signer.sign(data).unwrap());
+
+            let cert_bytes = std::fs::read(cert_path).unwrap();
+            let signcert = X509::from_pem(&cert_bytes).unwrap();
+            let pub_key = signcert.public_key().unwrap().public_key_to_der().unwrap();
+            assert!(EcValidator::new(alg)
+                .validate(&signature, data, &pub_key)
+                .unwrap());
+        }
+        reset_default_settings().unwrap();
+    }
+
+    #[test]
+    fn deterministic_signer_rfc6979_vector() {
+        // RFC 6979 appendix A.2.5, P-256 with SHA-256 over "sample"
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let ctx = BigNumContext::new().unwrap();
+        let x = BigNum::from_hex_str(
+            "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721",
+        )
+        .unwrap();
+        let mut public = EcPoint::new(&group).unwrap();
+        public.mul_generator(&group, &x, &ctx).unwrap();
+        let pkey = EcKey::from_private_components(&group, &x, &public).unwrap();
+
+        let signature = sign_deterministic(&pkey, SigningAlg::Es256, b"sample").unwrap();
+        let (r, s) = signature.split_at(32);
+        assert_eq!(
+            BigNum::from_slice(r)
+                .unwrap()
+                .to_hex_str()
+                .unwrap()
+                .to_string(),
+            "EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716"
+        );
+        assert_eq!(
+            BigNum::from_slice(s)
+                .unwrap()
+                .to_hex_str()
+                .unwrap()
+                .to_string(),
+            "F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8"
+        );
+    }
 }
@@ -94,6 +94,70 @@ fn check_chain_order(certs: &[X509]) -> bool {
     }
 }
 
+/// Checks that each certificate in a chain given as separate PEM blocks was
+/// issued by the certificate that follows it, and reports the first broken link.
+#[cfg(feature = "openssl_sign")]
+pub(crate) fn check_pem_chain_links(pems: &[&[u8]]) -> crate::Result<()> {
+    use crate::Error;
+
+    let _openssl = OpenSslMutex::acquire()?;
+
+    let mut certs: Vec<X509> = Vec::new();
+    for (i, pem) in pems.iter().enumerate() {
+        let stack = X509::stack_from_pem(pem).map_err(Error::OpenSslError)?;
+        if stack.is_empty() {
+            return Err(Error::BadParam(format!(
+                "certificate chain entry {} contains no PEM certificates",
+                i + 1
+            )));
+        }
+        certs.extend(stack);
+    }
+
+    for (i, pair) in certs.windows(2).enumerate() {
+        let (cert, issuer) = (&pair[0], &pair[1]);
+        let names_match = cert
+            .issuer_name()
+            .try_cmp(issuer.subject_name())
+            .map_err(Error::OpenSslError)?
+            .is_eq();
+        let verified = names_match
+            && issuer
+                .public_key()
+                .and_then(|pkey| cert.verify(&pkey))
+                .unwrap_or(false);
+
+        if !verified {
+            return Err(Error::BadParam(format!(
+                "certificate chain is broken: certificate {} ({}) was not issued by \
+                 certificate {} ({}); certificates must be ordered from the signing \
+                 certificate towards the root",
+                i + 1,
+                x509_name_to_string(cert.subject_name()),
+                i + 2,
+                x509_name_to_string(issuer.subject_name()),
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "openssl_sign")]
+fn x509_name_to_string(name: &openssl::x509::X509NameRef) -> String {
+    name.entries()
+        .map(|entry| {
+            let key = entry.object().nid().short_name().unwrap_or("?");
+            let value = entry
+                .data()
+                .as_utf8()
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            format!("{key}={value}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(not(feature = "openssl"))]
 fn check_chain_order(certs: &[X509]) -> bool {
     true
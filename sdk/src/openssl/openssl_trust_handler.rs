@@ -22,7 +22,7 @@ use openssl::x509::verify::X509VerifyFlags;
 
 use crate::{
     hash_utils::hash_sha256,
-    trust_handler::{load_eku_configuration, TrustHandlerConfig},
+    trust_handler::{load_eku_configuration, load_trust_from_data, TrustHandlerConfig},
     utils::base64,
     Error, Result,
 };
@@ -51,6 +51,7 @@ fn load_trust_from_pem_data(trust_data: &[u8]) -> Result<Vec<openssl::x509::X509
 pub(crate) struct OpenSSLTrustHandlerConfig {
     trust_anchors: Vec<openssl::x509::X509>,
     private_anchors: Vec<openssl::x509::X509>,
+    intermediates: Vec<Vec<u8>>,
     allowed_cert_set: HashSet<String>,
     trust_store: Option<openssl::x509::store::X509Store>,
     config_store: Vec<u8>,
@@ -113,6 +114,7 @@ impl TrustHandlerConfig for OpenSSLTrustHandlerConfig {
         let mut th = OpenSSLTrustHandlerConfig {
             trust_anchors: Vec::new(),
             private_anchors: Vec::new(),
+            intermediates: Vec::new(),
             allowed_cert_set: HashSet::new(),
             trust_store: None,
             config_store: Vec::new(),
@@ -187,9 +189,20 @@ impl TrustHandlerConfig for OpenSSLTrustHandlerConfig {
         self.update_store()
     }
 
+    // append intermediate certificates
+    fn append_intermediates(&mut self, intermediates_reader: &mut dyn Read) -> Result<()> {
+        let mut intermediates_data = Vec::new();
+        intermediates_reader.read_to_end(&mut intermediates_data)?;
+
+        let mut intermediates = load_trust_from_data(&intermediates_data)?;
+        self.intermediates.append(&mut intermediates);
+        Ok(())
+    }
+
     fn clear(&mut self) {
         self.trust_anchors = Vec::new();
         self.private_anchors = Vec::new();
+        self.intermediates = Vec::new();
         self.trust_store = None;
     }
 
@@ -233,6 +246,10 @@ impl TrustHandlerConfig for OpenSSLTrustHandlerConfig {
     fn get_allowed_list(&self) -> &HashSet<String> {
         &self.allowed_cert_set
     }
+
+    fn get_intermediates(&self) -> Vec<Vec<u8>> {
+        self.intermediates.clone()
+    }
 }
 
 // verify certificate and trust chain
@@ -11,7 +11,7 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::cell::Cell;
+use std::sync::{Mutex, MutexGuard};
 
 //use extfmt::Hexlify;
 use openssl::{
@@ -32,11 +32,10 @@ pub struct RsaSigner {
 
     certs_size: usize,
     timestamp_size: usize,
-    ocsp_size: Cell<usize>,
 
     alg: SigningAlg,
     tsa_url: Option<String>,
-    ocsp_rsp: Cell<OcspData>,
+    ocsp_rsp: Mutex<OcspData>,
 }
 
 impl RsaSigner {
@@ -52,15 +51,12 @@ impl RsaSigner {
         let now = chrono::offset::Utc::now();
 
         // is it time for an OCSP update
-        let ocsp_data = self.ocsp_rsp.take();
-        let next_update = ocsp_data.next_update;
-        self.ocsp_rsp.set(ocsp_data);
+        let next_update = self.ocsp_data().next_update;
         if now > next_update {
             #[cfg(feature = "psxxx_ocsp_stapling_experimental")]
             {
                 if let Ok(certs) = self.certs_internal() {
                     if let Some(ocsp_rsp) = crate::ocsp_utils::fetch_ocsp_response(&certs) {
-                        self.ocsp_size.set(ocsp_rsp.len());
                         let mut validation_log =
                             crate::status_tracker::DetailedStatusTracker::default();
                         if let Ok(ocsp_data) = crate::ocsp_utils::check_ocsp_response(
@@ -68,7 +64,7 @@ impl RsaSigner {
                             None,
                             &mut validation_log,
                         ) {
-                            self.ocsp_rsp.set(ocsp_data);
+                            *self.ocsp_data() = ocsp_data;
                         }
                     }
                 }
@@ -76,6 +72,13 @@ impl RsaSigner {
         }
     }
 
+    fn ocsp_data(&self) -> MutexGuard<'_, OcspData> {
+        // the OCSP data is replaced as a whole, so a panic elsewhere can't corrupt it
+        self.ocsp_rsp
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     fn certs_internal(&self) -> Result<Vec<Vec<u8>>> {
         // IMPORTANT: ffi_mutex::acquire() should have been called by calling fn. Please
         // don't make this pub or pub(crate) without finding a way to ensure that
@@ -149,10 +152,9 @@ impl ConfigurableSigner for RsaSigner {
             pkey,
             certs_size: signcert.len(),
             timestamp_size: 10000, /* todo: call out to TSA to get actual timestamp and use that size */
-            ocsp_size: Cell::new(0),
             alg,
             tsa_url,
-            ocsp_rsp: Cell::new(OcspData::new()),
+            ocsp_rsp: Mutex::new(OcspData::new()),
         };
 
         // get OCSP if possible
@@ -209,7 +211,8 @@ impl Signer for RsaSigner {
     }
 
     fn reserve_size(&self) -> usize {
-        1024 + self.certs_size + self.timestamp_size + self.ocsp_size.get() // the Cose_Sign1 contains complete certs, timestamps and ocsp so account for size
+        // the Cose_Sign1 contains complete certs, timestamps and ocsp so account for size
+        1024 + self.certs_size + self.timestamp_size + self.ocsp_data().ocsp_der.len()
     }
 
     fn certs(&self) -> Result<Vec<Vec<u8>>> {
@@ -231,9 +234,7 @@ impl Signer for RsaSigner {
         // update OCSP if needed
         self.update_ocsp();
 
-        let ocsp_data = self.ocsp_rsp.take();
-        let ocsp_rsp = ocsp_data.ocsp_der.clone();
-        self.ocsp_rsp.set(ocsp_data);
+        let ocsp_rsp = self.ocsp_data().ocsp_der.clone();
         if !ocsp_rsp.is_empty() {
             Some(ocsp_rsp)
         } else {
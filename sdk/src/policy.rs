@@ -0,0 +1,260 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Policies that decide whether to accept an asset based on what its manifest declares.
+
+use crate::{
+    assertions::{Action, Actions},
+    Manifest, Reader,
+};
+
+/// The IPTC namespace of the digital source type vocabulary.
+///
+/// See <https://cv.iptc.org/newscodes/digitalsourcetype/>.
+pub const IPTC_DIGITAL_SOURCE_TYPE: &str = "http://cv.iptc.org/newscodes/digitalsourcetype/";
+
+/// The result of evaluating a policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PolicyDecision {
+    /// The asset meets the policy.
+    Allowed,
+    /// The asset declares a digital source type the policy doesn't accept.
+    Denied(String),
+    /// The asset doesn't declare a digital source type, and the policy requires one.
+    MissingSourceType,
+    /// There is no active manifest to evaluate.
+    NoManifest,
+}
+
+impl PolicyDecision {
+    /// Returns `true` if the asset meets the policy.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allowed)
+    }
+}
+
+/// Accepts or rejects assets based on the `digitalSourceType` of the actions in their
+/// active manifest.
+///
+/// Source types can be given as full URIs or, for the IPTC vocabulary, by their term alone,
+/// such as `trainedAlgorithmicMedia`. A source type on the deny list is always rejected. When
+/// an allow list is given, every declared source type must be on it.
+///
+/// # Example
+/// ```
+/// use c2pa::policy::SourceTypePolicy;
+///
+/// let policy = SourceTypePolicy::new()
+///     .deny("trainedAlgorithmicMedia")
+///     .deny("compositeSynthetic");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SourceTypePolicy {
+    allowed: Option<Vec<String>>,
+    denied: Vec<String>,
+    require_source_type: bool,
+}
+
+impl SourceTypePolicy {
+    /// Creates a policy accepting every source type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source type to the allow list. Once an allow list is given, source types not on
+    /// it are rejected.
+    pub fn allow<S: Into<String>>(mut self, source_type: S) -> Self {
+        self.allowed
+            .get_or_insert_with(Vec::new)
+            .push(source_type.into());
+        self
+    }
+
+    /// Adds a source type to the deny list.
+    pub fn deny<S: Into<String>>(mut self, source_type: S) -> Self {
+        self.denied.push(source_type.into());
+        self
+    }
+
+    /// Sets whether assets must declare at least one source type. Defaults to `false`.
+    pub fn require_source_type(mut self, require: bool) -> Self {
+        self.require_source_type = require;
+        self
+    }
+
+    /// Evaluates the policy against the active manifest of `reader`.
+    pub fn evaluate(&self, reader: &Reader) -> PolicyDecision {
+        match reader.active_manifest() {
+            Some(manifest) => self.evaluate_manifest(manifest),
+            None => PolicyDecision::NoManifest,
+        }
+    }
+
+    /// Evaluates the policy against the actions of `manifest`, including related actions.
+    pub fn evaluate_manifest(&self, manifest: &Manifest) -> PolicyDecision {
+        let mut source_types = Vec::new();
+        for manifest_assertion in manifest.assertions() {
+            // both c2pa.actions and c2pa.actions.v2
+            if !manifest_assertion.label().starts_with(Actions::LABEL) {
+                continue;
+            }
+
+            if let Ok(actions) = manifest_assertion.to_assertion::<Actions>() {
+                collect_source_types(actions.actions(), &mut source_types);
+            }
+        }
+
+        if source_types.is_empty() && self.require_source_type {
+            return PolicyDecision::MissingSourceType;
+        }
+
+        for source_type in source_types {
+            let denied = self
+                .denied
+                .iter()
+                .any(|entry| source_type_matches(entry, &source_type));
+            let allowed = self.allowed.as_ref().map_or(true, |allowed| {
+                allowed
+                    .iter()
+                    .any(|entry| source_type_matches(entry, &source_type))
+            });
+
+            if denied || !allowed {
+                return PolicyDecision::Denied(source_type);
+            }
+        }
+
+        PolicyDecision::Allowed
+    }
+}
+
+fn collect_source_types(actions: &[Action], source_types: &mut Vec<String>) {
+    for action in actions {
+        if let Some(source_type) = action.source_type() {
+            source_types.push(source_type.to_owned());
+        }
+
+        if let Some(related) = action.related() {
+            collect_source_types(related, source_types);
+        }
+    }
+}
+
+// a policy entry matches a full URI, or an IPTC source type by its term
+fn source_type_matches(entry: &str, source_type: &str) -> bool {
+    entry == source_type
+        || source_type
+            .strip_prefix(IPTC_DIGITAL_SOURCE_TYPE)
+            .is_some_and(|term| term == entry)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::assertions::c2pa_action;
+
+    const TRAINED: &str = "http://cv.iptc.org/newscodes/digitalsourcetype/trainedAlgorithmicMedia";
+    const CAPTURE: &str = "http://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture";
+
+    fn manifest_with_source_type(source_type: Option<&str>) -> Manifest {
+        let mut action = Action::new(c2pa_action::CREATED);
+        if let Some(source_type) = source_type {
+            action = action.set_source_type(source_type);
+        }
+
+        let mut manifest = Manifest::new("test");
+        manifest
+            .add_assertion(&Actions::new().add_action(action))
+            .unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_allowed_source_type() {
+        let policy = SourceTypePolicy::new().allow("digitalCapture");
+
+        let decision = policy.evaluate_manifest(&manifest_with_source_type(Some(CAPTURE)));
+        assert_eq!(decision, PolicyDecision::Allowed);
+        assert!(decision.is_allowed());
+
+        assert_eq!(
+            policy.evaluate_manifest(&manifest_with_source_type(Some(TRAINED))),
+            PolicyDecision::Denied(TRAINED.to_owned())
+        );
+    }
+
+    #[test]
+    fn test_denied_source_type() {
+        let policy = SourceTypePolicy::new().deny("trainedAlgorithmicMedia");
+
+        assert_eq!(
+            policy.evaluate_manifest(&manifest_with_source_type(Some(TRAINED))),
+            PolicyDecision::Denied(TRAINED.to_owned())
+        );
+        assert!(policy
+            .evaluate_manifest(&manifest_with_source_type(Some(CAPTURE)))
+            .is_allowed());
+
+        // full URIs match too, but terms only match the IPTC vocabulary
+        let policy = SourceTypePolicy::new().deny(TRAINED);
+        assert!(!policy
+            .evaluate_manifest(&manifest_with_source_type(Some(TRAINED)))
+            .is_allowed());
+        assert!(SourceTypePolicy::new()
+            .deny("trainedAlgorithmicMedia")
+            .evaluate_manifest(&manifest_with_source_type(Some(
+                "https://example.com/trainedAlgorithmicMedia"
+            )))
+            .is_allowed());
+    }
+
+    #[test]
+    fn test_actions_v2_source_type() {
+        let actions =
+            Actions::new().add_action(Action::new(c2pa_action::CREATED).set_source_type(TRAINED));
+        let mut manifest = Manifest::new("test");
+        manifest
+            .add_labeled_assertion(format!("{}.v2", Actions::LABEL), &actions)
+            .unwrap();
+
+        assert_eq!(
+            SourceTypePolicy::new()
+                .deny("trainedAlgorithmicMedia")
+                .evaluate_manifest(&manifest),
+            PolicyDecision::Denied(TRAINED.to_owned())
+        );
+        assert!(SourceTypePolicy::new()
+            .require_source_type(true)
+            .evaluate_manifest(&manifest)
+            .is_allowed());
+    }
+
+    #[test]
+    fn test_missing_source_type() {
+        let manifest = manifest_with_source_type(None);
+
+        assert!(SourceTypePolicy::new()
+            .deny("trainedAlgorithmicMedia")
+            .evaluate_manifest(&manifest)
+            .is_allowed());
+        assert_eq!(
+            SourceTypePolicy::new()
+                .require_source_type(true)
+                .evaluate_manifest(&manifest),
+            PolicyDecision::MissingSourceType
+        );
+    }
+}
@@ -16,21 +16,330 @@
 
 #[cfg(feature = "file_io")]
 use std::fs::{read, File};
-use std::io::{Read, Seek, Write};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    io::{Cursor, Read, Seek, Write},
+};
 
 use async_generic::async_generic;
 #[cfg(feature = "json_schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
 
-#[cfg(feature = "file_io")]
-use crate::error::Error;
 use crate::{
-    claim::ClaimAssetData, error::Result, manifest_store::ManifestStore,
-    settings::get_settings_value, status_tracker::DetailedStatusTracker, store::Store,
-    validation_status::ValidationStatus, Manifest, ManifestStoreReport,
+    assertion::AssertionBase,
+    assertions::{
+        region_of_interest::RegionOfInterest, Actions, AssetMetadata, BoxHash, DataHash,
+        IdentityAssertion, Relationship, SoftwareAgent, TimeStamp,
+    },
+    asset_io::HashBlockObjectType,
+    builder::{BUNDLE_MANIFEST, BUNDLE_RESOURCES},
+    claim::{Claim, ClaimAssetData},
+    cose_validator::{get_signature_timestamp, get_signed_payload, get_unprotected_header},
+    error::{Error, Result},
+    hash_stream_by_alg,
+    jumbf::labels::{assertion_label_from_uri, box_name_from_uri, manifest_label_from_uri},
+    jumbf_io::{get_assetio_handler, object_locations_from_stream},
+    manifest_store::ManifestStore,
+    metrics::MetricsObserver,
+    settings::get_settings_value,
+    status_tracker::{DetailedStatusTracker, StatusTracker},
+    store::Store,
+    time_stamp::validate_timestamp,
+    utils::{
+        hash_utils::vec_compare,
+        image_size::image_dimensions,
+        io_utils::stream_len,
+        mime::{format_to_mime, image_mime_from_bytes},
+        xmp_inmemory_utils::XmpInfo,
+    },
+    validation_cache::ValidationCache,
+    validation_status::{self, ValidationStatus},
+    CAIRead, Ingredient, Manifest, ManifestStoreReport, ResourceStore, SigningAlg,
 };
 
+/// Success codes of which [`Reader::is_trusted_and_bound`] requires one for a hard binding.
+const HARD_BINDING_MATCHES: [&str; 3] = [
+    validation_status::ASSERTION_DATAHASH_MATCH,
+    validation_status::ASSERTION_BMFFHASH_MATCH,
+    validation_status::ASSERTION_BOXHASH_MATCH,
+];
+
+/// Failure codes that [`Reader::hard_binding_broken`] reports as a broken hard binding.
+const HARD_BINDING_MISMATCHES: [&str; 3] = [
+    validation_status::ASSERTION_DATAHASH_MISMATCH,
+    validation_status::ASSERTION_BMFFHASH_MISMATCH,
+    validation_status::ASSERTION_BOXHASH_MISMATCH,
+];
+
+/// The bytes signed by a manifest's claim signature.
+///
+/// These allow the claim signature to be verified independently of this SDK.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SignedPayload {
+    /// The COSE `Sig_structure` bytes that were signed.
+    pub to_be_signed: Vec<u8>,
+    /// The signature over `to_be_signed`, as stored in the COSE_Sign1.
+    ///
+    /// ECDSA signatures use the fixed length `r || s` encoding required by COSE.
+    pub signature: Vec<u8>,
+    /// The signing algorithm.
+    pub alg: SigningAlg,
+    /// The DER encoded signing certificate.
+    pub signing_cert: Vec<u8>,
+}
+
+/// A region of an asset that a data hash assertion excludes from its hash.
+///
+/// See [`Reader::hash_exclusions`].
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct HashExclusion {
+    /// The label of the data hash assertion declaring the exclusion.
+    pub label: String,
+    /// The name of the data hash, if it has one.
+    pub name: Option<String>,
+    /// The offset of the excluded region in the asset.
+    pub start: usize,
+    /// The length of the excluded region in bytes.
+    pub length: usize,
+    /// The excluded bytes, as read from the asset.
+    #[serde(skip)]
+    pub bytes: Vec<u8>,
+}
+
+/// A region of an asset that may have changed since the asset was signed.
+///
+/// See [`ModificationReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ModifiedRegion {
+    /// The label of the hard binding assertion that no longer matches.
+    pub label: String,
+    /// The names of the boxes in the region for a box hash, or the name of the data hash.
+    pub name: Option<String>,
+    /// The offset of the region in the asset.
+    pub start: usize,
+    /// The length of the region in bytes.
+    pub length: usize,
+}
+
+/// Whether an asset was modified after it was signed, and where.
+///
+/// See [`Reader::modification_report`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ModificationReport {
+    /// `true` if a hard binding of the active manifest no longer matches the asset.
+    pub modified: bool,
+    /// The regions that may hold the changes, in the order they appear in the asset.
+    ///
+    /// A hash only tells that some of the data it covers changed, so the regions are as
+    /// precise as the hard binding allows: single boxes for a box hash, the data between the
+    /// exclusions for a data hash, and the whole asset for a BMFF hash.
+    pub regions: Vec<ModifiedRegion>,
+}
+
+/// An assertion whose digest in its claim doesn't match the digest of its data.
+///
+/// See [`Reader::assertion_digest_mismatches`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct AssertionDigestMismatch {
+    /// The label of the manifest whose claim references the assertion.
+    pub manifest: String,
+    /// The label of the assertion, including any instance suffix.
+    pub label: String,
+    /// The digest recorded in the claim when it was signed.
+    pub expected: Vec<u8>,
+    /// The digest of the assertion data as read from the manifest store.
+    pub actual: Vec<u8>,
+}
+
+/// The validation results for one manifest in a manifest store.
+///
+/// See [`Reader::validation_reports`].
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct ManifestValidationReport {
+    /// The label of the manifest.
+    pub label: String,
+    /// `true` if this is the active manifest of the asset.
+    pub is_active: bool,
+    /// The failures found when validating the manifest.
+    pub validation_status: Vec<ValidationStatus>,
+}
+
+impl ManifestValidationReport {
+    /// Returns `true` if no failures were found for the manifest.
+    pub fn is_valid(&self) -> bool {
+        self.validation_status.is_empty()
+    }
+}
+
+/// Where a time-stamp for a manifest was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub enum TimestampSource {
+    /// The RFC 3161 time-stamp in the COSE header of the manifest's claim signature.
+    Signature,
+    /// A `c2pa.time-stamp` assertion, in the manifest itself or in a later one.
+    Assertion,
+}
+
+/// A time-stamp for a manifest and the result of validating it.
+///
+/// See [`Reader::timestamp_reports`].
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct TimestampReport {
+    /// Where the time-stamp was found.
+    pub source: TimestampSource,
+    /// The label of the time-stamped manifest.
+    pub manifest: String,
+    /// The label of the manifest holding the time-stamp.
+    pub found_in: String,
+    /// The time asserted by the time-stamp in RFC 3339 format, if it could be read.
+    pub time: Option<String>,
+    /// The validation status code for the time-stamp, such as `timeStamp.trusted`,
+    /// `timeStamp.untrusted` or `timeStamp.mismatch`.
+    pub status: String,
+}
+
+impl TimestampReport {
+    /// Returns `true` if the time-stamp is valid and its authority is on the trust list.
+    pub fn is_trusted(&self) -> bool {
+        self.status == validation_status::TIMESTAMP_TRUSTED
+    }
+}
+
+/// A CAWG identity assertion in a manifest and the result of validating it.
+///
+/// See [`Reader::identity_reports`].
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct IdentityReport {
+    /// The label of the manifest holding the identity assertion.
+    pub manifest: String,
+    /// The label of the identity assertion, including any instance suffix.
+    pub label: String,
+    /// The type of the identity signature, such as `cawg.x509.cose`, if the assertion could
+    /// be read.
+    pub sig_type: Option<String>,
+    /// The asserted identity, the organization of the signing certificate, if the signature
+    /// could be verified.
+    pub identity: Option<String>,
+    /// The roles the named actor had in creating the asset.
+    pub roles: Vec<String>,
+    /// The validation status code for the identity assertion, such as
+    /// `cawg.identity.trusted`, `cawg.identity.well-formed` or `cawg.x509.cose.invalid`.
+    pub status: String,
+}
+
+impl IdentityReport {
+    /// Returns `true` if the identity assertion is valid and its signer's credential is on
+    /// the trust list.
+    pub fn is_trusted(&self) -> bool {
+        self.status == validation_status::CAWG_IDENTITY_TRUSTED
+    }
+}
+
+/// Whether an assertion was created by a manifest's claim generator or gathered from other
+/// sources.
+///
+/// See [`Reader::assertion_origins`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum AssertionOrigin {
+    /// The assertion is listed in the claim's created assertions.
+    Created,
+    /// The assertion is listed in the claim's gathered assertions, which only v2 claims have.
+    Gathered,
+}
+
+/// The result of checking the active manifest's thumbnail against an asset.
+///
+/// See [`Reader::check_thumbnail`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ThumbnailCheck {
+    /// The thumbnail is a valid image of its declared format and a plausible downscale of
+    /// the asset.
+    Valid,
+    /// The active manifest has no thumbnail.
+    Missing,
+    /// The thumbnail is not a valid image of its declared format.
+    Corrupt(String),
+    /// The thumbnail's dimensions cannot be a downscale of the asset's dimensions.
+    DimensionMismatch {
+        /// The width and height of the thumbnail.
+        thumbnail: (u32, u32),
+        /// The width and height of the asset.
+        asset: (u32, u32),
+    },
+    /// The [`ThumbnailMatcher`] found that the thumbnail does not depict the asset.
+    Mismatch,
+}
+
+impl ThumbnailCheck {
+    /// Returns `true` if no problem was found with the thumbnail.
+    pub fn is_valid(&self) -> bool {
+        *self == Self::Valid
+    }
+}
+
+/// Decides whether a thumbnail depicts an asset, such as by perceptual hashing.
+///
+/// See [`Reader::check_thumbnail`]. This is implemented for closures with the same arguments
+/// as [`ThumbnailMatcher::matches`].
+pub trait ThumbnailMatcher {
+    /// Returns `true` if the thumbnail depicts the asset.
+    /// # Arguments
+    /// * `thumbnail_format` - The MIME type of the thumbnail.
+    /// * `thumbnail` - The thumbnail image data.
+    /// * `asset_format` - The MIME type of the asset.
+    /// * `asset` - The asset, positioned at its start.
+    fn matches(
+        &self,
+        thumbnail_format: &str,
+        thumbnail: &[u8],
+        asset_format: &str,
+        asset: &mut dyn CAIRead,
+    ) -> Result<bool>;
+}
+
+impl<F> ThumbnailMatcher for F
+where
+    F: Fn(&str, &[u8], &str, &mut dyn CAIRead) -> Result<bool>,
+{
+    fn matches(
+        &self,
+        thumbnail_format: &str,
+        thumbnail: &[u8],
+        asset_format: &str,
+        asset: &mut dyn CAIRead,
+    ) -> Result<bool> {
+        self(thumbnail_format, thumbnail, asset_format, asset)
+    }
+}
+
+/// The manifest store of an asset, or where to fetch it from.
+///
+/// See [`Reader::from_stream_or_remote`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ManifestSource {
+    /// The manifest store is embedded in the asset.
+    Embedded(Box<Reader>),
+    /// The asset has no embedded manifest store, but its XMP `dcterms:provenance` references
+    /// a remote one at this URL.
+    Remote(String),
+}
+
 /// A reader for the manifest store.
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
@@ -70,6 +379,199 @@ impl Reader {
         })
     }
 
+    /// Create a manifest store Reader from a stream, reporting the time spent in each stage to
+    /// `metrics`.
+    ///
+    /// This reads the stream as [`Reader::from_stream`] does. Parsing the manifest store is
+    /// reported as [`PipelineStage::Parse`](crate::metrics::PipelineStage::Parse) and
+    /// validating it as [`PipelineStage::Validate`](crate::metrics::PipelineStage::Validate).
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The stream to read from.
+    /// * `metrics` - The observer receiving the stage timings.
+    /// # Errors
+    /// If the stream is not a valid manifest store.
+    #[async_generic(async_signature(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        metrics: &dyn MetricsObserver,
+    ))]
+    pub fn from_stream_with_metrics(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        metrics: &dyn MetricsObserver,
+    ) -> Result<Reader> {
+        let verify = get_settings_value::<bool>("verify.verify_after_reading")?; // defaults to true
+        let manifest_store = if _sync {
+            ManifestStore::from_stream_with_metrics(format, &mut stream, verify, Some(metrics))
+        } else {
+            ManifestStore::from_stream_with_metrics_async(
+                format,
+                &mut stream,
+                verify,
+                Some(metrics),
+            )
+            .await
+        }?;
+        Ok(Reader { manifest_store })
+    }
+
+    /// Create a manifest store Reader from a memory mapped asset.
+    ///
+    /// Hashes are computed directly over the mapped bytes rather than through
+    /// [`Read`] buffers, so the asset is never copied into memory.
+    /// # Arguments
+    /// * `format` - The format of the asset.
+    /// * `mmap` - The memory mapped asset.
+    /// # Errors
+    /// If the asset is not a valid manifest store.
+    /// validation status should be checked for non severe errors
+    #[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
+    #[async_generic()]
+    pub fn from_mmap(format: &str, mmap: &crate::MmapReader) -> Result<Reader> {
+        let verify = get_settings_value::<bool>("verify.verify_after_reading")?; // defaults to true
+        let data = mmap.as_bytes();
+        let mut validation_log = DetailedStatusTracker::new();
+
+        let manifest_bytes = Store::load_jumbf_from_stream(format, &mut Cursor::new(data))?;
+        let store = Store::from_jumbf(&manifest_bytes, &mut validation_log)?;
+
+        if verify {
+            // verify store and claims
+            if _sync {
+                Store::verify_store(
+                    &store,
+                    &mut ClaimAssetData::Bytes(data, format),
+                    &mut validation_log,
+                )?;
+            } else {
+                Store::verify_store_async(
+                    &store,
+                    &mut ClaimAssetData::Bytes(data, format),
+                    &mut validation_log,
+                )
+                .await?;
+            }
+        }
+
+        let mut manifest_store = ManifestStore::from_store(store, &validation_log);
+        manifest_store.set_manifest_data(manifest_bytes);
+        Ok(Reader { manifest_store })
+    }
+
+    /// Create a manifest store Reader from a stream, or report where its remote manifest is.
+    ///
+    /// Unlike [`Reader::from_stream`], this never fetches a remote manifest store. If the asset
+    /// has no embedded manifest store but its XMP `dcterms:provenance` holds an `http` or
+    /// `https` URL, [`ManifestSource::Remote`] is returned with that URL so the caller can fetch
+    /// the manifest store and read it with [`Reader::from_manifest_data_and_stream`].
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The stream to read from.
+    /// # Errors
+    /// Returns [`Error::JumbfNotFound`] if the asset has neither an embedded manifest store nor
+    /// a remote manifest URL.
+    #[async_generic()]
+    pub fn from_stream_or_remote(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<ManifestSource> {
+        stream.rewind()?;
+        match crate::jumbf_io::load_jumbf_from_stream(format, &mut stream) {
+            Ok(_) => {}
+            Err(Error::JumbfNotFound) => {
+                stream.rewind()?;
+                return match XmpInfo::from_source(&mut stream, format).provenance {
+                    Some(url) if Store::is_valid_remote_url(&url) => {
+                        Ok(ManifestSource::Remote(url))
+                    }
+                    _ => Err(Error::JumbfNotFound),
+                };
+            }
+            Err(e) => return Err(e),
+        }
+
+        stream.rewind()?;
+        let reader = if _sync {
+            Reader::from_stream(format, stream)
+        } else {
+            Reader::from_stream_async(format, stream).await
+        }?;
+        Ok(ManifestSource::Embedded(Box::new(reader)))
+    }
+
+    /// Create a manifest store Reader from a stream, reusing validation results from `cache`.
+    ///
+    /// The cache is keyed by the SHA-256 digest of the stream's content. When it holds the
+    /// results for the same bytes, the manifest store is read without validating it again and
+    /// the cached validation status is reported. Otherwise the stream is validated as by
+    /// [`Reader::from_stream`] and the results are added to the cache.
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The stream to read from.
+    /// * `cache` - The cache of validation results, such as a
+    ///   [`MemoryValidationCache`](crate::validation_cache::MemoryValidationCache).
+    /// # Errors
+    /// If the stream is not a valid manifest store.
+    #[async_generic(async_signature(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        cache: &dyn ValidationCache,
+    ))]
+    pub fn from_stream_with_cache(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        cache: &dyn ValidationCache,
+    ) -> Result<Reader> {
+        let verify = get_settings_value::<bool>("verify.verify_after_reading")?; // defaults to true
+
+        stream.rewind()?;
+        let digest = hash_stream_by_alg("sha256", &mut stream, None, true)?;
+        stream.rewind()?;
+
+        let cached = if verify { cache.get(&digest) } else { None };
+        let verify_stream = verify && cached.is_none();
+
+        let mut manifest_store = if _sync {
+            ManifestStore::from_stream(format, &mut stream, verify_stream)
+        } else {
+            ManifestStore::from_stream_async(format, &mut stream, verify_stream).await
+        }?;
+
+        match cached {
+            Some(validation_results) => manifest_store.set_validation_results(validation_results),
+            None if verify => cache.insert(&digest, manifest_store.validation_results()),
+            None => {}
+        }
+
+        Ok(Reader { manifest_store })
+    }
+
+    /// Create a manifest store Reader from a stream holding a legacy manifest store.
+    ///
+    /// This is a best-effort migration for inspecting assets signed before version 1.0 of
+    /// the C2PA specification, which [`Reader::from_stream`] rejects with
+    /// [`Error::PrereleaseError`]. Supported are the CBOR claims written by prerelease
+    /// versions of this SDK:
+    /// * Assertion hashes covering only the assertion data are recomputed over the assertion box.
+    /// * Actions whose `parameters` are a string get that string as their `description`.
+    ///
+    /// Prerelease claims stored as JSON are not supported.
+    ///
+    /// The migrated manifests are not validated and carry no signature, so they must not be
+    /// trusted. The validation status reports
+    /// [`STATUS_MIGRATED`](crate::validation_status::STATUS_MIGRATED).
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The stream to read from.
+    /// # Errors
+    /// If the stream does not hold a manifest store that can be migrated.
+    pub fn from_legacy_stream(format: &str, mut stream: impl Read + Seek + Send) -> Result<Reader> {
+        Ok(Reader {
+            manifest_store: ManifestStore::from_legacy_stream(format, &mut stream)?,
+        })
+    }
+
     #[cfg(feature = "file_io")]
     /// Create a manifest store Reader from a file.
     /// # Arguments
@@ -171,9 +673,179 @@ impl Reader {
             .await?;
         }
 
-        Ok(Reader {
-            manifest_store: ManifestStore::from_store(store, &validation_log),
-        })
+        let mut manifest_store = ManifestStore::from_store(store, &validation_log);
+        manifest_store.set_manifest_data(c2pa_data.to_vec());
+        Ok(Reader { manifest_store })
+    }
+
+    /// Create a manifest store [`Reader`] by validating a manifest store that was fetched
+    /// separately from its asset.
+    ///
+    /// This decouples fetching a remote or sidecar manifest from validating it. The claim
+    /// signatures and the hard bindings of `manifest_bytes` are checked against the bytes read
+    /// from `asset`, and any failures are reported in [`Reader::validation_status`].
+    /// # Arguments
+    /// * `manifest_bytes` - The manifest store in JUMBF format
+    /// * `format` - The format of the asset
+    /// * `asset` - The asset to validate the manifest store against
+    /// # Returns
+    /// A [`Reader`] for the manifest store
+    /// # Errors
+    /// If `manifest_bytes` is not a valid manifest store, or severe errors occur in validation
+    #[async_generic()]
+    pub fn from_manifest_and_asset(
+        manifest_bytes: &[u8],
+        format: &str,
+        asset: impl Read + Seek + Send,
+    ) -> Result<Reader> {
+        if _sync {
+            Self::from_manifest_data_and_stream(manifest_bytes, format, asset)
+        } else {
+            Self::from_manifest_data_and_stream_async(manifest_bytes, format, asset).await
+        }
+    }
+
+    /// Create a manifest store [`Reader`] from a base64 encoded manifest store and the bytes of
+    /// its asset.
+    ///
+    /// Web clients often receive the manifest store as a base64 string, for instance in a JSON
+    /// response. It is decoded and validated against `asset` like
+    /// [`Reader::from_manifest_and_asset`]. Whitespace in the string, such as line breaks, is
+    /// ignored.
+    /// # Arguments
+    /// * `manifest_base64` - The manifest store in JUMBF format, base64 encoded
+    /// * `format` - The format of the asset
+    /// * `asset` - The bytes of the asset to validate the manifest store against
+    /// # Returns
+    /// A [`Reader`] for the manifest store
+    /// # Errors
+    /// If `manifest_base64` is not valid base64 or not a valid manifest store, or severe errors
+    /// occur in validation
+    #[async_generic()]
+    pub fn from_base64_manifest_and_bytes(
+        manifest_base64: &str,
+        format: &str,
+        asset: &[u8],
+    ) -> Result<Reader> {
+        let encoded: String = manifest_base64
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+        let manifest_bytes = crate::utils::base64::decode(&encoded)
+            .map_err(|_e| Error::BadParam("manifest store is not valid base64".to_string()))?;
+
+        if _sync {
+            Self::from_manifest_and_asset(&manifest_bytes, format, Cursor::new(asset))
+        } else {
+            Self::from_manifest_and_asset_async(&manifest_bytes, format, Cursor::new(asset)).await
+        }
+    }
+
+    /// Create a manifest store [`Reader`] from a signed manifest bundle and the asset it is
+    /// bound to.
+    ///
+    /// The manifest store in the bundle is validated against `asset`. The resources in the
+    /// bundle are not covered by the manifest's signature, so they are returned separately
+    /// and must not be trusted as manifest content. See
+    /// [`Builder::sign_to_archive`](crate::Builder::sign_to_archive) for the layout of the
+    /// bundle.
+    /// # Arguments
+    /// * `archive` - The signed manifest bundle.
+    /// * `format` - The format of the asset.
+    /// * `asset` - The asset to validate the manifest store against.
+    /// # Returns
+    /// A [`Reader`] for the manifest store and the unverified resources of the bundle.
+    /// # Errors
+    /// If the bundle cannot be read or has no manifest store, or severe errors occur in
+    /// validation.
+    pub fn from_archive(
+        archive: impl Read + Seek,
+        format: &str,
+        asset: impl Read + Seek + Send,
+    ) -> Result<(Reader, ResourceStore)> {
+        let mut zip = ZipArchive::new(archive).map_err(|e| Error::OtherError(Box::new(e)))?;
+
+        let mut manifest_bytes = Vec::new();
+        zip.by_name(BUNDLE_MANIFEST)
+            .map_err(|_| Error::JumbfNotFound)?
+            .read_to_end(&mut manifest_bytes)?;
+
+        let reader = Self::from_manifest_data_and_stream(&manifest_bytes, format, asset)?;
+
+        let mut resources = ResourceStore::new();
+        for i in 0..zip.len() {
+            let mut file = zip
+                .by_index(i)
+                .map_err(|e| Error::OtherError(Box::new(e)))?;
+            let Some(id) = file.name().strip_prefix(BUNDLE_RESOURCES) else {
+                continue;
+            };
+            if id.is_empty() {
+                continue;
+            }
+            let id = id.to_owned();
+            let mut data = Vec::new();
+            file.read_to_end(&mut data)?;
+            resources.add(id, data)?;
+        }
+        Ok((reader, resources))
+    }
+
+    /// Create a manifest store [`Reader`] from a stream whose active manifest has a detached
+    /// claim signature.
+    ///
+    /// Some workflows, such as PDF signing services, embed the claim and assertions in the
+    /// asset but keep the COSE signature elsewhere. The active manifest's signature box must be
+    /// empty or zero filled; `signature` is used in its place when validating the claim.
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The stream to read from.
+    /// * `signature` - The COSE_Sign1 signature of the active manifest's claim.
+    /// # Errors
+    /// Returns [`Error::BadParam`] if the active manifest already holds a signature.
+    /// If the stream is not a valid manifest store, or severe errors occur in validation
+    /// validation status should be checked for non severe errors
+    #[async_generic()]
+    pub fn from_stream_with_detached_signature(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        signature: &[u8],
+    ) -> Result<Reader> {
+        let mut validation_log = DetailedStatusTracker::new();
+
+        stream.rewind()?;
+        let c2pa_data = crate::jumbf_io::load_jumbf_from_stream(format, &mut stream)?;
+        let mut store = Store::from_jumbf(&c2pa_data, &mut validation_log)?;
+
+        let claim = store
+            .provenance_claim_mut()
+            .ok_or(Error::ProvenanceMissing)?;
+        if claim.signature_val().iter().any(|b| *b != 0) {
+            return Err(Error::BadParam(
+                "the active manifest is not missing its signature".to_string(),
+            ));
+        }
+        claim.set_signature_val(signature.to_vec());
+
+        stream.rewind()?;
+        if _sync {
+            Store::verify_store(
+                &store,
+                &mut ClaimAssetData::Stream(&mut stream, format),
+                &mut validation_log,
+            )?;
+        } else {
+            Store::verify_store_async(
+                &store,
+                &mut ClaimAssetData::Stream(&mut stream, format),
+                &mut validation_log,
+            )
+            .await?;
+        }
+
+        let mut manifest_store = ManifestStore::from_store(store, &validation_log);
+        manifest_store.set_manifest_data(c2pa_data);
+        Ok(Reader { manifest_store })
     }
 
     /// Get the manifest store as a JSON string
@@ -199,32 +871,1073 @@ impl Reader {
         self.manifest_store.validation_status()
     }
 
-    /// Return the active [`Manifest`] if it exists.
-    pub fn active_manifest(&self) -> Option<&Manifest> {
-        self.manifest_store.get_active()
+    /// Returns the informational codes logged when validating the manifest store.
+    ///
+    /// These are neither successes nor failures, such as
+    /// [`STATUS_INGREDIENT_WEAK_HASH`](validation_status::STATUS_INGREDIENT_WEAK_HASH), and are
+    /// not included in [`validation_status`](Reader::validation_status).
+    /// # Example
+    /// ```no_run
+    /// use c2pa::Reader;
+    /// let stream = std::io::Cursor::new(include_bytes!("../tests/fixtures/CA.jpg"));
+    /// let reader = Reader::from_stream("image/jpeg", stream).unwrap();
+    /// for status in reader.validation_informational() {
+    ///     println!("{}", status.code());
+    /// }
+    /// ```
+    pub fn validation_informational(&self) -> &[ValidationStatus] {
+        self.manifest_store.validation_informational()
     }
 
-    /// Return the active [`Manifest`] label if one exists.
-    pub fn active_label(&self) -> Option<&str> {
-        self.manifest_store.active_label()
-    }
+    /// Returns `true` if the active manifest's signature, trust and hard binding all passed
+    /// validation.
+    ///
+    /// This fails closed: the active manifest must have been validated with
+    /// `claimSignature.validated`, `signingCredential.trusted` and one of
+    /// `assertion.dataHash.match`, `assertion.bmffHash.match` or `assertion.boxesHash.match`,
+    /// and the [`validation_status`] must not report any failure for it. This includes
+    /// `com.adobe.signingCredential.developmentTrust`, so assets signed with test credentials
+    /// are never reported as trusted. Failures reported only for ingredients do not affect the
+    /// result.
+    ///
+    /// `signingCredential.trusted` is only reported when the `verify.verify_trust` setting is
+    /// enabled, so this always returns `false` without trust verification.
+    ///
+    /// [`validation_status`]: Reader::validation_status
+    /// # Example
+    /// ```no_run
+    /// use c2pa::Reader;
+    /// let stream = std::io::Cursor::new(include_bytes!("../tests/fixtures/CA.jpg"));
+    /// let reader = Reader::from_stream("image/jpeg", stream).unwrap();
+    /// if reader.is_trusted_and_bound() {
+    ///     println!("manifest is valid for this asset");
+    /// }
+    /// ```
+    pub fn is_trusted_and_bound(&self) -> bool {
+        let Some(active_label) = self.active_label() else {
+            return false;
+        };
 
-    /// Returns an iterator over [`Manifest`][Manifest]s.
-    pub fn iter_manifests(&self) -> impl Iterator<Item = &Manifest> + '_ {
-        self.manifest_store.manifests().values()
+        // statuses without a manifest URL, such as trust results, count for the active manifest
+        let is_active = |status: &&ValidationStatus| {
+            status
+                .url()
+                .and_then(manifest_label_from_uri)
+                .map_or(true, |label| label == active_label)
+        };
+
+        if self
+            .validation_status()
+            .unwrap_or_default()
+            .iter()
+            .any(|status| is_active(&status))
+        {
+            return false;
+        }
+
+        let successes: Vec<&str> = self
+            .manifest_store
+            .validation_successes()
+            .iter()
+            .filter(is_active)
+            .map(ValidationStatus::code)
+            .collect();
+
+        successes.contains(&validation_status::CLAIM_SIGNATURE_VALIDATED)
+            && successes.contains(&validation_status::SIGNING_CREDENTIAL_TRUSTED)
+            && successes
+                .iter()
+                .any(|code| HARD_BINDING_MATCHES.contains(code))
     }
 
-    /// Return a [`Manifest`] for a given label if it exists.
-    /// # Arguments
-    /// * `label` - The label of the requested [`Manifest`]
-    pub fn get_manifest(&self, label: &str) -> Option<&Manifest> {
-        self.manifest_store.get(label)
+    /// Returns `true` if the active manifest's hard binding no longer matches the asset.
+    ///
+    /// This is the case when the asset was changed after it was signed, and the
+    /// [`validation_status`] reports `assertion.dataHash.mismatch`,
+    /// `assertion.bmffHash.mismatch` or `assertion.boxesHash.mismatch` for the active
+    /// manifest. See [`resign_after_edit`](crate::resign_after_edit) to record such an edit in
+    /// a new manifest.
+    ///
+    /// [`validation_status`]: Reader::validation_status
+    pub fn hard_binding_broken(&self) -> bool {
+        let Some(active_label) = self.active_label() else {
+            return false;
+        };
+
+        self.validation_status()
+            .unwrap_or_default()
+            .iter()
+            .any(|status| {
+                let is_active = status
+                    .url()
+                    .and_then(manifest_label_from_uri)
+                    .map_or(true, |label| label == active_label);
+
+                is_active && HARD_BINDING_MISMATCHES.contains(&status.code())
+            })
     }
 
-    /// Write a resource identified by URI to the given stream.
-    /// # Arguments
-    /// * `uri` - The URI of the resource to write (from an identifier field).
-    /// * `stream` - The stream to write to.
+    /// Validates every manifest in the manifest store independently.
+    ///
+    /// Returns a report per manifest, in the order the manifests appear in the store. The active
+    /// manifest is the one the asset was last signed with and is always the last report. Its
+    /// report holds the failures found for it when the asset was read, including those for its
+    /// hard binding to the asset. Every other manifest is validated on its own: its signature
+    /// and assertions are checked, but not its hard bindings, since those apply to earlier
+    /// versions of the asset.
+    /// # Example
+    /// ```no_run
+    /// use c2pa::Reader;
+    /// let stream = std::io::Cursor::new(include_bytes!("../tests/fixtures/CA.jpg"));
+    /// let reader = Reader::from_stream("image/jpeg", stream).unwrap();
+    /// let reports = reader.validation_reports();
+    /// let all_valid = reports.iter().all(|report| report.is_valid());
+    /// ```
+    pub fn validation_reports(&self) -> Vec<ManifestValidationReport> {
+        let store = self.manifest_store.store();
+        let active_label = self.active_label();
+
+        store
+            .claims()
+            .iter()
+            .map(|claim| {
+                let is_active = Some(claim.label()) == active_label;
+
+                let validation_status = if is_active {
+                    self.validation_status()
+                        .unwrap_or_default()
+                        .iter()
+                        .filter(|status| {
+                            status
+                                .url()
+                                .and_then(manifest_label_from_uri)
+                                .map_or(true, |label| label == claim.label())
+                        })
+                        .cloned()
+                        .collect()
+                } else {
+                    let mut validation_log = DetailedStatusTracker::new();
+                    let result = store.verify_claim_standalone(claim, &mut validation_log);
+
+                    let mut statuses: Vec<ValidationStatus> = validation_log
+                        .get_log()
+                        .iter()
+                        .filter_map(ValidationStatus::from_validation_item)
+                        .filter(|status| !status.passed())
+                        .collect();
+
+                    if let (Err(e), true) = (result, statuses.is_empty()) {
+                        statuses.push(ValidationStatus::from_error(&e));
+                    }
+                    statuses
+                };
+
+                ManifestValidationReport {
+                    label: claim.label().to_owned(),
+                    is_active,
+                    validation_status,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the assertions whose digests recorded in their claims don't match their data,
+    /// for every manifest in the store.
+    ///
+    /// These are the assertions reported as `assertion.hashedURI.mismatch`, with both digests,
+    /// to pinpoint the assertions altered after their manifest was signed. Assertions missing
+    /// from the store are reported as `assertion.missing` and aren't included.
+    pub fn assertion_digest_mismatches(&self) -> Vec<AssertionDigestMismatch> {
+        let store = self.manifest_store.store();
+        let mut mismatches = Vec::new();
+
+        for claim in store.claims() {
+            for assertion in claim.all_assertions() {
+                let (label, instance) = Claim::assertion_label_from_link(&assertion.url());
+                let Some(claim_assertion) = claim.get_claim_assertion(&label, instance) else {
+                    continue;
+                };
+
+                let expected = assertion.hash();
+                if !vec_compare(claim_assertion.hash(), &expected) {
+                    mismatches.push(AssertionDigestMismatch {
+                        manifest: claim.label().to_owned(),
+                        label: claim_assertion.label(),
+                        expected,
+                        actual: claim_assertion.hash().to_vec(),
+                    });
+                }
+            }
+        }
+        mismatches
+    }
+
+    /// Returns the time-stamps for the manifests in the store and the results of validating
+    /// them.
+    ///
+    /// A manifest can be time-stamped by the RFC 3161 time-stamp in its claim signature, and
+    /// by `c2pa.time-stamp` assertions, which time-stamp the claim signature of the manifest
+    /// they reference. The [`TimestampReport::source`] tells the two apart. Each time-stamp is
+    /// verified against the data it time-stamps, and its authority is checked against the
+    /// trust list.
+    pub fn timestamp_reports(&self) -> Vec<TimestampReport> {
+        let store = self.manifest_store.store();
+        let mut reports = Vec::new();
+
+        let report = |source, manifest: &str, found_in: &str, ts: &[u8], data: &[u8]| {
+            let (time, status) = validate_timestamp(ts, data, store.trust_handler());
+            TimestampReport {
+                source,
+                manifest: manifest.to_owned(),
+                found_in: found_in.to_owned(),
+                time: time.map(|time| time.to_rfc3339()),
+                status: status.to_owned(),
+            }
+        };
+
+        for claim in store.claims() {
+            if let Ok(Some((ts, data))) = claim
+                .data()
+                .and_then(|data| get_signature_timestamp(claim.signature_val(), &data))
+            {
+                reports.push(report(
+                    TimestampSource::Signature,
+                    claim.label(),
+                    claim.label(),
+                    &ts,
+                    &data,
+                ));
+            }
+
+            for claim_assertion in claim.claim_assertion_store() {
+                if claim_assertion.label_raw() != TimeStamp::LABEL {
+                    continue;
+                }
+                let Ok(timestamp) = TimeStamp::from_assertion(claim_assertion.assertion()) else {
+                    continue;
+                };
+
+                for (manifest_id, ts) in timestamp.timestamps() {
+                    let label = Store::manifest_label_from_path(manifest_id);
+
+                    // the time-stamp is over the signature of the referenced claim
+                    let signature = store.get_claim(&label).and_then(|referenced| {
+                        let data = referenced.data().ok()?;
+                        get_signed_payload(referenced.signature_val(), &data)
+                            .ok()
+                            .map(|(_, signature, _, _)| signature)
+                    });
+
+                    reports.push(match signature {
+                        Some(signature) => report(
+                            TimestampSource::Assertion,
+                            &label,
+                            claim.label(),
+                            ts,
+                            &signature,
+                        ),
+                        None => TimestampReport {
+                            source: TimestampSource::Assertion,
+                            manifest: label,
+                            found_in: claim.label().to_owned(),
+                            time: None,
+                            status: validation_status::CLAIM_MISSING.to_owned(),
+                        },
+                    });
+                }
+            }
+        }
+        reports
+    }
+
+    /// Returns the CAWG identity assertions of the manifests in the store and the results of
+    /// validating them.
+    ///
+    /// Each identity assertion must reference the hard binding of its claim, and its
+    /// referenced assertions must match the claim. Identity signatures of type
+    /// `cawg.x509.cose` are verified and their signing certificate is checked against the
+    /// trust list; other signature types are reported as `cawg.identity.sig_type.unknown`.
+    pub fn identity_reports(&self) -> Vec<IdentityReport> {
+        let store = self.manifest_store.store();
+        let mut reports = Vec::new();
+
+        for claim in store.claims() {
+            for claim_assertion in claim.claim_assertion_store() {
+                if claim_assertion.label_raw() != IdentityAssertion::LABEL {
+                    continue;
+                }
+
+                let report = match IdentityAssertion::from_assertion(claim_assertion.assertion()) {
+                    Ok(identity) => {
+                        let (org, status) = identity.validate(claim, store.trust_handler());
+                        IdentityReport {
+                            manifest: claim.label().to_owned(),
+                            label: claim_assertion.label(),
+                            sig_type: Some(identity.signer_payload().sig_type().to_owned()),
+                            identity: org,
+                            roles: identity.signer_payload().roles().to_vec(),
+                            status: status.to_owned(),
+                        }
+                    }
+                    Err(_) => IdentityReport {
+                        manifest: claim.label().to_owned(),
+                        label: claim_assertion.label(),
+                        sig_type: None,
+                        identity: None,
+                        roles: Vec::new(),
+                        status: validation_status::CAWG_IDENTITY_CBOR_INVALID.to_owned(),
+                    },
+                };
+                reports.push(report);
+            }
+        }
+        reports
+    }
+
+    /// Reads and verifies only the time-stamp of the active manifest's claim signature.
+    ///
+    /// This is a quick way to find out when an asset was signed. The manifest store is read
+    /// without validating it: the claim signature, signing credential and hard bindings are not
+    /// checked. Only the RFC 3161 time-stamp is verified against the signature it time-stamps,
+    /// and its authority is checked against the trust list.
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The stream to read from.
+    /// # Returns
+    /// The time asserted by the time-stamp authority and the validation status of the
+    /// time-stamp, or `None` if the claim signature is not time-stamped.
+    /// # Errors
+    /// If the stream does not contain a manifest store or it has no active manifest.
+    pub fn verify_timestamp(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<Option<TimestampReport>> {
+        let mut validation_log = DetailedStatusTracker::new();
+        let manifest_bytes = Store::load_jumbf_from_stream(format, &mut stream)?;
+        let store = Store::from_jumbf(&manifest_bytes, &mut validation_log)?;
+        let claim = store.provenance_claim().ok_or(Error::ProvenanceMissing)?;
+
+        let Some((ts, data)) = get_signature_timestamp(claim.signature_val(), &claim.data()?)?
+        else {
+            return Ok(None);
+        };
+
+        let (time, status) = validate_timestamp(&ts, &data, store.trust_handler());
+        Ok(Some(TimestampReport {
+            source: TimestampSource::Signature,
+            manifest: claim.label().to_owned(),
+            found_in: claim.label().to_owned(),
+            time: time.map(|time| time.to_rfc3339()),
+            status: status.to_owned(),
+        }))
+    }
+
+    /// Validates that `stream` is the source asset of the active manifest's ingredient with
+    /// the given `instance_id`.
+    ///
+    /// The manifest in `stream` must match the hash the ingredient recorded for it and `stream`
+    /// must validate against that manifest's hard binding. An ingredient without a manifest
+    /// records no hash of its source, so `stream` must then have no manifest either and carry
+    /// the ingredient's instance ID in its XMP.
+    /// # Arguments
+    /// * `instance_id` - The instance ID of the ingredient.
+    /// * `format` - The format of the stream.
+    /// * `stream` - The source asset of the ingredient.
+    /// # Returns
+    /// The failure statuses found, an empty list means the source matches the ingredient.
+    /// # Errors
+    /// If there is no such ingredient, or it references a manifest and `stream` does not
+    /// contain a manifest store.
+    pub fn validate_ingredient_source(
+        &self,
+        instance_id: &str,
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<Vec<ValidationStatus>> {
+        let store = self.manifest_store.store();
+        let claim = store.provenance_claim().ok_or(Error::ProvenanceMissing)?;
+
+        let mut validation_log = DetailedStatusTracker::new();
+        Store::verify_ingredient_source(
+            claim,
+            instance_id,
+            format,
+            &mut stream,
+            &mut validation_log,
+        )?;
+
+        Ok(validation_log
+            .get_log()
+            .iter()
+            .filter_map(ValidationStatus::from_validation_item)
+            .filter(|status| !status.passed())
+            .collect())
+    }
+
+    /// Checks that the active manifest's thumbnail is plausibly a thumbnail of `stream`.
+    ///
+    /// An asset may be altered while keeping the thumbnail of the original. This checks that
+    /// the thumbnail is a valid image of its declared format and, for JPEG, PNG and GIF assets,
+    /// that its dimensions are a downscale of the asset's. The thumbnail is fully decoded when
+    /// the `add_thumbnails` feature is enabled, otherwise only its header is checked.
+    ///
+    /// If the thumbnail passes these checks and a `matcher` is given, it decides whether the
+    /// thumbnail depicts the asset.
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The asset the manifest is for.
+    /// * `matcher` - An optional check of the thumbnail's content against the asset.
+    /// # Errors
+    /// If there is no active manifest, the asset cannot be read, or the matcher fails.
+    pub fn check_thumbnail(
+        &self,
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        matcher: Option<&dyn ThumbnailMatcher>,
+    ) -> Result<ThumbnailCheck> {
+        let manifest = self.active_manifest().ok_or(Error::ProvenanceMissing)?;
+        let Some((thumbnail_format, thumbnail)) = self.manifest_store.thumbnail(manifest) else {
+            return Ok(ThumbnailCheck::Missing);
+        };
+
+        let thumbnail_format = format_to_mime(thumbnail_format);
+        let detected_format = image_mime_from_bytes(&thumbnail);
+        if detected_format != Some(thumbnail_format.as_str()) {
+            return Ok(ThumbnailCheck::Corrupt(format!(
+                "thumbnail declared as {thumbnail_format} is {}",
+                detected_format.unwrap_or("not a recognized image")
+            )));
+        }
+        let Some(thumbnail_size) = image_dimensions(&thumbnail) else {
+            return Ok(ThumbnailCheck::Corrupt(
+                "thumbnail header is malformed".to_string(),
+            ));
+        };
+        #[cfg(feature = "add_thumbnails")]
+        if let Err(err) = crate::utils::thumbnail::validate_image(&thumbnail_format, &thumbnail) {
+            return Ok(ThumbnailCheck::Corrupt(err.to_string()));
+        }
+
+        let asset_format = format_to_mime(format);
+        if matches!(
+            asset_format.as_str(),
+            "image/jpeg" | "image/png" | "image/gif"
+        ) {
+            stream.rewind()?;
+            let mut asset = Vec::new();
+            stream.read_to_end(&mut asset)?;
+            let asset_size = image_dimensions(&asset).ok_or(Error::InvalidAsset(
+                "asset image header is malformed".to_string(),
+            ))?;
+            if !is_downscale(thumbnail_size, asset_size) {
+                return Ok(ThumbnailCheck::DimensionMismatch {
+                    thumbnail: thumbnail_size,
+                    asset: asset_size,
+                });
+            }
+        }
+
+        if let Some(matcher) = matcher {
+            stream.rewind()?;
+            if !matcher.matches(&thumbnail_format, &thumbnail, &asset_format, &mut stream)? {
+                return Ok(ThumbnailCheck::Mismatch);
+            }
+        }
+        Ok(ThumbnailCheck::Valid)
+    }
+
+    /// Returns the bytes signed by the active manifest's claim signature.
+    ///
+    /// The signature can be verified with any crypto library using the public key of the
+    /// signing certificate.
+    /// # Errors
+    /// If there is no active manifest or its signature cannot be parsed.
+    pub fn signed_payload(&self) -> Result<SignedPayload> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        let (to_be_signed, signature, alg, signing_cert) =
+            get_signed_payload(claim.signature_val(), &claim.data()?)?;
+
+        Ok(SignedPayload {
+            to_be_signed,
+            signature,
+            alg,
+            signing_cert,
+        })
+    }
+
+    /// Returns the unprotected COSE header of the active manifest's claim signature as a CBOR
+    /// map.
+    ///
+    /// Besides standard fields such as the `sigTst` time-stamp, `rVals` OCSP responses or a
+    /// legacy `x5chain`, this includes any non-standard fields the signer added.
+    /// # Errors
+    /// If there is no active manifest or its signature cannot be parsed.
+    pub fn cose_unprotected_header(&self) -> Result<serde_cbor::Value> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        get_unprotected_header(claim.signature_val(), &claim.data()?)
+    }
+
+    /// Verifies the active manifest's claim signature against a pinned public key.
+    ///
+    /// The signature is checked directly with `public_key`, without using the signing
+    /// certificate or building a trust chain. This suits publishers whose key is known.
+    /// # Arguments
+    /// * `public_key` - The expected signer's public key as a DER or PEM encoded
+    ///   SubjectPublicKeyInfo.
+    /// # Returns
+    /// `true` if the claim was signed with `public_key`, `false` if it was not.
+    /// # Errors
+    /// If there is no active manifest, its signature cannot be parsed, or `public_key` is not a
+    /// public key.
+    #[cfg(feature = "openssl")]
+    pub fn verify_with_public_key(&self, public_key: &[u8]) -> Result<bool> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        crate::cose_validator::verify_cose_with_public_key(
+            claim.signature_val(),
+            &claim.data()?,
+            public_key,
+        )
+    }
+
+    /// Checks the active manifest's signing credential against several candidate trust lists.
+    ///
+    /// The lists are tried in order, each on its own, ignoring the anchors of the `trust`
+    /// settings. The configured intermediates are used to complete the certificate chain, and
+    /// the configured EKUs and allowed list apply to every list.
+    /// # Arguments
+    /// * `lists` - The candidate trust lists.
+    /// # Returns
+    /// The identifier of the first list whose anchors establish trust in the signing
+    /// certificate, or `None` if no list does. No list matches unless the claim signature
+    /// verifies and the signing certificate is valid and not revoked.
+    /// # Errors
+    /// If there is no active manifest or its signature cannot be parsed.
+    #[cfg(feature = "openssl")]
+    pub fn matching_trust_list<'a>(
+        &self,
+        lists: &'a [crate::settings::TrustList],
+    ) -> Result<Option<&'a str>> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        crate::cose_validator::matching_trust_list(claim.signature_val(), &claim.data()?, lists)
+    }
+
+    /// Returns the regions of `stream` that the active manifest's data hashes exclude.
+    ///
+    /// This allows auditing that nothing but the manifest store, or other regions the format
+    /// requires, was left out of the hard binding. Only `c2pa.hash.data` assertions are
+    /// reported, BMFF and box hashes describe their exclusions differently.
+    /// # Arguments
+    /// * `stream` - The asset the manifest is for.
+    /// # Errors
+    /// If there is no active manifest, a data hash cannot be decoded, or an excluded region is
+    /// not inside `stream`.
+    pub fn hash_exclusions(
+        &self,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<Vec<HashExclusion>> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        let mut exclusions = Vec::new();
+        for assertion in claim.hash_assertions() {
+            if assertion.label_root() != DataHash::LABEL {
+                continue;
+            }
+            let data_hash = DataHash::from_assertion(assertion)?;
+            for range in data_hash.exclusions.iter().flatten() {
+                // the range comes from the manifest, so only read what the stream holds
+                let mut bytes = Vec::new();
+                stream.seek(std::io::SeekFrom::Start(range.start() as u64))?;
+                (&mut stream)
+                    .take(range.length() as u64)
+                    .read_to_end(&mut bytes)?;
+                if bytes.len() != range.length() {
+                    return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+                }
+
+                exclusions.push(HashExclusion {
+                    label: assertion.label(),
+                    name: data_hash.name.clone(),
+                    start: range.start(),
+                    length: range.length(),
+                    bytes,
+                });
+            }
+        }
+        Ok(exclusions)
+    }
+
+    /// Validates every hard binding assertion of the active manifest against `stream`.
+    ///
+    /// A manifest may carry several binding assertions. Each data hash, BMFF hash and box hash
+    /// is checked in the way its type requires, and one status is returned per binding, so
+    /// matches are reported as well as mismatches. Remote data hashes are not checked.
+    /// # Arguments
+    /// * `format` - The format of the asset, as a mime type or extension.
+    /// * `stream` - The asset the manifest is for.
+    /// # Errors
+    /// If there is no active manifest or a binding assertion cannot be decoded.
+    pub fn validate_bindings(
+        &self,
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<Vec<ValidationStatus>> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        let mut validation_log = DetailedStatusTracker::new();
+        Claim::verify_hard_bindings(
+            claim,
+            &mut ClaimAssetData::Stream(&mut stream, format),
+            &mut validation_log,
+        )?;
+
+        Ok(validation_log
+            .get_log()
+            .iter()
+            .filter_map(ValidationStatus::from_validation_item)
+            .collect())
+    }
+
+    /// Returns whether `stream` was modified since the active manifest was signed, and which
+    /// regions of it may have changed.
+    ///
+    /// The hard bindings are validated as in [`Reader::validate_bindings`]. Each one that no
+    /// longer matches contributes the regions it hashes. A data hash exclusion that no longer
+    /// lines up with the manifest in the asset, such as a PDF manifest object that moved,
+    /// is reported too, since other data now sits in the excluded bytes.
+    /// # Arguments
+    /// * `format` - The format of the asset, as a mime type or extension.
+    /// * `stream` - The asset the manifest is for.
+    /// # Errors
+    /// If there is no active manifest or a binding assertion cannot be decoded.
+    pub fn modification_report(
+        &self,
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+    ) -> Result<ModificationReport> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        let statuses = self.validate_bindings(format, &mut stream)?;
+        let mut report = ModificationReport {
+            modified: statuses.iter().any(|status| !status.passed()),
+            regions: Vec::new(),
+        };
+        if !report.modified {
+            return Ok(report);
+        }
+
+        let asset_len = stream_len(&mut stream)? as usize;
+        for assertion in claim.hash_assertions() {
+            let label = assertion.label();
+            let url = claim.assertion_uri(&label);
+            let mismatched = statuses
+                .iter()
+                .any(|status| status.url() == Some(url.as_str()) && !status.passed());
+            if !mismatched {
+                continue;
+            }
+
+            let region = |name: Option<String>, start: usize, length: usize| ModifiedRegion {
+                label: label.clone(),
+                name,
+                start,
+                length,
+            };
+
+            if assertion.label_root() == DataHash::LABEL {
+                let data_hash = DataHash::from_assertion(assertion)?;
+                let mut exclusions = data_hash.exclusions.clone().unwrap_or_default();
+                exclusions.sort_by_key(|range| range.start());
+
+                // the hashed data lies between the exclusions
+                let mut pos = 0;
+                for range in &exclusions {
+                    let end = range.start().min(asset_len);
+                    if end > pos {
+                        report
+                            .regions
+                            .push(region(data_hash.name.clone(), pos, end - pos));
+                    }
+                    pos = pos.max(range.start().saturating_add(range.length()));
+                }
+                if asset_len > pos {
+                    report
+                        .regions
+                        .push(region(data_hash.name.clone(), pos, asset_len - pos));
+                }
+
+                // an exclusion that no longer holds the manifest now hides other data
+                let manifest_positions: Vec<_> = object_locations_from_stream(format, &mut stream)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|position| position.htype == HashBlockObjectType::Cai)
+                    .collect();
+                if !manifest_positions.is_empty() {
+                    for range in &exclusions {
+                        if !manifest_positions.iter().any(|position| {
+                            position.offset == range.start() && position.length == range.length()
+                        }) {
+                            report.regions.push(region(
+                                data_hash.name.clone(),
+                                range.start(),
+                                range.length(),
+                            ));
+                        }
+                    }
+                }
+            } else if assertion.label_root() == BoxHash::LABEL {
+                let box_hash = BoxHash::from_assertion(assertion)?;
+                let mismatched_boxes = get_assetio_handler(format)
+                    .and_then(|handler| handler.asset_box_hash_ref())
+                    .and_then(|bhp| {
+                        box_hash
+                            .mismatched_boxes(&mut stream, Some(claim.alg()), bhp)
+                            .ok()
+                    })
+                    .filter(|boxes| !boxes.is_empty());
+                match mismatched_boxes {
+                    Some(boxes) => {
+                        for (names, range) in boxes {
+                            report
+                                .regions
+                                .push(region(Some(names), range.start(), range.length()));
+                        }
+                    }
+                    // the boxes can't be lined up with the hashed ones, or an appended
+                    // segment changed
+                    None => report.regions.push(region(None, 0, asset_len)),
+                }
+            } else {
+                report.regions.push(region(None, 0, asset_len));
+            }
+        }
+        report
+            .regions
+            .sort_by_key(|region| (region.start, region.length));
+        report.regions.dedup();
+
+        Ok(report)
+    }
+
+    /// Reads and validates only the assertion of the active manifest with the given label.
+    ///
+    /// This is a quick way to confirm one assertion, such as the data hash of an asset. The
+    /// manifest store is read without validating it: the claim signature, signing credential
+    /// and other assertions are not checked. The hash recorded for the assertion in the claim
+    /// is checked against its data. If that matches and the assertion is a hard binding, it is
+    /// also checked against the asset.
+    /// # Arguments
+    /// * `format` - The format of the stream.
+    /// * `stream` - The asset to read the manifest store from and validate the hard binding
+    ///   against.
+    /// * `label` - The label of the assertion, with an instance suffix if there are several.
+    /// # Returns
+    /// The validation status of the assertion, such as `assertion.hashedURI.mismatch` or
+    /// `assertion.dataHash.match`. A hard binding that could not be checked against the asset
+    /// is reported as `com.adobe.assertion.notChecked`.
+    /// # Errors
+    /// If the stream does not contain a manifest store, it has no active manifest or the
+    /// active manifest has no assertion with that label.
+    pub fn validate_assertion(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        label: &str,
+    ) -> Result<ValidationStatus> {
+        let mut validation_log = DetailedStatusTracker::new();
+        let manifest_bytes = Store::load_jumbf_from_stream(format, &mut stream)?;
+        let store = Store::from_jumbf(&manifest_bytes, &mut validation_log)?;
+        let claim = store.provenance_claim().ok_or(Error::ProvenanceMissing)?;
+
+        let (base_label, instance) = Claim::assertion_label_from_link(label);
+        let missing = || Error::AssertionMissing {
+            url: claim.assertion_uri(label),
+        };
+        let claim_assertion = claim
+            .get_claim_assertion(&base_label, instance)
+            .ok_or_else(missing)?;
+        let hashed_uri = claim
+            .all_assertions()
+            .find(|a| Claim::assertion_label_from_link(&a.url()) == (base_label.clone(), instance))
+            .ok_or_else(missing)?;
+
+        let url = claim.assertion_uri(&claim_assertion.label());
+        if !vec_compare(claim_assertion.hash(), &hashed_uri.hash()) {
+            return Ok(
+                ValidationStatus::new(validation_status::ASSERTION_HASHEDURI_MISMATCH).set_url(url),
+            );
+        }
+
+        let is_binding = claim
+            .hash_assertions()
+            .iter()
+            .any(|a| a.label() == claim_assertion.assertion().label());
+        if is_binding {
+            stream.rewind()?;
+            let mut validation_log = DetailedStatusTracker::new();
+            Claim::verify_hard_bindings(
+                claim,
+                &mut ClaimAssetData::Stream(&mut stream, format),
+                &mut validation_log,
+            )?;
+
+            // bindings are logged with the label of their assertion, without an instance
+            let binding_url = claim.assertion_uri(&claim_assertion.assertion().label());
+            let status = validation_log
+                .get_log()
+                .iter()
+                .filter_map(ValidationStatus::from_validation_item)
+                .find(|status| status.url() == Some(binding_url.as_str()))
+                .unwrap_or_else(|| {
+                    ValidationStatus::new(validation_status::STATUS_ASSERTION_NOT_CHECKED)
+                        .set_url(binding_url)
+                });
+            return Ok(status);
+        }
+
+        Ok(ValidationStatus::new(validation_status::ASSERTION_HASHEDURI_MATCH).set_url(url))
+    }
+
+    /// Returns the number of bytes each component of the active manifest takes.
+    ///
+    /// The claim and its signature are reported as `claim` and `signature`, each assertion by
+    /// its label, with an instance suffix if there are several, and each data box by its name.
+    /// Thumbnails are assertions, so this shows which of them are worth stripping to fit a
+    /// manifest into a size-constrained embedding.
+    /// # Errors
+    /// If there is no active manifest or its claim cannot be encoded.
+    pub fn size_breakdown(&self) -> Result<BTreeMap<String, usize>> {
+        let claim = self
+            .manifest_store
+            .store()
+            .provenance_claim()
+            .ok_or(Error::ProvenanceMissing)?;
+
+        let mut sizes = BTreeMap::new();
+        sizes.insert("claim".to_string(), claim.data()?.len());
+        sizes.insert("signature".to_string(), claim.signature_val().len());
+        for claim_assertion in claim.claim_assertion_store() {
+            sizes.insert(
+                claim_assertion.label(),
+                claim_assertion.assertion().data().len(),
+            );
+        }
+        for (uri, data_box) in claim.databoxes() {
+            let name = box_name_from_uri(&uri.url()).unwrap_or_else(|| uri.url());
+            sizes.insert(name, serde_cbor::to_vec(data_box)?.len());
+        }
+        Ok(sizes)
+    }
+
+    /// Return the active [`Manifest`] if it exists.
+    pub fn active_manifest(&self) -> Option<&Manifest> {
+        self.manifest_store.get_active()
+    }
+
+    /// Return the active [`Manifest`] label if one exists.
+    pub fn active_label(&self) -> Option<&str> {
+        self.manifest_store.active_label()
+    }
+
+    /// Returns the [`AssetMetadata`] assertion of the active [`Manifest`].
+    /// # Errors
+    /// Returns [`Error::NotFound`] if there is no active manifest or it has no metadata assertion.
+    pub fn asset_metadata(&self) -> Result<AssetMetadata> {
+        self.active_manifest()
+            .ok_or(Error::NotFound)?
+            .find_assertion(AssetMetadata::LABEL)
+    }
+
+    /// Returns the [`Actions`] assertion of the active [`Manifest`], in its v1 or v2 form.
+    ///
+    /// Version 2 actions also have templates, see [`Actions::template_for`], and may state
+    /// whether they list all the actions taken on the asset.
+    /// # Errors
+    /// Returns [`Error::NotFound`] if there is no active manifest or it has no actions assertion.
+    pub fn actions(&self) -> Result<Actions> {
+        manifest_actions(self.active_manifest().ok_or(Error::NotFound)?)
+            .next()
+            .ok_or(Error::NotFound)
+    }
+
+    /// Returns the software agents of the actions along the active manifest's chain of
+    /// parent ingredients, from the oldest manifest to the active one.
+    ///
+    /// This lists the tools that processed the asset in order. Within each manifest the agents
+    /// are listed in the order of its actions, and an agent repeated by consecutive actions of
+    /// the manifest is listed once. Version 1 and version 2 actions assertions are both read.
+    pub fn software_agent_chain(&self) -> Vec<SoftwareAgent> {
+        let mut chain = Vec::new();
+        let mut next = self.active_manifest();
+        while let Some(manifest) = next {
+            // guard against manifests that reference each other
+            if chain.iter().any(|m: &&Manifest| std::ptr::eq(*m, manifest)) {
+                break;
+            }
+            chain.push(manifest);
+
+            next = manifest
+                .ingredients()
+                .iter()
+                .find(|ingredient| *ingredient.relationship() == Relationship::ParentOf)
+                .and_then(|ingredient| ingredient.active_manifest())
+                .and_then(|label| self.get_manifest(label));
+        }
+
+        let mut agents: Vec<SoftwareAgent> = Vec::new();
+        for manifest in chain.into_iter().rev() {
+            // each manifest is a separate step, even if it was made by the previous tool
+            let mut previous = None;
+            for actions in manifest_actions(manifest) {
+                for agent in actions.actions().iter().filter_map(|a| a.software_agent()) {
+                    if previous.as_ref() != Some(agent) {
+                        agents.push(agent.clone());
+                        previous = Some(agent.clone());
+                    }
+                }
+            }
+        }
+        agents
+    }
+
+    /// Returns whether each assertion of the manifest with the given label was created by its
+    /// claim generator or gathered from other sources, as listed by its claim.
+    ///
+    /// Assertions are identified by their labels, including any instance suffix such as
+    /// `c2pa.actions__1`. Returns an empty list if there is no such manifest.
+    pub fn assertion_origins(&self, label: &str) -> Vec<(String, AssertionOrigin)> {
+        let Some(claim) = self.manifest_store.store().get_claim(label) else {
+            return Vec::new();
+        };
+
+        let created = claim
+            .assertions()
+            .iter()
+            .map(|hashed_uri| (hashed_uri, AssertionOrigin::Created));
+        let gathered = claim
+            .gathered_assertions()
+            .iter()
+            .map(|hashed_uri| (hashed_uri, AssertionOrigin::Gathered));
+        created
+            .chain(gathered)
+            .filter_map(|(hashed_uri, origin)| {
+                assertion_label_from_uri(&hashed_uri.url()).map(|label| (label, origin))
+            })
+            .collect()
+    }
+
+    /// Returns the regions of interest changed by the actions of the active manifest, with the
+    /// label of the action that changed each one.
+    ///
+    /// Regions of PDF pages can be converted back to page coordinates with
+    /// [`RegionOfInterest::to_page_rectangle`].
+    pub fn changed_regions(&self) -> Vec<(String, RegionOfInterest)> {
+        let mut regions = Vec::new();
+        let Some(manifest) = self.active_manifest() else {
+            return regions;
+        };
+
+        for actions in manifest_actions(manifest) {
+            for action in actions.actions() {
+                for region in action.changes().unwrap_or_default() {
+                    regions.push((action.action().to_owned(), region.clone()));
+                }
+            }
+        }
+        regions
+    }
+
+    /// Returns an iterator over [`Manifest`][Manifest]s.
+    pub fn iter_manifests(&self) -> impl Iterator<Item = &Manifest> + '_ {
+        self.manifest_store.manifests().values()
+    }
+
+    /// Return a [`Manifest`] for a given label if it exists.
+    /// # Arguments
+    /// * `label` - The label of the requested [`Manifest`]
+    pub fn get_manifest(&self, label: &str) -> Option<&Manifest> {
+        self.manifest_store.get(label)
+    }
+
+    /// Returns the format and data of the thumbnail of the manifest with the given label.
+    ///
+    /// Thumbnails can be large. When the `core.lazy_thumbnails` setting is enabled, the manifests
+    /// of a Reader don't copy their data, which is borrowed from the manifest store when it is
+    /// requested here or by [`Manifest::thumbnail`].
+    /// # Arguments
+    /// * `label` - The label of the manifest.
+    /// # Returns
+    /// The format and data of the thumbnail, or `None` if the manifest doesn't exist or has no
+    /// thumbnail.
+    pub fn manifest_thumbnail(&self, label: &str) -> Option<(&str, Cow<[u8]>)> {
+        let manifest = self.manifest_store.get(label)?;
+        self.manifest_store.thumbnail(manifest)
+    }
+
+    /// Returns the ingredients of the manifest with the given label that have a thumbnail, with
+    /// the format and data of each ingredient's thumbnail.
+    ///
+    /// Ingredient thumbnails depict the assets a manifest was made from, so they are kept apart
+    /// from the manifest's own thumbnail returned by [`Reader::manifest_thumbnail`], even though
+    /// [`Reader::resource_to_stream`] can write either.
+    /// # Arguments
+    /// * `label` - The label of the manifest.
+    /// # Returns
+    /// The ingredients with thumbnails, in the order of [`Manifest::ingredients`], or an empty
+    /// list if the manifest doesn't exist.
+    pub fn ingredient_thumbnails(&self, label: &str) -> Vec<(&Ingredient, &str, Cow<[u8]>)> {
+        let Some(manifest) = self.manifest_store.get(label) else {
+            return Vec::new();
+        };
+
+        manifest
+            .ingredients()
+            .iter()
+            .filter_map(|ingredient| {
+                let (format, data) = ingredient.thumbnail()?;
+                let data = match data {
+                    Cow::Borrowed(data) => Cow::Borrowed(data.as_slice()),
+                    Cow::Owned(data) => Cow::Owned(data),
+                };
+                Some((ingredient, format, data))
+            })
+            .collect()
+    }
+
+    /// Write a resource identified by URI to the given stream.
+    /// # Arguments
+    /// * `uri` - The URI of the resource to write (from an identifier field).
+    /// * `stream` - The stream to write to.
     /// # Returns
     /// The number of bytes written.
     /// # Errors
@@ -247,6 +1960,73 @@ impl Reader {
             .get_resource(uri, &mut stream)
             .map(|size| size as usize)
     }
+
+    /// Writes the manifest store to a stream as a standalone manifest store in JUMBF format,
+    /// exactly as it was read.
+    ///
+    /// The output is a `.c2pa` manifest store that can be stored as a sidecar or remote
+    /// manifest and validated against the asset with [`Reader::from_manifest_data_and_stream`].
+    /// # Arguments
+    /// * `stream` - The stream to write to.
+    /// # Errors
+    /// Returns [`Error::JumbfNotFound`] if the manifest store was not read from an asset or
+    /// from JUMBF data, such as by [`Reader::from_json`].
+    pub fn export_manifest_to_stream(&self, mut stream: impl Write) -> Result<()> {
+        let manifest_data = self
+            .manifest_store
+            .manifest_data()
+            .ok_or(Error::JumbfNotFound)?;
+        stream.write_all(manifest_data)?;
+        Ok(())
+    }
+
+    /// Writes the manifest store embedded in an asset to a stream in JUMBF format, without
+    /// reading or validating it.
+    ///
+    /// The manifest store is copied from the asset rather than loaded into memory where the
+    /// format allows it, such as for BMFF and most PDF files. A manifest store referenced by a
+    /// remote URL is only fetched with the `fetch_remote_manifests` feature, otherwise
+    /// [`Error::RemoteManifestUrl`] is returned.
+    /// # Arguments
+    /// * `format` - The format of the asset.
+    /// * `stream` - The asset to read the manifest store from.
+    /// * `writer` - The stream to write the manifest store to.
+    /// # Returns
+    /// The number of bytes written.
+    /// # Errors
+    /// Returns [`Error::JumbfNotFound`] if the asset has no manifest store.
+    /// # Example
+    /// ```no_run
+    /// use std::io::Cursor;
+    ///
+    /// use c2pa::Reader;
+    /// let stream = Cursor::new(include_bytes!("../tests/fixtures/CA.jpg"));
+    /// let mut manifest_store = Vec::new();
+    /// Reader::extract_manifest_to_stream("image/jpeg", stream, &mut manifest_store).unwrap();
+    /// ```
+    pub fn extract_manifest_to_stream(
+        format: &str,
+        mut stream: impl Read + Seek + Send,
+        mut writer: impl Write,
+    ) -> Result<u64> {
+        Store::load_jumbf_to_writer(format, &mut stream, &mut writer)
+    }
+
+    /// Writes the manifest store to a file as a standalone manifest store in JUMBF format,
+    /// exactly as it was read.
+    ///
+    /// Saved next to an asset with the same name and a `.c2pa` extension, the manifest store
+    /// is found as a sidecar by [`Reader::from_file`].
+    /// # Arguments
+    /// * `path` - The path of the `.c2pa` file to write.
+    /// # Errors
+    /// Returns [`Error::JumbfNotFound`] if the manifest store was not read from an asset or
+    /// from JUMBF data, such as by [`Reader::from_json`].
+    #[cfg(feature = "file_io")]
+    pub fn export_manifest<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let mut file = File::create(path)?;
+        self.export_manifest_to_stream(&mut file)
+    }
 }
 
 impl Default for Reader {
@@ -270,3 +2050,20 @@ impl std::fmt::Debug for Reader {
         f.write_str(&report.to_string())
     }
 }
+
+// Returns the actions assertions of a manifest, in both their v1 and v2 forms.
+fn manifest_actions(manifest: &Manifest) -> impl Iterator<Item = Actions> + '_ {
+    manifest
+        .assertions()
+        .iter()
+        .filter(|manifest_assertion| manifest_assertion.label().starts_with(Actions::LABEL))
+        .filter_map(|manifest_assertion| manifest_assertion.to_assertion::<Actions>().ok())
+}
+
+// Returns `true` if an image of size `small` can be a downscale of one of size `large`,
+// allowing a pixel of rounding in either dimension.
+fn is_downscale(small: (u32, u32), large: (u32, u32)) -> bool {
+    let (sw, sh) = (u64::from(small.0), u64::from(small.1));
+    let (lw, lh) = (u64::from(large.0), u64::from(large.1));
+    sw <= lw && sh <= lh && (sw * lh).abs_diff(sh * lw) <= lw.max(lh)
+}
@@ -137,7 +137,7 @@ impl ResourceRef {
 }
 
 /// Resource store to contain binary objects referenced from JSON serializable structures
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
 pub struct ResourceStore {
     resources: HashMap<String, Vec<u8>>,
@@ -48,6 +48,8 @@ pub(crate) struct Trust {
     trust_anchors: Option<String>,
     trust_config: Option<String>,
     allowed_list: Option<String>,
+    development_anchors: bool, // also trust the bundled C2PA test/development anchors
+    intermediates: Option<String>, // PEM certificates used to build chains, but not trusted
 }
 
 impl Trust {
@@ -113,10 +115,146 @@ impl SettingsValidate for Trust {
             self.test_load_trust(al.as_bytes())?;
         }
 
+        if let Some(im) = &self.intermediates {
+            if self.load_trust_from_data(im.as_bytes())?.is_empty() {
+                return Err(Error::NotFound);
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Built-in trust presets, applied to the current settings with [`TrustConfig::load`].
+///
+/// A preset may also carry intermediate certificates, added with
+/// [`TrustConfig::with_intermediates_dir`]. Loading a preset replaces the `trust.intermediates`
+/// setting with them, clearing it when the preset has none.
+///
+/// # Examples
+///
+/// ```
+/// use c2pa::settings::TrustConfig;
+///
+/// // trust the C2PA test/development anchors while developing
+/// TrustConfig::development().load().unwrap();
+/// # c2pa::settings::reset_default_settings().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustConfig {
+    // whether the C2PA test/development anchors bundled with this crate are trusted in
+    // addition to the configured anchors
+    development_anchors: bool,
+    // PEM encoded intermediate certificates
+    intermediates: Option<String>,
+}
+
+impl TrustConfig {
+    /// Returns the production preset, which never trusts the bundled test/development anchors.
+    /// Only the anchors configured in the `trust` settings are trusted.
+    pub fn production() -> Self {
+        Self {
+            development_anchors: false,
+            intermediates: None,
+        }
+    }
+
+    /// Returns the development preset, which trusts the bundled test/development anchors in
+    /// addition to the configured anchors.
+    ///
+    /// Manifests whose signing certificate chains to one of these anchors are reported with
+    /// [`STATUS_DEVELOPMENT_TRUST`](crate::validation_status::STATUS_DEVELOPMENT_TRUST).
+    pub fn development() -> Self {
+        Self {
+            development_anchors: true,
+            intermediates: None,
+        }
+    }
+
+    /// Adds the certificates found in a directory to the intermediate certificates of the
+    /// preset, which are set when it is [loaded](Self::load).
+    ///
+    /// Intermediate certificates are not trusted themselves. They are used to build the chain
+    /// from a signing certificate to a trust anchor when a manifest only includes the signing
+    /// certificate. Each file may hold PEM certificates or a single DER certificate; other
+    /// files are ignored.
+    ///
+    /// Returns [`Error::NotFound`] if the directory holds no certificate.
+    #[cfg(feature = "file_io")]
+    pub fn with_intermediates_dir<P: AsRef<Path>>(mut self, dir: P) -> Result<Self> {
+        let mut intermediates = self.intermediates.take().unwrap_or_default();
+        let mut found = false;
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            let data = std::fs::read(&path)?;
+            let ders = match pem::parse_many(&data) {
+                Ok(pems) if !pems.is_empty() => pems
+                    .into_iter()
+                    .filter(|pem| pem.tag() == "CERTIFICATE")
+                    .map(|pem| pem.into_contents())
+                    .collect(),
+                _ if x509_parser::parse_x509_certificate(&data).is_ok() => vec![data],
+                _ => continue,
+            };
+
+            for der in ders {
+                intermediates.push_str(&pem::encode(&pem::Pem::new("CERTIFICATE", der)));
+                found = true;
+            }
+        }
+
+        if !found {
+            return Err(Error::NotFound);
+        }
+        self.intermediates = Some(intermediates);
+        Ok(self)
+    }
+
+    /// Applies the preset to the current settings and enables trust verification.
+    pub fn load(self) -> Result<()> {
+        set_settings_value("trust.development_anchors", self.development_anchors)?;
+        set_settings_value("trust.intermediates", self.intermediates)?;
+        set_settings_value("verify.verify_trust", true)
+    }
+}
+
+/// A named set of trust anchors, one of several candidate trust lists a manifest can be
+/// checked against with [`Reader::matching_trust_list`](crate::Reader::matching_trust_list).
+///
+/// Federated ecosystems may each publish their own trust list. Unlike the `trust` settings,
+/// candidate trust lists are not merged: a manifest is trusted by a list only if its signing
+/// certificate chains to one of that list's anchors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustList {
+    id: String,
+    anchors: Vec<u8>,
+}
+
+impl TrustList {
+    /// Creates a trust list from its identifier and its PEM encoded anchor certificates.
+    pub fn new<S: Into<String>, A: Into<Vec<u8>>>(id: S, anchors: A) -> Self {
+        Self {
+            id: id.into(),
+            anchors: anchors.into(),
+        }
+    }
+
+    /// Returns the identifier of the trust list.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the PEM encoded anchor certificates of the trust list.
+    pub fn anchors(&self) -> &[u8] {
+        &self.anchors
+    }
+}
+
 // Settings for core C2PA-RS functionality
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[cfg_attr(feature = "json_schema", derive(JsonSchema))]
@@ -129,6 +267,25 @@ pub(crate) struct Core {
     prefer_bmff_merkle_tree: bool,
     compress_manifests: bool,
     max_memory_usage: Option<u64>,
+    // directory for temporary files, the system temp directory if not set
+    temp_dir: Option<String>,
+    // keep intermediate data in memory instead of in temporary files
+    in_memory: bool,
+    // decode claim thumbnails from the manifest store on request instead of copying them
+    lazy_thumbnails: bool,
+    // derive ECDSA nonces from the key and message (RFC 6979) so signatures are reproducible
+    deterministic_ecdsa: bool,
+    // label of the manifest store superbox for private manifest stores, "c2pa" if not set
+    manifest_store_label: Option<String>,
+    // UUID of the manifest store superbox as 32 hex digits, the c2pa one if not set
+    manifest_store_uuid: Option<String>,
+    // PDF catalog and trailer keys holding a manifest reference, "C2PAManifest" and "C2PA"
+    pdf_manifest_reference_keys: Vec<String>,
+    // name, AFRelationship and description of the embedded file holding a PDF manifest store,
+    // "Content Credentials", "C2PA_Manifest" and "Content Credentials" if not set
+    pdf_embedded_file_name: Option<String>,
+    pdf_embedded_file_relationship: Option<String>,
+    pdf_embedded_file_description: Option<String>,
 }
 
 impl Default for Core {
@@ -141,12 +298,48 @@ impl Default for Core {
             prefer_bmff_merkle_tree: false,
             compress_manifests: true,
             max_memory_usage: None,
+            temp_dir: None,
+            in_memory: false,
+            lazy_thumbnails: false,
+            deterministic_ecdsa: false,
+            manifest_store_label: None,
+            manifest_store_uuid: None,
+            pdf_manifest_reference_keys: vec!["C2PAManifest".into(), "C2PA".into()],
+            pdf_embedded_file_name: None,
+            pdf_embedded_file_relationship: None,
+            pdf_embedded_file_description: None,
         }
     }
 }
 
 impl SettingsValidate for Core {
     fn validate(&self) -> Result<()> {
+        if self
+            .manifest_store_label
+            .as_ref()
+            .is_some_and(String::is_empty)
+        {
+            return Err(Error::BadParam(
+                "core.manifest_store_label must not be empty".into(),
+            ));
+        }
+        if self
+            .pdf_embedded_file_name
+            .as_ref()
+            .is_some_and(String::is_empty)
+        {
+            return Err(Error::BadParam(
+                "core.pdf_embedded_file_name must not be empty".into(),
+            ));
+        }
+        if let Some(uuid) = &self.manifest_store_uuid {
+            if uuid.len() != 32 || !uuid.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(Error::BadParam(
+                    "core.manifest_store_uuid must be 32 hex digits".into(),
+                ));
+            }
+        }
+
         match self.hash_alg.as_str() {
             "sha256" | "sha384" | "sha512" => Ok(()),
             _ => Err(Error::UnsupportedType),
@@ -165,6 +358,9 @@ pub(crate) struct Verify {
     ocsp_fetch: bool,
     remote_manifest_fetch: bool,
     check_ingredient_trust: bool,
+    check_cert_usage: bool,
+    fail_closed_on_network_error: bool,
+    require_trusted_timestamp: bool,
 }
 
 impl Default for Verify {
@@ -176,6 +372,9 @@ impl Default for Verify {
             ocsp_fetch: false,
             remote_manifest_fetch: true,
             check_ingredient_trust: true,
+            check_cert_usage: true,
+            fail_closed_on_network_error: false,
+            require_trusted_timestamp: false,
         }
     }
 }
@@ -413,7 +612,16 @@ pub mod tests {
     use super::*;
 
     // prevent tests from polluting the results of each other because of Rust unit test concurrency
-    static PROTECT: Mutex<u32> = Mutex::new(1); // prevent tests from polluting the results of each other
+    pub(crate) static PROTECT: Mutex<u32> = Mutex::new(1); // prevent tests from polluting the results of each other
+
+    // restores the default settings when dropped, even if the test holding it fails
+    pub(crate) struct ResetSettings;
+
+    impl Drop for ResetSettings {
+        fn drop(&mut self) {
+            let _ = reset_default_settings();
+        }
+    }
 
     #[test]
     fn test_get_defaults() {
@@ -537,6 +745,21 @@ pub mod tests {
     }
 
     #[cfg(feature = "file_io")]
+    #[test]
+    fn test_trust_config_presets() {
+        let _protect = PROTECT.lock().unwrap();
+
+        TrustConfig::development().load().unwrap();
+        assert!(get_settings_value::<bool>("trust.development_anchors").unwrap());
+        assert!(get_settings_value::<bool>("verify.verify_trust").unwrap());
+
+        TrustConfig::production().load().unwrap();
+        assert!(!get_settings_value::<bool>("trust.development_anchors").unwrap());
+        assert!(get_settings_value::<bool>("verify.verify_trust").unwrap());
+
+        reset_default_settings().unwrap();
+    }
+
     #[test]
     fn test_save_load() {
         let _protect = PROTECT.lock().unwrap();
@@ -14,7 +14,12 @@ use crate::{Result, SigningAlg};
 /// The `Signer` trait generates a cryptographic signature over a byte array.
 ///
 /// This trait exists to allow the signature mechanism to be extended.
-pub trait Signer {
+///
+/// Signers must be `Send + Sync` so one signer can be shared by threads signing different
+/// assets at the same time, as `sign_batch` does.
+/// Implementations holding mutable state, such as a cached OCSP response, must synchronize
+/// access to it.
+pub trait Signer: Send + Sync {
     /// Returns a new byte array which is a signature over the original.
     fn sign(&self, data: &[u8]) -> Result<Vec<u8>>;
 
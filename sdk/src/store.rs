@@ -13,7 +13,8 @@
 
 use std::{
     collections::HashMap,
-    io::{Cursor, Read, Seek},
+    io::{Cursor, Read, Seek, Write},
+    sync::Arc,
 };
 #[cfg(feature = "file_io")]
 use std::{
@@ -23,6 +24,7 @@ use std::{
 
 use async_generic::async_generic;
 use async_recursion::async_recursion;
+use chrono::{DateTime, Utc};
 use log::error;
 
 #[cfg(feature = "file_io")]
@@ -30,6 +32,8 @@ use crate::jumbf_io::{
     get_file_extension, get_supported_file_extension, load_jumbf_from_file, object_locations,
     remove_jumbf_from_file, save_jumbf_to_file,
 };
+#[cfg(feature = "file_io")]
+use crate::utils::temp_files;
 use crate::{
     assertion::{
         Assertion, AssertionBase, AssertionData, AssertionDecodeError, AssertionDecodeErrorCause,
@@ -39,10 +43,11 @@ use crate::{
         BmffHash, DataBox, DataHash, DataMap, ExclusionsMap, Ingredient, Relationship, SubsetMap,
     },
     asset_io::{
-        CAIRead, CAIReadWrite, HashBlockObjectType, HashObjectPositions, RemoteRefEmbedType,
+        CAIRead, CAIReadWrite, CAIWriter, HashBlockObjectType, HashObjectPositions,
+        RemoteRefEmbedType,
     },
     claim::{Claim, ClaimAssertion, ClaimAssetData, RemoteManifest},
-    cose_sign::{cose_sign, cose_sign_async},
+    cose_sign::{cose_sign_with_metrics, cose_sign_with_metrics_async},
     cose_validator::{check_ocsp_status, verify_cose, verify_cose_async},
     error::{Error, Result},
     external_manifest::ManifestPatchCallback,
@@ -54,17 +59,19 @@ use crate::{
         labels::{ASSERTIONS, CREDENTIALS, DATABOXES, SIGNATURE},
     },
     jumbf_io::{
-        get_assetio_handler, is_bmff_format, load_jumbf_from_stream, object_locations_from_stream,
-        save_jumbf_to_memory, save_jumbf_to_stream,
+        get_assetio_handler, is_bmff_format, load_jumbf_from_stream, load_jumbf_to_writer,
+        save_jumbf_to_memory, CAIReadAdapter,
     },
     manifest_store_report::ManifestStoreReport,
+    metrics::{MetricsObserver, PipelineStage, StageTimer},
     salt::DefaultSalt,
     settings::get_settings_value,
     status_tracker::{log_item, DetailedStatusTracker, OneShotStatusTracker, StatusTracker},
-    trust_handler::TrustHandlerConfig,
+    trust_handler::{TrustHandlerConfig, DEVELOPMENT_TRUST_ANCHORS},
     utils::{
-        hash_utils::{hash_sha256, HashRange},
+        hash_utils::{hash_alg_strength, hash_sha256, HashRange},
         io_utils::stream_len,
+        mime::canonical_mime,
         patch::patch_bytes,
     },
     validation_status, AsyncSigner, RemoteSigner, Signer,
@@ -83,6 +90,11 @@ pub struct Store {
     label: String,
     provenance_path: Option<String>,
     trust_handler: Box<dyn TrustHandlerConfig>,
+    padded_size: Option<usize>,
+    compress_manifest: bool,
+    signing_time: Option<DateTime<Utc>>,
+    metrics: Option<Arc<dyn MetricsObserver>>,
+    manifest_store_box: Option<(String, String)>,
 }
 
 struct ManifestInfo<'a> {
@@ -105,6 +117,20 @@ impl<T> PushGetIndex for Vec<T> {
     }
 }
 
+// Returns the label and UUID of the manifest store superbox, the standard c2pa ones unless a
+// private manifest store was configured.
+fn manifest_store_box_type() -> (String, String) {
+    let label = get_settings_value::<Option<String>>("core.manifest_store_label")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| jumbf::labels::MANIFEST_STORE.to_owned());
+    let uuid = get_settings_value::<Option<String>>("core.manifest_store_uuid")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| CAI_BLOCK_UUID.to_owned());
+    (label, uuid)
+}
+
 impl Default for Store {
     fn default() -> Self {
         Self::new()
@@ -133,6 +159,11 @@ impl Store {
             #[cfg(all(not(feature = "openssl"), not(target_arch = "wasm32")))]
             trust_handler: Box::new(crate::trust_handler::TrustPassThrough::new()),
             provenance_path: None,
+            padded_size: None,
+            compress_manifest: false,
+            signing_time: None,
+            metrics: None,
+            manifest_store_box: None,
         };
 
         // load the trust handler settings, don't worry about status as these are checked during setting generation
@@ -148,6 +179,16 @@ impl Store {
             }
         });
 
+        let _ = get_settings_value::<Option<String>>("trust.intermediates").map(|im_opt| {
+            if let Some(im) = im_opt {
+                let _v = store.add_trust_intermediates(im.as_bytes());
+            }
+        });
+
+        if get_settings_value::<bool>("trust.development_anchors").unwrap_or_default() {
+            let _v = store.add_private_trust_anchors(DEVELOPMENT_TRUST_ANCHORS);
+        }
+
         let _ = get_settings_value::<Option<String>>("trust.trust_config").map(|tc_opt| {
             if let Some(tc) = tc_opt {
                 let _v = store.add_trust_config(tc.as_bytes());
@@ -185,6 +226,14 @@ impl Store {
             .append_private_trust_data(&mut trust_reader)
     }
 
+    /// Load a set of intermediate certificates used to build certificate chains that are
+    /// missing them. They are not trusted themselves.
+    pub fn add_trust_intermediates(&mut self, intermediates_vec: &[u8]) -> Result<()> {
+        let mut intermediates_reader = Cursor::new(intermediates_vec);
+        self.trust_handler
+            .append_intermediates(&mut intermediates_reader)
+    }
+
     pub fn add_trust_config(&mut self, trust_vec: &[u8]) -> Result<()> {
         let mut trust_reader = Cursor::new(trust_vec);
         self.trust_handler.load_configuration(&mut trust_reader)
@@ -200,7 +249,7 @@ impl Store {
         self.trust_handler.clear();
     }
 
-    fn trust_handler(&self) -> &dyn TrustHandlerConfig {
+    pub(crate) fn trust_handler(&self) -> &dyn TrustHandlerConfig {
         self.trust_handler.as_ref()
     }
 
@@ -223,6 +272,61 @@ impl Store {
         self.provenance_path = Some(path);
     }
 
+    /// Pad the JUMBF written for this store to `size` bytes.
+    ///
+    /// The padding is a JUMBF padding box following the manifest store, so a later
+    /// replacement manifest up to `size` bytes can be written over this one in place.
+    pub fn set_padded_size(&mut self, size: usize) {
+        self.padded_size = Some(size);
+    }
+
+    /// Store the JUMBF written for this store compressed, for asset formats that support it.
+    ///
+    /// Currently only PDF supports this, where the manifest is written as a `/FlateDecode`
+    /// embedded file stream. The manifest is written unchanged to other formats.
+    pub fn set_compress_manifest(&mut self, compress: bool) {
+        self.compress_manifest = compress;
+    }
+
+    /// Record `signing_time` in the asset where its format has a place for it.
+    ///
+    /// Currently only PDF uses this, as the `/CreationDate` and `/ModDate` of the manifest's
+    /// embedded file. Without a signing time those dates are omitted.
+    pub fn set_signing_time(&mut self, signing_time: DateTime<Utc>) {
+        self.signing_time = Some(signing_time);
+    }
+
+    /// Write this store as a private manifest store, with a custom superbox label and UUID.
+    ///
+    /// The UUID is given as 32 hex digits. Without one, the store is written with the label
+    /// and UUID of the `core.manifest_store_label` and `core.manifest_store_uuid` settings,
+    /// or the standard c2pa ones.
+    pub fn set_manifest_store_box(&mut self, label: &str, uuid: &str) {
+        self.manifest_store_box = Some((label.to_owned(), uuid.to_owned()));
+    }
+
+    /// Report the time spent in each stage of signing this store to `metrics`.
+    pub fn set_metrics_observer(&mut self, metrics: Arc<dyn MetricsObserver>) {
+        self.metrics = Some(metrics);
+    }
+
+    fn metrics(&self) -> Option<&dyn MetricsObserver> {
+        self.metrics.as_deref()
+    }
+
+    /// Append a JUMBF padding box to `jumbf` so it is `size` bytes long.
+    ///
+    /// Nothing is added if there is no room for the padding box header.
+    pub(crate) fn pad_jumbf(jumbf: &mut Vec<u8>, size: usize) -> Result<()> {
+        const PADDING_HEADER_SIZE: usize = 8;
+
+        if jumbf.len() + PADDING_HEADER_SIZE <= size {
+            let padding = JUMBFPaddingContentBox::new(size - jumbf.len() - PADDING_HEADER_SIZE);
+            padding.write_box(jumbf)?;
+        }
+        Ok(())
+    }
+
     /// get the list of claims for this store
     pub fn claims(&self) -> &Vec<Claim> {
         &self.claims
@@ -326,7 +430,7 @@ impl Store {
         }
 
         // make sure ingredient c2pa.manifest points to provenance claim
-        if let Some(c2pa_manifest) = ingredient_helper.c2pa_manifest {
+        if let Some(c2pa_manifest) = ingredient_helper.manifest_uri() {
             // the manifest should refer to provenance claim
             if let Some(pc) = self.provenance_claim() {
                 if !c2pa_manifest.url().contains(pc.label()) {
@@ -433,12 +537,26 @@ impl Store {
 
     // Returns placeholder that will be searched for and replaced
     // with actual signature data.
-    fn sign_claim_placeholder(claim: &Claim, min_reserve_size: usize) -> Vec<u8> {
+    fn sign_claim_placeholder(&self, claim: &Claim, min_reserve_size: usize) -> Vec<u8> {
+        Store::claim_placeholder(claim, min_reserve_size, self.compress_manifest)
+    }
+
+    fn claim_placeholder(claim: &Claim, min_reserve_size: usize, incompressible: bool) -> Vec<u8> {
         let placeholder_str = format!("signature placeholder:{}", claim.label());
         let mut placeholder = hash_sha256(placeholder_str.as_bytes());
 
         use std::cmp::max;
-        placeholder.resize(max(placeholder.len(), min_reserve_size), 0);
+        if incompressible {
+            // A compressed manifest is patched in place once signed, so fill the placeholder with
+            // hash output rather than zeros to keep it from compressing better than the signature.
+            while placeholder.len() < min_reserve_size {
+                let block = hash_sha256(&placeholder[placeholder.len() - 32..]);
+                placeholder.extend_from_slice(&block);
+            }
+            placeholder.truncate(max(32, min_reserve_size));
+        } else {
+            placeholder.resize(max(placeholder.len(), min_reserve_size), 0);
+        }
 
         placeholder
     }
@@ -498,21 +616,24 @@ impl Store {
         signer: &dyn Signer,
         box_size: usize,
     ) -> Result<Vec<u8>> {
+        let _sign_timer = StageTimer::start(self.metrics(), PipelineStage::Sign);
         let claim_bytes = claim.data()?;
 
         let result = if _sync {
             if signer.direct_cose_handling() {
+                let _timer = StageTimer::start(self.metrics(), PipelineStage::Network);
                 // Let the signer do all the COSE processing and return the structured COSE data.
                 return signer.sign(&claim_bytes); // do not verify remote signers (we never did)
             } else {
-                cose_sign(signer, &claim_bytes, box_size)
+                cose_sign_with_metrics(signer, &claim_bytes, box_size, self.metrics())
             }
         } else {
             if signer.direct_cose_handling() {
+                let _timer = StageTimer::start(self.metrics(), PipelineStage::Network);
                 // Let the signer do all the COSE processing and return the structured COSE data.
                 return signer.sign(claim_bytes.clone()).await; // do not verify remote signers (we never did)
             } else {
-                cose_sign_async(signer, &claim_bytes, box_size).await
+                cose_sign_with_metrics_async(signer, &claim_bytes, box_size, self.metrics()).await
             }
         };
         match result {
@@ -559,6 +680,17 @@ impl Store {
         }
     }
 
+    /// Sign the claim with a [`RemoteSigner`] and return the signature.
+    async fn sign_claim_remote(
+        &self,
+        claim: &Claim,
+        remote_signer: &dyn RemoteSigner,
+    ) -> Result<Vec<u8>> {
+        let _sign_timer = StageTimer::start(self.metrics(), PipelineStage::Sign);
+        let _timer = StageTimer::start(self.metrics(), PipelineStage::Network);
+        remote_signer.sign_remote(&claim.data()?).await
+    }
+
     /// return the current provenance claim label if available
     pub fn provenance_label(&self) -> Option<String> {
         self.provenance_path()
@@ -680,7 +812,7 @@ impl Store {
         let result = match assertion_desc_box.uuid().as_ref() {
             CAI_JSON_ASSERTION_UUID => {
                 let json_box = assertion_box
-                    .data_box_as_json_box(0)
+                    .find_data_box::<JUMBFJSONContentBox>()
                     .ok_or(Error::JumbfBoxNotFound)?;
                 let assertion = Assertion::from_data_json(&raw_label, json_box.json())?;
                 let hash = Claim::calc_assertion_box_hash(label, &assertion, salt.clone(), &alg)?;
@@ -688,10 +820,10 @@ impl Store {
             }
             CAI_EMBEDDED_FILE_UUID => {
                 let ef_box = assertion_box
-                    .data_box_as_embedded_media_type_box(0)
+                    .find_data_box::<JUMBFEmbeddedFileDescriptionBox>()
                     .ok_or(Error::JumbfBoxNotFound)?;
                 let data_box = assertion_box
-                    .data_box_as_embedded_file_content_box(1)
+                    .find_data_box::<JUMBFEmbeddedFileContentBox>()
                     .ok_or(Error::JumbfBoxNotFound)?;
                 let media_type = ef_box.media_type();
                 let assertion =
@@ -701,7 +833,7 @@ impl Store {
             }
             CAI_CBOR_ASSERTION_UUID => {
                 let cbor_box = assertion_box
-                    .data_box_as_cbor_box(0)
+                    .find_data_box::<JUMBFCBORContentBox>()
                     .ok_or(Error::JumbfBoxNotFound)?;
                 let assertion = Assertion::from_data_cbor(&raw_label, cbor_box.cbor());
                 let hash = Claim::calc_assertion_box_hash(label, &assertion, salt.clone(), &alg)?;
@@ -709,7 +841,7 @@ impl Store {
             }
             CAI_UUID_ASSERTION_UUID => {
                 let uuid_box = assertion_box
-                    .data_box_as_uuid_box(0)
+                    .find_data_box::<JUMBFUUIDContentBox>()
                     .ok_or(Error::JumbfBoxNotFound)?;
                 let uuid_str = hex::encode(uuid_box.uuid());
                 let assertion = Assertion::from_data_uuid(&raw_label, &uuid_str, uuid_box.data());
@@ -754,11 +886,16 @@ impl Store {
 
     fn to_jumbf_internal(&self, min_reserve_size: usize) -> Result<Vec<u8>> {
         // Create the CAI block.
-        let mut cai_block = Cai::new();
+        let (label, uuid) = self
+            .manifest_store_box
+            .clone()
+            .unwrap_or_else(manifest_store_box_type);
+        let mut cai_block = Cai::with_label(&label, &uuid);
 
         // Add claims and assertions in this store to the JUMBF store.
         for claim in &self.claims {
-            let cai_store = Store::build_manifest_box(claim, min_reserve_size)?;
+            let cai_store =
+                Store::build_manifest_box(claim, min_reserve_size, self.compress_manifest)?;
 
             // add the completed CAI store into the CAI block.
             cai_block.add_box(Box::new(cai_store));
@@ -768,6 +905,10 @@ impl Store {
         let mut mem_box: Vec<u8> = Vec::new();
         cai_block.write_box(&mut mem_box)?;
 
+        if let Some(size) = self.padded_size {
+            Store::pad_jumbf(&mut mem_box, size)?;
+        }
+
         if mem_box.is_empty() {
             Err(Error::JumbfCreationError)
         } else {
@@ -775,7 +916,11 @@ impl Store {
         }
     }
 
-    fn build_manifest_box(claim: &Claim, min_reserve_size: usize) -> Result<CAIStore> {
+    fn build_manifest_box(
+        claim: &Claim,
+        min_reserve_size: usize,
+        incompressible_placeholder: bool,
+    ) -> Result<CAIStore> {
         // box label
         let label = claim.label();
 
@@ -809,7 +954,11 @@ impl Store {
                     let mut sigb = CAISignatureBox::new();
                     let signed_data = match claim.signature_val().is_empty() {
                         false => claim.signature_val().clone(), // existing claims have sig values
-                        true => Store::sign_claim_placeholder(claim, min_reserve_size), /* empty is the new sig to be replaced */
+                        true => Store::claim_placeholder(
+                            claim,
+                            min_reserve_size,
+                            incompressible_placeholder,
+                        ), /* empty is the new sig to be replaced */
                     };
 
                     let sigc = JUMBFCBORContentBox::new(signed_data);
@@ -883,7 +1032,7 @@ impl Store {
         let mut hash_bytes = Vec::with_capacity(4096);
 
         // build box
-        let mut cai_store = Store::build_manifest_box(claim, 0)?;
+        let mut cai_store = Store::build_manifest_box(claim, 0, false)?;
 
         // add salt if requested
         if let Some(salt) = salt {
@@ -898,8 +1047,7 @@ impl Store {
 
     fn manifest_map<'a>(sb: &'a JUMBFSuperBox) -> Result<HashMap<String, ManifestInfo<'a>>> {
         let mut box_info: HashMap<String, ManifestInfo<'a>> = HashMap::new();
-        for i in 0..sb.data_box_count() {
-            let sbox = sb.data_box_as_superbox(i).ok_or(Error::JumbfBoxNotFound)?;
+        for sbox in sb.data_superboxes() {
             let desc_box = sbox.desc_box();
 
             let label = desc_box.uuid();
@@ -928,6 +1076,27 @@ impl Store {
     }
 
     pub fn from_jumbf(buffer: &[u8], validation_log: &mut impl StatusTracker) -> Result<Store> {
+        Store::from_jumbf_impl(buffer, false, validation_log)
+    }
+
+    /// Loads a store from a prerelease (pre-1.0) manifest, migrating each claim to the
+    /// current layout.
+    ///
+    /// Supported are the CBOR claims written by prerelease versions of this SDK, whose
+    /// assertion hashes cover only the assertion data. Prerelease claims stored as JSON are
+    /// still rejected with [`Error::PrereleaseError`]. The migrated claims are unsigned.
+    pub(crate) fn from_jumbf_legacy(
+        buffer: &[u8],
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<Store> {
+        Store::from_jumbf_impl(buffer, true, validation_log)
+    }
+
+    fn from_jumbf_impl(
+        buffer: &[u8],
+        migrate_legacy: bool,
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<Store> {
         if buffer.is_empty() {
             return Err(Error::JumbfNotFound);
         }
@@ -943,9 +1112,12 @@ impl Store {
         // this loads up all the boxes...
         let cai_block = Cai::from(super_box);
 
-        // check the CAI Block
+        // check the CAI Block, which may also be a private one with the configured label
         let desc_box = cai_block.desc_box();
-        if desc_box.uuid() != CAI_BLOCK_UUID {
+        let (label, uuid) = manifest_store_box_type();
+        let is_private_store =
+            desc_box.label() == label && desc_box.uuid().eq_ignore_ascii_case(&uuid);
+        if desc_box.uuid() != CAI_BLOCK_UUID && !is_private_store {
             let log_item = log_item!("JUMBF", "c2pa box not found", "from_jumbf")
                 .error(Error::InvalidClaim(InvalidClaimError::C2paBlockNotFound));
             validation_log.log(
@@ -956,11 +1128,8 @@ impl Store {
             return Err(Error::InvalidClaim(InvalidClaimError::C2paBlockNotFound));
         }
 
-        let num_stores = cai_block.data_box_count();
-        for idx in 0..num_stores {
-            let cai_store_box = cai_block
-                .data_box_as_superbox(idx)
-                .ok_or(Error::JumbfBoxNotFound)?;
+        // manifests are located by their description box, other boxes are skipped
+        for cai_store_box in cai_block.super_box().data_superboxes() {
             let cai_store_desc_box = cai_store_box.desc_box();
 
             // ignore unknown boxes per the spec
@@ -975,10 +1144,7 @@ impl Store {
 
             // make sure there are not multiple claim boxes
             let mut claim_box_cnt = 0;
-            for i in 0..cai_store_box.data_box_count() {
-                let sbox = cai_store_box
-                    .data_box_as_superbox(i)
-                    .ok_or(Error::JumbfBoxNotFound)?;
+            for sbox in cai_store_box.data_superboxes() {
                 let desc_box = sbox.desc_box();
 
                 if desc_box.uuid() == CAI_CLAIM_UUID {
@@ -1058,11 +1224,11 @@ impl Store {
                 }
                 // better be, but just in case...
 
-                let cbor_box = match claim_superbox.data_box_as_cbor_box(0) {
+                let cbor_box = match claim_superbox.find_data_box::<JUMBFCBORContentBox>() {
                     Some(c) => c,
                     None => {
                         // check for old claims for reporting
-                        match claim_superbox.data_box_as_json_box(0) {
+                        match claim_superbox.find_data_box::<JUMBFJSONContentBox>() {
                             Some(_c) => {
                                 let log_item =
                                     log_item!("JUMBF", "error loading claim data", "from_jumbf")
@@ -1109,7 +1275,7 @@ impl Store {
             if sig_desc_box.uuid() == CAI_SIGNATURE_UUID {
                 // better be, but just in case...
                 let sig_box = sig_superbox
-                    .data_box_as_cbor_box(0)
+                    .find_data_box::<JUMBFCBORContentBox>()
                     .ok_or(Error::JumbfBoxNotFound)?;
                 if sig_box.box_uuid() != JUMBF_CBOR_UUID {
                     return Err(Error::InvalidClaim(
@@ -1119,12 +1285,12 @@ impl Store {
             }
             // save signature to be validated on load
             let sig_data = sig_superbox
-                .data_box_as_cbor_box(0)
+                .find_data_box::<JUMBFCBORContentBox>()
                 .ok_or(Error::JumbfBoxNotFound)?;
 
             // Create a new Claim object from jumbf data after validations
             let cbor_box = claim_superbox
-                .data_box_as_cbor_box(0)
+                .find_data_box::<JUMBFCBORContentBox>()
                 .ok_or(Error::JumbfBoxNotFound)?;
             let mut claim = Claim::from_data(&cai_store_desc_box.label(), cbor_box.cbor())?;
 
@@ -1145,14 +1311,9 @@ impl Store {
                 ))?
                 .sbox;
 
-            let num_assertions = assertion_store_box.data_box_count();
-
             // loop over all assertions...
-            let mut check_for_legacy_assertion = true;
-            for idx in 0..num_assertions {
-                let assertion_box = assertion_store_box
-                    .data_box_as_superbox(idx)
-                    .ok_or(Error::JumbfBoxNotFound)?;
+            let mut check_for_legacy_assertion = !migrate_legacy;
+            for assertion_box in assertion_store_box.data_superboxes() {
                 let assertion_desc_box = assertion_box.desc_box();
 
                 // Add assertions to claim after validation
@@ -1190,14 +1351,10 @@ impl Store {
             // load vc_store if available
             if let Some(mi) = manifest_boxes.get(CAI_VERIFIABLE_CREDENTIALS_STORE_UUID) {
                 let vc_store = mi.sbox;
-                let num_vcs = vc_store.data_box_count();
 
-                for idx in 0..num_vcs {
-                    let vc_box = vc_store
-                        .data_box_as_superbox(idx)
-                        .ok_or(Error::JumbfBoxNotFound)?;
+                for vc_box in vc_store.data_superboxes() {
                     let vc_json = vc_box
-                        .data_box_as_json_box(0)
+                        .find_data_box::<JUMBFJSONContentBox>()
                         .ok_or(Error::JumbfBoxNotFound)?;
                     let vc_desc_box = vc_box.desc_box();
                     let _id = vc_desc_box.label();
@@ -1214,14 +1371,10 @@ impl Store {
             // load databox store if available
             if let Some(mi) = manifest_boxes.get(CAI_DATABOXES_STORE_UUID) {
                 let databox_store = mi.sbox;
-                let num_databoxes = databox_store.data_box_count();
 
-                for idx in 0..num_databoxes {
-                    let db_box = databox_store
-                        .data_box_as_superbox(idx)
-                        .ok_or(Error::JumbfBoxNotFound)?;
+                for db_box in databox_store.data_superboxes() {
                     let db_cbor = db_box
-                        .data_box_as_cbor_box(0)
+                        .find_data_box::<JUMBFCBORContentBox>()
                         .ok_or(Error::JumbfBoxNotFound)?;
                     let db_desc_box = db_box.desc_box();
                     let label = db_desc_box.label();
@@ -1232,6 +1385,10 @@ impl Store {
                 }
             }
 
+            if migrate_legacy {
+                claim.migrate_legacy()?;
+            }
+
             // save the hash of the loaded manifest for ingredient validation
             store.manifest_box_hash_cache.insert(
                 claim.label().to_owned(),
@@ -1260,6 +1417,7 @@ impl Store {
         claim: &Claim,
         asset_data: &mut ClaimAssetData<'_>,
         validation_log: &mut impl StatusTracker,
+        chain: &mut Vec<String>,
     ) -> Result<()> {
         let mut num_parent_ofs = 0;
 
@@ -1268,8 +1426,9 @@ impl Store {
             let ingredient_assertion = Ingredient::from_assertion(i)?;
 
             // is this an ingredient
-            if let Some(ref c2pa_manifest) = &ingredient_assertion.c2pa_manifest {
+            if let Some(c2pa_manifest) = ingredient_assertion.manifest_uri() {
                 let label = Store::manifest_label_from_path(&c2pa_manifest.url());
+                Store::check_ingredient_hash_alg(claim, c2pa_manifest, validation_log);
 
                 // check for parentOf relationships
                 if ingredient_assertion.relationship == Relationship::ParentOf {
@@ -1277,6 +1436,21 @@ impl Store {
                 }
 
                 if let Some(ingredient) = store.get_claim(&label) {
+                    // a claim already on the path from the provenance claim would be
+                    // validated again and again
+                    if chain.iter().any(|l| l == ingredient.label()) {
+                        let log_item = log_item!(
+                            &c2pa_manifest.url(),
+                            "ingredient cycle",
+                            "ingredient_checks"
+                        )
+                        .error(Error::IngredientCycle(label.clone()));
+                        validation_log
+                            .log(log_item, Some(Error::IngredientCycle(label.clone())))?;
+
+                        return Err(Error::IngredientCycle(label));
+                    }
+
                     let alg = match c2pa_manifest.alg() {
                         Some(a) => a,
                         None => ingredient.alg().to_owned(),
@@ -1320,7 +1494,9 @@ impl Store {
                     )?;
 
                     // recurse nested ingredients
-                    Store::ingredient_checks(store, ingredient, asset_data, validation_log)?;
+                    chain.push(ingredient.label().to_string());
+                    Store::ingredient_checks(store, ingredient, asset_data, validation_log, chain)?;
+                    chain.pop();
                 } else {
                     let log_item = log_item!(
                         &c2pa_manifest.url(),
@@ -1381,6 +1557,123 @@ impl Store {
         Ok(())
     }
 
+    /// Verifies that `source` is the asset recorded by the ingredient with `instance_id` in
+    /// `claim`.
+    ///
+    /// The manifest found in `source` must match the hash the ingredient recorded for its
+    /// manifest, otherwise `ingredient.hashedURI.mismatch` is logged. When it matches, `source`
+    /// is validated against that manifest so that changes to the source bytes are reported.
+    ///
+    /// An ingredient without a manifest records no hash of its source, so `source` must then
+    /// have no manifest either and carry the ingredient's instance ID in its XMP, otherwise
+    /// [`STATUS_INGREDIENT_SOURCE_MISMATCH`](validation_status::STATUS_INGREDIENT_SOURCE_MISMATCH)
+    /// is logged.
+    pub fn verify_ingredient_source(
+        claim: &Claim,
+        instance_id: &str,
+        asset_type: &str,
+        source: &mut dyn CAIRead,
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<()> {
+        let ingredient = claim
+            .ingredient_assertions()
+            .into_iter()
+            .filter_map(|i| Ingredient::from_assertion(i).ok())
+            .find(|i| i.instance_id.as_deref() == Some(instance_id))
+            .ok_or(Error::IngredientNotFound)?;
+
+        let Some(c2pa_manifest) = ingredient.manifest_uri() else {
+            return Store::verify_ingredient_source_without_manifest(
+                claim,
+                instance_id,
+                asset_type,
+                source,
+                validation_log,
+            );
+        };
+
+        let jumbf = Store::load_jumbf_from_stream(asset_type, source)?;
+        let mut source_store = Store::from_jumbf(&jumbf, validation_log)?;
+
+        let label = Store::manifest_label_from_path(&c2pa_manifest.url());
+        let matches = match source_store.get_claim(&label) {
+            Some(source_claim) => {
+                let alg = match c2pa_manifest.alg() {
+                    Some(a) => a,
+                    None => source_claim.alg().to_owned(),
+                };
+
+                // test for 1.1 hash then 1.0 version
+                let box_hash = source_store.get_manifest_box_hash(source_claim);
+                vec_compare(&c2pa_manifest.hash(), &box_hash)
+                    || verify_by_alg(&alg, &c2pa_manifest.hash(), &source_claim.data()?, None)
+            }
+            None => false,
+        };
+
+        if !matches {
+            let log_item = log_item!(
+                &c2pa_manifest.url(),
+                "ingredient hash does not match source",
+                "verify_ingredient_source"
+            )
+            .error(Error::HashMismatch(
+                "ingredient hash does not match source asset".to_string(),
+            ))
+            .validation_status(validation_status::INGREDIENT_HASHEDURI_MISMATCH);
+            validation_log.log(
+                log_item,
+                Some(Error::HashMismatch(
+                    "ingredient hash does not match source asset".to_string(),
+                )),
+            )?;
+
+            return Ok(());
+        }
+
+        // the source bytes must still be bound to the ingredient's manifest
+        source.rewind()?;
+        source_store.verify_from_stream(source, asset_type, validation_log)
+    }
+
+    // Matches the source of an ingredient without a manifest by its XMP instance ID.
+    fn verify_ingredient_source_without_manifest(
+        claim: &Claim,
+        instance_id: &str,
+        asset_type: &str,
+        source: &mut dyn CAIRead,
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<()> {
+        let has_manifest = match Store::load_jumbf_from_stream(asset_type, source) {
+            Ok(_) => true,
+            Err(Error::JumbfNotFound) => false,
+            Err(e) => return Err(e),
+        };
+
+        source.rewind()?;
+        let source_instance_id =
+            crate::utils::xmp_inmemory_utils::XmpInfo::from_source(source, asset_type).instance_id;
+
+        let explanation = if has_manifest {
+            "the source has a manifest but the ingredient does not"
+        } else if source_instance_id.is_none() {
+            "the source has no XMP instance ID to match the ingredient"
+        } else if source_instance_id.as_deref() != Some(instance_id) {
+            "the source XMP instance ID does not match the ingredient"
+        } else {
+            return Ok(());
+        };
+
+        let log_item = log_item!(
+            claim.label(),
+            explanation,
+            "verify_ingredient_source_without_manifest"
+        )
+        .error(Error::HashMismatch(explanation.to_string()))
+        .validation_status(validation_status::STATUS_INGREDIENT_SOURCE_MISMATCH);
+        validation_log.log(log_item, Some(Error::HashMismatch(explanation.to_string())))
+    }
+
     // wake the ingredients and validate
     #[async_recursion(?Send)]
     async fn ingredient_checks_async(
@@ -1388,16 +1681,33 @@ impl Store {
         claim: &Claim,
         asset_data: &mut ClaimAssetData<'_>,
         validation_log: &mut impl StatusTracker,
+        chain: &mut Vec<String>,
     ) -> Result<()> {
         // walk the ingredients
         for i in claim.ingredient_assertions() {
             let ingredient_assertion = Ingredient::from_assertion(i)?;
 
             // is this an ingredient
-            if let Some(ref c2pa_manifest) = &ingredient_assertion.c2pa_manifest {
+            if let Some(c2pa_manifest) = ingredient_assertion.manifest_uri() {
                 let label = Store::manifest_label_from_path(&c2pa_manifest.url());
+                Store::check_ingredient_hash_alg(claim, c2pa_manifest, validation_log);
 
                 if let Some(ingredient) = store.get_claim(&label) {
+                    // a claim already on the path from the provenance claim would be
+                    // validated again and again
+                    if chain.iter().any(|l| l == ingredient.label()) {
+                        let log_item = log_item!(
+                            &c2pa_manifest.url(),
+                            "ingredient cycle",
+                            "ingredient_checks_async"
+                        )
+                        .error(Error::IngredientCycle(label.clone()));
+                        validation_log
+                            .log(log_item, Some(Error::IngredientCycle(label.clone())))?;
+
+                        return Err(Error::IngredientCycle(label));
+                    }
+
                     let alg = match c2pa_manifest.alg() {
                         Some(a) => a,
                         None => ingredient.alg().to_owned(),
@@ -1442,8 +1752,16 @@ impl Store {
                     .await?;
 
                     // recurse nested ingredients
-                    Store::ingredient_checks_async(store, ingredient, asset_data, validation_log)
-                        .await?;
+                    chain.push(ingredient.label().to_string());
+                    Store::ingredient_checks_async(
+                        store,
+                        ingredient,
+                        asset_data,
+                        validation_log,
+                        chain,
+                    )
+                    .await?;
+                    chain.pop();
                 } else {
                     let log_item = log_item!(
                         &c2pa_manifest.url(),
@@ -1500,8 +1818,11 @@ impl Store {
             validation_log,
         )
         .await?;
+        Store::check_claim_format(claim, asset_data, validation_log);
 
-        Store::ingredient_checks_async(store, claim, asset_data, validation_log).await?;
+        let mut chain = vec![claim.label().to_string()];
+        Store::ingredient_checks_async(store, claim, asset_data, validation_log, &mut chain)
+            .await?;
 
         Ok(())
     }
@@ -1538,12 +1859,118 @@ impl Store {
             store.trust_handler(),
             validation_log,
         )?;
+        Store::check_claim_format(claim, asset_data, validation_log);
 
-        Store::ingredient_checks(store, claim, asset_data, validation_log)?;
+        let mut chain = vec![claim.label().to_string()];
+        Store::ingredient_checks(store, claim, asset_data, validation_log, &mut chain)?;
 
         Ok(())
     }
 
+    /// Reports [`STATUS_INGREDIENT_WEAK_HASH`](validation_status::STATUS_INGREDIENT_WEAK_HASH)
+    /// if an ingredient's manifest is bound with a weaker hash algorithm than the claim's own.
+    ///
+    /// A hashed URI without an algorithm uses the claim's, and unknown algorithms are left to
+    /// the hash check itself.
+    fn check_ingredient_hash_alg(
+        claim: &Claim,
+        c2pa_manifest: &HashedUri,
+        validation_log: &mut impl StatusTracker,
+    ) {
+        let Some(alg) = c2pa_manifest.alg() else {
+            return;
+        };
+
+        if let (Some(strength), Some(claim_strength)) =
+            (hash_alg_strength(&alg), hash_alg_strength(claim.alg()))
+        {
+            if strength < claim_strength {
+                let log_item = log_item!(
+                    &c2pa_manifest.url(),
+                    format!(
+                        "ingredient hash {alg} is weaker than claim hash {}",
+                        claim.alg()
+                    ),
+                    "check_ingredient_hash_alg"
+                )
+                .validation_status(validation_status::STATUS_INGREDIENT_WEAK_HASH);
+                validation_log.log_silent(log_item);
+            }
+        }
+    }
+
+    /// Reports [`STATUS_FORMAT_MISMATCH`](validation_status::STATUS_FORMAT_MISMATCH) if the
+    /// claim's format and the asset's format are different MIME types.
+    ///
+    /// Nothing is reported when either format is unknown or not given, or the asset is a
+    /// standalone manifest store.
+    fn check_claim_format(
+        claim: &Claim,
+        asset_data: &ClaimAssetData<'_>,
+        validation_log: &mut impl StatusTracker,
+    ) {
+        let asset_format = match asset_data {
+            #[cfg(feature = "file_io")]
+            ClaimAssetData::Path(path) => path.extension().and_then(|ext| ext.to_str()),
+            ClaimAssetData::Bytes(_, format)
+            | ClaimAssetData::Stream(_, format)
+            | ClaimAssetData::StreamFragment(_, _, format) => Some(*format),
+            #[cfg(feature = "file_io")]
+            ClaimAssetData::StreamFragments(_, _, format) => Some(*format),
+        };
+
+        // only formats with an asset handler are known well enough to compare
+        let Some(asset_format) = asset_format else {
+            return;
+        };
+        if get_assetio_handler(asset_format).is_none()
+            || get_assetio_handler(claim.format()).is_none()
+        {
+            return;
+        }
+
+        // compare MIME types rather than handlers, which read several formats each
+        let asset_mime = canonical_mime(asset_format);
+
+        // a standalone manifest store has no asset format to compare
+        let is_manifest_store = asset_mime == canonical_mime(MANIFEST_STORE_EXT);
+
+        if !is_manifest_store && asset_mime != canonical_mime(claim.format()) {
+            let log_item = log_item!(
+                claim.uri(),
+                format!(
+                    "claim format {} does not match asset format {asset_format}",
+                    claim.format()
+                ),
+                "check_claim_format"
+            )
+            .validation_status(validation_status::STATUS_FORMAT_MISMATCH);
+            validation_log.log_silent(log_item);
+        }
+    }
+
+    /// Verify a single claim in this store on its own.
+    ///
+    /// The claim signature and assertions are checked, but not hard bindings to the asset, which
+    /// only apply to the provenance claim.
+    pub(crate) fn verify_claim_standalone(
+        &self,
+        claim: &Claim,
+        validation_log: &mut impl StatusTracker,
+    ) -> Result<()> {
+        let check_ingredient_trust: bool =
+            crate::settings::get_settings_value("verify.check_ingredient_trust")?;
+
+        Claim::verify_claim(
+            claim,
+            &mut ClaimAssetData::Bytes(&[], ""),
+            false,
+            check_ingredient_trust,
+            self.trust_handler(),
+            validation_log,
+        )
+    }
+
     // generate a list of AssetHashes based on the location of objects in the file
     #[cfg(feature = "file_io")]
     fn generate_data_hashes(
@@ -1816,6 +2243,8 @@ impl Store {
         dh: &DataHash,
         asset_reader: Option<&mut dyn CAIRead>,
     ) -> Result<Vec<u8>> {
+        let metrics = self.metrics.clone();
+        let _timer = StageTimer::start(metrics.as_deref(), PipelineStage::Hash);
         let pc = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
 
         // make sure there are data hashes present before generating
@@ -1832,7 +2261,9 @@ impl Store {
             ));
         }
 
-        let mut adjusted_dh = DataHash::new("jumbf manifest", pc.alg());
+        // use the algorithm requested by the caller, falling back to the claim's algorithm
+        let alg = dh.alg.as_deref().unwrap_or(pc.alg()).to_owned();
+        let mut adjusted_dh = DataHash::new("jumbf manifest", &alg);
         adjusted_dh.exclusions.clone_from(&dh.exclusions);
         adjusted_dh.hash.clone_from(&dh.hash);
 
@@ -1854,6 +2285,8 @@ impl Store {
         jumbf_bytes: &mut Vec<u8>,
         format: &str,
     ) -> Result<Vec<u8>> {
+        let metrics = self.metrics.clone();
+        let _timer = StageTimer::start(metrics.as_deref(), PipelineStage::Embed);
         if sig_placeholder.len() != sig.len() {
             return Err(Error::CoseSigboxTooSmall);
         }
@@ -1888,7 +2321,7 @@ impl Store {
         let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
         let sig = self.sign_claim(pc, signer, signer.reserve_size())?;
 
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         self.finish_embeddable_store(&sig, &sig_placeholder, &mut jumbf_bytes, format)
     }
@@ -1920,7 +2353,7 @@ impl Store {
             .sign_claim_async(pc, signer, signer.reserve_size())
             .await?;
 
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         self.finish_embeddable_store(&sig, &sig_placeholder, &mut jumbf_bytes, format)
     }
@@ -1948,10 +2381,9 @@ impl Store {
 
         // sign contents
         let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
-        let claim_bytes = pc.data()?;
-        let sig = signer.sign_remote(&claim_bytes).await?;
+        let sig = self.sign_claim_remote(pc, signer).await?;
 
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         self.finish_embeddable_store(&sig, &sig_placeholder, &mut jumbf_bytes, format)
     }
@@ -1977,7 +2409,7 @@ impl Store {
 
         // sign contents
         let sig = self.sign_claim(pc, signer, signer.reserve_size())?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         if sig_placeholder.len() != sig.len() {
             return Err(Error::CoseSigboxTooSmall);
@@ -2015,7 +2447,7 @@ impl Store {
         let sig = self
             .sign_claim_async(pc, signer, signer.reserve_size())
             .await?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         if sig_placeholder.len() != sig.len() {
             return Err(Error::CoseSigboxTooSmall);
@@ -2027,6 +2459,63 @@ impl Store {
         Ok(jumbf_bytes)
     }
 
+    /// Returns a signed manifest store bound to `asset` exactly as it is, and the data hash of
+    /// that binding.
+    ///
+    /// A data hash over all of `asset` is added to the provenance claim, so the manifest store
+    /// can be kept apart from the asset and validated against the unmodified bytes. It is an
+    /// error if the provenance claim already has a hash binding assertion.
+    #[async_generic(async_signature(
+        &mut self,
+        asset: &mut dyn CAIRead,
+        signer: &dyn AsyncSigner,
+    ))]
+    pub fn get_detached_manifest(
+        &mut self,
+        asset: &mut dyn CAIRead,
+        signer: &dyn Signer,
+    ) -> Result<(Vec<u8>, DataHash)> {
+        let metrics = self.metrics.clone();
+        let pc = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
+        if !pc.hash_assertions().is_empty() {
+            return Err(Error::BadParam(
+                "a detached manifest cannot have a hash binding assertion".to_string(),
+            ));
+        }
+
+        let mut dh = DataHash::new("jumbf manifest", pc.alg());
+        asset.rewind()?;
+        let timer = StageTimer::start(metrics.as_deref(), PipelineStage::Hash);
+        dh.gen_hash_from_stream(asset)?;
+        drop(timer);
+        pc.add_assertion(&dh)?;
+
+        let mut jumbf_bytes = self.to_jumbf_internal(signer.reserve_size())?;
+
+        // sign contents
+        let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
+        let sig = if _sync {
+            self.sign_claim(pc, signer, signer.reserve_size())
+        } else {
+            self.sign_claim_async(pc, signer, signer.reserve_size())
+                .await
+        }?;
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
+
+        if sig_placeholder.len() != sig.len() {
+            return Err(Error::CoseSigboxTooSmall);
+        }
+
+        patch_bytes(&mut jumbf_bytes, &sig_placeholder, &sig)
+            .map_err(|_| Error::JumbfCreationError)?;
+
+        // save sig so store is up to date
+        let pc_mut = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
+        pc_mut.set_signature_val(sig);
+
+        Ok((jumbf_bytes, dh))
+    }
+
     /// Returns the supplied manifest composed to be directly compatible with the desired format.
     /// For example, if format is JPEG function will return the set of APP11 segments that contains
     /// the manifest.  Similarly for PNG it would be the PNG chunk complete with header and  CRC.   
@@ -2047,6 +2536,8 @@ impl Store {
         output_dir: &Path,
         reserve_size: usize,
     ) -> Result<Vec<u8>> {
+        let metrics = self.metrics.clone();
+        let _timer = StageTimer::start(metrics.as_deref(), PipelineStage::Hash);
         // get the provenance claim changing mutability
         let pc = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
         pc.clear_data(); // clear since we are reusing an existing claim
@@ -2125,6 +2616,7 @@ impl Store {
 
         // use temp store so mulitple calls will work (the Store is not finalized this way)
         let mut temp_store = Store::from_jumbf(&jumbf, &mut validation_log)?;
+        temp_store.metrics = self.metrics.clone();
 
         let jumbf_bytes = temp_store.start_save_bmff_fragmented(
             asset_path,
@@ -2135,7 +2627,7 @@ impl Store {
 
         let pc = temp_store.provenance_claim().ok_or(Error::ClaimEncoding)?;
         let sig = temp_store.sign_claim(pc, signer, signer.reserve_size())?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = temp_store.sign_claim_placeholder(pc, signer.reserve_size());
 
         let output_filename = asset_path.file_name().ok_or(Error::NotFound)?;
         let dest_path = output_path.join(output_filename);
@@ -2182,7 +2674,7 @@ impl Store {
             self.sign_claim_async(pc, signer, signer.reserve_size())
                 .await
         }?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         intermediate_stream.rewind()?;
         match self.finish_save_stream(
@@ -2302,7 +2794,7 @@ impl Store {
                 let ingredient_assertion = Ingredient::from_assertion(i)?;
 
                 // is this an ingredient
-                if let Some(ref c2pa_manifest) = &ingredient_assertion.c2pa_manifest {
+                if let Some(c2pa_manifest) = ingredient_assertion.manifest_uri() {
                     let label = Store::manifest_label_from_path(&c2pa_manifest.url());
 
                     if let Some(ingredient) = new_store.get_claim(&label) {
@@ -2401,7 +2893,7 @@ impl Store {
                 .sign_claim_async(pc, signer, signer.reserve_size())
                 .await?
         };
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = store.sign_claim_placeholder(pc, signer.reserve_size());
 
         match store.finish_save_stream(
             manifest_bytes_updated,
@@ -2438,8 +2930,8 @@ impl Store {
         )?;
 
         let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
-        let sig = remote_signer.sign_remote(&pc.data()?).await?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, remote_signer.reserve_size());
+        let sig = self.sign_claim_remote(pc, remote_signer).await?;
+        let sig_placeholder = self.sign_claim_placeholder(pc, remote_signer.reserve_size());
 
         match self.finish_save_to_memory(
             jumbf_bytes,
@@ -2467,8 +2959,26 @@ impl Store {
         signer: &dyn Signer,
         dest_path: &Path,
     ) -> Result<Vec<u8>> {
+        if temp_files::in_memory() {
+            let (format, jumbf_bytes, intermediate_stream) =
+                self.start_save_in_memory(asset_path, signer.reserve_size())?;
+
+            let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
+            let sig = self.sign_claim(pc, signer, signer.reserve_size())?;
+            let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
+
+            return self.finish_save_in_memory(
+                jumbf_bytes,
+                &format,
+                intermediate_stream,
+                sig,
+                &sig_placeholder,
+                dest_path,
+            );
+        }
+
         // set up temp dir, contents auto deleted
-        let td = tempfile::TempDir::new()?;
+        let td = temp_files::temp_dir()?;
         let temp_path = td.path();
         let temp_file = temp_path.join(
             dest_path
@@ -2480,7 +2990,7 @@ impl Store {
 
         let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
         let sig = self.sign_claim(pc, signer, signer.reserve_size())?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         // get correct output path for remote manifest
         let output_path = match pc.remote_manifest() {
@@ -2521,8 +3031,28 @@ impl Store {
         signer: &dyn AsyncSigner,
         dest_path: &Path,
     ) -> Result<Vec<u8>> {
+        if temp_files::in_memory() {
+            let (format, jumbf_bytes, intermediate_stream) =
+                self.start_save_in_memory(asset_path, signer.reserve_size())?;
+
+            let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
+            let sig = self
+                .sign_claim_async(pc, signer, signer.reserve_size())
+                .await?;
+            let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
+
+            return self.finish_save_in_memory(
+                jumbf_bytes,
+                &format,
+                intermediate_stream,
+                sig,
+                &sig_placeholder,
+                dest_path,
+            );
+        }
+
         // set up temp dir, contents auto deleted
-        let td = tempfile::TempDir::new()?;
+        let td = temp_files::temp_dir()?;
         let temp_path = td.path();
         let temp_file = temp_path.join(
             dest_path
@@ -2536,7 +3066,7 @@ impl Store {
         let sig = self
             .sign_claim_async(pc, signer, signer.reserve_size())
             .await?;
-        let sig_placeholder = Store::sign_claim_placeholder(pc, signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, signer.reserve_size());
 
         // get correct output path for remote manifest
         let output_path = match pc.remote_manifest() {
@@ -2577,8 +3107,26 @@ impl Store {
         remote_signer: &dyn crate::signer::RemoteSigner,
         dest_path: &Path,
     ) -> Result<Vec<u8>> {
+        if temp_files::in_memory() {
+            let (format, jumbf_bytes, intermediate_stream) =
+                self.start_save_in_memory(asset_path, remote_signer.reserve_size())?;
+
+            let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
+            let sig = self.sign_claim_remote(pc, remote_signer).await?;
+            let sig_placeholder = self.sign_claim_placeholder(pc, remote_signer.reserve_size());
+
+            return self.finish_save_in_memory(
+                jumbf_bytes,
+                &format,
+                intermediate_stream,
+                sig,
+                &sig_placeholder,
+                dest_path,
+            );
+        }
+
         // set up temp dir, contents auto deleted
-        let td = tempfile::TempDir::new()?;
+        let td = temp_files::temp_dir()?;
         let temp_path = td.path();
         let temp_file = temp_path.join(
             dest_path
@@ -2589,9 +3137,9 @@ impl Store {
         let jumbf_bytes = self.start_save(asset_path, &temp_file, remote_signer.reserve_size())?;
 
         let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
-        let sig = remote_signer.sign_remote(&pc.data()?).await?;
+        let sig = self.sign_claim_remote(pc, remote_signer).await?;
 
-        let sig_placeholder = Store::sign_claim_placeholder(pc, remote_signer.reserve_size());
+        let sig_placeholder = self.sign_claim_placeholder(pc, remote_signer.reserve_size());
 
         // get correct output path for remote manifest
         let output_path = match pc.remote_manifest() {
@@ -2624,17 +3172,78 @@ impl Store {
         }
     }
 
-    fn start_save_stream(
+    // Starts embedding the store in the asset at `asset_path` without a temporary copy of the
+    // asset, keeping the intermediate output in memory. Returns the asset's format, the JUMBF
+    // to sign and the intermediate output.
+    #[cfg(feature = "file_io")]
+    fn start_save_in_memory(
+        &mut self,
+        asset_path: &Path,
+        reserve_size: usize,
+    ) -> Result<(String, Vec<u8>, Cursor<Vec<u8>>)> {
+        let format = get_supported_file_extension(asset_path).ok_or(Error::UnsupportedType)?;
+        let mut input_stream = Cursor::new(std::fs::read(asset_path)?);
+        let mut intermediate_stream = Cursor::new(Vec::new());
+
+        let jumbf_bytes = self.start_save_stream(
+            &format,
+            &mut input_stream,
+            &mut intermediate_stream,
+            reserve_size,
+        )?;
+
+        Ok((format, jumbf_bytes, intermediate_stream))
+    }
+
+    // Finishes an embedding started with `start_save_in_memory`, writing the signed asset to
+    // `dest_path` along with the manifest store if it is remote or a sidecar.
+    #[cfg(feature = "file_io")]
+    fn finish_save_in_memory(
+        &mut self,
+        jumbf_bytes: Vec<u8>,
+        format: &str,
+        mut intermediate_stream: Cursor<Vec<u8>>,
+        sig: Vec<u8>,
+        sig_placeholder: &[u8],
+        dest_path: &Path,
+    ) -> Result<Vec<u8>> {
+        let mut output_stream = Cursor::new(Vec::new());
+
+        intermediate_stream.rewind()?;
+        let (s, m) = self.finish_save_stream(
+            jumbf_bytes,
+            format,
+            &mut intermediate_stream,
+            &mut output_stream,
+            sig,
+            sig_placeholder,
+        )?;
+
+        // save sig so store is up to date
+        let pc_mut = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
+        pc_mut.set_signature_val(s);
+
+        std::fs::write(dest_path, output_stream.into_inner())?;
+        if pc_mut.remote_manifest() != RemoteManifest::NoRemote {
+            std::fs::write(dest_path.with_extension(MANIFEST_STORE_EXT), &m)?;
+        }
+
+        Ok(m)
+    }
+
+    pub(crate) fn start_save_stream(
         &mut self,
         format: &str,
         input_stream: &mut dyn CAIRead,
         output_stream: &mut dyn CAIReadWrite,
         reserve_size: usize,
     ) -> Result<Vec<u8>> {
+        let metrics = self.metrics.clone();
+        let _timer = StageTimer::start(metrics.as_deref(), PipelineStage::Hash);
         let intermediate_output: Vec<u8> = Vec::new();
         let mut intermediate_stream = Cursor::new(intermediate_output);
 
-        let pc = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
+        let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
 
         // Add remote reference XMP if needed and strip out existing manifest
         // We don't need to strip manifests if we are replacing an existing one
@@ -2691,22 +3300,13 @@ impl Store {
 
         let is_bmff = is_bmff_format(format);
 
+        // 2) Get hash ranges if needed
+        let needs_hashing = self.add_placeholder_hard_binding(format, &mut intermediate_stream)?;
+
         let mut data;
         let jumbf_size;
 
         if is_bmff {
-            // 2) Get hash ranges if needed, do not generate for update manifests
-            if !pc.update_manifest() {
-                intermediate_stream.rewind()?;
-                let bmff_hash = Store::generate_bmff_data_hash_for_stream(
-                    &mut intermediate_stream,
-                    pc.alg(),
-                    false,
-                    false,
-                )?;
-                pc.add_assertion(&bmff_hash)?;
-            }
-
             // 3) Generate in memory CAI jumbf block
             // and write preliminary jumbf store to file
             // source and dest the same so save_jumbf_to_file will use the same file since we have already cloned
@@ -2715,7 +3315,7 @@ impl Store {
             // write the jumbf to the output stream if we are embedding the manifest
             if !remove_manifests {
                 intermediate_stream.rewind()?;
-                save_jumbf_to_stream(format, &mut intermediate_stream, output_stream, &data)?;
+                self.save_jumbf(format, &mut intermediate_stream, output_stream, &data)?;
             } else {
                 // just copy the asset to the output stream without an embedded manifest (may be stripping one out here)
                 intermediate_stream.rewind()?;
@@ -2736,34 +3336,6 @@ impl Store {
                 }
             }
         } else {
-            // we will not do automatic hashing if we detect a box hash present
-            let mut needs_hashing = false;
-            if pc.hash_assertions().is_empty() {
-                // 2) Get hash ranges if needed, do not generate for update manifests
-                let mut hash_ranges =
-                    object_locations_from_stream(format, &mut intermediate_stream)?;
-                let hashes: Vec<DataHash> = if pc.update_manifest() {
-                    Vec::new()
-                } else {
-                    Store::generate_data_hashes_for_stream(
-                        &mut intermediate_stream,
-                        pc.alg(),
-                        &mut hash_ranges,
-                        false,
-                    )?
-                };
-
-                // add the placeholder data hashes to provenance claim so that the required space is reserved
-                for mut hash in hashes {
-                    // add padding to account for possible cbor expansion of final DataHash
-                    let padding: Vec<u8> = vec![0x0; 10];
-                    hash.add_padding(padding);
-
-                    pc.add_assertion(&hash)?;
-                }
-                needs_hashing = true;
-            }
-
             // 3) Generate in memory CAI jumbf block
             data = self.to_jumbf_internal(reserve_size)?;
             jumbf_size = data.len();
@@ -2771,7 +3343,7 @@ impl Store {
             // write the jumbf to the output stream if we are embedding the manifest
             if !remove_manifests {
                 intermediate_stream.rewind()?;
-                save_jumbf_to_stream(format, &mut intermediate_stream, output_stream, &data)?;
+                self.save_jumbf(format, &mut intermediate_stream, output_stream, &data)?;
             } else {
                 // just copy the asset to the output stream without an embedded manifest (may be stripping one out here)
                 intermediate_stream.rewind()?;
@@ -2785,7 +3357,11 @@ impl Store {
 
                 // get the final hash ranges, but not for update manifests
                 output_stream.rewind()?;
-                let mut new_hash_ranges = object_locations_from_stream(format, output_stream)?;
+                let mut new_hash_ranges = self
+                    .asset_writer(format)?
+                    .get_object_locations_from_stream(&mut CAIReadAdapter {
+                        reader: output_stream,
+                    })?;
                 if !pc.update_manifest() {
                     let updated_hashes = Store::generate_data_hashes_for_stream(
                         output_stream,
@@ -2811,6 +3387,100 @@ impl Store {
         Ok(data) // return JUMBF data
     }
 
+    // Adds the hard binding of the provenance claim for the asset in `stream`, with placeholder
+    // hashes reserving the space of the final ones. Hash ranges are not generated for update
+    // manifests, and no data hash is added if the claim already has a hash binding, such as a
+    // box hash. Returns `true` if data hashes were added, which must be generated once the
+    // manifest store is in place.
+    fn add_placeholder_hard_binding(
+        &mut self,
+        format: &str,
+        stream: &mut dyn CAIRead,
+    ) -> Result<bool> {
+        let asset_writer = self.asset_writer(format)?;
+        let pc = self.provenance_claim_mut().ok_or(Error::ClaimEncoding)?;
+
+        if is_bmff_format(format) {
+            if !pc.update_manifest() {
+                stream.rewind()?;
+                let bmff_hash =
+                    Store::generate_bmff_data_hash_for_stream(stream, pc.alg(), false, false)?;
+                pc.add_assertion(&bmff_hash)?;
+            }
+            return Ok(false);
+        }
+
+        // we will not do automatic hashing if we detect a box hash present
+        if !pc.hash_assertions().is_empty() {
+            return Ok(false);
+        }
+
+        let mut hash_ranges = asset_writer.get_object_locations_from_stream(stream)?;
+        let hashes: Vec<DataHash> = if pc.update_manifest() {
+            Vec::new()
+        } else {
+            Store::generate_data_hashes_for_stream(stream, pc.alg(), &mut hash_ranges, false)?
+        };
+
+        // add the placeholder data hashes to provenance claim so that the required space is reserved
+        for mut hash in hashes {
+            // add padding to account for possible cbor expansion of final DataHash
+            let padding: Vec<u8> = vec![0x0; 10];
+            hash.add_padding(padding);
+
+            pc.add_assertion(&hash)?;
+        }
+        Ok(true)
+    }
+
+    /// Returns the size of the manifest store that saving the asset in `input_stream` would
+    /// embed, without writing the asset.
+    ///
+    /// The asset is only read to find where the manifest store goes. The hard binding is
+    /// measured with placeholder hashes, which take the same space as the final ones.
+    pub(crate) fn manifest_size(
+        &mut self,
+        format: &str,
+        input_stream: &mut dyn CAIRead,
+        reserve_size: usize,
+    ) -> Result<usize> {
+        self.add_placeholder_hard_binding(format, input_stream)?;
+        Ok(self.to_jumbf_internal(reserve_size)?.len())
+    }
+
+    /// Writes `jumbf` into the asset, compressed if [`Store::set_compress_manifest`] is set.
+    fn save_jumbf(
+        &self,
+        format: &str,
+        input_stream: &mut dyn CAIRead,
+        output_stream: &mut dyn CAIReadWrite,
+        jumbf: &[u8],
+    ) -> Result<()> {
+        let asset_writer = self.asset_writer(format)?;
+        if self.compress_manifest {
+            asset_writer.write_cai_compressed(input_stream, output_stream, jumbf)
+        } else {
+            asset_writer.write_cai(input_stream, output_stream, jumbf)
+        }
+    }
+
+    /// Returns the writer for `format`, given the signing time set with
+    /// [`Store::set_signing_time`] and the manifest store set with
+    /// [`Store::set_manifest_store_box`].
+    fn asset_writer(&self, format: &str) -> Result<Box<dyn CAIWriter>> {
+        let mut asset_writer = get_assetio_handler(format)
+            .and_then(|handler| handler.get_writer(format))
+            .ok_or(Error::UnsupportedType)?;
+        if let Some(signing_time) = self.signing_time {
+            asset_writer.set_signing_time(signing_time);
+        }
+        if let Some((_, uuid)) = &self.manifest_store_box {
+            asset_writer.set_manifest_store_uuid(uuid);
+        }
+
+        Ok(asset_writer)
+    }
+
     fn finish_save_stream(
         &self,
         mut jumbf_bytes: Vec<u8>,
@@ -2820,6 +3490,7 @@ impl Store {
         sig: Vec<u8>,
         sig_placeholder: &[u8],
     ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let _timer = StageTimer::start(self.metrics(), PipelineStage::Embed);
         if sig_placeholder.len() != sig.len() {
             return Err(Error::CoseSigboxTooSmall);
         }
@@ -2831,7 +3502,7 @@ impl Store {
         let pc = self.provenance_claim().ok_or(Error::ClaimEncoding)?;
         match pc.remote_manifest() {
             RemoteManifest::NoRemote | RemoteManifest::EmbedWithRemote(_) => {
-                save_jumbf_to_stream(format, input_stream, output_stream, &jumbf_bytes)?;
+                self.save_jumbf(format, input_stream, output_stream, &jumbf_bytes)?;
             }
             RemoteManifest::SideCar | RemoteManifest::Remote(_) => {
                 // just copy the asset to the output stream without an embedded manifest (may be stripping one out here)
@@ -2850,6 +3521,7 @@ impl Store {
         sig: Vec<u8>,
         sig_placeholder: &[u8],
     ) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let _timer = StageTimer::start(self.metrics(), PipelineStage::Embed);
         if sig_placeholder.len() != sig.len() {
             return Err(Error::CoseSigboxTooSmall);
         }
@@ -2872,6 +3544,8 @@ impl Store {
         dest_path: &Path,
         reserve_size: usize,
     ) -> Result<Vec<u8>> {
+        let metrics = self.metrics.clone();
+        let _timer = StageTimer::start(metrics.as_deref(), PipelineStage::Hash);
         // force generate external manifests for unknown types
 
         let ext = match get_supported_file_extension(dest_path) {
@@ -3040,6 +3714,7 @@ impl Store {
         sig: Vec<u8>,
         sig_placeholder: &[u8],
     ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let _timer = StageTimer::start(self.metrics(), PipelineStage::Embed);
         if sig_placeholder.len() != sig.len() {
             return Err(Error::CoseSigboxTooSmall);
         }
@@ -3177,22 +3852,54 @@ impl Store {
             .map(|manifest_bytes| Store::from_jumbf(&manifest_bytes, validation_log))?
     }
 
-    /// load jumbf given a stream
+    /// load jumbf given a stream
+    ///
+    /// This handles, embedded and remote manifests
+    ///
+    /// asset_type -  mime type of the stream
+    /// stream - a readable stream of an asset
+    pub fn load_jumbf_from_stream(asset_type: &str, stream: &mut dyn CAIRead) -> Result<Vec<u8>> {
+        match load_jumbf_from_stream(asset_type, stream) {
+            Ok(manifest_bytes) => Ok(manifest_bytes),
+            Err(Error::JumbfNotFound) => {
+                stream.rewind()?;
+                if let Some(ext_ref) =
+                    crate::utils::xmp_inmemory_utils::XmpInfo::from_source(stream, asset_type)
+                        .provenance
+                {
+                    Store::handle_remote_manifest(&ext_ref)
+                } else {
+                    Err(Error::JumbfNotFound)
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// write jumbf given a stream to writer, returning the number of bytes written
     ///
-    /// This handles, embedded and remote manifests
+    /// This handles embedded and remote manifests like [`Store::load_jumbf_from_stream`], but
+    /// copies an embedded manifest store to writer without holding it in memory
     ///
     /// asset_type -  mime type of the stream
     /// stream - a readable stream of an asset
-    pub fn load_jumbf_from_stream(asset_type: &str, stream: &mut dyn CAIRead) -> Result<Vec<u8>> {
-        match load_jumbf_from_stream(asset_type, stream) {
-            Ok(manifest_bytes) => Ok(manifest_bytes),
+    /// writer - where the manifest store is written
+    pub fn load_jumbf_to_writer(
+        asset_type: &str,
+        stream: &mut dyn CAIRead,
+        writer: &mut dyn Write,
+    ) -> Result<u64> {
+        match load_jumbf_to_writer(asset_type, stream, writer) {
+            Ok(len) => Ok(len),
             Err(Error::JumbfNotFound) => {
                 stream.rewind()?;
                 if let Some(ext_ref) =
                     crate::utils::xmp_inmemory_utils::XmpInfo::from_source(stream, asset_type)
                         .provenance
                 {
-                    Store::handle_remote_manifest(&ext_ref)
+                    let manifest_bytes = Store::handle_remote_manifest(&ext_ref)?;
+                    writer.write_all(&manifest_bytes)?;
+                    Ok(manifest_bytes.len() as u64)
                 } else {
                     Err(Error::JumbfNotFound)
                 }
@@ -3589,7 +4296,7 @@ pub mod tests {
         assertions::{labels::BOX_HASH, Action, Actions, BoxHash, Uuid},
         claim::AssertionStoreJsonFormat,
         hashed_uri::HashedUri,
-        jumbf_io::{get_assetio_handler_from_path, update_file_jumbf},
+        jumbf_io::{get_assetio_handler_from_path, load_jumbf_from_memory, update_file_jumbf},
         status_tracker::*,
         utils::{
             hash_utils::Hasher,
@@ -3739,6 +4446,88 @@ pub mod tests {
             .expect_err("Should not verify");
     }
 
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_save_to_asset_in_memory() {
+        use crate::{
+            settings::{set_settings_value, tests::PROTECT},
+            utils::temp_files::{with_temp_fs, MockTempFs},
+        };
+
+        let _protect = PROTECT.lock().unwrap();
+
+        let ap = fixture_path("earth_apollo17.jpg");
+        let temp_dir = tempdir().expect("temp dir");
+        let op = temp_dir_path(&temp_dir, "in-memory.jpg");
+
+        let mut store = Store::new();
+        let claim = create_test_claim().unwrap();
+        let claim_label = claim.label().to_string();
+        store.commit_claim(claim).unwrap();
+
+        let signer = temp_signer();
+
+        // signing must not create any temporary files or directories
+        let mut temp_fs = MockTempFs::new();
+        temp_fs.expect_temp_file().never();
+        temp_fs.expect_temp_dir().never();
+
+        set_settings_value("core.in_memory", true).unwrap();
+        let result = with_temp_fs(temp_fs, || store.save_to_asset(&ap, signer.as_ref(), &op));
+        set_settings_value("core.in_memory", false).unwrap();
+        result.unwrap();
+
+        let mut report = DetailedStatusTracker::new();
+        let restored_store = Store::load_from_asset(&op, true, &mut report).unwrap();
+        assert_eq!(
+            restored_store.provenance_claim().unwrap().label(),
+            claim_label
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_save_to_asset_uses_temp_dir_setting() {
+        use crate::{
+            settings::{set_settings_value, tests::PROTECT},
+            utils::temp_files::{with_temp_fs, MockTempFs},
+        };
+
+        let _protect = PROTECT.lock().unwrap();
+
+        let ap = fixture_path("earth_apollo17.jpg");
+        let temp_dir = tempdir().expect("temp dir");
+        let op = temp_dir_path(&temp_dir, "temp-dir.jpg");
+
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+
+        let signer = temp_signer();
+
+        // other tests may run while the setting is changed, so use a directory that outlives this one
+        let configured_dir = std::env::temp_dir().join("c2pa_temp_dir_test");
+        std::fs::create_dir_all(&configured_dir).unwrap();
+
+        // temporary files may only be created in the configured directory
+        let mut temp_fs = MockTempFs::new();
+        let expected_dir = configured_dir.clone();
+        temp_fs
+            .expect_temp_dir()
+            .withf(move |dir| *dir == expected_dir.as_path())
+            .times(1)
+            .returning(|dir| tempfile::tempdir_in(dir));
+        temp_fs.expect_temp_file().never();
+
+        set_settings_value(
+            "core.temp_dir",
+            configured_dir.to_string_lossy().to_string(),
+        )
+        .unwrap();
+        let result = with_temp_fs(temp_fs, || store.save_to_asset(&ap, signer.as_ref(), &op));
+        set_settings_value("core.temp_dir", None::<String>).unwrap();
+        result.unwrap();
+    }
+
     #[test]
     #[cfg(feature = "file_io")]
     fn test_unknown_asset_type_generation() {
@@ -5302,6 +6091,180 @@ pub mod tests {
         // std::fs::write("target/test.jpg", result).unwrap();
     }
 
+    #[test]
+    fn test_private_manifest_store_label() {
+        use crate::settings::{reset_default_settings, set_settings_value, tests::PROTECT};
+
+        let _protect = PROTECT.lock().unwrap();
+        set_settings_value("core.manifest_store_label", "com.example.private").unwrap();
+        set_settings_value(
+            "core.manifest_store_uuid",
+            "707276740011001080000000AA000001",
+        )
+        .unwrap();
+
+        let signer = temp_signer();
+        let sign = |format: &str, source: &[u8]| {
+            let mut store = Store::new();
+            store.commit_claim(create_test_claim().unwrap()).unwrap();
+            let mut output = Cursor::new(Vec::new());
+            store
+                .save_to_stream(
+                    format,
+                    &mut Cursor::new(source),
+                    &mut output,
+                    signer.as_ref(),
+                )
+                .unwrap();
+            (store, output.into_inner())
+        };
+
+        let gif = include_bytes!("../tests/fixtures/sample1.gif").as_slice();
+        let jpeg = include_bytes!("../tests/fixtures/earth_apollo17.jpg").as_slice();
+        let mut outputs = Vec::new();
+        for (format, source) in [("gif", gif), ("jpeg", jpeg)] {
+            let (store, output) = sign(format, source);
+
+            // the manifest store superbox carries the configured label and UUID
+            let jumbf = Store::load_jumbf_from_stream(format, &mut Cursor::new(&output)).unwrap();
+            let cai_block = Cai::from(BoxReader::read_super_box(&mut Cursor::new(&jumbf)).unwrap());
+            assert_eq!(cai_block.desc_box().label(), "com.example.private");
+            assert_eq!(
+                cai_block.desc_box().uuid(),
+                "707276740011001080000000AA000001"
+            );
+
+            // and is located by it
+            let mut report = DetailedStatusTracker::new();
+            let new_store = Store::load_from_memory(format, &output, true, &mut report).unwrap();
+            assert!(report_split_errors(report.get_log_mut()).is_empty());
+            assert_eq!(new_store.provenance_label(), store.provenance_label());
+            outputs.push((format, output));
+        }
+
+        // signing again replaces the private manifest store of the JPEG
+        let (store, resigned) = sign("jpeg", outputs[1].1.as_slice());
+        let mut report = DetailedStatusTracker::new();
+        let new_store = Store::load_from_memory("jpeg", &resigned, true, &mut report).unwrap();
+        assert!(report_split_errors(report.get_log_mut()).is_empty());
+        assert_eq!(new_store.provenance_label(), store.provenance_label());
+        assert_eq!(new_store.claims().len(), 1);
+
+        // standard manifest stores are still located
+        let ca = include_bytes!("../tests/fixtures/CA.jpg");
+        let mut report = DetailedStatusTracker::new();
+        assert!(Store::load_from_memory("jpeg", ca, false, &mut report).is_ok());
+
+        // a private manifest store is not found without its label, the JPEG handler doesn't
+        // even find its JUMBF
+        reset_default_settings().unwrap();
+        for (format, output) in outputs {
+            assert!(matches!(
+                Store::load_from_memory(format, &output, false, &mut DetailedStatusTracker::new()),
+                Err(Error::InvalidClaim(InvalidClaimError::C2paBlockNotFound)
+                    | Error::JumbfNotFound)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_manifest_size() {
+        let reserve_size = 10000;
+        for (format, asset) in [
+            (
+                "image/jpeg",
+                include_bytes!("../tests/fixtures/CA.jpg").as_slice(),
+            ),
+            (
+                "video/mp4",
+                include_bytes!("../tests/fixtures/video1.mp4").as_slice(),
+            ),
+        ] {
+            let mut store = Store::new();
+            store.commit_claim(create_test_claim().unwrap()).unwrap();
+            let size = store
+                .manifest_size(format, &mut Cursor::new(asset), reserve_size)
+                .unwrap();
+
+            // the size of the manifest store written into the asset
+            let mut store = Store::new();
+            store.commit_claim(create_test_claim().unwrap()).unwrap();
+            let placed = store
+                .get_placed_manifest(reserve_size, format, &mut Cursor::new(asset))
+                .unwrap();
+            assert_eq!(size, placed.len());
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "file_io", feature = "openssl_sign"))]
+    fn test_intermediates_dir() {
+        use crate::settings::{reset_default_settings, tests::PROTECT, TrustConfig};
+
+        let _protect = PROTECT.lock().unwrap();
+
+        // a manifest signed with only the signing certificate, without its intermediate
+        let signer = crate::create_signer::from_chain(
+            &[include_bytes!(
+                "../tests/fixtures/certs/chain/es256_signer.pem"
+            )],
+            include_bytes!("../tests/fixtures/certs/es256.pem"),
+            SigningAlg::Es256,
+            None,
+        )
+        .unwrap();
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+        let mut source = Cursor::new(include_bytes!("../tests/fixtures/sample1.gif").to_vec());
+        let mut output = Cursor::new(Vec::new());
+        store
+            .save_to_stream("gif", &mut source, &mut output, signer.as_ref())
+            .unwrap();
+        let output = output.into_inner();
+
+        let trusted = |asset: &[u8]| {
+            let mut report = DetailedStatusTracker::new();
+            Store::load_from_memory("gif", asset, true, &mut report).ok();
+            report_has_status(
+                report.get_log(),
+                validation_status::SIGNING_CREDENTIAL_TRUSTED,
+            )
+        };
+
+        TrustConfig::production().load().unwrap();
+        assert!(!trusted(&output));
+
+        // supply the intermediate as a DER file, next to a file that is not a certificate
+        let intermediate = pem::parse(include_bytes!(
+            "../tests/fixtures/certs/chain/es256_intermediate.pem"
+        ))
+        .unwrap();
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("intermediate.der"), intermediate.contents()).unwrap();
+        std::fs::write(dir.path().join("README.txt"), "intermediate certificates").unwrap();
+
+        let config = TrustConfig::production()
+            .with_intermediates_dir(dir.path())
+            .unwrap();
+        // the settings only change when the preset is loaded
+        assert!(!trusted(&output));
+        config.load().unwrap();
+        assert!(trusted(&output));
+
+        // loading a preset without intermediates clears them
+        TrustConfig::production().load().unwrap();
+        assert!(!trusted(&output));
+
+        // a directory without certificates is rejected
+        let empty = tempdir().unwrap();
+        assert!(matches!(
+            TrustConfig::production().with_intermediates_dir(empty.path()),
+            Err(Error::NotFound)
+        ));
+
+        reset_default_settings().unwrap();
+    }
+
     #[test]
     #[cfg(feature = "file_io")]
     fn test_tiff_jumbf_generation() {
@@ -5678,6 +6641,77 @@ pub mod tests {
         assert!(errors.is_empty());
     }
 
+    #[test]
+    #[cfg(feature = "file_io")]
+    fn test_datahash_embeddable_manifest_sha512() {
+        use std::io::SeekFrom;
+        let ap = fixture_path("cloud.jpg");
+
+        let signer = temp_signer();
+
+        // the claim itself uses the default sha256 algorithm
+        let mut store = Store::new();
+        let claim = create_test_claim().unwrap();
+        store.commit_claim(claim).unwrap();
+
+        let placeholder = store
+            .get_data_hashed_manifest_placeholder(signer.reserve_size(), "jpeg")
+            .unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir_path(&temp_dir, "sha512-out.jpg");
+        let mut output_file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&output)
+            .unwrap();
+
+        let offset =
+            write_jpeg_placeholder_file(&placeholder, &ap, &mut output_file, None).unwrap();
+
+        // the data hash uses sha512
+        let mut dh = DataHash::new("source_hash", "sha512");
+        dh.exclusions = Some(vec![HashRange::new(offset, placeholder.len())]);
+
+        output_file.rewind().unwrap();
+        let cm = store
+            .get_data_hashed_embeddable_manifest(
+                &dh,
+                signer.as_ref(),
+                "jpeg",
+                Some(&mut output_file),
+            )
+            .unwrap();
+
+        output_file.seek(SeekFrom::Start(offset as u64)).unwrap();
+        output_file.write_all(&cm).unwrap();
+
+        let mut report = DetailedStatusTracker::new();
+        let new_store = Store::load_from_asset(&output, true, &mut report).unwrap();
+
+        let errors = report_split_errors(report.get_log_mut());
+        assert!(errors.is_empty());
+
+        // the data hash must have been recorded and validated with sha512
+        let pc = new_store.provenance_claim().unwrap();
+        let data_hash = DataHash::from_assertion(pc.hash_assertions()[0]).unwrap();
+        assert_eq!(data_hash.alg.as_deref(), Some("sha512"));
+        assert_eq!(data_hash.hash.len(), 64);
+
+        // validate again from a stream
+        let mut stream = std::fs::File::open(&output).unwrap();
+        let mut report = DetailedStatusTracker::new();
+        Store::verify_store(
+            &new_store,
+            &mut ClaimAssetData::Stream(&mut stream, "jpeg"),
+            &mut report,
+        )
+        .unwrap();
+        assert!(report_split_errors(report.get_log_mut()).is_empty());
+    }
+
     #[test]
     #[cfg(feature = "file_io")]
     fn test_datahash_embeddable_manifest_user_hashed() {
@@ -5958,4 +6992,238 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_ingredient_cycle() {
+        // a claim that lists its own manifest as an ingredient
+        let mut claim = create_test_claim().unwrap();
+        let label = claim.label().to_string();
+        let ingredient = Ingredient::new("self.jpg", "image/jpeg", "xmp.iid:self", None)
+            .set_c2pa_manifest_from_hashed_uri(Some(HashedUri::new(claim.uri(), None, b"hash")));
+        claim.add_assertion(&ingredient).unwrap();
+
+        let mut store = Store::new();
+        store.commit_claim(claim).unwrap();
+        let claim = store.provenance_claim().unwrap();
+
+        let mut validation_log = DetailedStatusTracker::new();
+        let result = Store::ingredient_checks(
+            &store,
+            claim,
+            &mut ClaimAssetData::Bytes(&[], "image/jpeg"),
+            &mut validation_log,
+            &mut vec![label.clone()],
+        );
+        assert!(matches!(result, Err(Error::IngredientCycle(l)) if l == label));
+
+        let errors = report_split_errors(validation_log.get_log_mut());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_ingredient_weak_hash() {
+        // a SHA-256 claim with ingredients bound with SHA-1 and with SHA-256
+        let mut claim = create_test_claim().unwrap();
+        assert_eq!(claim.alg(), "sha256");
+        for (name, alg, hash) in [
+            ("weak.jpg", "sha1", [0u8; 20].as_slice()),
+            ("strong.jpg", "sha256", [0u8; 32].as_slice()),
+        ] {
+            let manifest_uri = jumbf::labels::to_manifest_uri(&format!("urn:uuid:{name}"));
+            let ingredient = Ingredient::new(name, "image/jpeg", "xmp.iid:ingredient", None)
+                .set_c2pa_manifest_from_hashed_uri(Some(HashedUri::new(
+                    manifest_uri,
+                    Some(alg.to_owned()),
+                    hash,
+                )));
+            claim.add_assertion(&ingredient).unwrap();
+        }
+
+        let mut store = Store::new();
+        store.commit_claim(claim).unwrap();
+        let claim = store.provenance_claim().unwrap();
+
+        let mut validation_log = DetailedStatusTracker::new();
+        Store::ingredient_checks(
+            &store,
+            claim,
+            &mut ClaimAssetData::Bytes(&[], "image/jpeg"),
+            &mut validation_log,
+            &mut vec![claim.label().to_string()],
+        )
+        .unwrap();
+
+        let weak: Vec<_> = validation_log
+            .get_log()
+            .iter()
+            .filter(|item| {
+                item.validation_status.as_deref()
+                    == Some(validation_status::STATUS_INGREDIENT_WEAK_HASH)
+            })
+            .collect();
+        assert_eq!(weak.len(), 1);
+        assert!(weak[0].label.contains("weak.jpg"));
+
+        // reported as informational, not as a failure
+        assert!(
+            !validation_status::status_for_store(&store, &validation_log)
+                .iter()
+                .any(|s| s.code() == validation_status::STATUS_INGREDIENT_WEAK_HASH)
+        );
+        let informational = validation_status::informational_statuses(&validation_log);
+        assert_eq!(informational.len(), 1);
+        assert_eq!(
+            informational[0].code(),
+            validation_status::STATUS_INGREDIENT_WEAK_HASH
+        );
+    }
+
+    #[test]
+    fn test_claim_format_mismatch() {
+        let asset_bytes = include_bytes!("../tests/fixtures/CA.jpg");
+        let jumbf = load_jumbf_from_memory("jpg", asset_bytes).unwrap();
+        let mut report = DetailedStatusTracker::new();
+        let store = Store::from_jumbf(&jumbf, &mut report).unwrap();
+        assert_eq!(store.provenance_claim().unwrap().format(), "image/jpeg");
+
+        let mut report = DetailedStatusTracker::new();
+        Store::verify_store(
+            &store,
+            &mut ClaimAssetData::Bytes(asset_bytes, "jpg"),
+            &mut report,
+        )
+        .unwrap();
+        assert!(!report_has_status(
+            report.get_log(),
+            validation_status::STATUS_FORMAT_MISMATCH
+        ));
+
+        // the same asset served as a PNG
+        let mut report = DetailedStatusTracker::new();
+        Store::verify_store(
+            &store,
+            &mut ClaimAssetData::Bytes(asset_bytes, "image/png"),
+            &mut report,
+        )
+        .unwrap();
+        assert!(report_has_status(
+            report.get_log(),
+            validation_status::STATUS_FORMAT_MISMATCH
+        ));
+
+        // formats read by the same handler are still told apart
+        let mut claim = store.provenance_claim().unwrap().clone();
+        claim.format = "video/mp4".to_string();
+        let mut report = DetailedStatusTracker::new();
+        Store::check_claim_format(&claim, &ClaimAssetData::Bytes(&[], "heic"), &mut report);
+        assert!(report_has_status(
+            report.get_log(),
+            validation_status::STATUS_FORMAT_MISMATCH
+        ));
+
+        // but aliases of the same format are not
+        claim.format = "audio/wav".to_string();
+        let mut report = DetailedStatusTracker::new();
+        Store::check_claim_format(
+            &claim,
+            &ClaimAssetData::Bytes(&[], "audio/x-wav"),
+            &mut report,
+        );
+        assert!(!report_has_status(
+            report.get_log(),
+            validation_status::STATUS_FORMAT_MISMATCH
+        ));
+    }
+
+    #[test]
+    fn test_missing_hard_binding() {
+        // a manifest store signed without binding it to any asset
+        let mut store = Store::new();
+        store.commit_claim(create_test_claim().unwrap()).unwrap();
+        let signer = temp_signer();
+        let jumbf = store.to_jumbf(signer.as_ref()).unwrap();
+
+        let asset_bytes = include_bytes!("../tests/fixtures/no_manifest.jpg");
+        let mut report = DetailedStatusTracker::new();
+        let store = Store::from_jumbf(&jumbf, &mut report).unwrap();
+        Store::verify_store(
+            &store,
+            &mut ClaimAssetData::Bytes(asset_bytes, "image/jpeg"),
+            &mut report,
+        )
+        .unwrap();
+        assert!(report_has_status(
+            report.get_log(),
+            validation_status::HARD_BINDINGS_MISSING
+        ));
+
+        #[cfg(feature = "unstable_api")]
+        {
+            let reader = crate::Reader::from_manifest_data_and_stream(
+                &jumbf,
+                "image/jpeg",
+                Cursor::new(asset_bytes),
+            )
+            .unwrap();
+            assert!(!reader.is_trusted_and_bound());
+        }
+    }
+
+    #[test]
+    fn test_reordered_manifest_boxes() {
+        let asset_bytes = include_bytes!("../tests/fixtures/cloud.jpg");
+        let original = include_bytes!("../tests/fixtures/cloud_manifest.c2pa");
+        // the active manifest has its signature, claim and assertion boxes reordered
+        // and padding boxes ahead of the content boxes
+        let reordered = include_bytes!("../tests/fixtures/cloud_manifest_reordered.c2pa");
+
+        let mut report = DetailedStatusTracker::new();
+        let expected = Store::from_jumbf(original, &mut report).unwrap();
+        let store = Store::from_jumbf(reordered, &mut report).unwrap();
+        assert!(report_split_errors(report.get_log_mut()).is_empty());
+
+        assert_eq!(store.claims().len(), expected.claims().len());
+        for (claim, expected_claim) in store.claims().iter().zip(expected.claims()) {
+            assert_eq!(claim.label(), expected_claim.label());
+            assert_eq!(claim.signature_val(), expected_claim.signature_val());
+
+            let mut labels: Vec<String> = claim
+                .claim_assertion_store()
+                .iter()
+                .map(|ca| ca.label())
+                .collect();
+            let mut expected_labels: Vec<String> = expected_claim
+                .claim_assertion_store()
+                .iter()
+                .map(|ca| ca.label())
+                .collect();
+            labels.sort();
+            expected_labels.sort();
+            assert_eq!(labels, expected_labels);
+        }
+        assert_eq!(
+            store.provenance_label(),
+            expected.provenance_label(),
+            "active manifest must not change"
+        );
+
+        let mut expected_log = DetailedStatusTracker::new();
+        Store::verify_store(
+            &expected,
+            &mut ClaimAssetData::Bytes(asset_bytes, "image/jpeg"),
+            &mut expected_log,
+        )
+        .unwrap();
+        let mut validation_log = DetailedStatusTracker::new();
+        Store::verify_store(
+            &store,
+            &mut ClaimAssetData::Bytes(asset_bytes, "image/jpeg"),
+            &mut validation_log,
+        )
+        .unwrap();
+        assert_eq!(
+            report_split_errors(validation_log.get_log_mut()).len(),
+            report_split_errors(expected_log.get_log_mut()).len()
+        );
+    }
 }
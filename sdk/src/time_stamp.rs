@@ -33,13 +33,14 @@ use crate::{
     asn1::{
         rfc3161::{TimeStampResp, TstInfo, OID_CONTENT_TYPE_TST_INFO},
         rfc5652::{
-            CertificateChoices::Certificate, SignedData, OID_ID_SIGNED_DATA, OID_MESSAGE_DIGEST,
-            OID_SIGNING_TIME,
+            CertificateChoices::Certificate, CertificateSet, SignedData, SignerInfo,
+            OID_ID_SIGNED_DATA, OID_MESSAGE_DIGEST, OID_SIGNING_TIME,
         },
     },
     error::{Error, Result},
     hash_utils::vec_compare,
-    AsyncSigner, Signer,
+    trust_handler::TrustHandlerConfig,
+    validation_status, AsyncSigner, Signer,
 };
 
 // Generate TimeStamp signature according to https://datatracker.ietf.org/doc/html/rfc3161
@@ -146,7 +147,10 @@ fn time_stamp_request_http(
     let response = req
         .set("Content-Type", HTTP_CONTENT_TYPE_REQUEST)
         .send(body_reader)
-        .map_err(|_err| Error::CoseTimeStampGeneration)?;
+        .map_err(|err| match err {
+            ureq::Error::Transport(_) => Error::NetworkUnreachable(url.to_string()),
+            _ => Error::CoseTimeStampGeneration,
+        })?;
 
     if response.status() == 200 && response.content_type() == HTTP_CONTENT_TYPE_RESPONSE {
         let len = response
@@ -261,7 +265,7 @@ impl TimeStampResponse {
         }
     }
 
-    fn tst_info(&self) -> Result<Option<TstInfo>> {
+    pub(crate) fn tst_info(&self) -> Result<Option<TstInfo>> {
         if let Some(signed_data) = self.signed_data()? {
             if signed_data.content_info.content_type == OID_CONTENT_TYPE_TST_INFO {
                 if let Some(content) = signed_data.content_info.content {
@@ -446,6 +450,82 @@ fn get_validator_type(sig_alg: &bcder::Oid, hash_alg: &bcder::Oid) -> Option<Str
     }
 }
 
+// Returns the certificate identified by the signer info
+fn find_signer_cert<'a>(
+    certs: &'a CertificateSet,
+    signer_info: &SignerInfo,
+) -> Option<&'a x509_certificate::rfc5280::Certificate> {
+    certs.iter().find_map(|cc| {
+        let c = match cc {
+            Certificate(c) => c,
+            _ => return None,
+        };
+
+        match &signer_info.sid {
+            crate::asn1::rfc5652::SignerIdentifier::IssuerAndSerialNumber(sn) => {
+                if sn.issuer == c.tbs_certificate.issuer
+                    && sn.serial_number == c.tbs_certificate.serial_number
+                {
+                    Some(c.as_ref())
+                } else {
+                    None
+                }
+            }
+            crate::asn1::rfc5652::SignerIdentifier::SubjectKeyIdentifier(ski) => {
+                const SKI_OID: ConstOid = bcder::Oid(&[2, 5, 29, 14]);
+                if let Some(extensions) = &c.tbs_certificate.extensions {
+                    if extensions.iter().any(|e| {
+                        if e.id == SKI_OID {
+                            return *ski == e.value;
+                        }
+                        false
+                    }) {
+                        Some(c.as_ref())
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+        }
+    })
+}
+
+#[cfg(feature = "openssl")]
+/// Returns the DER encoded certificate of the authority that signed the time stamp, and the
+/// DER encoded certificates of the rest of its chain included in the time stamp.
+pub(crate) fn timestamp_signer_certs(ts: &[u8]) -> Result<(Vec<u8>, Vec<Vec<u8>>)> {
+    let ts_resp = get_timestamp_response(ts)?;
+    let sd = ts_resp.signed_data()?.ok_or(Error::CoseInvalidTimeStamp)?;
+    let certs = sd
+        .certificates
+        .as_ref()
+        .ok_or(Error::CoseTimeStampValidity)?;
+
+    let signer_cert = sd
+        .signer_infos
+        .iter()
+        .find_map(|signer_info| find_signer_cert(certs, signer_info))
+        .ok_or(Error::CoseInvalidTimeStamp)?;
+
+    let mut signer_der = Vec::new();
+    let mut chain_der = Vec::new();
+    for cc in certs.iter() {
+        if let Certificate(c) = cc {
+            let mut der = Vec::new();
+            c.encode_ref().write_encoded(bcder::Mode::Der, &mut der)?;
+            if std::ptr::eq(c.as_ref(), signer_cert) {
+                signer_der = der;
+            } else {
+                chain_der.push(der);
+            }
+        }
+    }
+
+    Ok((signer_der, chain_der))
+}
+
 // Returns TimeStamp token info if ts verifies against supplied data
 #[allow(unused_variables)]
 #[async_generic]
@@ -463,41 +543,7 @@ pub(crate) fn verify_timestamp(ts: &[u8], data: &[u8]) -> Result<TstInfo> {
         // look for any valid signer
         for signer_info in sd.signer_infos.iter() {
             // find signer's cert
-            let cert = match certs.iter().find_map(|cc| {
-                let c = match cc {
-                    Certificate(c) => c,
-                    _ => return None,
-                };
-
-                match &signer_info.sid {
-                    crate::asn1::rfc5652::SignerIdentifier::IssuerAndSerialNumber(sn) => {
-                        if sn.issuer == c.tbs_certificate.issuer
-                            && sn.serial_number == c.tbs_certificate.serial_number
-                        {
-                            Some(c)
-                        } else {
-                            None
-                        }
-                    }
-                    crate::asn1::rfc5652::SignerIdentifier::SubjectKeyIdentifier(ski) => {
-                        const SKI_OID: ConstOid = bcder::Oid(&[2, 5, 29, 14]);
-                        if let Some(extensions) = &c.tbs_certificate.extensions {
-                            if extensions.iter().any(|e| {
-                                if e.id == SKI_OID {
-                                    return *ski == e.value;
-                                }
-                                false
-                            }) {
-                                Some(c)
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    }
-                }
-            }) {
+            let cert = match find_signer_cert(&certs, signer_info) {
                 Some(c) => c,
                 None => continue,
             };
@@ -717,6 +763,59 @@ pub(crate) fn verify_timestamp(ts: &[u8], data: &[u8]) -> Result<TstInfo> {
     Err(last_err)
 }
 
+/// Verifies a time-stamp response over `data` and checks that its authority is trusted.
+///
+/// Returns the time asserted by the time-stamp, even when it can't be verified, and the
+/// validation status code for the time-stamp.
+pub(crate) fn validate_timestamp(
+    ts: &[u8],
+    data: &[u8],
+    th: &dyn TrustHandlerConfig,
+) -> (Option<chrono::DateTime<chrono::Utc>>, &'static str) {
+    let asserted_time = get_timestamp_response(ts)
+        .and_then(|ts_resp| ts_resp.tst_info())
+        .ok()
+        .flatten()
+        .map(|tst| gt_to_datetime(tst.gen_time));
+
+    let tst = match verify_timestamp(ts, data) {
+        Ok(tst) => tst,
+        Err(Error::CoseTimeStampMismatch) => {
+            return (asserted_time, validation_status::TIMESTAMP_MISMATCH)
+        }
+        Err(Error::CoseTimeStampValidity) => {
+            return (asserted_time, validation_status::TIMESTAMP_OUTSIDE_VALIDITY)
+        }
+        Err(_) => return (asserted_time, validation_status::GENERAL_ERROR),
+    };
+
+    let signing_time = gt_to_datetime(tst.gen_time);
+    let status = if timestamp_trusted(ts, th, signing_time.timestamp()) {
+        validation_status::TIMESTAMP_TRUSTED
+    } else {
+        validation_status::TIMESTAMP_UNTRUSTED
+    };
+    (Some(signing_time), status)
+}
+
+// is the authority that signed the time-stamp on the trust list
+#[allow(unused_variables)]
+fn timestamp_trusted(ts: &[u8], th: &dyn TrustHandlerConfig, signing_time: i64) -> bool {
+    #[cfg(feature = "openssl")]
+    {
+        timestamp_signer_certs(ts)
+            .and_then(|(signer_der, chain_der)| {
+                crate::openssl::verify_trust(th, &chain_der, &signer_der, Some(signing_time))
+            })
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(feature = "openssl"))]
+    {
+        false
+    }
+}
+
 // Get TimeStampResponse from DER TimeStampResp bytes
 pub(crate) fn get_timestamp_response(tsresp: &[u8]) -> Result<TimeStampResponse> {
     let ts = TimeStampResponse(
@@ -770,3 +869,51 @@ pub(crate) fn make_cose_timestamp(ts_data: &[u8]) -> TstContainer {
 
     container
 }
+
+#[cfg(all(test, feature = "openssl"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::{openssl::OpenSSLTrustHandlerConfig, utils::test::fixture_path};
+
+    // sig_es256.tsr is a time-stamp over sig_es256.data by the authority in tsa_root.pem
+    fn timestamp_fixture() -> (Vec<u8>, Vec<u8>) {
+        (
+            std::fs::read(fixture_path("sig_es256.tsr")).unwrap(),
+            std::fs::read(fixture_path("sig_es256.data")).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_validate_timestamp_trust() {
+        let (ts, data) = timestamp_fixture();
+
+        let mut th = OpenSSLTrustHandlerConfig::new();
+        let (time, status) = validate_timestamp(&ts, &data, &th);
+        assert_eq!(status, validation_status::TIMESTAMP_UNTRUSTED);
+        assert!(time.is_some());
+
+        let tsa_root = std::fs::read(fixture_path("certs/tsa_root.pem")).unwrap();
+        th.append_private_trust_data(&mut std::io::Cursor::new(tsa_root))
+            .unwrap();
+        let (trusted_time, status) = validate_timestamp(&ts, &data, &th);
+        assert_eq!(status, validation_status::TIMESTAMP_TRUSTED);
+        assert_eq!(trusted_time, time);
+    }
+
+    #[test]
+    fn test_validate_timestamp_mismatch() {
+        let (ts, _) = timestamp_fixture();
+        let th = OpenSSLTrustHandlerConfig::new();
+
+        // the asserted time is reported even though it doesn't cover the data
+        let (time, status) = validate_timestamp(&ts, b"other data", &th);
+        assert_eq!(status, validation_status::TIMESTAMP_MISMATCH);
+        assert!(time.is_some());
+
+        let (time, status) = validate_timestamp(b"not a time-stamp", b"other data", &th);
+        assert_eq!(status, validation_status::GENERAL_ERROR);
+        assert!(time.is_none());
+    }
+}
@@ -27,6 +27,10 @@ pub(crate) static TIMESTAMPING_OID: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .3 .8)
 pub(crate) static OCSP_SIGNING_OID: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .3 .9);
 pub(crate) static DOCUMENT_SIGNING_OID: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .3 .36);
 
+// The C2PA test/development trust anchors, trusted only with the development trust preset
+pub(crate) static DEVELOPMENT_TRUST_ANCHORS: &[u8] =
+    include_bytes!("./development_trust_anchors.pem");
+
 // Trait for supply configuration and handling of trust lists and EKU configuration store
 //
 // `RefUnwindSafe` + `UnwindSafe` were added to ensure `Store` is unwind safe and to preserve
@@ -45,6 +49,9 @@ pub(crate) trait TrustHandlerConfig: RefUnwindSafe + UnwindSafe + Sync + Send {
     // append private trust anchors
     fn append_private_trust_data(&mut self, private_anchors_data: &mut dyn Read) -> Result<()>;
 
+    // append intermediate certificates, used to build chains but not trusted
+    fn append_intermediates(&mut self, intermediates_data: &mut dyn Read) -> Result<()>;
+
     // clear all entries in trust handler list
     fn clear(&mut self);
 
@@ -61,6 +68,9 @@ pub(crate) trait TrustHandlerConfig: RefUnwindSafe + UnwindSafe + Sync + Send {
     // set of allowed cert hashes
     #[allow(dead_code)] // Only used in calls with allow dead_code
     fn get_allowed_list(&self) -> &HashSet<String>;
+
+    // list of intermediate certificates
+    fn get_intermediates(&self) -> Vec<Vec<u8>>;
 }
 
 impl std::fmt::Debug for dyn TrustHandlerConfig {
@@ -124,6 +134,40 @@ pub(crate) fn load_trust_from_data(trust_data: &[u8]) -> Result<Vec<Vec<u8>>> {
     Ok(certs)
 }
 
+// Extends a certificate chain with the intermediates issuing its last certificate, in order,
+// until the chain ends with a self-signed certificate or no issuer can be found.
+pub(crate) fn complete_chain(
+    chain_der: &[Vec<u8>],
+    cert_der: &[u8],
+    intermediates: &[Vec<u8>],
+) -> Vec<Vec<u8>> {
+    use x509_parser::prelude::*;
+
+    let mut chain = chain_der.to_vec();
+    loop {
+        let issuer = {
+            let last = chain.last().map(Vec::as_slice).unwrap_or(cert_der);
+            let Ok((_, last)) = X509Certificate::from_der(last) else {
+                break;
+            };
+            if last.subject() == last.issuer() {
+                break;
+            }
+            intermediates.iter().find(|der| {
+                !chain.contains(*der)
+                    && X509Certificate::from_der(der)
+                        .is_ok_and(|(_, cert)| cert.subject() == last.issuer())
+            })
+        };
+
+        match issuer {
+            Some(der) => chain.push(der.clone()),
+            None => break,
+        }
+    }
+    chain
+}
+
 // Pass through trust for the case of claim signer usage since it has known trust with context
 // configured to all email protection, timestamping, ocsp signing and document signing
 #[derive(Debug)]
@@ -154,6 +198,10 @@ impl TrustHandlerConfig for TrustPassThrough {
         Ok(())
     }
 
+    fn append_intermediates(&mut self, _intermediates_data: &mut dyn std::io::Read) -> Result<()> {
+        Ok(())
+    }
+
     fn clear(&mut self) {}
 
     fn load_configuration(&mut self, config_data: &mut dyn Read) -> Result<()> {
@@ -206,4 +254,8 @@ impl TrustHandlerConfig for TrustPassThrough {
     fn get_allowed_list(&self) -> &std::collections::HashSet<String> {
         &self.allowed_cert_set
     }
+
+    fn get_intermediates(&self) -> Vec<Vec<u8>> {
+        Vec::new()
+    }
 }
@@ -143,6 +143,81 @@ impl fmt::Display for BytesT {
     }
 }
 
+// Nesting depth beyond which CBOR is treated as malformed, to bound recursion.
+const MAX_CBOR_DEPTH: usize = 128;
+
+/// Returns `true` if the CBOR data item at the start of `data` contains a semantic tag.
+///
+/// Any tag is accepted, including ones this crate doesn't know. Tags have no JSON form, so a
+/// tagged item converted to JSON can't be encoded back to the same bytes. Malformed CBOR is
+/// reported as having no tags, leaving it to the decoder to report.
+pub(crate) fn cbor_has_tags(data: &[u8]) -> bool {
+    let mut pos = 0;
+    matches!(scan_cbor_item(data, &mut pos, 0), Some(true))
+}
+
+// Skips over the CBOR data item at `pos`, returning whether it contains a tag, or `None` if
+// it is malformed.
+fn scan_cbor_item(data: &[u8], pos: &mut usize, depth: usize) -> Option<bool> {
+    if depth > MAX_CBOR_DEPTH {
+        return None;
+    }
+
+    let initial = *data.get(*pos)?;
+    *pos += 1;
+
+    // the argument, or None for an indefinite length
+    let argument = match initial & 0x1f {
+        info @ 0..=23 => Some(u64::from(info)),
+        info @ 24..=27 => {
+            let len = 1 << (info - 24);
+            let bytes = data.get(*pos..pos.checked_add(len)?)?;
+            *pos += len;
+            Some(bytes.iter().fold(0, |acc, b| (acc << 8) | u64::from(*b)))
+        }
+        31 => None,
+        _ => return None,
+    };
+
+    let mut tagged = false;
+    match (initial >> 5, argument) {
+        // integers and simple values
+        (0 | 1 | 7, Some(_)) => {}
+        // byte and text strings
+        (2 | 3, Some(len)) => {
+            *pos = pos.checked_add(usize::try_from(len).ok()?)?;
+            if *pos > data.len() {
+                return None;
+            }
+        }
+        // arrays and maps
+        (4 | 5, Some(count)) => {
+            let items = if initial >> 5 == 5 {
+                count.checked_mul(2)?
+            } else {
+                count
+            };
+            for _ in 0..items {
+                tagged |= scan_cbor_item(data, pos, depth + 1)?;
+            }
+        }
+        // indefinite length strings, arrays and maps, ended by a break
+        (2..=5, None) => {
+            while *data.get(*pos)? != 0xff {
+                tagged |= scan_cbor_item(data, pos, depth + 1)?;
+            }
+            *pos += 1;
+        }
+        (6, Some(_)) => {
+            scan_cbor_item(data, pos, depth + 1)?;
+            tagged = true;
+        }
+        _ => return None,
+    }
+
+    Some(tagged)
+}
+
 #[cfg(test)]
 pub mod tests {
     #![allow(clippy::expect_used)]
@@ -160,4 +235,24 @@ pub mod tests {
 
         assert_eq!(uri.as_ref(), uri_restored.as_ref());
     }
+
+    #[test]
+    fn test_cbor_has_tags() {
+        let untagged =
+            serde_cbor::to_vec(&serde_json::json!({"a": [1, "b", {"c": null}]})).unwrap();
+        assert!(!cbor_has_tags(&untagged));
+
+        // {"when": 1(1700000000)}, an epoch date-time
+        assert!(cbor_has_tags(&[
+            0xa1, 0x64, b'w', b'h', b'e', b'n', 0xc1, 0x1a, 0x65, 0x53, 0xf1, 0x00
+        ]));
+        // [1, 40000("x")] in an indefinite length array, with a tag no registry knows
+        assert!(cbor_has_tags(&[
+            0x9f, 0x01, 0xd9, 0x9c, 0x40, 0x61, b'x', 0xff
+        ]));
+
+        // truncated
+        assert!(!cbor_has_tags(&[0x82, 0x01, 0xc1]));
+        assert!(!cbor_has_tags(&[0x5a, 0xff, 0xff, 0xff, 0xff]));
+    }
 }
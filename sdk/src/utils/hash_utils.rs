@@ -13,7 +13,7 @@
 
 use std::{
     fs::File,
-    io::{Cursor, Read, Seek, SeekFrom},
+    io::{Read, Seek, SeekFrom},
     ops::RangeInclusive,
     path::Path,
 };
@@ -104,6 +104,19 @@ pub fn hash_by_type(hash_type: u8, data: &[u8]) -> Option<Multihash> {
     }
 }
 
+// Returns the digest size in bits of a hash algorithm, to compare the strength of
+// algorithms, or None if the algorithm is unknown.
+pub(crate) fn hash_alg_strength(alg: &str) -> Option<usize> {
+    match alg {
+        "sha1" => Some(160),
+        "sha224" => Some(224),
+        "sha256" => Some(256),
+        "sha384" => Some(384),
+        "sha512" => Some(512),
+        _ => None,
+    }
+}
+
 #[derive(Clone)]
 pub enum Hasher {
     SHA256(Sha256),
@@ -137,16 +150,12 @@ impl Hasher {
 
 // Return hash bytes for desired hashing algorithm.
 pub fn hash_by_alg(alg: &str, data: &[u8], exclusions: Option<Vec<HashRange>>) -> Vec<u8> {
-    let mut reader = Cursor::new(data);
-
-    hash_stream_by_alg(alg, &mut reader, exclusions, true).unwrap_or_default()
+    hash_bytes_by_alg(alg, data, exclusions, true).unwrap_or_default()
 }
 
 // Return hash inclusive bytes for desired hashing algorithm.
 pub fn hash_by_alg_with_inclusions(alg: &str, data: &[u8], inclusions: Vec<HashRange>) -> Vec<u8> {
-    let mut reader = Cursor::new(data);
-
-    hash_stream_by_alg(alg, &mut reader, Some(inclusions), false).unwrap_or_default()
+    hash_bytes_by_alg(alg, data, Some(inclusions), false).unwrap_or_default()
 }
 
 // Return hash bytes for asset using desired hashing algorithm.
@@ -169,71 +178,29 @@ pub fn hash_asset_by_alg_with_inclusions(
     hash_stream_by_alg(alg, &mut file, Some(inclusions), false)
 }
 
-/*  Returns hash bytes for a stream using desired hashing algorithm.  The function handles the many
-    possible hash requirements of C2PA.  The function accepts a source stream 'data', an optional
-    set of hash ranges 'hash_range' and a boolean to indicate whether the hash range is an exclusion
-    or inclusion set of hash ranges.
-
-    The basic case is to hash a stream without hash ranges:
-    The data represents a single contiguous stream of bytes to be hash where D are data bytes
-
-    to_be_hashed: [DDDDDDDDD...DDDDDDDDDD]
-
-    The data is then chunked and hashed in groups to reduce memory
-    footprint and increase performance.
-
-    The most common case for C2PA is the use of an exclusion hash.  In this case the 'hash_range' indicate
-    which byte ranges should be excluded shown here depicted with I for included bytes and  X for excluded bytes
-
-    to_be_hashed: [IIIIXXXIIIIXXXXXIIIXXIII...IIII]
-
-    In this case the data is split into a set of ranges covering the included bytes.  The set of ranged bytes
-    are then chunked and hashed just like the default case.
-
-    The opposite of this is when 'is_exclusion' is set to false indicating the 'hash_ranges' represent the bytes
-    to include in the hash. Here are the bytes in 'data' are excluded except those explicitly referenced.
-
-    to_be_hashed: [XXXXXXIIIIXXXXXIIXXXX...XXXX]
-
-    Again a set of ranged bytes are created and hashed as described above.
-
-    The last case is a special requirement for BMFF based assets (exclusion hashes only).  For this case we not
-    only hash the data but also the location where the data was found in the asset.  To do this we add a special
-    HashRange object to the hash ranges to indicate which locations in the stream require this special offset
-    hash.  To make processing efficient we again split the data into ranges at not just the exclusion
-    points but also for these markers.  The hashing loop knows to pause at these special marker ranges to insert
-    the hash of the offset.  The stream sent to the hashing loop logically looks like this where M is the marker.
-    to_be_hashed: [IIIIIXXXXXMIIIIIMXXXXXMXXXXIII...III]
-
-    The data is again split into range sets breaking at the exclusion points and now also the markers.
-*/
-pub fn hash_stream_by_alg<R>(
-    alg: &str,
-    data: &mut R,
-    hash_range: Option<Vec<HashRange>>,
-    is_exclusion: bool,
-) -> Result<Vec<u8>>
-where
-    R: Read + Seek + ?Sized,
-{
-    let mut bmff_v2_starts: Vec<u64> = Vec::new();
-
+// Returns the hasher for the desired hashing algorithm.
+fn hasher_for_alg(alg: &str) -> Result<Hasher> {
     use Hasher::*;
-    let mut hasher_enum = match alg {
-        "sha256" => SHA256(Sha256::new()),
-        "sha384" => SHA384(Sha384::new()),
-        "sha512" => SHA512(Sha512::new()),
+    match alg {
+        "sha256" => Ok(SHA256(Sha256::new())),
+        "sha384" => Ok(SHA384(Sha384::new())),
+        "sha512" => Ok(SHA512(Sha512::new())),
         _ => {
-            warn!(
-                "Unsupported hashing algorithm: {}, substituting sha256",
-                alg
-            );
-            SHA256(Sha256::new())
+            // never substitute another algorithm, the result could not match the expected hash
+            warn!("Unsupported hashing algorithm: {}", alg);
+            Err(Error::UnsupportedType)
         }
-    };
+    }
+}
 
-    let data_len = stream_len(data)?;
-    data.rewind()?;
+// Returns the sorted byte ranges to hash for data of 'data_len' bytes along with the
+// BMFF V2 offset markers found in 'hash_range'.  See hash_stream_by_alg for details.
+fn ranges_to_hash(
+    data_len: u64,
+    hash_range: Option<Vec<HashRange>>,
+    is_exclusion: bool,
+) -> Result<(Vec<RangeInclusive<u64>>, Vec<u64>)> {
+    let mut bmff_v2_starts: Vec<u64> = Vec::new();
 
     if data_len < 1 {
         return Err(Error::OtherError("no data to hash".into()));
@@ -332,6 +299,96 @@ where
         }
     };
 
+    Ok((ranges, bmff_v2_starts))
+}
+
+// Returns hash bytes for in-memory data using desired hashing algorithm.  The byte ranges
+// are the same as hash_stream_by_alg but are hashed directly from 'data' without copying.
+pub fn hash_bytes_by_alg(
+    alg: &str,
+    data: &[u8],
+    hash_range: Option<Vec<HashRange>>,
+    is_exclusion: bool,
+) -> Result<Vec<u8>> {
+    let mut hasher_enum = hasher_for_alg(alg)?;
+
+    let (ranges, bmff_v2_starts) = ranges_to_hash(data.len() as u64, hash_range, is_exclusion)?;
+
+    for r in ranges {
+        let start = *r.start();
+        let end = *r.end();
+
+        // check to see if this range is an BMFF V2 offset to include in the hash
+        if bmff_v2_starts.contains(&start) && (end - start) == 0 {
+            hasher_enum.update(&start.to_be_bytes());
+        }
+
+        let slice = usize::try_from(start)
+            .ok()
+            .zip(usize::try_from(end).ok())
+            .and_then(|(start, end)| data.get(start..=end))
+            .ok_or_else(|| Error::BadParam("The hash range exceeds the data length".to_string()))?;
+        hasher_enum.update(slice);
+    }
+
+    // return the hash
+    Ok(Hasher::finalize(hasher_enum))
+}
+
+/*  Returns hash bytes for a stream using desired hashing algorithm.  The function handles the many
+    possible hash requirements of C2PA.  The function accepts a source stream 'data', an optional
+    set of hash ranges 'hash_range' and a boolean to indicate whether the hash range is an exclusion
+    or inclusion set of hash ranges.
+
+    The basic case is to hash a stream without hash ranges:
+    The data represents a single contiguous stream of bytes to be hash where D are data bytes
+
+    to_be_hashed: [DDDDDDDDD...DDDDDDDDDD]
+
+    The data is then chunked and hashed in groups to reduce memory
+    footprint and increase performance.
+
+    The most common case for C2PA is the use of an exclusion hash.  In this case the 'hash_range' indicate
+    which byte ranges should be excluded shown here depicted with I for included bytes and  X for excluded bytes
+
+    to_be_hashed: [IIIIXXXIIIIXXXXXIIIXXIII...IIII]
+
+    In this case the data is split into a set of ranges covering the included bytes.  The set of ranged bytes
+    are then chunked and hashed just like the default case.
+
+    The opposite of this is when 'is_exclusion' is set to false indicating the 'hash_ranges' represent the bytes
+    to include in the hash. Here are the bytes in 'data' are excluded except those explicitly referenced.
+
+    to_be_hashed: [XXXXXXIIIIXXXXXIIXXXX...XXXX]
+
+    Again a set of ranged bytes are created and hashed as described above.
+
+    The last case is a special requirement for BMFF based assets (exclusion hashes only).  For this case we not
+    only hash the data but also the location where the data was found in the asset.  To do this we add a special
+    HashRange object to the hash ranges to indicate which locations in the stream require this special offset
+    hash.  To make processing efficient we again split the data into ranges at not just the exclusion
+    points but also for these markers.  The hashing loop knows to pause at these special marker ranges to insert
+    the hash of the offset.  The stream sent to the hashing loop logically looks like this where M is the marker.
+    to_be_hashed: [IIIIIXXXXXMIIIIIMXXXXXMXXXXIII...III]
+
+    The data is again split into range sets breaking at the exclusion points and now also the markers.
+*/
+pub fn hash_stream_by_alg<R>(
+    alg: &str,
+    data: &mut R,
+    hash_range: Option<Vec<HashRange>>,
+    is_exclusion: bool,
+) -> Result<Vec<u8>>
+where
+    R: Read + Seek + ?Sized,
+{
+    let mut hasher_enum = hasher_for_alg(alg)?;
+
+    let data_len = stream_len(data)?;
+    data.rewind()?;
+
+    let (ranges, bmff_v2_starts) = ranges_to_hash(data_len, hash_range, is_exclusion)?;
+
     if cfg!(feature = "no_interleaved_io") || cfg!(target_arch = "wasm32") {
         // hash the data for ranges
         for r in ranges {
@@ -0,0 +1,102 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Reads the dimensions of an image from its header without decoding it.
+
+/// Returns the width and height in pixels of JPEG, PNG or GIF image data.
+///
+/// Returns `None` if the data is not one of these formats or its header is malformed.
+pub(crate) fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    match data {
+        [0xff, 0xd8, ..] => jpeg_dimensions(data),
+        [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, _, _, _, _, b'I', b'H', b'D', b'R', w0, w1, w2, w3, h0, h1, h2, h3, ..] => {
+            let width = u32::from_be_bytes([*w0, *w1, *w2, *w3]);
+            let height = u32::from_be_bytes([*h0, *h1, *h2, *h3]);
+            Some((width, height))
+        }
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', w0, w1, h0, h1, ..] => {
+            let width = u16::from_le_bytes([*w0, *w1]);
+            let height = u16::from_le_bytes([*h0, *h1]);
+            Some((width.into(), height.into()))
+        }
+        _ => None,
+    }
+    .filter(|(width, height)| *width > 0 && *height > 0)
+}
+
+// walks the JPEG segments up to the first start of frame segment, which holds the dimensions
+fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let mut pos = 2;
+    loop {
+        if *data.get(pos)? != 0xff {
+            return None;
+        }
+        // markers may be preceded by any number of fill bytes
+        while *data.get(pos)? == 0xff {
+            pos += 1;
+        }
+        let marker = data[pos];
+        pos += 1;
+
+        match marker {
+            // standalone markers have no segment
+            0x01 | 0xd0..=0xd7 => continue,
+            // end of image or start of scan before any frame
+            0xd9 | 0xda => return None,
+            _ => (),
+        }
+
+        let len = u16::from_be_bytes([*data.get(pos)?, *data.get(pos + 1)?]) as usize;
+        let is_start_of_frame =
+            matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc);
+        if is_start_of_frame {
+            // length, precision, height, width
+            let segment = data.get(pos..pos + 7)?;
+            let height = u16::from_be_bytes([segment[3], segment[4]]);
+            let width = u16::from_be_bytes([segment[5], segment[6]]);
+            return Some((width.into(), height.into()));
+        }
+        pos += len;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::utils::test::fixture_path;
+
+    #[test]
+    fn test_image_dimensions() {
+        let jpeg = std::fs::read(fixture_path("CA.jpg")).unwrap();
+        assert_eq!(image_dimensions(&jpeg), Some((1024, 683)));
+
+        // a 3x2 PNG header
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0, 0, 0, 13];
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(image_dimensions(&png), Some((3, 2)));
+
+        assert_eq!(image_dimensions(b"GIF89a\x05\x00\x04\x00"), Some((5, 4)));
+    }
+
+    #[test]
+    fn test_image_dimensions_corrupt() {
+        let jpeg = std::fs::read(fixture_path("CA.jpg")).unwrap();
+        assert_eq!(image_dimensions(&jpeg[..100]), None);
+        assert_eq!(image_dimensions(&[0xff, 0xd8, 0xff, 0xe0, 0x00]), None);
+        assert_eq!(image_dimensions(b"not an image"), None);
+        assert_eq!(image_dimensions(b"GIF89a\x00\x00\x00\x00"), None);
+    }
+}
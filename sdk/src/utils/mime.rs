@@ -57,6 +57,36 @@ pub fn format_to_mime(format: &str) -> String {
     .to_string()
 }
 
+/// Converts a format to a canonical MIME type, so that aliases of the same format compare equal.
+///
+/// Case and MIME type parameters are ignored.
+pub fn canonical_mime(format: &str) -> String {
+    let format = format.split(';').next().unwrap_or_default().trim();
+    let mime = format_to_mime(&format.to_ascii_lowercase());
+    match mime.as_str() {
+        "image/jpg" | "image/pjpeg" => "image/jpeg",
+        "image/dng" => "image/x-adobe-dng",
+        "audio/wave" | "audio/x-wav" | "audio/vnd.wave" => "audio/wav",
+        "video/x-msvideo" | "video/msvideo" | "application/x-troff-msvideo" => "video/avi",
+        "application/x-c2pa-manifest-store" => "application/c2pa",
+        _ => return mime,
+    }
+    .to_string()
+}
+
+/// Detects the MIME type of image data by examining its leading magic bytes.
+///
+/// Returns `None` if the data is not a recognized image format.
+pub fn image_mime_from_bytes(data: &[u8]) -> Option<&'static str> {
+    Some(match data {
+        [0xff, 0xd8, 0xff, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, ..] => "image/png",
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ => return None,
+    })
+}
+
 /// Converts a format to a file extension
 #[cfg(feature = "file_io")]
 pub fn format_to_extension(format: &str) -> Option<&'static str> {
@@ -0,0 +1,160 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use crate::Result;
+
+/// Reads an asset file through a read-only memory map.
+///
+/// Pass it to `Reader::from_mmap` to hash the asset directly over the mapped bytes.
+///
+/// `MmapReader` also implements `Read + Seek`, so it can be passed to any function that reads
+/// an asset from a stream, such as `Reader::from_stream`. Those functions copy the bytes they
+/// read into buffers just as they would from a [`File`].
+///
+/// Mapping a file larger than the address space fails, so on 32-bit targets files over a few
+/// gigabytes still have to be read with a [`File`].
+///
+/// # Safety
+///
+/// A memory map reflects the file on disk. If another process writes to the file while it is
+/// mapped, the bytes read may change between reads, and hashes computed over the file won't
+/// match its contents at any single point in time. If the file is truncated, reading past its
+/// new end raises `SIGBUS` on Unix. Only map files that no other process modifies, such as
+/// files the caller created or controls.
+#[derive(Debug)]
+pub struct MmapReader {
+    mmap: Mmap,
+    pos: u64,
+}
+
+impl MmapReader {
+    /// Maps the file at `path` for reading.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the reader exists. See [`MmapReader`].
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        Self::from_file(&file)
+    }
+
+    /// Maps `file` for reading. The file may be closed once this returns.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the reader exists. See [`MmapReader`].
+    pub unsafe fn from_file(file: &File) -> Result<Self> {
+        let mmap = Mmap::map(file)?;
+        Ok(Self { mmap, pos: 0 })
+    }
+
+    /// Returns the mapped bytes of the file.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    // the unread bytes, or none if the position is past the end
+    fn remaining(&self) -> &[u8] {
+        let start = usize::try_from(self.pos)
+            .unwrap_or(usize::MAX)
+            .min(self.mmap.len());
+        &self.mmap[start..]
+    }
+}
+
+impl Read for MmapReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.remaining().read(buf)?;
+        self.pos += len as u64;
+        Ok(len)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let remaining = self.remaining();
+        let len = remaining.len();
+        buf.extend_from_slice(remaining);
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+impl Seek for MmapReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => {
+                self.pos = offset;
+                return Ok(offset);
+            }
+            SeekFrom::End(offset) => (self.mmap.len() as u64, offset),
+            SeekFrom::Current(offset) => (self.pos, offset),
+        };
+
+        match base.checked_add_signed(offset) {
+            Some(pos) => {
+                self.pos = pos;
+                Ok(pos)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::utils::test::fixture_path;
+
+    #[test]
+    fn test_mmap_reader_read_and_seek() {
+        let path = fixture_path("CA.jpg");
+        let expected = std::fs::read(&path).unwrap();
+        let mut reader = unsafe { MmapReader::open(&path) }.unwrap();
+        assert_eq!(reader.as_bytes(), expected.as_slice());
+
+        let mut buf = [0u8; 4];
+        reader.seek(SeekFrom::Start(2)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, expected[2..6]);
+
+        assert_eq!(
+            reader.seek(SeekFrom::Current(-4)).unwrap(),
+            2,
+            "seeks relative to the position"
+        );
+        assert_eq!(
+            reader.seek(SeekFrom::End(-4)).unwrap(),
+            expected.len() as u64 - 4
+        );
+        let mut rest = Vec::new();
+        assert_eq!(reader.read_to_end(&mut rest).unwrap(), 4);
+        assert_eq!(rest, expected[expected.len() - 4..]);
+
+        // reading past the end returns nothing, and seeking before the start fails
+        reader.seek(SeekFrom::End(10)).unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+        assert!(reader.seek(SeekFrom::Current(-100_000_000)).is_err());
+    }
+}
@@ -15,12 +15,16 @@ pub(crate) mod base64;
 pub(crate) mod cbor_types;
 #[allow(dead_code)]
 pub(crate) mod hash_utils;
+pub(crate) mod image_size;
 pub(crate) mod io_utils;
 pub(crate) mod merkle;
 pub(crate) mod mime;
+#[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
+pub(crate) mod mmap_reader;
 #[allow(dead_code)] // for wasm build
 pub(crate) mod patch;
 pub(crate) mod sig_utils;
+pub(crate) mod temp_files;
 #[cfg(feature = "add_thumbnails")]
 pub(crate) mod thumbnail;
 pub(crate) mod time_it;
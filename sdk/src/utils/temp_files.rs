@@ -0,0 +1,123 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Temporary files and directories used by operations on files.
+//!
+//! Temporary files are created in the directory given by the `core.temp_dir` setting, or the
+//! system's temporary directory if it isn't set. When the `core.in_memory` setting is `true`
+//! no temporary files are created at all: callers keep intermediate data in memory instead, and
+//! [temp_file] and [temp_dir] return an error.
+
+#[cfg(test)]
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use tempfile::{Builder, NamedTempFile, TempDir};
+
+use crate::{settings::get_settings_value, Error, Result};
+
+const TEMP_PREFIX: &str = "c2pa_temp";
+
+/// Creates temporary files and directories.
+#[cfg_attr(test, mockall::automock)]
+pub(crate) trait TempFs {
+    /// Creates a temporary file in `dir`.
+    fn temp_file(&self, dir: &Path) -> std::io::Result<NamedTempFile>;
+
+    /// Creates a temporary directory in `dir`.
+    fn temp_dir(&self, dir: &Path) -> std::io::Result<TempDir>;
+}
+
+/// Creates temporary files and directories on the file system.
+struct SystemTempFs;
+
+impl TempFs for SystemTempFs {
+    fn temp_file(&self, dir: &Path) -> std::io::Result<NamedTempFile> {
+        Builder::new()
+            .prefix(TEMP_PREFIX)
+            .rand_bytes(5)
+            .tempfile_in(dir)
+    }
+
+    fn temp_dir(&self, dir: &Path) -> std::io::Result<TempDir> {
+        Builder::new()
+            .prefix(TEMP_PREFIX)
+            .rand_bytes(5)
+            .tempdir_in(dir)
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_TEMP_FS: RefCell<Option<Box<dyn TempFs>>> = RefCell::new(None);
+}
+
+/// Runs `f` with temporary files and directories created on this thread by `temp_fs`.
+#[cfg(test)]
+pub(crate) fn with_temp_fs<R>(temp_fs: impl TempFs + 'static, f: impl FnOnce() -> R) -> R {
+    TEST_TEMP_FS.with(|fs| *fs.borrow_mut() = Some(Box::new(temp_fs)));
+    let result = f();
+    TEST_TEMP_FS.with(|fs| fs.borrow_mut().take());
+    result
+}
+
+fn with_fs<R>(f: impl FnOnce(&dyn TempFs) -> R) -> R {
+    #[cfg(test)]
+    {
+        TEST_TEMP_FS.with(|fs| match fs.borrow().as_deref() {
+            Some(fs) => f(fs),
+            None => f(&SystemTempFs),
+        })
+    }
+
+    #[cfg(not(test))]
+    f(&SystemTempFs)
+}
+
+/// Returns `true` if intermediate data must be kept in memory rather than in temporary files.
+pub(crate) fn in_memory() -> bool {
+    get_settings_value::<bool>("core.in_memory").unwrap_or(false)
+}
+
+/// Returns the directory temporary files are created in.
+pub(crate) fn temp_dir_path() -> PathBuf {
+    get_settings_value::<Option<String>>("core.temp_dir")
+        .ok()
+        .flatten()
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+fn check_temp_files_allowed() -> Result<()> {
+    if in_memory() {
+        return Err(Error::BadParam(
+            "temporary files are disabled by the core.in_memory setting".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Creates a temporary file in the configured temporary directory.
+pub(crate) fn temp_file() -> Result<NamedTempFile> {
+    check_temp_files_allowed()?;
+    let dir = temp_dir_path();
+    with_fs(|fs| fs.temp_file(&dir)).map_err(Error::IoError)
+}
+
+/// Creates a temporary directory in the configured temporary directory. The directory and its
+/// contents are deleted when it's dropped.
+pub(crate) fn temp_dir() -> Result<TempDir> {
+    check_temp_files_allowed()?;
+    let dir = temp_dir_path();
+    with_fs(|fs| fs.temp_dir(&dir)).map_err(Error::IoError)
+}
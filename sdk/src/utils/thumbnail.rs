@@ -87,3 +87,20 @@ pub fn make_thumbnail_from_stream<R: Read + Seek + ?Sized>(
     let format = format.to_owned();
     Ok((format, cursor.into_inner()))
 }
+
+/// Decodes image data to check that it's a valid image of the given format.
+///
+/// Formats that cannot be decoded by this build are not checked.
+pub fn validate_image(format: &str, data: &[u8]) -> Result<()> {
+    let Some(format) =
+        ImageFormat::from_extension(format).or_else(|| ImageFormat::from_mime_type(format))
+    else {
+        return Ok(());
+    };
+    if !format.reading_enabled() {
+        return Ok(());
+    }
+
+    image::load_from_memory_with_format(data, format)?;
+    Ok(())
+}
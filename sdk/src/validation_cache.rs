@@ -0,0 +1,269 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Caches the results of validating assets, so that reading the same bytes again
+//! doesn't repeat the validation work.
+//!
+//! See [`Reader::from_stream_with_cache`](crate::Reader::from_stream_with_cache).
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::validation_status::ValidationStatus;
+
+/// Stores validation results keyed by the SHA-256 digest of the validated asset's content.
+///
+/// Validation results depend on the trust settings in effect when the asset was validated,
+/// so a cache should be cleared when those settings change.
+pub trait ValidationCache: Send + Sync {
+    /// Returns the validation status stored for the asset with the given digest, if any.
+    ///
+    /// Besides the failures of [`Reader::validation_status`](crate::Reader::validation_status),
+    /// this holds the success codes [`Reader::is_trusted_and_bound`](crate::Reader::is_trusted_and_bound)
+    /// relies on.
+    fn get(&self, digest: &[u8]) -> Option<Vec<ValidationStatus>>;
+
+    /// Stores the validation status, including success codes, for the asset with the given
+    /// digest.
+    fn insert(&self, digest: &[u8], validation_status: Vec<ValidationStatus>);
+}
+
+/// Describes an entry of a [`MemoryValidationCache`] to an [`EvictionPolicy`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct CacheEntryInfo {
+    /// The digest of the asset the entry is for.
+    pub digest: Vec<u8>,
+    /// When the entry was last read or written, as a count of cache accesses.
+    pub last_used: u64,
+    /// The number of times the entry was read.
+    pub hits: u64,
+}
+
+/// Chooses the entry a [`MemoryValidationCache`] evicts to make room for a new one.
+pub trait EvictionPolicy: Send + Sync {
+    /// Returns the index in `entries` of the entry to evict, or `None` to keep the new entry
+    /// out of the cache instead.
+    fn select_eviction(&self, entries: &[CacheEntryInfo]) -> Option<usize>;
+}
+
+/// An [`EvictionPolicy`] that evicts the least recently used entry.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LeastRecentlyUsed;
+
+impl EvictionPolicy for LeastRecentlyUsed {
+    fn select_eviction(&self, entries: &[CacheEntryInfo]) -> Option<usize> {
+        entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(index, _)| index)
+    }
+}
+
+struct CacheEntry {
+    info: CacheEntryInfo,
+    validation_status: Vec<ValidationStatus>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<Vec<u8>, CacheEntry>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// A [`ValidationCache`] that keeps up to `capacity` entries in memory.
+///
+/// When the cache is full, the [`EvictionPolicy`] chooses the entry to evict. The default
+/// policy is [`LeastRecentlyUsed`].
+pub struct MemoryValidationCache {
+    capacity: usize,
+    policy: Box<dyn EvictionPolicy>,
+    state: Mutex<CacheState>,
+}
+
+impl MemoryValidationCache {
+    /// Creates an empty cache holding up to `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            policy: Box::new(LeastRecentlyUsed),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Sets the policy choosing the entry to evict when the cache is full.
+    pub fn with_eviction_policy<P: EvictionPolicy + 'static>(mut self, policy: P) -> Self {
+        self.policy = Box::new(policy);
+        self
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    /// Returns `true` if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.state().entries.is_empty()
+    }
+
+    /// Returns the number of lookups that found an entry.
+    pub fn hits(&self) -> u64 {
+        self.state().hits
+    }
+
+    /// Returns the number of lookups that didn't find an entry.
+    pub fn misses(&self) -> u64 {
+        self.state().misses
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&self) {
+        self.state().entries.clear();
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        // the state is consistent between statements, so a panic elsewhere can't corrupt it
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ValidationCache for MemoryValidationCache {
+    fn get(&self, digest: &[u8]) -> Option<Vec<ValidationStatus>> {
+        let mut state = self.state();
+        state.clock += 1;
+        let clock = state.clock;
+
+        match state.entries.get_mut(digest) {
+            Some(entry) => {
+                entry.info.last_used = clock;
+                entry.info.hits += 1;
+                let validation_status = entry.validation_status.clone();
+                state.hits += 1;
+                Some(validation_status)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&self, digest: &[u8], validation_status: Vec<ValidationStatus>) {
+        let mut state = self.state();
+        state.clock += 1;
+        let clock = state.clock;
+
+        if !state.entries.contains_key(digest) && state.entries.len() >= self.capacity {
+            let infos: Vec<CacheEntryInfo> = state
+                .entries
+                .values()
+                .map(|entry| entry.info.clone())
+                .collect();
+
+            match self
+                .policy
+                .select_eviction(&infos)
+                .and_then(|index| infos.get(index))
+            {
+                Some(evicted) => {
+                    state.entries.remove(&evicted.digest);
+                }
+                None => return,
+            }
+        }
+
+        state.entries.insert(
+            digest.to_vec(),
+            CacheEntry {
+                info: CacheEntryInfo {
+                    digest: digest.to_vec(),
+                    last_used: clock,
+                    hits: 0,
+                },
+                validation_status,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn status(code: &str) -> Vec<ValidationStatus> {
+        vec![ValidationStatus::new(code)]
+    }
+
+    #[test]
+    fn test_memory_cache_get_and_insert() {
+        let cache = MemoryValidationCache::new(2);
+        assert!(cache.get(b"a").is_none());
+
+        cache.insert(b"a", status("code.a"));
+        assert_eq!(cache.get(b"a").unwrap()[0].code(), "code.a");
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryValidationCache::new(2);
+        cache.insert(b"a", status("code.a"));
+        cache.insert(b"b", status("code.b"));
+
+        // reading "a" makes "b" the least recently used entry
+        cache.get(b"a");
+        cache.insert(b"c", status("code.c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(b"a").is_some());
+        assert!(cache.get(b"b").is_none());
+        assert!(cache.get(b"c").is_some());
+    }
+
+    #[test]
+    fn test_memory_cache_custom_eviction_policy() {
+        // keeps the existing entries and rejects new ones when full
+        struct KeepExisting;
+        impl EvictionPolicy for KeepExisting {
+            fn select_eviction(&self, _entries: &[CacheEntryInfo]) -> Option<usize> {
+                None
+            }
+        }
+
+        let cache = MemoryValidationCache::new(1).with_eviction_policy(KeepExisting);
+        cache.insert(b"a", status("code.a"));
+        cache.insert(b"b", status("code.b"));
+
+        assert!(cache.get(b"a").is_some());
+        assert!(cache.get(b"b").is_none());
+
+        // replacing an existing entry doesn't need an eviction
+        cache.insert(b"a", status("code.a2"));
+        assert_eq!(cache.get(b"a").unwrap()[0].code(), "code.a2");
+    }
+}
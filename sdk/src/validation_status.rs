@@ -67,6 +67,13 @@ impl ValidationStatus {
         &self.code
     }
 
+    /// Returns the validation status code as a [`ValidationStatusCode`].
+    ///
+    /// Returns `None` if the code is not one of the standard C2PA status codes.
+    pub fn status_code(&self) -> Option<ValidationStatusCode> {
+        ValidationStatusCode::from_code(&self.code)
+    }
+
     /// Returns the internal JUMBF reference to the entity that was validated.
     pub fn url(&self) -> Option<&str> {
         self.url.as_deref()
@@ -154,8 +161,114 @@ impl PartialEq for ValidationStatus {
     }
 }
 
+/// Validation status codes grouped by outcome.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(JsonSchema))]
+pub struct StatusCodes {
+    /// Validations that succeeded.
+    pub success: Vec<ValidationStatus>,
+
+    /// Validations that neither succeeded nor failed.
+    pub informational: Vec<ValidationStatus>,
+
+    /// Validations that failed.
+    pub failure: Vec<ValidationStatus>,
+}
+
+impl StatusCodes {
+    /// Groups `statuses` into successes, informational codes and failures.
+    pub fn from_statuses(statuses: &[ValidationStatus]) -> Self {
+        let mut codes = Self::default();
+        for status in statuses {
+            if status.passed() {
+                codes.success.push(status.clone());
+            } else if is_informational(status.code()) {
+                codes.informational.push(status.clone());
+            } else {
+                codes.failure.push(status.clone());
+            }
+        }
+        codes
+    }
+}
+
+/// The validation results of a manifest referenced by an ingredient of an active manifest.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(JsonSchema))]
+pub struct IngredientDeltaValidationResult {
+    /// URI of the ingredient assertion of the active manifest that refers to the manifest.
+    #[serde(rename = "ingredientAssertionURI")]
+    pub ingredient_assertion_uri: String,
+
+    /// Status codes of the referenced manifest not already recorded by that ingredient.
+    #[serde(rename = "validationDeltas")]
+    pub validation_deltas: StatusCodes,
+}
+
+/// The results of validating a manifest store, as recorded by v3 ingredient assertions.
+///
+/// An ingredient records the results from when it was ingested, so later readers can see the
+/// trust state of a source at edit time.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(JsonSchema))]
+pub struct ValidationResults {
+    /// Status codes of the active manifest.
+    #[serde(rename = "activeManifest", skip_serializing_if = "Option::is_none")]
+    pub active_manifest: Option<StatusCodes>,
+
+    /// Status codes of the manifests its ingredients refer to.
+    #[serde(rename = "ingredientDeltas", skip_serializing_if = "Option::is_none")]
+    pub ingredient_deltas: Option<Vec<IngredientDeltaValidationResult>>,
+}
+
+impl ValidationResults {
+    /// Creates validation results for an active manifest from its `statuses`.
+    pub fn from_statuses(statuses: &[ValidationStatus]) -> Self {
+        Self {
+            active_manifest: Some(StatusCodes::from_statuses(statuses)),
+            ingredient_deltas: None,
+        }
+    }
+
+    /// Returns all failure status codes of the active manifest and of its ingredient deltas.
+    pub fn failures(&self) -> Vec<&ValidationStatus> {
+        self.active_manifest
+            .iter()
+            .chain(
+                self.ingredient_deltas
+                    .iter()
+                    .flatten()
+                    .map(|delta| &delta.validation_deltas),
+            )
+            .flat_map(|codes| codes.failure.iter())
+            .collect()
+    }
+}
+
 // TODO: Does this still need to be public? (I do see one reference in the JS SDK.)
 
+/// Returns a `ValidationStatus` for each success code logged in `validation_log`, which
+/// [`status_for_store`] leaves out.
+pub(crate) fn success_statuses(validation_log: &impl StatusTracker) -> Vec<ValidationStatus> {
+    validation_log
+        .get_log()
+        .iter()
+        .filter_map(ValidationStatus::from_validation_item)
+        .filter(|s| is_success(&s.code))
+        .collect()
+}
+
+/// Returns a `ValidationStatus` for each informational code logged in `validation_log`, which
+/// [`status_for_store`] leaves out.
+pub(crate) fn informational_statuses(validation_log: &impl StatusTracker) -> Vec<ValidationStatus> {
+    validation_log
+        .get_log()
+        .iter()
+        .filter_map(ValidationStatus::from_validation_item)
+        .filter(|s| is_informational(&s.code))
+        .collect()
+}
+
 /// Given a `Store` and a `StatusTracker`, return `ValidationStatus` items for each
 /// item in the tracker which reflect errors in the active manifest or which would not
 /// be reported as a validation error for any ingredient.
@@ -167,7 +280,7 @@ pub fn status_for_store(
         .get_log()
         .iter()
         .filter_map(ValidationStatus::from_validation_item)
-        .filter(|s| !is_success(&s.code))
+        .filter(|s| !is_success(&s.code) && !is_informational(&s.code))
         .collect();
 
     // Filter out any status that is already captured in an ingredient assertion.
@@ -191,8 +304,19 @@ pub fn status_for_store(
                 .ingredient_assertions()
                 .iter()
                 .filter_map(|a| Ingredient::from_assertion(a).ok())
-                .filter_map(|i| i.validation_status)
-                .flat_map(|x| x.into_iter())
+                .flat_map(|i| {
+                    // v3 ingredients record their statuses in validation results
+                    let recorded: Vec<ValidationStatus> = i
+                        .validation_results
+                        .iter()
+                        .flat_map(|r| r.failures())
+                        .cloned()
+                        .collect();
+                    i.validation_status
+                        .unwrap_or_default()
+                        .into_iter()
+                        .chain(recorded)
+                })
                 .collect();
 
             // Filter to only contain the active statuses and nested statuses not found in active.
@@ -349,6 +473,14 @@ pub const SIGNING_CREDENTIAL_REVOKED: &str = "signingCredential.revoked";
 /// `ValidationStatus.url()` will point to a C2PA claim signature box.
 pub const SIGNING_CREDENTIAL_EXPIRED: &str = "signingCredential.expired";
 
+/// The OCSP responder for the signing credential could not be reached.
+///
+/// Reported as a failure only when the `verify.fail_closed_on_network_error` setting is
+/// enabled. Otherwise revocation is not checked and the status is informational.
+///
+/// `ValidationStatus.url()` will point to a C2PA claim signature box.
+pub const SIGNING_CREDENTIAL_OCSP_INACCESSIBLE: &str = "signingCredential.ocsp.inaccessible";
+
 /// The time-stamp does not correspond to the contents of the claim.
 ///
 /// `ValidationStatus.url()` will point to a C2PA claim signature box.
@@ -473,10 +605,362 @@ pub const ALGORITHM_UNSUPPORTED: &str = "algorithm.unsupported";
 /// `ValidationStatus.url()` will point to a C2PA claim box or C2PA assertion.
 pub const GENERAL_ERROR: &str = "general.error";
 
+/// The standard C2PA validation status codes.
+///
+/// Each variant corresponds to one of the status code constants in this module.
+/// Use [`ValidationStatusCode::from_code`] and [`ValidationStatusCode::as_str`] to
+/// convert to and from the code strings used in the C2PA specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValidationStatusCode {
+    /// `claimSignature.validated`
+    ClaimSignatureValidated,
+    /// `signingCredential.trusted`
+    SigningCredentialTrusted,
+    /// `timeStamp.trusted`
+    TimestampTrusted,
+    /// `assertion.hashedURI.match`
+    AssertionHashedUriMatch,
+    /// `assertion.dataHash.match`
+    AssertionDataHashMatch,
+    /// `assertion.bmffHash.match`
+    AssertionBmffHashMatch,
+    /// `assertion.boxesHash.match`
+    AssertionBoxHashMatch,
+    /// `assertion.accessible`
+    AssertionAccessible,
+    /// `claim.missing`
+    ClaimMissing,
+    /// `claim.multiple`
+    ClaimMultiple,
+    /// `claim.hardBindings.missing`
+    HardBindingsMissing,
+    /// `claim.required.missing`
+    ClaimRequiredMissing,
+    /// `claim.cbor.invalid`
+    ClaimCborInvalid,
+    /// `ingredient.hashedURI.mismatch`
+    IngredientHashedUriMismatch,
+    /// `claimSignature.missing`
+    ClaimSignatureMissing,
+    /// `claimSignature.mismatch`
+    ClaimSignatureMismatch,
+    /// `manifest.inaccessible`
+    ManifestInaccessible,
+    /// `manifest.multipleParents`
+    ManifestMultipleParents,
+    /// `manifest.update.invalid`
+    ManifestUpdateInvalid,
+    /// `manifest.update.wrongParents`
+    ManifestUpdateWrongParents,
+    /// `signingCredential.untrusted`
+    SigningCredentialUntrusted,
+    /// `signingCredential.invalid`
+    SigningCredentialInvalid,
+    /// `signingCredential.revoked`
+    SigningCredentialRevoked,
+    /// `signingCredential.expired`
+    SigningCredentialExpired,
+    /// `signingCredential.ocsp.inaccessible`
+    SigningCredentialOcspInaccessible,
+    /// `timeStamp.mismatch`
+    TimestampMismatch,
+    /// `timeStamp.untrusted`
+    TimestampUntrusted,
+    /// `timeStamp.outsideValidity`
+    TimestampOutsideValidity,
+    /// `assertion.hashedURI.mismatch`
+    AssertionHashedUriMismatch,
+    /// `assertion.missing`
+    AssertionMissing,
+    /// `assertion.undeclared`
+    AssertionUndeclared,
+    /// `assertion.inaccessible`
+    AssertionInaccessible,
+    /// `assertion.notRedacted`
+    AssertionNotRedacted,
+    /// `assertion.selfRedacted`
+    AssertionSelfRedacted,
+    /// `assertion.required.missing`
+    AssertionRequiredMissing,
+    /// `assertion.json.invalid`
+    AssertionJsonInvalid,
+    /// `assertion.cbor.invalid`
+    AssertionCborInvalid,
+    /// `assertion.action.ingredientMismatch`
+    ActionAssertionIngredientMismatch,
+    /// `assertion.action.redacted`
+    ActionAssertionRedacted,
+    /// `assertion.dataHash.mismatch`
+    AssertionDataHashMismatch,
+    /// `assertion.bmffHash.mismatch`
+    AssertionBmffHashMismatch,
+    /// `assertion.boxesHash.mismatch`
+    AssertionBoxHashMismatch,
+    /// `assertion.cloud-data.hardBinding`
+    AssertionCloudDataHardBinding,
+    /// `assertion.cloud-data.actions`
+    AssertionCloudDataActions,
+    /// `algorithm.unsupported`
+    AlgorithmUnsupported,
+    /// `general.error`
+    GeneralError,
+}
+
+impl ValidationStatusCode {
+    /// Returns the C2PA specification string for this status code.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ClaimSignatureValidated => CLAIM_SIGNATURE_VALIDATED,
+            Self::SigningCredentialTrusted => SIGNING_CREDENTIAL_TRUSTED,
+            Self::TimestampTrusted => TIMESTAMP_TRUSTED,
+            Self::AssertionHashedUriMatch => ASSERTION_HASHEDURI_MATCH,
+            Self::AssertionDataHashMatch => ASSERTION_DATAHASH_MATCH,
+            Self::AssertionBmffHashMatch => ASSERTION_BMFFHASH_MATCH,
+            Self::AssertionBoxHashMatch => ASSERTION_BOXHASH_MATCH,
+            Self::AssertionAccessible => ASSERTION_ACCESSIBLE,
+            Self::ClaimMissing => CLAIM_MISSING,
+            Self::ClaimMultiple => CLAIM_MULTIPLE,
+            Self::HardBindingsMissing => HARD_BINDINGS_MISSING,
+            Self::ClaimRequiredMissing => CLAIM_REQUIRED_MISSING,
+            Self::ClaimCborInvalid => CLAIM_CBOR_INVALID,
+            Self::IngredientHashedUriMismatch => INGREDIENT_HASHEDURI_MISMATCH,
+            Self::ClaimSignatureMissing => CLAIM_SIGNATURE_MISSING,
+            Self::ClaimSignatureMismatch => CLAIM_SIGNATURE_MISMATCH,
+            Self::ManifestInaccessible => MANIFEST_INACCESSIBLE,
+            Self::ManifestMultipleParents => MANIFEST_MULTIPLE_PARENTS,
+            Self::ManifestUpdateInvalid => MANIFEST_UPDATE_INVALID,
+            Self::ManifestUpdateWrongParents => MANIFEST_UPDATE_WRONG_PARENTS,
+            Self::SigningCredentialUntrusted => SIGNING_CREDENTIAL_UNTRUSTED,
+            Self::SigningCredentialInvalid => SIGNING_CREDENTIAL_INVALID,
+            Self::SigningCredentialRevoked => SIGNING_CREDENTIAL_REVOKED,
+            Self::SigningCredentialExpired => SIGNING_CREDENTIAL_EXPIRED,
+            Self::SigningCredentialOcspInaccessible => SIGNING_CREDENTIAL_OCSP_INACCESSIBLE,
+            Self::TimestampMismatch => TIMESTAMP_MISMATCH,
+            Self::TimestampUntrusted => TIMESTAMP_UNTRUSTED,
+            Self::TimestampOutsideValidity => TIMESTAMP_OUTSIDE_VALIDITY,
+            Self::AssertionHashedUriMismatch => ASSERTION_HASHEDURI_MISMATCH,
+            Self::AssertionMissing => ASSERTION_MISSING,
+            Self::AssertionUndeclared => ASSERTION_UNDECLARED,
+            Self::AssertionInaccessible => ASSERTION_INACCESSIBLE,
+            Self::AssertionNotRedacted => ASSERTION_NOT_REDACTED,
+            Self::AssertionSelfRedacted => ASSERTION_SELF_REDACTED,
+            Self::AssertionRequiredMissing => ASSERTION_REQUIRED_MISSING,
+            Self::AssertionJsonInvalid => ASSERTION_JSON_INVALID,
+            Self::AssertionCborInvalid => ASSERTION_CBOR_INVALID,
+            Self::ActionAssertionIngredientMismatch => ACTION_ASSERTION_INGREDIENT_MISMATCH,
+            Self::ActionAssertionRedacted => ACTION_ASSERTION_REDACTED,
+            Self::AssertionDataHashMismatch => ASSERTION_DATAHASH_MISMATCH,
+            Self::AssertionBmffHashMismatch => ASSERTION_BMFFHASH_MISMATCH,
+            Self::AssertionBoxHashMismatch => ASSERTION_BOXHASH_MISMATCH,
+            Self::AssertionCloudDataHardBinding => ASSERTION_CLOUD_DATA_HARD_BINDING,
+            Self::AssertionCloudDataActions => ASSERTION_CLOUD_DATA_ACTIONS,
+            Self::AlgorithmUnsupported => ALGORITHM_UNSUPPORTED,
+            Self::GeneralError => GENERAL_ERROR,
+        }
+    }
+
+    /// Returns the status code matching a C2PA specification string, or `None` if the
+    /// string is not a standard status code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            CLAIM_SIGNATURE_VALIDATED => Self::ClaimSignatureValidated,
+            SIGNING_CREDENTIAL_TRUSTED => Self::SigningCredentialTrusted,
+            TIMESTAMP_TRUSTED => Self::TimestampTrusted,
+            ASSERTION_HASHEDURI_MATCH => Self::AssertionHashedUriMatch,
+            ASSERTION_DATAHASH_MATCH => Self::AssertionDataHashMatch,
+            ASSERTION_BMFFHASH_MATCH => Self::AssertionBmffHashMatch,
+            ASSERTION_BOXHASH_MATCH => Self::AssertionBoxHashMatch,
+            ASSERTION_ACCESSIBLE => Self::AssertionAccessible,
+            CLAIM_MISSING => Self::ClaimMissing,
+            CLAIM_MULTIPLE => Self::ClaimMultiple,
+            HARD_BINDINGS_MISSING => Self::HardBindingsMissing,
+            CLAIM_REQUIRED_MISSING => Self::ClaimRequiredMissing,
+            CLAIM_CBOR_INVALID => Self::ClaimCborInvalid,
+            INGREDIENT_HASHEDURI_MISMATCH => Self::IngredientHashedUriMismatch,
+            CLAIM_SIGNATURE_MISSING => Self::ClaimSignatureMissing,
+            CLAIM_SIGNATURE_MISMATCH => Self::ClaimSignatureMismatch,
+            MANIFEST_INACCESSIBLE => Self::ManifestInaccessible,
+            MANIFEST_MULTIPLE_PARENTS => Self::ManifestMultipleParents,
+            MANIFEST_UPDATE_INVALID => Self::ManifestUpdateInvalid,
+            MANIFEST_UPDATE_WRONG_PARENTS => Self::ManifestUpdateWrongParents,
+            SIGNING_CREDENTIAL_UNTRUSTED => Self::SigningCredentialUntrusted,
+            SIGNING_CREDENTIAL_INVALID => Self::SigningCredentialInvalid,
+            SIGNING_CREDENTIAL_REVOKED => Self::SigningCredentialRevoked,
+            SIGNING_CREDENTIAL_EXPIRED => Self::SigningCredentialExpired,
+            SIGNING_CREDENTIAL_OCSP_INACCESSIBLE => Self::SigningCredentialOcspInaccessible,
+            TIMESTAMP_MISMATCH => Self::TimestampMismatch,
+            TIMESTAMP_UNTRUSTED => Self::TimestampUntrusted,
+            TIMESTAMP_OUTSIDE_VALIDITY => Self::TimestampOutsideValidity,
+            ASSERTION_HASHEDURI_MISMATCH => Self::AssertionHashedUriMismatch,
+            ASSERTION_MISSING => Self::AssertionMissing,
+            ASSERTION_UNDECLARED => Self::AssertionUndeclared,
+            ASSERTION_INACCESSIBLE => Self::AssertionInaccessible,
+            ASSERTION_NOT_REDACTED => Self::AssertionNotRedacted,
+            ASSERTION_SELF_REDACTED => Self::AssertionSelfRedacted,
+            ASSERTION_REQUIRED_MISSING => Self::AssertionRequiredMissing,
+            ASSERTION_JSON_INVALID => Self::AssertionJsonInvalid,
+            ASSERTION_CBOR_INVALID => Self::AssertionCborInvalid,
+            ACTION_ASSERTION_INGREDIENT_MISMATCH => Self::ActionAssertionIngredientMismatch,
+            ACTION_ASSERTION_REDACTED => Self::ActionAssertionRedacted,
+            ASSERTION_DATAHASH_MISMATCH => Self::AssertionDataHashMismatch,
+            ASSERTION_BMFFHASH_MISMATCH => Self::AssertionBmffHashMismatch,
+            ASSERTION_BOXHASH_MISMATCH => Self::AssertionBoxHashMismatch,
+            ASSERTION_CLOUD_DATA_HARD_BINDING => Self::AssertionCloudDataHardBinding,
+            ASSERTION_CLOUD_DATA_ACTIONS => Self::AssertionCloudDataActions,
+            ALGORITHM_UNSUPPORTED => Self::AlgorithmUnsupported,
+            GENERAL_ERROR => Self::GeneralError,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if this is a success status code.
+    pub fn passed(&self) -> bool {
+        is_success(self.as_str())
+    }
+}
+
+impl std::fmt::Display for ValidationStatusCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 // -- unofficial status codes --
 
 pub(crate) const STATUS_PRERELEASE: &str = "com.adobe.prerelease";
 
+/// The manifest store was migrated from a prerelease (pre-1.0) layout and is unsigned.
+pub const STATUS_MIGRATED: &str = "com.adobe.migrated";
+
+/// The signing certificate's extended key usage (EKU) does not meet the C2PA certificate profile.
+///
+/// Reported after [`SIGNING_CREDENTIAL_INVALID`] when a certificate is rejected because of its
+/// EKUs, as more detail. The check can be disabled with the `verify.check_cert_usage` setting.
+pub const STATUS_INVALID_EKU: &str = "com.adobe.signingCredential.invalidEku";
+
+/// The signing certificate is trusted only through the C2PA test/development trust anchors.
+///
+/// Reported alongside [`SIGNING_CREDENTIAL_TRUSTED`] so that assets signed with test
+/// credentials are never mistaken for production ones. See
+/// [`TrustConfig`](crate::settings::TrustConfig).
+pub const STATUS_DEVELOPMENT_TRUST: &str = "com.adobe.signingCredential.developmentTrust";
+
+/// The claim's `dc:format` names a different format than the one the asset was read as.
+///
+/// The asset may be served with the wrong format, or its manifest may have been copied from
+/// another asset.
+pub const STATUS_FORMAT_MISMATCH: &str = "com.adobe.format.mismatch";
+
+/// The claim signature has no valid RFC 3161 time-stamp, which the
+/// `verify.require_trusted_timestamp` setting requires.
+///
+/// When trust verification is enabled, the time-stamp authority must also be on the trust
+/// list. This is reported independently of the validity of the signing certificate.
+///
+/// `ValidationStatus.url()` will point to a C2PA claim signature box.
+pub const STATUS_TIMESTAMP_REQUIRED: &str = "com.adobe.timeStamp.required";
+
+/// An ingredient's manifest is bound with a weaker hash algorithm than the one of the claim
+/// referencing it, such as SHA-1 in a SHA-256 claim.
+///
+/// This is informational and does not make the manifest invalid. It is reported by
+/// [`Reader::validation_informational`](crate::Reader::validation_informational).
+///
+/// `ValidationStatus.url()` will point to the ingredient's manifest.
+pub const STATUS_INGREDIENT_WEAK_HASH: &str = "com.adobe.ingredient.weakHash";
+
+/// The source asset given for an ingredient without a manifest could not be matched to it.
+///
+/// Such an ingredient records no hash of its source, so the source must have no manifest
+/// either and carry the ingredient's instance ID in its XMP.
+///
+/// `ValidationStatus.url()` will point to the manifest holding the ingredient.
+pub const STATUS_INGREDIENT_SOURCE_MISMATCH: &str = "com.adobe.ingredient.sourceMismatch";
+
+/// An assertion is listed more than once under the same label, or the claim holds more than
+/// one instance of an assertion that must be unique, such as `c2pa.actions` or a claim
+/// thumbnail.
+///
+/// Ingredients, metadata and other assertions that may legitimately repeat are not reported.
+///
+/// This is informational, as such manifests have been signed by earlier versions of the SDK.
+/// It is reported by
+/// [`Reader::validation_informational`](crate::Reader::validation_informational).
+///
+/// `ValidationStatus.url()` will point to the duplicate assertion.
+pub const STATUS_ASSERTION_DUPLICATE: &str = "com.adobe.assertion.duplicate";
+
+/// A hard binding assertion could not be checked against the asset, so whether it matches is
+/// unknown.
+///
+/// This is informational. It is reported by
+/// [`Reader::validation_informational`](crate::Reader::validation_informational).
+///
+/// `ValidationStatus.url()` will point to the hard binding assertion.
+pub const STATUS_ASSERTION_NOT_CHECKED: &str = "com.adobe.assertion.notChecked";
+
+/// A PDF's data hash matches the revision holding its manifest, but the PDF has later
+/// incremental updates that the data hash doesn't cover. They only add PDF digital signature
+/// dictionaries and signature fields, any other update fails the data hash.
+///
+/// This is informational. It is reported by
+/// [`Reader::validation_informational`](crate::Reader::validation_informational).
+///
+/// `ValidationStatus.url()` will point to the data hash assertion.
+pub const STATUS_PDF_UNBOUND_REVISIONS: &str = "com.adobe.pdf.unboundRevisions";
+
+// -- CAWG identity assertion status codes --
+//
+// See <https://cawg.io/identity/1.1/#_validation>.
+
+/// An identity assertion is well-formed and its signature is valid, but its signer's
+/// credential was not checked against the trust list.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_WELL_FORMED: &str = "cawg.identity.well-formed";
+
+/// An identity assertion is valid and its signer's credential is on the trust list.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_TRUSTED: &str = "cawg.identity.trusted";
+
+/// An identity assertion could not be decoded.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_CBOR_INVALID: &str = "cawg.identity.cbor.invalid";
+
+/// An identity assertion references an assertion that isn't in its claim, or whose hash
+/// doesn't match the claim's.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_ASSERTION_MISMATCH: &str = "cawg.identity.assertion.mismatch";
+
+/// An identity assertion references the same assertion more than once.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_ASSERTION_DUPLICATE: &str = "cawg.identity.assertion.duplicate";
+
+/// An identity assertion doesn't reference the hard binding of its claim.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_HARD_BINDING_MISSING: &str = "cawg.identity.hard_binding_missing";
+
+/// The `sig_type` of an identity assertion is not supported.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_SIG_TYPE_UNKNOWN: &str = "cawg.identity.sig_type.unknown";
+
+/// The padding of an identity assertion contains bytes other than zero.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_IDENTITY_PAD_INVALID: &str = "cawg.identity.pad.invalid";
+
+/// The X.509 COSE signature of an identity assertion does not match its signer payload.
+///
+/// `ValidationStatus.url()` will point to a CAWG identity assertion.
+pub const CAWG_X509_SIGNATURE_INVALID: &str = "cawg.x509.cose.invalid";
+
 /// Returns `true` if the status code is a known C2PA success status code.
 ///
 /// Returns `false` if the status code is a known C2PA failure status
@@ -503,3 +987,65 @@ pub fn is_success(status_code: &str) -> bool {
             | ASSERTION_BOXHASH_MATCH
     )
 }
+
+/// Returns `true` if the status code is informational, neither a success nor a failure.
+///
+/// Informational codes are not reported by
+/// [`Reader::validation_status`](crate::Reader::validation_status), but by
+/// [`Reader::validation_informational`](crate::Reader::validation_informational).
+///
+/// # Examples
+///
+/// ```
+/// use c2pa::validation_status::*;
+///
+/// assert!(is_informational(STATUS_INGREDIENT_WEAK_HASH));
+/// assert!(!is_informational(CLAIM_SIGNATURE_VALIDATED));
+/// assert!(!is_informational(SIGNING_CREDENTIAL_REVOKED));
+/// ```
+pub fn is_informational(status_code: &str) -> bool {
+    matches!(
+        status_code,
+        STATUS_INGREDIENT_WEAK_HASH
+            | STATUS_ASSERTION_DUPLICATE
+            | STATUS_ASSERTION_NOT_CHECKED
+            | STATUS_PDF_UNBOUND_REVISIONS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_status_code_round_trip() {
+        for code in [
+            CLAIM_SIGNATURE_VALIDATED,
+            SIGNING_CREDENTIAL_TRUSTED,
+            ASSERTION_HASHEDURI_MISMATCH,
+            ASSERTION_DATAHASH_MISMATCH,
+            GENERAL_ERROR,
+        ] {
+            let status_code = ValidationStatusCode::from_code(code).unwrap();
+            assert_eq!(status_code.as_str(), code);
+            assert_eq!(status_code.to_string(), code);
+        }
+    }
+
+    #[test]
+    fn test_status_code_mapping() {
+        assert_eq!(
+            ValidationStatusCode::from_code("claimSignature.validated"),
+            Some(ValidationStatusCode::ClaimSignatureValidated)
+        );
+        assert_eq!(
+            ValidationStatusCode::from_code("assertion.hashedURI.mismatch"),
+            Some(ValidationStatusCode::AssertionHashedUriMismatch)
+        );
+        assert!(ValidationStatusCode::SigningCredentialTrusted.passed());
+        assert!(!ValidationStatusCode::SigningCredentialUntrusted.passed());
+        assert_eq!(ValidationStatusCode::from_code("com.example.unknown"), None);
+    }
+}
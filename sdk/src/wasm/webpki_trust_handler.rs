@@ -40,6 +40,7 @@ use crate::{
 pub(crate) struct WebTrustHandlerConfig {
     pub trust_anchors: Vec<Vec<u8>>,
     pub private_anchors: Vec<Vec<u8>>,
+    intermediates: Vec<Vec<u8>>,
     allowed_cert_set: HashSet<String>,
     config_store: Vec<u8>,
 }
@@ -79,6 +80,7 @@ impl TrustHandlerConfig for WebTrustHandlerConfig {
         let mut th = WebTrustHandlerConfig {
             trust_anchors: Vec::new(),
             private_anchors: Vec::new(),
+            intermediates: Vec::new(),
             allowed_cert_set: HashSet::new(),
             config_store: Vec::new(),
         };
@@ -111,9 +113,21 @@ impl TrustHandlerConfig for WebTrustHandlerConfig {
         Ok(())
     }
 
+    // append intermediate certificates
+    fn append_intermediates(&mut self, intermediates_reader: &mut dyn Read) -> Result<()> {
+        let mut intermediates_data = Vec::new();
+        intermediates_reader.read_to_end(&mut intermediates_data)?;
+
+        let mut intermediates = load_trust_from_data(&intermediates_data)?;
+        self.intermediates.append(&mut intermediates);
+
+        Ok(())
+    }
+
     fn clear(&mut self) {
         self.trust_anchors = Vec::new();
         self.private_anchors = Vec::new();
+        self.intermediates = Vec::new();
     }
 
     // load EKU configuration
@@ -184,6 +198,10 @@ impl TrustHandlerConfig for WebTrustHandlerConfig {
     fn get_allowed_list(&self) -> &HashSet<String> {
         &self.allowed_cert_set
     }
+
+    fn get_intermediates(&self) -> Vec<Vec<u8>> {
+        self.intermediates.clone()
+    }
 }
 
 fn find_allowed_eku<'a>(cert_der: &'a [u8], allowed_ekus: &'a Vec<Oid<'a>>) -> Option<&'a Oid<'a>> {
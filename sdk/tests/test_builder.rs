@@ -53,3 +53,636 @@ fn test_builder_riff() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_resign_after_edit_pdf() -> Result<()> {
+    use std::io::{Seek, Write};
+
+    use c2pa::{assertions::Action, Reader};
+
+    let format = "application/pdf";
+    let mut source = Cursor::new(include_bytes!("fixtures/basic.pdf"));
+    let mut signed = Cursor::new(Vec::new());
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    builder.sign(&test_signer(), format, &mut source, &mut signed)?;
+
+    // an intact hard binding needs no new manifest
+    let mut dest = Cursor::new(Vec::new());
+    let edit = Action::new("c2pa.edited");
+    assert!(
+        c2pa::resign_after_edit(format, &mut signed, &mut dest, edit, &test_signer())?.is_none()
+    );
+    assert!(dest.get_ref().is_empty());
+
+    // a benign edit appended to the signed PDF breaks the hard binding
+    signed.seek(io::SeekFrom::End(0))?;
+    signed.write_all(b"% edited\n")?;
+    signed.rewind()?;
+    let reader = Reader::from_stream(format, &mut signed)?;
+    assert!(reader.hard_binding_broken());
+    let edited_label = reader.active_label().map(str::to_owned);
+
+    let edit = Action::new("c2pa.edited");
+    assert!(
+        c2pa::resign_after_edit(format, &mut signed, &mut dest, edit, &test_signer())?.is_some()
+    );
+
+    dest.rewind()?;
+    let reader = Reader::from_stream(format, &mut dest)?;
+    assert_eq!(reader.validation_status(), None);
+    assert!(!reader.hard_binding_broken());
+
+    // the edit is recorded in a new manifest whose parent carries the previous one
+    let manifest = reader.active_manifest().unwrap();
+    assert_ne!(reader.active_label().map(str::to_owned), edited_label);
+    assert_eq!(manifest.title(), Some("basic.pdf"));
+    let parent = manifest.ingredients().first().unwrap();
+    assert_eq!(parent.active_manifest().map(str::to_owned), edited_label);
+    let actions: c2pa::assertions::Actions = manifest.find_assertion("c2pa.actions")?;
+    assert_eq!(actions.actions()[0].action(), "c2pa.edited");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_without_embedded_files_name_tree() -> Result<()> {
+    use c2pa::Reader;
+
+    let format = "application/pdf";
+    let mut source = Cursor::new(include_bytes!("fixtures/basic-empty-names.pdf"));
+    let mut dest = Cursor::new(Vec::new());
+
+    let mut builder = Builder::from_json(r#"{"title": "basic-empty-names.pdf"}"#)?;
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+    assert_eq!(reader.validation_status(), None);
+    assert!(reader.active_manifest().is_some());
+    Ok(())
+}
+
+#[test]
+fn test_builder_sidecar_round_trip() -> Result<()> {
+    use std::io::Seek;
+
+    use c2pa::{assertions::Actions, Reader};
+
+    for (format, asset) in [
+        ("image/jpeg", include_bytes!("fixtures/C.jpg").as_slice()),
+        (
+            "image/png",
+            include_bytes!("fixtures/sample1.png").as_slice(),
+        ),
+    ] {
+        let mut source = Cursor::new(asset);
+        let mut embedded = Cursor::new(Vec::new());
+        let mut builder = Builder::from_json(r#"{"title": "Sidecar Test"}"#)?;
+        builder.sign(&test_signer(), format, &mut source, &mut embedded)?;
+
+        // embedded to sidecar
+        let mut stripped = Cursor::new(Vec::new());
+        let sidecar =
+            c2pa::convert_to_sidecar(format, &mut embedded, &mut stripped, &test_signer())?;
+
+        stripped.rewind()?;
+        assert!(matches!(
+            Reader::from_stream(format, &mut stripped),
+            Err(c2pa::Error::JumbfNotFound)
+        ));
+
+        stripped.rewind()?;
+        let reader = Reader::from_manifest_data_and_stream(&sidecar, format, &mut stripped)?;
+        assert_eq!(reader.validation_status(), None);
+        let manifest = reader.active_manifest().unwrap();
+        assert_eq!(manifest.title(), Some("Sidecar Test"));
+        let actions: Actions = manifest.find_assertion(Actions::LABEL)?;
+        assert_eq!(actions.actions()[0].action(), "c2pa.repackaged");
+        let sidecar_label = reader.active_label().map(str::to_owned);
+
+        // sidecar back to embedded
+        stripped.rewind()?;
+        let mut restored = Cursor::new(Vec::new());
+        c2pa::embed_from_sidecar(
+            format,
+            &mut stripped,
+            &sidecar,
+            &mut restored,
+            &test_signer(),
+        )?;
+
+        restored.rewind()?;
+        let reader = Reader::from_stream(format, &mut restored)?;
+        assert_eq!(reader.validation_status(), None);
+        let parent = reader
+            .active_manifest()
+            .unwrap()
+            .ingredients()
+            .first()
+            .unwrap();
+        assert!(parent.is_parent());
+        assert_eq!(parent.active_manifest().map(str::to_owned), sidecar_label);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_builder_unknown_assertion_labels() -> Result<()> {
+    use std::collections::BTreeMap;
+
+    use c2pa::{assertions::Actions, Reader};
+
+    let manifest_def = std::fs::read_to_string(fixtures_path("future_assertion_manifest.json"))?;
+    let mut builder = Builder::from_json(&manifest_def)?;
+    // integer map keys are valid CBOR but have no JSON equivalent
+    let opaque: BTreeMap<i64, &str> = [(1, "future"), (2, "data")].into_iter().collect();
+    builder.add_assertion("org.example.future.opaque", &opaque)?;
+
+    let format = "image/jpeg";
+    let mut source = Cursor::new(include_bytes!("fixtures/C.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+    assert_eq!(reader.validation_status(), None);
+
+    let manifest = reader.active_manifest().unwrap();
+    let actions: Actions = manifest.find_assertion(Actions::LABEL)?;
+    assert_eq!(actions.actions()[0].action(), "c2pa.created");
+
+    let widget = manifest
+        .assertions()
+        .iter()
+        .find(|a| a.label() == "org.example.future.widget.v9")
+        .unwrap();
+    assert_eq!(widget.value()?["revision"], 9);
+
+    let opaque_assertion = manifest
+        .assertions()
+        .iter()
+        .find(|a| a.label() == "org.example.future.opaque")
+        .unwrap();
+    let decoded: BTreeMap<i64, String> =
+        serde_cbor::from_slice(opaque_assertion.binary()?).unwrap();
+    assert_eq!(decoded[&1], "future");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_compressed_manifest() -> Result<()> {
+    use c2pa::Reader;
+
+    let format = "application/pdf";
+    let notes = "This document was reviewed and approved. ".repeat(200);
+
+    let sign = |compress: bool| -> Result<Vec<u8>> {
+        let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+        builder.add_assertion_json("org.example.review", &serde_json::json!({ "notes": notes }))?;
+        builder.set_compress_manifest(compress);
+
+        let mut source = Cursor::new(include_bytes!("fixtures/basic.pdf"));
+        let mut dest = Cursor::new(Vec::new());
+        builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+        Ok(dest.into_inner())
+    };
+
+    let uncompressed = sign(false)?;
+    let compressed = sign(true)?;
+    assert!(compressed.len() < uncompressed.len());
+
+    for signed in [uncompressed, compressed] {
+        let reader = Reader::from_stream(format, Cursor::new(signed))?;
+        assert_eq!(reader.validation_status(), None);
+        let review = reader
+            .active_manifest()
+            .unwrap()
+            .assertions()
+            .iter()
+            .find(|a| a.label() == "org.example.review")
+            .unwrap();
+        assert_eq!(review.value()?["notes"], notes.as_str());
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_signing_time() -> Result<()> {
+    use c2pa::Reader;
+    use chrono::{TimeZone, Utc};
+
+    let format = "application/pdf";
+    let signing_time = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    builder.set_time_provider(move || signing_time);
+
+    let mut source = Cursor::new(include_bytes!("fixtures/basic.pdf"));
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+    let signed = dest.into_inner();
+
+    // the manifest's embedded file has /CreationDate and /ModDate set to the signing time
+    let date = b"(D:20240102030405Z)";
+    let dates = signed.windows(date.len()).filter(|w| w == date).count();
+    assert_eq!(dates, 2);
+
+    let reader = Reader::from_stream(format, Cursor::new(signed))?;
+    assert_eq!(reader.validation_status(), None);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_replace_manifest() -> Result<()> {
+    use c2pa::{Error, PdfIO, Reader};
+
+    let format = "application/pdf";
+    let source = include_bytes!("fixtures/basic.pdf");
+
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    let mut dest = Cursor::new(Vec::new());
+    let manifest_bytes =
+        builder.sign(&test_signer(), format, &mut Cursor::new(source), &mut dest)?;
+    let signed = dest.into_inner();
+
+    // the manifest store fits in place, so the signed bytes are patched
+    let mut output = Cursor::new(Vec::new());
+    PdfIO::default().replace_manifest(
+        &mut Cursor::new(signed.clone()),
+        &mut output,
+        &manifest_bytes,
+    )?;
+    assert_eq!(output.get_ref().len(), signed.len());
+
+    let reader = Reader::from_stream(format, Cursor::new(output.into_inner()))?;
+    assert_eq!(reader.validation_status(), None);
+
+    let result = PdfIO::default().replace_manifest(
+        &mut Cursor::new(source.to_vec()),
+        &mut Cursor::new(Vec::new()),
+        &manifest_bytes,
+    );
+    assert!(matches!(result, Err(Error::JumbfNotFound)));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_manifest_kind() -> Result<()> {
+    use c2pa::{PdfIO, PdfManifestKind};
+
+    let source = include_bytes!("fixtures/basic.pdf");
+    let pdf_io = PdfIO::default();
+    assert_eq!(
+        pdf_io.manifest_kind(&mut Cursor::new(source))?,
+        PdfManifestKind::None
+    );
+
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    let mut dest = Cursor::new(Vec::new());
+    let manifest_bytes = builder.sign(
+        &test_signer(),
+        "application/pdf",
+        &mut Cursor::new(source),
+        &mut dest,
+    )?;
+
+    let PdfManifestKind::Embedded(offset, len) = pdf_io.manifest_kind(&mut dest)? else {
+        panic!("expected an embedded manifest store");
+    };
+    assert_eq!(
+        &dest.get_ref()[offset..offset + len],
+        manifest_bytes.as_slice()
+    );
+
+    let remote = include_bytes!("fixtures/basic-remote-manifest.pdf");
+    assert!(matches!(
+        pdf_io.manifest_kind(&mut Cursor::new(remote))?,
+        PdfManifestKind::Remote(_)
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_embedded_files() -> Result<()> {
+    use c2pa::PdfIO;
+
+    let source = include_bytes!("fixtures/basic-attachments.pdf");
+    let mut builder = Builder::from_json(r#"{"title": "basic-attachments.pdf"}"#)?;
+    let mut dest = Cursor::new(Vec::new());
+    let manifest_bytes = builder.sign(
+        &test_signer(),
+        "application/pdf",
+        &mut Cursor::new(source),
+        &mut dest,
+    )?;
+
+    // the attachments are listed along with the manifest store
+    let pdf_io = PdfIO::default();
+    let files = pdf_io.list_embedded_files(&mut dest)?;
+    let names: Vec<&str> = files.iter().map(|file| file.name.as_str()).collect();
+    assert!(names.contains(&"libpng-test.png"));
+    let manifest = files
+        .iter()
+        .find(|file| file.relationship.as_deref() == Some("C2PA_Manifest"))
+        .unwrap();
+    assert_eq!(manifest.size, manifest_bytes.len());
+
+    let png = include_bytes!("fixtures/libpng-test.png");
+    assert_eq!(
+        pdf_io.extract_embedded_file(&mut dest, "libpng-test.png")?,
+        Some(png.to_vec())
+    );
+    assert_eq!(
+        pdf_io.extract_embedded_file(&mut dest, &manifest.name)?,
+        Some(manifest_bytes)
+    );
+    assert_eq!(
+        pdf_io.extract_embedded_file(&mut dest, "missing.png")?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_version() -> Result<()> {
+    use c2pa::PdfIO;
+
+    let pdf_io = PdfIO::default();
+
+    let mut source = Cursor::new(include_bytes!("fixtures/basic.pdf"));
+    assert_eq!(pdf_io.version(&mut source)?, "1.6");
+    assert!(pdf_io.unsupported_features(&mut source)?.is_empty());
+
+    let mut source = Cursor::new(include_bytes!("fixtures/basic-pdf2.pdf"));
+    assert_eq!(pdf_io.version(&mut source)?, "2.0");
+    assert_eq!(pdf_io.unsupported_features(&mut source)?, vec!["/DSS"]);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_builder_pdf_padded_size() -> Result<()> {
+    use c2pa::{PdfIO, PdfManifestKind, Reader};
+
+    let format = "application/pdf";
+    let source = include_bytes!("fixtures/basic.pdf");
+
+    let sign = |padded_size: Option<usize>| -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+        if let Some(size) = padded_size {
+            builder.set_padded_size(size);
+        }
+        let mut dest = Cursor::new(Vec::new());
+        let manifest_bytes =
+            builder.sign(&test_signer(), format, &mut Cursor::new(source), &mut dest)?;
+        Ok((manifest_bytes, dest.into_inner()))
+    };
+
+    let (unpadded, _) = sign(None)?;
+    let padded_size = unpadded.len() + 1024;
+    let (manifest_bytes, signed) = sign(Some(padded_size))?;
+
+    // the embedded manifest store is padded to the requested size
+    assert_eq!(manifest_bytes.len(), padded_size);
+    let PdfManifestKind::Embedded(_, len) =
+        PdfIO::default().manifest_kind(&mut Cursor::new(signed.as_slice()))?
+    else {
+        panic!("expected an embedded manifest store");
+    };
+    assert_eq!(len, padded_size);
+
+    let reader = Reader::from_stream(format, Cursor::new(signed))?;
+    assert_eq!(reader.validation_status(), None);
+    Ok(())
+}
+
+#[test]
+fn test_builder_ingredient_relationship() -> Result<()> {
+    use c2pa::{Ingredient, Reader, Relationship};
+
+    let format = "image/jpeg";
+    let mut ingredient_source = Cursor::new(include_bytes!("fixtures/earth_apollo17.jpg"));
+    let ingredient = Ingredient::from_stream(format, &mut ingredient_source)?;
+
+    let mut builder = Builder::from_json(r#"{"title": "Composite"}"#)?;
+    builder.add_ingredient_with_relationship(ingredient, Relationship::ComponentOf)?;
+
+    let mut source = Cursor::new(include_bytes!("fixtures/C.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+    let ingredients = reader.active_manifest().unwrap().ingredients();
+    assert_eq!(ingredients.len(), 1);
+    assert_eq!(ingredients[0].relationship(), &Relationship::ComponentOf);
+    assert!(!ingredients[0].is_parent());
+    Ok(())
+}
+
+#[test]
+fn test_builder_sign_detached() -> Result<()> {
+    use c2pa::{HashRange, Reader};
+
+    let format = "image/jpeg";
+    let asset = include_bytes!("fixtures/C.jpg");
+    let mut source = Cursor::new(asset);
+
+    let mut builder = Builder::from_json(r#"{"title": "Detached"}"#)?;
+    let detached = builder.sign_detached(&test_signer(), format, &mut source)?;
+    assert_eq!(detached.alg, "sha256");
+    assert_eq!(detached.ranges, vec![HashRange::new(0, asset.len())]);
+
+    // the manifest validates against the unmodified asset
+    let reader = Reader::from_manifest_data_and_stream(&detached.manifest, format, &mut source)?;
+    assert_eq!(reader.validation_status(), None);
+    assert_eq!(reader.active_manifest().unwrap().title(), Some("Detached"));
+
+    // but not against any other bytes
+    let mut altered = asset.to_vec();
+    altered.push(0);
+    let reader =
+        Reader::from_manifest_data_and_stream(&detached.manifest, format, Cursor::new(altered))?;
+    assert!(reader.hard_binding_broken());
+    Ok(())
+}
+
+#[test]
+fn test_builder_ingredient_validation_results() -> Result<()> {
+    use c2pa::{validation_status, Ingredient, Reader};
+
+    let format = "image/jpeg";
+    let mut ingredient_source = Cursor::new(include_bytes!("fixtures/CA.jpg"));
+    let ingredient = Ingredient::from_stream(format, &mut ingredient_source)?;
+    let source_manifest = ingredient.active_manifest().map(str::to_owned);
+    assert!(source_manifest.is_some());
+
+    // the results of validating the ingredient as it was added, successes included
+    let results = ingredient.validation_results().unwrap();
+    assert!(results
+        .active_manifest
+        .as_ref()
+        .unwrap()
+        .success
+        .iter()
+        .any(|s| s.code() == validation_status::CLAIM_SIGNATURE_VALIDATED));
+
+    let mut builder = Builder::from_json(r#"{"title": "Edited"}"#)?;
+    builder.add_ingredient(ingredient);
+
+    let mut source = Cursor::new(include_bytes!("fixtures/C.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+
+    // v3 ingredient assertions belong in v2 claims, so the v1 claim records validation status
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+    let manifest = reader.active_manifest().unwrap();
+    assert!(manifest
+        .assertions()
+        .iter()
+        .all(|assertion| assertion.label() != "c2pa.ingredient.v3"));
+    let ingredient = &manifest.ingredients()[0];
+    assert_eq!(ingredient.validation_results(), None);
+    assert_eq!(
+        ingredient.active_manifest().map(str::to_owned),
+        source_manifest
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_creative_work_round_trip() -> Result<()> {
+    use c2pa::{
+        assertions::{CreativeWork, SchemaDotOrg, SchemaDotOrgPerson},
+        Reader,
+    };
+
+    let publisher =
+        SchemaDotOrg::new("Organization".to_owned()).insert("name".to_owned(), "BBC News")?;
+    let creative_work = CreativeWork::new()
+        .add_author(SchemaDotOrgPerson::new_person(
+            "Joe Bloggs",
+            "https://en.wikipedia.org/wiki/Joe_Bloggs",
+        )?)?
+        .set_publisher(publisher)?
+        .set_url("https://www.bbc.co.uk/news/av/world-europe-57194011")?;
+
+    let mut builder = Builder::from_json(r#"{"title": "CA.jpg"}"#)?;
+    builder.add_assertion_json(CreativeWork::LABEL, &creative_work)?;
+
+    let format = "image/jpeg";
+    let mut source = Cursor::new(include_bytes!("fixtures/CA.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+    let result: CreativeWork = reader
+        .active_manifest()
+        .unwrap()
+        .find_assertion(CreativeWork::LABEL)?;
+
+    let authors = result.author().unwrap();
+    assert_eq!(authors[0].name().as_deref(), Some("Joe Bloggs"));
+    assert_eq!(
+        authors[0].identifier().as_deref(),
+        Some("https://en.wikipedia.org/wiki/Joe_Bloggs")
+    );
+    let publisher = result.publisher().unwrap();
+    assert_eq!(publisher.object_type(), "Organization");
+    assert_eq!(publisher.get::<String>("name").as_deref(), Some("BBC News"));
+    assert_eq!(
+        result.url().as_deref(),
+        Some("https://www.bbc.co.uk/news/av/world-europe-57194011")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_sign_to_archive() -> Result<()> {
+    use std::io::Seek;
+
+    use c2pa::Reader;
+
+    let format = "image/jpeg";
+    let asset = include_bytes!("fixtures/C.jpg");
+    let cloud_data = b"a resource too large to embed".to_vec();
+
+    let mut builder = Builder::from_json(r#"{"title": "Bundled"}"#)?;
+    builder.add_resource("cloud_data.bin", Cursor::new(cloud_data.clone()))?;
+
+    let mut source = Cursor::new(asset);
+    let mut archive = Cursor::new(Vec::new());
+    let manifest = builder.sign_to_archive(&test_signer(), format, &mut source, &mut archive)?;
+
+    archive.rewind()?;
+    let (reader, resources) = Reader::from_archive(&mut archive, format, Cursor::new(asset))?;
+    assert_eq!(reader.validation_status(), None);
+    assert_eq!(reader.active_manifest().unwrap().title(), Some("Bundled"));
+
+    // the bundled resources are unsigned, so they are kept out of the manifest
+    assert_eq!(resources.get("cloud_data.bin")?.as_slice(), cloud_data);
+    assert!(reader
+        .resource_to_stream("cloud_data.bin", Cursor::new(Vec::new()))
+        .is_err());
+
+    // the bundled manifest store is the one that was signed
+    let mut exported = Vec::new();
+    reader.export_manifest_to_stream(&mut exported)?;
+    assert_eq!(exported, manifest);
+    Ok(())
+}
+
+#[test]
+fn test_builder_actions_v2_round_trip() -> Result<()> {
+    use c2pa::{
+        assertions::{c2pa_action, Action, ActionTemplate, Actions},
+        Reader,
+    };
+
+    let mut template = ActionTemplate::new(c2pa_action::COLOR_ADJUSTMENTS);
+    template.description = Some("Auto white balance".to_owned());
+
+    let actions = Actions::new()
+        .add_action(
+            Action::new(c2pa_action::CREATED)
+                .set_source_type("http://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture"),
+        )
+        .add_action(Action::new(c2pa_action::COLOR_ADJUSTMENTS))
+        .add_template(template)
+        .set_all_actions_included(true);
+
+    let mut builder = Builder::from_json(r#"{"title": "C.jpg"}"#)?;
+    builder.add_assertion(Actions::LABEL, &actions)?;
+
+    let format = "image/jpeg";
+    let mut source = Cursor::new(include_bytes!("fixtures/C.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&test_signer(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+    assert_eq!(reader.validation_status(), None);
+    assert!(reader
+        .active_manifest()
+        .unwrap()
+        .assertions()
+        .iter()
+        .any(|assertion| assertion.label() == "c2pa.actions.v2"));
+
+    let result = reader.actions()?;
+    assert_eq!(result.all_actions_included(), Some(true));
+    assert_eq!(result.templates().len(), 1);
+    let template = result.template_for(&result.actions()[1]).unwrap();
+    assert_eq!(template.description.as_deref(), Some("Auto white balance"));
+    assert!(result.template_for(&result.actions()[0]).is_none());
+    Ok(())
+}
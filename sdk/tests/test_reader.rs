@@ -13,7 +13,7 @@
 
 mod common;
 use c2pa::{Error, Reader, Result};
-use common::{assert_err, compare_to_known_good, fixture_stream};
+use common::{assert_err, compare_to_known_good, fixture_stream, fixtures_path};
 
 #[test]
 #[cfg(feature = "file_io")]
@@ -52,6 +52,981 @@ fn test_reader_xca_jpg() -> Result<()> {
     compare_to_known_good(&reader, "XCA.json")
 }
 
+#[test]
+fn test_reader_is_trusted_and_bound() -> Result<()> {
+    use c2pa::{
+        validation_cache::{MemoryValidationCache, ValidationCache},
+        validation_status::{self, ValidationStatus},
+    };
+
+    // without trust verification the signer is never reported as trusted
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+    assert!(!reader.is_trusted_and_bound());
+
+    // cached results stand in for a validation with trust configured
+    let label = reader.active_label().unwrap().to_owned();
+    let status = |code: &str, uri: &str| -> ValidationStatus {
+        serde_json::from_value(serde_json::json!({
+            "code": code,
+            "url": format!("self#jumbf=/c2pa/{label}/{uri}")
+        }))
+        .unwrap()
+    };
+    let validated = vec![
+        status(
+            validation_status::CLAIM_SIGNATURE_VALIDATED,
+            "c2pa.signature",
+        ),
+        status(
+            validation_status::SIGNING_CREDENTIAL_TRUSTED,
+            "c2pa.signature",
+        ),
+        status(
+            validation_status::ASSERTION_DATAHASH_MATCH,
+            "c2pa.assertions/c2pa.hash.data",
+        ),
+    ];
+
+    let (_, mut stream) = fixture_stream("CA.jpg")?;
+    let digest = c2pa::hash_stream_by_alg("sha256", &mut stream, None, true)?;
+    let is_trusted_and_bound = |results: Vec<ValidationStatus>| -> Result<bool> {
+        let cache = MemoryValidationCache::new(1);
+        cache.insert(&digest, results);
+        let (format, mut stream) = fixture_stream("CA.jpg")?;
+        Ok(Reader::from_stream_with_cache(&format, &mut stream, &cache)?.is_trusted_and_bound())
+    };
+
+    assert!(is_trusted_and_bound(validated.clone())?);
+
+    // every success code is required
+    for missing in 0..validated.len() {
+        let mut results = validated.clone();
+        results.remove(missing);
+        assert!(!is_trusted_and_bound(results)?);
+    }
+
+    // and any failure of the active manifest is disqualifying, not just known ones
+    for failure in [
+        status(
+            validation_status::ASSERTION_HASHEDURI_MISMATCH,
+            "c2pa.assertions/c2pa.actions",
+        ),
+        status(
+            validation_status::STATUS_DEVELOPMENT_TRUST,
+            "c2pa.signature",
+        ),
+        status("com.example.unknown.failure", "c2pa.signature"),
+    ] {
+        let mut results = validated.clone();
+        results.push(failure);
+        assert!(!is_trusted_and_bound(results)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reader_tampered_is_not_trusted_and_bound() -> Result<()> {
+    // XCA.jpg is CA.jpg with modified image data, so its data hash no longer matches.
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+    assert!(!reader.is_trusted_and_bound());
+    Ok(())
+}
+
+#[test]
+fn test_reader_validation_status_code() -> Result<()> {
+    use c2pa::validation_status::ValidationStatusCode;
+
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+    let codes: Vec<_> = reader
+        .validation_status()
+        .unwrap_or_default()
+        .iter()
+        .map(|status| status.status_code())
+        .collect();
+    assert_eq!(
+        codes,
+        vec![Some(ValidationStatusCode::AssertionDataHashMismatch)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_validation_reports() -> Result<()> {
+    // CA.jpg has two manifests: the active one and the one from its ingredient C.jpg.
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+
+    let reports = reader.validation_reports();
+    assert_eq!(reports.len(), 2);
+    assert!(!reports[0].is_active);
+    assert!(reports[1].is_active);
+    assert_eq!(Some(reports[1].label.as_str()), reader.active_label());
+    assert_ne!(reports[0].label, reports[1].label);
+    assert!(reports.iter().all(|report| report.is_valid()));
+    Ok(())
+}
+
+#[test]
+fn test_reader_validation_reports_tampered() -> Result<()> {
+    // XCA.jpg has modified image data, which only invalidates the active manifest.
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+
+    let reports = reader.validation_reports();
+    assert_eq!(reports.len(), 2);
+    assert!(reports[0].is_valid());
+    assert!(reports[1].is_active);
+    assert!(reports[1]
+        .validation_status
+        .iter()
+        .any(|status| status.code() == c2pa::validation_status::ASSERTION_DATAHASH_MISMATCH));
+    Ok(())
+}
+
+#[test]
+fn test_reader_validate_assertion() -> Result<()> {
+    use c2pa::validation_status;
+
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let status = Reader::validate_assertion(&format, &mut stream, "c2pa.hash.data")?;
+    assert_eq!(status.code(), validation_status::ASSERTION_DATAHASH_MATCH);
+    assert!(status.url().unwrap().ends_with("c2pa.hash.data"));
+
+    // assertions other than hard bindings are only checked against the claim
+    let status = Reader::validate_assertion(&format, &mut stream, "c2pa.actions")?;
+    assert_eq!(status.code(), validation_status::ASSERTION_HASHEDURI_MATCH);
+
+    // XCA.jpg is CA.jpg with modified image data
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let status = Reader::validate_assertion(&format, &mut stream, "c2pa.hash.data")?;
+    assert_eq!(
+        status.code(),
+        validation_status::ASSERTION_DATAHASH_MISMATCH
+    );
+
+    assert_err!(
+        Reader::validate_assertion(&format, &mut stream, "org.example.unknown"),
+        Err(Error::AssertionMissing { .. })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_validation_cache_hit() -> Result<()> {
+    use c2pa::validation_cache::MemoryValidationCache;
+
+    let cache = MemoryValidationCache::new(8);
+
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let first = Reader::from_stream_with_cache(&format, &mut stream, &cache)?;
+    assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let second = Reader::from_stream_with_cache(&format, &mut stream, &cache)?;
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    assert_eq!(cache.len(), 1);
+
+    let codes = |reader: &Reader| -> Vec<String> {
+        reader
+            .validation_status()
+            .unwrap_or_default()
+            .iter()
+            .map(|status| status.code().to_owned())
+            .collect()
+    };
+    assert!(codes(&first)
+        .iter()
+        .any(|code| code == c2pa::validation_status::ASSERTION_DATAHASH_MISMATCH));
+    assert_eq!(codes(&first), codes(&second));
+    assert_eq!(first.active_label(), second.active_label());
+    Ok(())
+}
+
+#[test]
+fn test_reader_validation_cache_skips_validation() -> Result<()> {
+    use c2pa::validation_cache::{MemoryValidationCache, ValidationCache};
+
+    // a cache entry for the tampered asset claiming it is valid is trusted without validating
+    let (format, mut stream) = fixture_stream("XCA.jpg")?;
+    let digest = c2pa::hash_stream_by_alg("sha256", &mut stream, None, true)?;
+    let cache = MemoryValidationCache::new(8);
+    cache.insert(&digest, Vec::new());
+
+    let reader = Reader::from_stream_with_cache(&format, &mut stream, &cache)?;
+    assert!(reader.validation_status().is_none());
+    assert_eq!(cache.hits(), 1);
+
+    // different bytes miss the cache and are validated
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream_with_cache(&format, &mut stream, &cache)?;
+    assert!(reader.validation_status().is_none());
+    assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 1, 2));
+    Ok(())
+}
+
+#[test]
+fn test_reader_from_legacy_stream() -> Result<()> {
+    use c2pa::{assertions::Actions, validation_status::STATUS_MIGRATED};
+
+    let (format, mut stream) = fixture_stream("prerelease.jpg")?;
+    let result = Reader::from_stream(&format, &mut stream);
+    assert_err!(result, Err(Error::PrereleaseError));
+
+    let (format, mut stream) = fixture_stream("prerelease.jpg")?;
+    let reader = Reader::from_legacy_stream(&format, &mut stream)?;
+
+    let manifest = reader.active_manifest().unwrap();
+    assert_eq!(manifest.title(), Some("CA.jpg"));
+    assert!(manifest.signature_info().is_none());
+
+    // string parameters are migrated to a description
+    let actions: Actions = manifest.find_assertion(Actions::LABEL)?;
+    assert!(actions
+        .actions()
+        .iter()
+        .any(|action| action.get_parameter("description").is_some()));
+
+    assert!(reader
+        .validation_status()
+        .unwrap()
+        .iter()
+        .any(|status| status.code() == STATUS_MIGRATED));
+    Ok(())
+}
+
+/// Verifies a signed payload with OpenSSL, independently of the SDK.
+#[cfg(feature = "openssl_sign")]
+fn verify_signed_payload_with_openssl(payload: &c2pa::SignedPayload) -> bool {
+    use c2pa::SigningAlg;
+    use openssl::{
+        bn::BigNum,
+        ecdsa::EcdsaSig,
+        hash::MessageDigest,
+        rsa::Padding,
+        sign::{RsaPssSaltlen, Verifier},
+        x509::X509,
+    };
+
+    let public_key = X509::from_der(&payload.signing_cert)
+        .unwrap()
+        .public_key()
+        .unwrap();
+
+    match payload.alg {
+        SigningAlg::Ps256 => {
+            let mut verifier = Verifier::new(MessageDigest::sha256(), &public_key).unwrap();
+            verifier.set_rsa_padding(Padding::PKCS1_PSS).unwrap();
+            verifier
+                .set_rsa_pss_saltlen(RsaPssSaltlen::DIGEST_LENGTH)
+                .unwrap();
+            verifier.update(&payload.to_be_signed).unwrap();
+            verifier.verify(&payload.signature).unwrap()
+        }
+        SigningAlg::Es256 => {
+            // COSE uses r || s, OpenSSL expects a DER encoded signature
+            let (r, s) = payload.signature.split_at(payload.signature.len() / 2);
+            let der_sig = EcdsaSig::from_private_components(
+                BigNum::from_slice(r).unwrap(),
+                BigNum::from_slice(s).unwrap(),
+            )
+            .unwrap()
+            .to_der()
+            .unwrap();
+
+            let mut verifier = Verifier::new(MessageDigest::sha256(), &public_key).unwrap();
+            verifier.update(&payload.to_be_signed).unwrap();
+            verifier.verify(&der_sig).unwrap()
+        }
+        alg => panic!("unexpected signing alg {alg}"),
+    }
+}
+
+#[test]
+#[cfg(feature = "openssl_sign")]
+fn test_reader_signed_payload_rsa() -> Result<()> {
+    use c2pa::SigningAlg;
+
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+
+    let mut payload = reader.signed_payload()?;
+    assert_eq!(payload.alg, SigningAlg::Ps256);
+    assert!(verify_signed_payload_with_openssl(&payload));
+
+    // any change to the signed bytes must fail verification
+    payload.to_be_signed[0] ^= 0xff;
+    assert!(!verify_signed_payload_with_openssl(&payload));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "openssl_sign")]
+fn test_reader_signed_payload_ecdsa() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::{create_signer, Builder, SigningAlg};
+
+    let signer = create_signer::from_files(
+        common::fixtures_path("certs/es256.pub"),
+        common::fixtures_path("certs/es256.pem"),
+        SigningAlg::Es256,
+        None,
+    )?;
+
+    let (format, mut source) = fixture_stream("IMG_0003.jpg")?;
+    let mut dest = Cursor::new(Vec::new());
+    let mut builder = Builder::from_json(r#"{"title": "Signed payload test"}"#)?;
+    builder.sign(signer.as_ref(), &format, &mut source, &mut dest)?;
+
+    dest.rewind()?;
+    let reader = Reader::from_stream(&format, &mut dest)?;
+
+    let payload = reader.signed_payload()?;
+    assert_eq!(payload.alg, SigningAlg::Es256);
+    assert!(verify_signed_payload_with_openssl(&payload));
+    Ok(())
+}
+
+#[test]
+fn test_reader_software_agent_chain() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::{
+        assertions::{c2pa_action, Action, Actions, SoftwareAgent},
+        Builder,
+    };
+
+    // signs the source with a manifest whose actions were performed by the given agents,
+    // as a version 2 actions assertion if `v2` is set
+    fn sign_with_agents(
+        source: &mut Cursor<Vec<u8>>,
+        agents: &[&str],
+        parent: bool,
+        v2: bool,
+    ) -> Result<Cursor<Vec<u8>>> {
+        let mut actions = agents.iter().fold(Actions::new(), |actions, agent| {
+            actions.add_action(Action::new(c2pa_action::EDITED).set_software_agent(*agent))
+        });
+        if v2 {
+            actions = actions.set_all_actions_included(false);
+        }
+
+        let mut builder = Builder::from_json(r#"{"title": "chain.jpg"}"#)?;
+        builder.add_assertion(Actions::LABEL, &actions)?;
+        if parent {
+            source.rewind()?;
+            builder.add_ingredient_from_stream(
+                r#"{"title": "parent.jpg", "relationship": "parentOf"}"#,
+                "image/jpeg",
+                source,
+            )?;
+        }
+
+        source.rewind()?;
+        let mut dest = Cursor::new(Vec::new());
+        builder.sign(&common::test_signer(), "image/jpeg", source, &mut dest)?;
+        dest.rewind()?;
+        Ok(dest)
+    }
+
+    let data = std::fs::read(common::fixtures_path("IMG_0003.jpg"))?;
+    let mut first = sign_with_agents(&mut Cursor::new(data), &["Tool A"], false, false)?;
+    let mut second = sign_with_agents(&mut first, &["Tool B", "Tool B"], true, true)?;
+    let mut third = sign_with_agents(&mut second, &["Tool B", "Tool C", "Tool A"], true, false)?;
+
+    // the third manifest's own use of Tool B is listed after the second's
+    let reader = Reader::from_stream("image/jpeg", &mut third)?;
+    assert_eq!(
+        reader.software_agent_chain(),
+        ["Tool A", "Tool B", "Tool B", "Tool C", "Tool A"].map(SoftwareAgent::from)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_timestamp_reports() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::{assertions::TimeStamp, Builder, TimestampSource};
+
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+    let reports = reader.timestamp_reports();
+    let report = reports
+        .iter()
+        .find(|r| Some(r.manifest.as_str()) == reader.active_label())
+        .unwrap();
+    assert_eq!(report.source, TimestampSource::Signature);
+    assert_eq!(report.found_in, report.manifest);
+    assert_eq!(report.time.as_deref(), Some("2024-08-06T21:53:37+00:00"));
+
+    // sign an asset, then time-stamp its manifest from a later one
+    let mut source = Cursor::new(std::fs::read(common::fixtures_path("IMG_0003.jpg"))?);
+    let mut first = Cursor::new(Vec::new());
+    Builder::from_json(r#"{"title": "first.jpg"}"#)?.sign(
+        &common::test_signer(),
+        "image/jpeg",
+        &mut source,
+        &mut first,
+    )?;
+    first.rewind()?;
+    let first_label = Reader::from_stream("image/jpeg", &mut first)?
+        .active_label()
+        .unwrap()
+        .to_owned();
+
+    // the fixture time-stamp is not over this manifest's signature
+    let mut timestamp = TimeStamp::new();
+    timestamp.add_timestamp(
+        &first_label,
+        &std::fs::read(common::fixtures_path("sig_es256.tsr"))?,
+    );
+
+    let mut builder = Builder::from_json(r#"{"title": "second.jpg"}"#)?;
+    first.rewind()?;
+    builder.add_ingredient_from_stream(
+        r#"{"title": "first.jpg", "relationship": "parentOf"}"#,
+        "image/jpeg",
+        &mut first,
+    )?;
+    builder.add_assertion(TimeStamp::LABEL, &timestamp)?;
+    first.rewind()?;
+    let mut second = Cursor::new(Vec::new());
+    builder.sign(
+        &common::test_signer(),
+        "image/jpeg",
+        &mut first,
+        &mut second,
+    )?;
+    second.rewind()?;
+
+    let reader = Reader::from_stream("image/jpeg", &mut second)?;
+    let report = reader
+        .timestamp_reports()
+        .into_iter()
+        .find(|r| r.source == TimestampSource::Assertion)
+        .unwrap();
+    assert_eq!(report.manifest, first_label);
+    assert_eq!(Some(report.found_in.as_str()), reader.active_label());
+    assert_eq!(report.status, "timeStamp.mismatch");
+    assert!(report.time.is_some());
+    assert!(!report.is_trusted());
+    Ok(())
+}
+
+#[test]
+fn test_reader_verify_timestamp() -> Result<()> {
+    use std::io::Cursor;
+
+    use c2pa::{Builder, TimestampSource};
+
+    let (format, stream) = fixture_stream("CA.jpg")?;
+    let report = Reader::verify_timestamp(&format, stream)?.unwrap();
+    assert_eq!(report.source, TimestampSource::Signature);
+    assert_eq!(report.time.as_deref(), Some("2024-08-06T21:53:37+00:00"));
+    assert!(
+        report.status == "timeStamp.trusted" || report.status == "timeStamp.untrusted",
+        "{}",
+        report.status
+    );
+
+    // the time-stamp is verified on its own, so a broken hard binding doesn't affect it
+    let (format, stream) = fixture_stream("XCA.jpg")?;
+    let tampered = Reader::verify_timestamp(&format, stream)?.unwrap();
+    assert_eq!(tampered.time, report.time);
+    assert_eq!(tampered.status, report.status);
+
+    // the test signer doesn't time-stamp its signatures
+    let mut source = Cursor::new(std::fs::read(common::fixtures_path("IMG_0003.jpg"))?);
+    let mut signed = Cursor::new(Vec::new());
+    Builder::from_json(r#"{"title": "signed.jpg"}"#)?.sign(
+        &common::test_signer(),
+        "image/jpeg",
+        &mut source,
+        &mut signed,
+    )?;
+    signed.set_position(0);
+    assert!(Reader::verify_timestamp("image/jpeg", signed)?.is_none());
+
+    let (format, stream) = fixture_stream("sample1.png")?;
+    assert_err!(
+        Reader::verify_timestamp(&format, stream),
+        Err(Error::JumbfNotFound)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_changed_regions() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::{
+        assertions::region_of_interest::{PageRect, RegionOfInterest, Role},
+        Builder,
+    };
+
+    let page_box = PageRect::new(0.0, 0.0, 612.0, 792.0);
+    let face = PageRect::new(153.0, 594.0, 306.0, 792.0);
+    let region = RegionOfInterest::page_rectangle(0, face, page_box).set_role(Role::Redacted);
+
+    let manifest_def = serde_json::json!({
+        "title": "blurred.jpg",
+        "assertions": [{
+            "label": "c2pa.actions",
+            "data": {"actions": [{"action": "c2pa.edited", "changes": [region]}]}
+        }]
+    });
+    let mut builder = Builder::from_json(&manifest_def.to_string())?;
+
+    let (format, mut source) = fixture_stream("IMG_0003.jpg")?;
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut dest)?;
+    dest.rewind()?;
+
+    let reader = Reader::from_stream(&format, &mut dest)?;
+    let regions = reader.changed_regions();
+    assert_eq!(regions, vec![("c2pa.edited".to_owned(), region)]);
+    assert_eq!(regions[0].1.to_page_rectangle(page_box), Some((0, face)));
+    Ok(())
+}
+
+#[test]
+fn test_reader_ingredient_thumbnails() -> Result<()> {
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+    let label = reader.active_label().unwrap();
+
+    let (claim_format, claim_thumbnail) = reader.manifest_thumbnail(label).unwrap();
+    assert_eq!(claim_format, "image/jpeg");
+
+    let ingredient_thumbnails = reader.ingredient_thumbnails(label);
+    assert_eq!(ingredient_thumbnails.len(), 1);
+    let (ingredient, format, data) = &ingredient_thumbnails[0];
+    assert!(ingredient.is_parent());
+    assert_eq!(*format, "image/jpeg");
+    assert_ne!(data, &claim_thumbnail);
+    assert_ne!(
+        ingredient.thumbnail_ref().unwrap().identifier,
+        reader
+            .active_manifest()
+            .unwrap()
+            .thumbnail_ref()
+            .unwrap()
+            .identifier
+    );
+
+    assert!(reader.ingredient_thumbnails("not a manifest").is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_reader_assertion_digest_mismatches() -> Result<()> {
+    use std::io::Cursor;
+
+    use c2pa::Builder;
+
+    let mut builder = Builder::from_json(r#"{"title": "IMG_0003.jpg"}"#)?;
+    builder.add_assertion_json(
+        "org.example.notes",
+        &serde_json::json!({"note": "untouched note"}),
+    )?;
+    builder.add_assertion_json("org.example.other", &serde_json::json!({"note": "kept"}))?;
+    let (format, mut source) = fixture_stream("IMG_0003.jpg")?;
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut dest)?;
+
+    let reader = Reader::from_stream(&format, Cursor::new(dest.get_ref().clone()))?;
+    assert!(reader.assertion_digest_mismatches().is_empty());
+
+    // alter one assertion without changing its size
+    let mut signed = dest.into_inner();
+    let pos = signed
+        .windows(14)
+        .position(|w| w == b"untouched note")
+        .unwrap();
+    signed[pos..pos + 14].copy_from_slice(b"tampered notes");
+
+    let reader = Reader::from_stream(&format, Cursor::new(signed))?;
+    let mismatches = reader.assertion_digest_mismatches();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].manifest, reader.active_label().unwrap());
+    assert_eq!(mismatches[0].label, "org.example.notes");
+    assert_ne!(mismatches[0].expected, mismatches[0].actual);
+    assert!(reader.validation_status().unwrap().iter().any(|status| {
+        status.code() == c2pa::validation_status::ASSERTION_HASHEDURI_MISMATCH
+            && status
+                .url()
+                .is_some_and(|url| url.contains("org.example.notes"))
+    }));
+    Ok(())
+}
+
+#[test]
+fn test_reader_identity_reports() -> Result<()> {
+    use std::io::Cursor;
+
+    use c2pa::{
+        assertions::{IdentityAssertion, SignerPayload},
+        validation_status, Builder,
+    };
+
+    // the signer payload can't reference the hard binding, which is computed at signing time
+    let signer_payload = SignerPayload::new(SignerPayload::X509_COSE, Vec::new())
+        .set_roles(vec!["cawg.creator".to_owned()]);
+    let identity = IdentityAssertion::new(signer_payload, Vec::new());
+
+    let mut builder = Builder::from_json(r#"{"title": "IMG_0003.jpg"}"#)?;
+    builder.add_assertion(IdentityAssertion::LABEL, &identity)?;
+    let (format, mut source) = fixture_stream("IMG_0003.jpg")?;
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut dest)?;
+
+    let reader = Reader::from_stream(&format, Cursor::new(dest.into_inner()))?;
+    let reports = reader.identity_reports();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].manifest, reader.active_label().unwrap());
+    assert_eq!(reports[0].label, IdentityAssertion::LABEL);
+    assert_eq!(
+        reports[0].sig_type.as_deref(),
+        Some(SignerPayload::X509_COSE)
+    );
+    assert_eq!(reports[0].roles, ["cawg.creator"]);
+    assert_eq!(reports[0].identity, None);
+    assert_eq!(
+        reports[0].status,
+        validation_status::CAWG_IDENTITY_HARD_BINDING_MISSING
+    );
+    assert!(!reports[0].is_trusted());
+
+    let reader = Reader::from_stream("image/jpeg", fixture_stream("CA.jpg")?.1)?;
+    assert!(reader.identity_reports().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_reader_check_thumbnail() -> Result<()> {
+    use std::io::{Cursor, Read, Seek};
+
+    use c2pa::{Builder, CAIRead, ThumbnailCheck};
+
+    // signs IMG_0003.jpg with the given claim thumbnail
+    fn sign_with_thumbnail(format: &str, thumbnail: &[u8]) -> Result<Cursor<Vec<u8>>> {
+        let mut builder = Builder::from_json(r#"{"title": "IMG_0003.jpg"}"#)?;
+        builder.set_thumbnail(format, &mut Cursor::new(thumbnail))?;
+
+        let (asset_format, mut source) = fixture_stream("IMG_0003.jpg")?;
+        let mut dest = Cursor::new(Vec::new());
+        builder.sign(
+            &common::test_signer(),
+            &asset_format,
+            &mut source,
+            &mut dest,
+        )?;
+        dest.rewind()?;
+        Ok(dest)
+    }
+
+    fn check(format: &str, thumbnail: &[u8]) -> Result<ThumbnailCheck> {
+        let mut asset = sign_with_thumbnail(format, thumbnail)?;
+        let reader = Reader::from_stream("image/jpeg", &mut asset)?;
+        reader.check_thumbnail("image/jpeg", &mut asset, None)
+    }
+
+    let asset = std::fs::read(common::fixtures_path("IMG_0003.jpg"))?;
+    assert_eq!(check("image/jpeg", &asset)?, ThumbnailCheck::Valid);
+
+    // a JPEG header followed by garbage
+    let corrupt = b"\xff\xd8\xff\xe0not really a jpeg";
+    assert!(matches!(
+        check("image/jpeg", corrupt)?,
+        ThumbnailCheck::Corrupt(_)
+    ));
+    // a valid image that isn't the declared format
+    assert!(matches!(
+        check("image/png", &asset)?,
+        ThumbnailCheck::Corrupt(_)
+    ));
+
+    // square, and larger than the asset
+    let square = std::fs::read(common::fixtures_path("earth_apollo17.jpg"))?;
+    assert_eq!(
+        check("image/jpeg", &square)?,
+        ThumbnailCheck::DimensionMismatch {
+            thumbnail: (375, 375),
+            asset: (1024, 683)
+        }
+    );
+    let larger = std::fs::read(common::fixtures_path("C.jpg"))?;
+    assert!(matches!(
+        check("image/jpeg", &larger)?,
+        ThumbnailCheck::DimensionMismatch { .. }
+    ));
+
+    // the matcher has the final say
+    let mut signed = sign_with_thumbnail("image/jpeg", &asset)?;
+    let reader = Reader::from_stream("image/jpeg", &mut signed)?;
+    let reject = |_: &str, _: &[u8], _: &str, _: &mut dyn CAIRead| -> Result<bool> { Ok(false) };
+    assert_eq!(
+        reader.check_thumbnail("image/jpeg", &mut signed, Some(&reject))?,
+        ThumbnailCheck::Mismatch
+    );
+    let accept_jpeg =
+        |format: &str, thumbnail: &[u8], _: &str, asset: &mut dyn CAIRead| -> Result<bool> {
+            let mut data = Vec::new();
+            asset.read_to_end(&mut data)?;
+            Ok(format == "image/jpeg" && thumbnail.len() <= data.len())
+        };
+    assert_eq!(
+        reader.check_thumbnail("image/jpeg", &mut signed, Some(&accept_jpeg))?,
+        ThumbnailCheck::Valid
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_assertion_origins() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::{AssertionOrigin, Builder};
+
+    let manifest_def = serde_json::json!({
+        "title": "IMG_0003.jpg",
+        "assertions": [
+            {
+                "label": "org.contentauth.test.created",
+                "data": {"made": "here"}
+            }
+        ]
+    })
+    .to_string();
+
+    let (format, mut source) = fixture_stream("IMG_0003.jpg")?;
+    let mut builder = Builder::from_json(&manifest_def)?;
+    let mut dest = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut dest)?;
+    dest.rewind()?;
+
+    let reader = Reader::from_stream(&format, &mut dest)?;
+    assert_eq!(reader.validation_status(), None);
+
+    // every assertion of a v1 claim was created by its claim generator
+    let origins = reader.assertion_origins(reader.active_label().unwrap());
+    let origin = |label: &str| {
+        origins
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, origin)| *origin)
+    };
+    for label in ["org.contentauth.test.created", "c2pa.hash.data"] {
+        assert_eq!(origin(label), Some(AssertionOrigin::Created));
+    }
+
+    assert!(reader.assertion_origins("unknown").is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "pdf", feature = "file_io"))]
+fn test_reader_export_manifest_pdf() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::Builder;
+
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    let (format, mut source) = fixture_stream("basic.pdf")?;
+    let mut signed = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut signed)?;
+    signed.rewind()?;
+    let reader = Reader::from_stream(&format, &mut signed)?;
+    assert_eq!(reader.validation_status(), None);
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("basic.c2pa");
+    reader.export_manifest(&path)?;
+    let exported = std::fs::read(&path)?;
+
+    let mut streamed = Vec::new();
+    reader.export_manifest_to_stream(&mut streamed)?;
+    assert_eq!(streamed, exported);
+
+    // the exported manifest store validates against the asset it was exported from
+    signed.rewind()?;
+    let detached = Reader::from_manifest_data_and_stream(&exported, &format, &mut signed)?;
+    assert_eq!(detached.validation_status(), None);
+    assert_eq!(detached.active_label(), reader.active_label());
+
+    let mut reexported = Vec::new();
+    detached.export_manifest_to_stream(&mut reexported)?;
+    assert_eq!(reexported, exported);
+
+    // a reader that wasn't read from an asset has nothing to export
+    assert_err!(
+        Reader::default().export_manifest_to_stream(Vec::new()),
+        Err(Error::JumbfNotFound)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_extract_manifest_to_stream() -> Result<()> {
+    let mut fixtures = vec!["CA.jpg"];
+    if cfg!(feature = "pdf") {
+        fixtures.push("express-signed.pdf");
+    }
+
+    for name in fixtures {
+        let (format, mut source) = fixture_stream(name)?;
+        let mut extracted = Vec::new();
+        let len = Reader::extract_manifest_to_stream(&format, &mut source, &mut extracted)?;
+        assert_eq!(len, extracted.len() as u64);
+
+        // the same manifest store a full read exports
+        let (format, source) = fixture_stream(name)?;
+        let mut exported = Vec::new();
+        Reader::from_stream(&format, source)?.export_manifest_to_stream(&mut exported)?;
+        assert_eq!(extracted, exported);
+    }
+
+    let (format, source) = fixture_stream("no_manifest.jpg")?;
+    assert_err!(
+        Reader::extract_manifest_to_stream(&format, source, std::io::sink()),
+        Err(Error::JumbfNotFound)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_from_manifest_and_asset() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::Builder;
+
+    let mut builder = Builder::from_json(r#"{"title": "Detached Test"}"#)?;
+    builder.set_no_embed(true);
+    let (format, mut source) = fixture_stream("CA.jpg")?;
+    let mut asset = Cursor::new(Vec::new());
+    let manifest = builder.sign(&common::test_signer(), &format, &mut source, &mut asset)?;
+
+    asset.rewind()?;
+    let reader = Reader::from_manifest_and_asset(&manifest, &format, &mut asset)?;
+    assert_eq!(reader.validation_status(), None);
+    assert_eq!(
+        reader.active_manifest().and_then(|m| m.title()),
+        Some("Detached Test")
+    );
+
+    // the same manifest does not validate against a different asset
+    let (_, other) = fixture_stream("C.jpg")?;
+    let reader = Reader::from_manifest_and_asset(&manifest, &format, other)?;
+    assert!(reader.hard_binding_broken());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_reader_hash_exclusions_pdf() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::Builder;
+
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    let (format, mut source) = fixture_stream("basic.pdf")?;
+    let mut signed = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut signed)?;
+    signed.rewind()?;
+    let reader = Reader::from_stream(&format, &mut signed)?;
+    assert_eq!(reader.validation_status(), None);
+
+    // the manifest store is the only region left out of the hash
+    let exclusions = reader.hash_exclusions(&mut signed)?;
+    assert_eq!(exclusions.len(), 1);
+    let exclusion = &exclusions[0];
+    assert_eq!(exclusion.label, "c2pa.hash.data");
+    assert_eq!(exclusion.length, exclusion.bytes.len());
+    assert!(exclusion.start + exclusion.length <= signed.get_ref().len());
+
+    let mut manifest_store = Vec::new();
+    reader.export_manifest_to_stream(&mut manifest_store)?;
+    assert_eq!(exclusion.bytes, manifest_store);
+
+    // an excluded region past the end of the stream is an error
+    let truncated = signed.get_ref()[..exclusion.start + 1].to_vec();
+    assert!(reader.hash_exclusions(Cursor::new(truncated)).is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_reader_modification_report_pdf() -> Result<()> {
+    use std::io::{Cursor, Seek};
+
+    use c2pa::Builder;
+
+    let mut builder = Builder::from_json(r#"{"title": "basic.pdf"}"#)?;
+    let (format, mut source) = fixture_stream("basic.pdf")?;
+    let mut signed = Cursor::new(Vec::new());
+    builder.sign(&common::test_signer(), &format, &mut source, &mut signed)?;
+    signed.rewind()?;
+    let reader = Reader::from_stream(&format, &mut signed)?;
+
+    let report = reader.modification_report(&format, &mut signed)?;
+    assert!(!report.modified);
+    assert!(report.regions.is_empty());
+
+    // a comment appended to the signed PDF is part of the hashed data
+    let mut tampered = signed.into_inner();
+    tampered.extend_from_slice(b"% edited\n");
+    let edit = tampered.len() - 1;
+
+    let report = reader.modification_report(&format, Cursor::new(&tampered))?;
+    assert!(report.modified);
+    assert!(report
+        .regions
+        .iter()
+        .any(|region| region.label == "c2pa.hash.data"
+            && region.start <= edit
+            && edit < region.start + region.length));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_reader_from_stream_or_remote() -> Result<()> {
+    use c2pa::ManifestSource;
+
+    // the PDF has no embedded manifest, only a dcterms:provenance URL in its XMP
+    let (format, mut stream) = fixture_stream("basic-remote-manifest.pdf")?;
+    match Reader::from_stream_or_remote(&format, &mut stream)? {
+        ManifestSource::Remote(url) => assert_eq!(
+            url,
+            "https://cai-manifests.adobe.com/manifests/adobe-urn-uuid-5f37e182-3687-462e-a7fb-573462780391"
+        ),
+        _ => panic!("expected a remote manifest"),
+    }
+
+    #[cfg(not(feature = "fetch_remote_manifests"))]
+    assert_err!(
+        Reader::from_stream(&format, fixture_stream("basic-remote-manifest.pdf")?.1),
+        Err(Error::RemoteManifestUrl(_))
+    );
+
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    assert!(matches!(
+        Reader::from_stream_or_remote(&format, &mut stream)?,
+        ManifestSource::Embedded(_)
+    ));
+
+    let (format, mut stream) = fixture_stream("basic-no-xmp.pdf")?;
+    assert_err!(
+        Reader::from_stream_or_remote(&format, &mut stream),
+        Err(Error::JumbfNotFound)
+    );
+    Ok(())
+}
+
 #[test]
 #[ignore]
 /// Generates the known good for the above tests
@@ -64,3 +1039,222 @@ fn write_known_goods() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "openssl")]
+fn test_reader_verify_with_public_key() -> Result<()> {
+    use std::io::Cursor;
+
+    use c2pa::Builder;
+
+    let format = "image/jpeg";
+    let mut source = Cursor::new(include_bytes!("fixtures/C.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    let mut builder = Builder::from_json(r#"{"title": "Pinned"}"#)?;
+    builder.sign(&common::test_signer(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+
+    // the public key of the signing certificate
+    let signer_key = include_bytes!("fixtures/certs/ed25519.pub_key");
+    assert!(reader.verify_with_public_key(signer_key)?);
+
+    // a key of the same type that did not sign the claim
+    let other_key = include_bytes!("fixtures/certs/ed25519_root.pub_key");
+    assert!(!reader.verify_with_public_key(other_key)?);
+
+    assert!(matches!(
+        reader.verify_with_public_key(b"not a key"),
+        Err(Error::BadParam(_))
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "openssl")]
+fn test_reader_matching_trust_list() -> Result<()> {
+    use std::io::Cursor;
+
+    use c2pa::{create_signer, settings::TrustList, Builder, SigningAlg};
+
+    let signer = create_signer::from_chain(
+        &[
+            include_bytes!("fixtures/certs/chain/es256_signer.pem").as_slice(),
+            include_bytes!("fixtures/certs/chain/es256_intermediate.pem").as_slice(),
+        ],
+        include_bytes!("fixtures/certs/es256.pem"),
+        SigningAlg::Es256,
+        None,
+    )?;
+
+    let format = "image/jpeg";
+    let mut source = Cursor::new(include_bytes!("fixtures/C.jpg"));
+    let mut dest = Cursor::new(Vec::new());
+    let mut builder = Builder::from_json(r#"{"title": "Federated"}"#)?;
+    builder.sign(signer.as_ref(), format, &mut source, &mut dest)?;
+
+    dest.set_position(0);
+    let reader = Reader::from_stream(format, &mut dest)?;
+
+    // only the second list holds the root the signing certificate chains to
+    let other = TrustList::new(
+        "other",
+        include_bytes!("fixtures/certs/chain/other_root.pem").as_slice(),
+    );
+    let es256 = TrustList::new(
+        "es256",
+        include_bytes!("fixtures/certs/chain/es256_root.pem").as_slice(),
+    );
+    assert_eq!(
+        reader.matching_trust_list(&[other.clone(), es256])?,
+        Some("es256")
+    );
+    assert_eq!(reader.matching_trust_list(&[other])?, None);
+    assert_eq!(reader.matching_trust_list(&[])?, None);
+    Ok(())
+}
+
+#[test]
+fn test_reader_size_breakdown() -> Result<()> {
+    let (format, mut stream) = fixture_stream("CA.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+
+    let sizes = reader.size_breakdown()?;
+    assert!(sizes["claim"] > 0);
+    assert!(sizes["signature"] > 0);
+    assert!(sizes.contains_key("c2pa.actions"));
+
+    // thumbnails dominate the manifest
+    let (largest, _) = sizes.iter().max_by_key(|(_, size)| **size).unwrap();
+    assert_eq!(largest, "c2pa.thumbnail.claim.jpeg");
+    let total: usize = sizes.values().sum();
+    let thumbnails: usize = sizes
+        .iter()
+        .filter(|(label, _)| label.starts_with("c2pa.thumbnail"))
+        .map(|(_, size)| size)
+        .sum();
+    assert!(thumbnails > total / 2);
+    Ok(())
+}
+
+#[test]
+fn test_reader_from_base64_manifest_and_bytes() -> Result<()> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    // encode the manifest store with line breaks, as base64 often is when transported
+    let manifest = std::fs::read(fixtures_path("cloud_manifest.c2pa"))?;
+    let manifest_base64 = general_purpose::STANDARD
+        .encode(manifest)
+        .as_bytes()
+        .chunks(76)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let asset = std::fs::read(fixtures_path("cloud.jpg"))?;
+
+    let reader = Reader::from_base64_manifest_and_bytes(&manifest_base64, "image/jpeg", &asset)?;
+    assert!(reader.validation_status().is_none());
+    assert!(reader
+        .active_manifest()
+        .unwrap()
+        .claim_generator()
+        .contains("c2patool"));
+
+    // the same manifest does not validate against a different asset
+    let other = std::fs::read(fixtures_path("C.jpg"))?;
+    let reader = Reader::from_base64_manifest_and_bytes(&manifest_base64, "image/jpeg", &other)?;
+    assert!(reader.hard_binding_broken());
+
+    assert_err!(
+        Reader::from_base64_manifest_and_bytes("not base64!", "image/jpeg", &asset),
+        Err(Error::BadParam(_))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reader_cose_unprotected_header() -> Result<()> {
+    let (format, mut stream) = fixture_stream("legacy_ingredient_hash.jpg")?;
+    let reader = Reader::from_stream(&format, &mut stream)?;
+
+    let serde_cbor::Value::Map(header) = reader.cose_unprotected_header()? else {
+        unreachable!("the unprotected header is a map");
+    };
+    let field = |name: &str| header.get(&serde_cbor::Value::Text(name.to_string()));
+
+    // this legacy signature keeps its certificate chain in the unprotected header
+    assert!(matches!(
+        field("x5chain"),
+        Some(serde_cbor::Value::Bytes(_) | serde_cbor::Value::Array(_))
+    ));
+    assert!(matches!(field("sigTst"), Some(serde_cbor::Value::Map(_))));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn test_reader_mmap() -> Result<()> {
+    use c2pa::MmapReader;
+
+    // the largest fixture with a manifest store
+    let path = fixtures_path("exp-test1.png");
+    // Safety: the fixture isn't modified while the tests run
+    let mut mapped = unsafe { MmapReader::open(&path)? };
+    let reader = Reader::from_stream("image/png", &mut mapped)?;
+    // hashes the mapped bytes directly
+    let mapped_reader = Reader::from_mmap("image/png", &mapped)?;
+
+    let (format, mut stream) = fixture_stream("exp-test1.png")?;
+    let expected = Reader::from_stream(&format, &mut stream)?;
+    assert!(reader.active_label().is_some());
+    for reader in [&reader, &mapped_reader] {
+        assert_eq!(reader.active_label(), expected.active_label());
+        assert_eq!(reader.validation_status(), expected.validation_status());
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pdf")]
+fn test_reader_detached_signature_pdf() -> Result<()> {
+    use std::io::Cursor;
+
+    // express-detached.cose is the active claim signature of express-signed.pdf, which is
+    // zeroed out in the PDF to detach it
+    let signature = std::fs::read(fixtures_path("express-detached.cose"))?;
+    let signed_pdf = std::fs::read(fixtures_path("express-signed.pdf"))?;
+    let offset = signed_pdf
+        .windows(signature.len())
+        .position(|window| window == signature)
+        .unwrap();
+    let mut detached_pdf = signed_pdf.clone();
+    detached_pdf[offset..offset + signature.len()].fill(0);
+
+    let reader = Reader::from_stream_with_detached_signature(
+        "application/pdf",
+        Cursor::new(&detached_pdf),
+        &signature,
+    )?;
+
+    let signed = Reader::from_stream("application/pdf", Cursor::new(&signed_pdf))?;
+    assert_eq!(reader.active_label(), signed.active_label());
+    assert_eq!(reader.validation_status(), signed.validation_status());
+
+    // the signature must be a COSE signature
+    let result = Reader::from_stream_with_detached_signature(
+        "application/pdf",
+        Cursor::new(&detached_pdf),
+        b"not a signature",
+    );
+    assert_err!(result, Err(Error::CoseSignature));
+
+    // an embedded signature is not replaced
+    let result = Reader::from_stream_with_detached_signature(
+        "application/pdf",
+        Cursor::new(&signed_pdf),
+        &signature,
+    );
+    assert_err!(result, Err(Error::BadParam(_)));
+    Ok(())
+}